@@ -0,0 +1,11 @@
+pub mod factorial;
+pub mod necklaces;
+pub mod pairing;
+pub mod partitions;
+pub mod primorial;
+
+pub use factorial::Factorial;
+pub use necklaces::Necklaces;
+pub use pairing::Pairing;
+pub use partitions::{RestrictedPartitions, StandardYoungTableaux};
+pub use primorial::Primorial;
@@ -0,0 +1,1038 @@
+use crate::number_theory::primes::{Modular, Small};
+
+pub trait Factorial: Sized {
+    /// The exact type of the `Iterator<Item = Self>` returned by
+    /// `factorial_iter`.
+    type Iter: Iterator<Item = Self>;
+
+    /// Returns an `Option` containing either n! or `None` if that would cause
+    /// overflow or n is negative.
+    ///
+    /// For `rug::Integer`, this uses the prime-swing algorithm of
+    /// `prime_swing_factorial`. As `rug::Integer` has an inherent `factorial`
+    /// function taking a `u32`, call this one as
+    /// `<rug::Integer as Factorial>::factorial`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Factorial;
+    ///
+    /// assert_eq!(u32::factorial(&12), Some(479_001_600));
+    /// assert_eq!(u32::factorial(&13), None);
+    /// assert_eq!(i8::factorial(&0), Some(1));
+    /// assert_eq!(i8::factorial(&-1), None);
+    /// ```
+    fn factorial(n: &Self) -> Option<Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through all
+    /// factorials 0!, 1!, 2!, … until just before overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Factorial;
+    ///
+    /// let terms: Vec<_> = u16::factorial_iter().collect();
+    /// assert_eq!(terms, vec![1, 1, 2, 6, 24, 120, 720, 5040, 40320]);
+    /// assert_eq!(u128::factorial_iter().count(), 35);
+    /// ```
+    fn factorial_iter() -> Self::Iter;
+
+    /// Returns an `Option` containing either the double factorial
+    /// n!! = n (n − 2) (n − 4) ⋯, the product of the positive integers up to
+    /// n with the same parity as n, or `None` if that would cause overflow or
+    /// n is less than −1. Both 0!! and (−1)!! are one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Factorial;
+    ///
+    /// assert_eq!(u32::double_factorial(&9), Some(945));
+    /// assert_eq!(u32::double_factorial(&10), Some(3840));
+    /// assert_eq!(i8::double_factorial(&-1), Some(1));
+    /// assert_eq!(i8::double_factorial(&-2), None);
+    /// assert_eq!(u8::double_factorial(&8), None);
+    /// ```
+    fn double_factorial(n: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the rising factorial
+    /// x (x + 1) ⋯ (x + n − 1), which is one when n is zero, or `None` if
+    /// that would cause overflow or n is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Factorial;
+    ///
+    /// assert_eq!(u32::rising_factorial(&5, &3), Some(210));
+    /// assert_eq!(i32::rising_factorial(&-5, &3), Some(-60));
+    /// assert_eq!(i8::rising_factorial(&-100, &120), Some(0));
+    /// assert_eq!(u8::rising_factorial(&16, &2), None);
+    /// ```
+    fn rising_factorial(x: &Self, n: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the falling factorial
+    /// x (x − 1) ⋯ (x − n + 1), which is one when n is zero, or `None` if
+    /// that would cause overflow or n is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Factorial;
+    ///
+    /// assert_eq!(u32::falling_factorial(&7, &3), Some(210));
+    /// assert_eq!(u32::falling_factorial(&3, &7), Some(0));
+    /// assert_eq!(i32::falling_factorial(&-5, &3), Some(-210));
+    /// assert_eq!(u64::falling_factorial(&u64::MAX, &1), Some(u64::MAX));
+    /// ```
+    fn falling_factorial(x: &Self, n: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the subfactorial !n, the number
+    /// of derangements of n elements, or `None` if that would cause overflow
+    /// or n is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Factorial;
+    ///
+    /// assert_eq!(u32::subfactorial(&10), Some(1_334_961));
+    /// assert_eq!(u32::subfactorial(&14), None);
+    /// assert_eq!(i8::subfactorial(&-1), None);
+    /// ```
+    fn subfactorial(n: &Self) -> Option<Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through all
+    /// subfactorials !0, !1, !2, … until just before overflow, using
+    /// !n = n · !(n − 1) + (−1)<sup>n</sup>.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Factorial;
+    ///
+    /// let terms: Vec<_> = u16::subfactorial_iter().collect();
+    /// assert_eq!(terms, vec![1, 0, 1, 2, 9, 44, 265, 1854, 14833]);
+    /// ```
+    fn subfactorial_iter() -> SubfactorialIter<Self>;
+}
+
+/// The iterator returned by `Factorial::factorial_iter`.
+#[derive(Clone, Debug)]
+pub struct FactorialIter<T> {
+    index: T,
+    term: Option<T>,
+}
+
+/// The iterator returned by `Factorial::subfactorial_iter`.
+#[derive(Clone, Debug)]
+pub struct SubfactorialIter<T> {
+    index: T,
+    term: Option<T>,
+}
+
+macro_rules! factorial_trait_for_primitive {
+    ($type:ty) => {
+        impl $crate::combinatorics::factorial::Factorial for $type {
+            type Iter = $crate::combinatorics::factorial::FactorialIter<$type>;
+
+            fn factorial(n: &Self) -> ::core::option::Option<Self> {
+                if *n < (0 as $type) {
+                    return ::core::option::Option::None;
+                }
+                // This overflows after at most 34 factors.
+                let mut result: $type = 1;
+                let mut factor: $type = 2;
+                while factor <= *n {
+                    result = result.checked_mul(factor)?;
+                    factor += 1;
+                }
+                ::core::option::Option::Some(result)
+            }
+
+            fn factorial_iter() -> Self::Iter {
+                $crate::combinatorics::factorial::FactorialIter {
+                    index: 0,
+                    term: ::core::option::Option::Some(1),
+                }
+            }
+
+            fn double_factorial(n: &Self) -> ::core::option::Option<Self> {
+                if *n < (0 as $type) {
+                    return if *n as i128 == -1 {
+                        ::core::option::Option::Some(1)
+                    } else {
+                        ::core::option::Option::None
+                    };
+                }
+                let mut result: $type = 1;
+                let mut factor = *n;
+                while factor > 1 {
+                    result = result.checked_mul(factor)?;
+                    factor -= 2;
+                }
+                ::core::option::Option::Some(result)
+            }
+
+            fn rising_factorial(x: &Self, n: &Self) -> ::core::option::Option<Self> {
+                let (x, n) = (
+                    *x,
+                    <u128 as ::core::convert::TryFrom<$type>>::try_from(*n).ok()?,
+                );
+                // The factors pass through zero when x ≤ 0 < x + n. Otherwise
+                // at most two of them are ±1, so the product overflows within
+                // a couple hundred factors.
+                if x <= (0 as $type) && n > (x as i128).unsigned_abs() {
+                    return ::core::option::Option::Some(0);
+                }
+                let mut result: $type = 1;
+                let mut factor = x;
+                for i in 0..n {
+                    if i != 0 {
+                        factor = factor.checked_add(1)?;
+                    }
+                    result = result.checked_mul(factor)?;
+                }
+                ::core::option::Option::Some(result)
+            }
+
+            fn falling_factorial(x: &Self, n: &Self) -> ::core::option::Option<Self> {
+                let (x, n) = (
+                    *x,
+                    <u128 as ::core::convert::TryFrom<$type>>::try_from(*n).ok()?,
+                );
+                // The factors pass through zero when x − n < 0 ≤ x.
+                if x >= (0 as $type) && n > x as u128 {
+                    return ::core::option::Option::Some(0);
+                }
+                let mut result: $type = 1;
+                let mut factor = x;
+                for i in 0..n {
+                    if i != 0 {
+                        factor = factor.checked_sub(1)?;
+                    }
+                    result = result.checked_mul(factor)?;
+                }
+                ::core::option::Option::Some(result)
+            }
+
+            fn subfactorial(n: &Self) -> ::core::option::Option<Self> {
+                let n = <usize as ::core::convert::TryFrom<$type>>::try_from(*n).ok()?;
+                Self::subfactorial_iter().nth(n)
+            }
+
+            fn subfactorial_iter() -> $crate::combinatorics::factorial::SubfactorialIter<Self> {
+                $crate::combinatorics::factorial::SubfactorialIter {
+                    index: 0,
+                    term: ::core::option::Option::Some(1),
+                }
+            }
+        }
+
+        impl ::core::iter::Iterator for $crate::combinatorics::factorial::SubfactorialIter<$type> {
+            type Item = $type;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                let term = self.term?;
+                // When one is subtracted, n !(n − 1) has the odd factor n, so
+                // it is not a power of two and cannot overflow by exactly one.
+                // The product therefore only overflows when the next term does.
+                self.index += 1;
+                self.term = term.checked_mul(self.index).and_then(|product| {
+                    if self.index % 2 == 0 {
+                        product.checked_add(1)
+                    } else {
+                        ::core::option::Option::Some(product - 1)
+                    }
+                });
+                ::core::option::Option::Some(term)
+            }
+        }
+
+        impl ::core::iter::FusedIterator
+            for $crate::combinatorics::factorial::SubfactorialIter<$type>
+        {
+        }
+
+        impl ::core::iter::Iterator for $crate::combinatorics::factorial::FactorialIter<$type> {
+            type Item = $type;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                let term = self.term?;
+                // The index is at most the next term, so it cannot overflow
+                // first.
+                self.index += 1;
+                self.term = term.checked_mul(self.index);
+                ::core::option::Option::Some(term)
+            }
+        }
+
+        impl ::core::iter::FusedIterator
+            for $crate::combinatorics::factorial::FactorialIter<$type>
+        {
+        }
+    };
+}
+
+factorial_trait_for_primitive!(::core::primitive::i8);
+factorial_trait_for_primitive!(::core::primitive::u8);
+factorial_trait_for_primitive!(::core::primitive::i16);
+factorial_trait_for_primitive!(::core::primitive::u16);
+factorial_trait_for_primitive!(::core::primitive::i32);
+factorial_trait_for_primitive!(::core::primitive::u32);
+factorial_trait_for_primitive!(::core::primitive::i64);
+factorial_trait_for_primitive!(::core::primitive::u64);
+factorial_trait_for_primitive!(::core::primitive::i128);
+factorial_trait_for_primitive!(::core::primitive::u128);
+factorial_trait_for_primitive!(::core::primitive::isize);
+factorial_trait_for_primitive!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Factorial for rug::Integer {
+    type Iter = FactorialIter<rug::Integer>;
+
+    fn factorial(n: &Self) -> Option<Self> {
+        if *n < 0 {
+            return None;
+        }
+        n.to_u32().map(prime_swing_factorial)
+    }
+
+    fn factorial_iter() -> Self::Iter {
+        FactorialIter {
+            index: rug::Integer::new(),
+            term: Some(rug::Integer::from(1)),
+        }
+    }
+
+    fn double_factorial(n: &Self) -> Option<Self> {
+        if *n == -1 {
+            return Some(rug::Integer::from(1));
+        }
+        n.to_u32()
+            .map(|n| rug::Integer::from(rug::Integer::factorial_2(n)))
+    }
+
+    fn rising_factorial(x: &Self, n: &Self) -> Option<Self> {
+        Some(rising_factorial(x, n.to_u32()?))
+    }
+
+    fn falling_factorial(x: &Self, n: &Self) -> Option<Self> {
+        let n = n.to_u32()?;
+        // x (x − 1) ⋯ (x − n + 1) is the rising factorial from x − n + 1.
+        Some(rising_factorial(&(rug::Integer::from(x - n) + 1u8), n))
+    }
+
+    fn subfactorial(n: &Self) -> Option<Self> {
+        n.to_usize().and_then(|n| Self::subfactorial_iter().nth(n))
+    }
+
+    fn subfactorial_iter() -> SubfactorialIter<Self> {
+        SubfactorialIter {
+            index: rug::Integer::new(),
+            term: Some(rug::Integer::from(1)),
+        }
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for SubfactorialIter<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let term = self.term.take()?;
+        self.index += 1u8;
+        let mut next = rug::Integer::from(&term * &self.index);
+        if self.index.is_even() {
+            next += 1u8;
+        } else {
+            next -= 1u8;
+        }
+        self.term = Some(next);
+        Some(term)
+    }
+}
+
+/// Returns the subfactorial !n modulo m.
+///
+/// This follows !k = k · !(k − 1) + (−1)<sup>k</sup> reduced modulo m, so m
+/// need not be prime. This takes O(n) time and O(1) space.
+///
+/// # Panics
+///
+/// Panics if m is zero.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::combinatorics::factorial::subfactorial_mod;
+///
+/// assert_eq!(subfactorial_mod(10, 1000), 961);
+/// assert_eq!(subfactorial_mod(1_000_000, 1_000_000_007), 102_701_088);
+/// ```
+pub fn subfactorial_mod(n: u64, m: u64) -> u64 {
+    assert!(m != 0, "modulus must be nonzero");
+    let modular = Small { n: m };
+    let one = 1 % m;
+    let mut term = one;
+    for k in 1..=n {
+        term = modular.mul(term, k % m);
+        term = if k % 2 == 0 {
+            modular.add(term, one)
+        } else {
+            modular.sub(term, one)
+        };
+    }
+    term
+}
+
+/// Returns the exponent of the prime p in n!, by Legendre's formula.
+///
+/// The exponent is ⌊n/p⌋ + ⌊n/p²⌋ + ⋯, which is (n − s) / (p − 1) where s
+/// is the sum of the base-p digits of n, so it takes O(log n) time however
+/// large n! is.
+///
+/// # Panics
+///
+/// Panics if p is not prime.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::combinatorics::factorial::factorial_valuation;
+///
+/// // 10! = 2^8 × 3^4 × 5^2 × 7.
+/// assert_eq!(factorial_valuation(10, 2), 8);
+/// assert_eq!(factorial_valuation(10, 11), 0);
+/// // The number of trailing zeros of 10^18 !.
+/// assert_eq!(factorial_valuation(1_000_000_000_000_000_000, 5), 249_999_999_999_999_995);
+/// ```
+pub fn factorial_valuation(n: u64, p: u64) -> u64 {
+    assert!(
+        <u64 as crate::number_theory::Prime>::is_prime(&p),
+        "p must be prime"
+    );
+    (n - digit_sum(n, p)) / (p - 1)
+}
+
+/// Returns an `Option` containing either the exponent of the prime p in the
+/// binomial coefficient C(n, k) or `None` if k is greater than n, so that
+/// C(n, k) is zero.
+///
+/// By Kummer's theorem, the exponent is the number of carries when k and
+/// n − k are added in base p, which is (s(k) + s(n − k) − s(n)) / (p − 1)
+/// where s gives the sum of the base-p digits.
+///
+/// # Panics
+///
+/// Panics if p is not prime.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::combinatorics::factorial::binomial_valuation;
+///
+/// // C(10, 4) = 210 = 2 × 3 × 5 × 7.
+/// assert_eq!(binomial_valuation(10, 4, 2), Some(1));
+/// assert_eq!(binomial_valuation(10, 4, 11), Some(0));
+/// // C(2^k, j) is even for 0 < j < 2^k, but C(2^k − 1, j) is odd.
+/// assert_eq!(binomial_valuation(1 << 40, 1 << 39, 2), Some(1));
+/// assert_eq!(binomial_valuation((1 << 40) - 1, 12_345, 2), Some(0));
+/// assert_eq!(binomial_valuation(4, 10, 2), None);
+/// ```
+pub fn binomial_valuation(n: u64, k: u64, p: u64) -> Option<u64> {
+    assert!(
+        <u64 as crate::number_theory::Prime>::is_prime(&p),
+        "p must be prime"
+    );
+    if k > n {
+        return None;
+    }
+    Some((digit_sum(k, p) + digit_sum(n - k, p) - digit_sum(n, p)) / (p - 1))
+}
+
+/// Returns the sum of the base-b digits of n.
+fn digit_sum(mut n: u64, b: u64) -> u64 {
+    let mut sum = 0;
+    while n != 0 {
+        sum += n % b;
+        n /= b;
+    }
+    sum
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for FactorialIter<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let term = self.term.take()?;
+        self.index += 1u8;
+        self.term = Some(rug::Integer::from(&term * &self.index));
+        Some(term)
+    }
+}
+
+/// Returns n! computed by the prime-swing algorithm.
+///
+/// This uses n! = (⌊n/2⌋!)² · n≀, where the swing n≀ = n! / (⌊n/2⌋!)² is
+/// assembled from its prime factorization, and every product is split in
+/// halves so that the multiplications are between numbers of similar size.
+/// This is far faster than multiplying 1, 2, …, n in turn.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::combinatorics::factorial::prime_swing_factorial;
+///
+/// assert_eq!(prime_swing_factorial(20), 2_432_902_008_176_640_000u64);
+/// assert_eq!(prime_swing_factorial(0), 1);
+/// assert_eq!(prime_swing_factorial(1000).significant_bits(), 8530);
+/// ```
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+pub fn prime_swing_factorial(n: u32) -> rug::Integer {
+    let primes: Vec<_> = crate::number_theory::Sieve::new(n.into())
+        .primes()
+        .map(|p| p as u32)
+        .collect();
+    let mut result = rug::Integer::from(1);
+    // Build n! from the top bit of n down, so that each step squares the
+    // factorial of the prefix and multiplies in the swing of the next prefix.
+    for bit in (0..32 - n.leading_zeros()).rev() {
+        let m = n >> bit;
+        result.square_mut();
+        result *= swing(m, &primes);
+    }
+    result
+}
+
+/// Returns the binomial coefficient C(n, k), or zero if k is greater than n,
+/// assembled from its prime factorization by Kummer's theorem.
+///
+/// The power of each prime p in C(n, k) is the number of borrows when k is
+/// subtracted from n in base p, and the prime powers are multiplied together
+/// by binary splitting.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::combinatorics::factorial::prime_swing_binomial;
+/// use rug::Integer;
+///
+/// assert_eq!(prime_swing_binomial(10, 4), 210);
+/// assert_eq!(prime_swing_binomial(4, 10), 0);
+/// assert_eq!(
+///     prime_swing_binomial(100, 50),
+///     Integer::from(Integer::binomial_u(100, 50))
+/// );
+/// ```
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+pub fn prime_swing_binomial(n: u32, k: u32) -> rug::Integer {
+    if k > n {
+        return rug::Integer::new();
+    }
+    let factors: Vec<_> = crate::number_theory::Sieve::new(n.into())
+        .primes()
+        .filter_map(|p| {
+            let (n, k) = (n as u64, k as u64);
+            let mut power = 1;
+            let mut place = p;
+            while place <= n {
+                // A borrow leaves the digits of k and n − k summing to more
+                // than those of n.
+                if n / place - k / place - (n - k) / place == 1 {
+                    power *= p;
+                }
+                place = match place.checked_mul(p) {
+                    Some(next) => next,
+                    None => break,
+                };
+            }
+            if power > 1 {
+                Some(power)
+            } else {
+                None
+            }
+        })
+        .collect();
+    product(&factors)
+}
+
+/// Returns the product of the primes no larger than x, computed by binary
+/// splitting.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::combinatorics::factorial::primorial_up_to;
+///
+/// assert_eq!(primorial_up_to(10), 210);
+/// assert_eq!(primorial_up_to(1), 1);
+/// ```
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+pub fn primorial_up_to(x: u32) -> rug::Integer {
+    let primes: Vec<_> = crate::number_theory::Sieve::new(x.into())
+        .primes()
+        .collect();
+    product(&primes)
+}
+
+/// Returns the rising factorial x (x + 1) ⋯ (x + n − 1), which is one when n
+/// is zero, computed by binary splitting.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::combinatorics::factorial::rising_factorial;
+/// use rug::Integer;
+///
+/// assert_eq!(rising_factorial(&Integer::from(5), 3), 210);
+/// assert_eq!(rising_factorial(&Integer::from(-2), 3), 0);
+/// assert_eq!(rising_factorial(&Integer::from(1), 20), 2_432_902_008_176_640_000u64);
+/// ```
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+pub fn rising_factorial(x: &rug::Integer, n: u32) -> rug::Integer {
+    fn split(x: &rug::Integer, low: u32, high: u32) -> rug::Integer {
+        match high - low {
+            0 => rug::Integer::from(1),
+            1 => rug::Integer::from(x + low),
+            _ => {
+                let middle = low + (high - low) / 2;
+                split(x, low, middle) * split(x, middle, high)
+            }
+        }
+    }
+
+    split(x, 0, n)
+}
+
+/// Returns the swing m≀ = m! / (⌊m/2⌋!)² given the primes up to at least m.
+///
+/// The power of each prime p in the swing is the number of k ≥ 1 for which
+/// ⌊m / p<sup>k</sup>⌋ is odd, so every prime power factor is at most m.
+#[cfg(any(feature = "rug", doc, test))]
+fn swing(m: u32, primes: &[u32]) -> rug::Integer {
+    let m = m as u64;
+    let factors: Vec<_> = primes
+        .iter()
+        .map(|&p| p as u64)
+        .take_while(|&p| p <= m)
+        .filter_map(|p| {
+            let mut power = 1;
+            let mut q = m / p;
+            while q != 0 {
+                if q & 1 == 1 {
+                    power *= p;
+                }
+                q /= p;
+            }
+            if power > 1 {
+                Some(power)
+            } else {
+                None
+            }
+        })
+        .collect();
+    product(&factors)
+}
+
+/// Returns the product of the factors by binary splitting.
+#[cfg(any(feature = "rug", doc, test))]
+pub(crate) fn product(factors: &[u64]) -> rug::Integer {
+    match factors.len() {
+        0 => rug::Integer::from(1),
+        1 => rug::Integer::from(factors[0]),
+        len => product(&factors[..len / 2]) * product(&factors[len / 2..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rug::Integer;
+
+    macro_rules! test_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let max = Integer::from(<$type>::MAX);
+                let expected: ::std::vec::Vec<Integer> = (0..40)
+                    .map(|n| Integer::from(Integer::factorial(n)))
+                    .take_while(|term| *term <= max)
+                    .collect();
+                let terms: ::std::vec::Vec<Integer> =
+                    <$type>::factorial_iter().map(Integer::from).collect();
+                ::core::assert_eq!(terms, expected);
+
+                for n in 0..=40u8 {
+                    let n = match <$type as ::core::convert::TryFrom<u8>>::try_from(n) {
+                        ::core::result::Result::Ok(n) => n,
+                        ::core::result::Result::Err(_) => break,
+                    };
+                    ::core::assert_eq!(
+                        <$type>::factorial(&n).map(Integer::from),
+                        expected.get(n as usize).cloned()
+                    );
+                }
+                ::core::assert_eq!(
+                    <$type>::factorial(&<$type>::MAX),
+                    ::core::option::Option::None
+                );
+            }
+        };
+    }
+
+    test_against_rug!(::core::primitive::i8, i8_factorial);
+    test_against_rug!(::core::primitive::u8, u8_factorial);
+    test_against_rug!(::core::primitive::i16, i16_factorial);
+    test_against_rug!(::core::primitive::u16, u16_factorial);
+    test_against_rug!(::core::primitive::i32, i32_factorial);
+    test_against_rug!(::core::primitive::u32, u32_factorial);
+    test_against_rug!(::core::primitive::i64, i64_factorial);
+    test_against_rug!(::core::primitive::u64, u64_factorial);
+    test_against_rug!(::core::primitive::i128, i128_factorial);
+    test_against_rug!(::core::primitive::u128, u128_factorial);
+    test_against_rug!(::core::primitive::isize, isize_factorial);
+    test_against_rug!(::core::primitive::usize, usize_factorial);
+
+    #[test]
+    fn rug_factorial_trait() {
+        let terms: Vec<_> = <Integer as Factorial>::factorial_iter().take(300).collect();
+        for (n, term) in terms.iter().enumerate() {
+            assert_eq!(*term, Integer::from(Integer::factorial(n as u32)));
+            assert_eq!(
+                <Integer as Factorial>::factorial(&Integer::from(n)).as_ref(),
+                Some(term)
+            );
+        }
+        assert_eq!(<Integer as Factorial>::factorial(&Integer::from(-1)), None);
+        assert_eq!(
+            <Integer as Factorial>::factorial(&Integer::from(u64::MAX)),
+            None
+        );
+    }
+
+    macro_rules! test_variants_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let fits = |value: Integer| {
+                    value
+                        .to_i128()
+                        .and_then(|value| {
+                            <$type as ::core::convert::TryFrom<i128>>::try_from(value).ok()
+                        })
+                        .or_else(|| {
+                            value.to_u128().and_then(|value| {
+                                <$type as ::core::convert::TryFrom<u128>>::try_from(value).ok()
+                            })
+                        })
+                };
+
+                for n in -3..=80i8 {
+                    let expected = if n == -1 {
+                        ::core::option::Option::Some(1)
+                    } else if n < -1 {
+                        ::core::option::Option::None
+                    } else {
+                        fits(Integer::from(Integer::factorial_2(n as u32)))
+                    };
+                    if let ::core::option::Option::Some(n) = fits(Integer::from(n)) {
+                        ::core::assert_eq!(<$type>::double_factorial(&n), expected, "n = {}", n);
+                    }
+                }
+
+                let mut values: ::std::vec::Vec<Integer> =
+                    (-130..=130).map(Integer::from).collect();
+                for extreme in [Integer::from(<$type>::MIN), Integer::from(<$type>::MAX)] {
+                    values.push(Integer::from(&extreme + 2u8));
+                    values.push(Integer::from(&extreme + 1u8));
+                    values.push(Integer::from(&extreme - 1u8));
+                    values.push(Integer::from(&extreme - 2u8));
+                    values.push(extreme);
+                }
+                for x in values.iter().filter_map(|x| fits(x.clone())) {
+                    // So many nonzero factors always overflow.
+                    let max = <$type>::MAX;
+                    let big_x = Integer::from(x);
+                    let rising_zero = big_x <= 0 && Integer::from(&big_x + max) > 0;
+                    let falling_zero = big_x >= 0 && Integer::from(&big_x - max) < 0;
+                    ::core::assert_eq!(
+                        <$type>::rising_factorial(&x, &max),
+                        rising_zero.then_some(0)
+                    );
+                    ::core::assert_eq!(
+                        <$type>::falling_factorial(&x, &max),
+                        falling_zero.then_some(0)
+                    );
+
+                    for n in 0..=140 {
+                        let n = match fits(Integer::from(n)) {
+                            ::core::option::Option::Some(n) => n,
+                            ::core::option::Option::None => continue,
+                        };
+                        let big_x = Integer::from(x);
+                        let big_n = Integer::from(n);
+                        ::core::assert_eq!(
+                            <$type>::rising_factorial(&x, &n),
+                            <Integer as Factorial>::rising_factorial(&big_x, &big_n).and_then(fits),
+                            "x = {}, n = {}",
+                            x,
+                            n
+                        );
+                        ::core::assert_eq!(
+                            <$type>::falling_factorial(&x, &n),
+                            <Integer as Factorial>::falling_factorial(&big_x, &big_n)
+                                .and_then(fits),
+                            "x = {}, n = {}",
+                            x,
+                            n
+                        );
+                    }
+                }
+            }
+        };
+    }
+
+    test_variants_against_rug!(::core::primitive::i8, i8_factorial_variants);
+    test_variants_against_rug!(::core::primitive::u8, u8_factorial_variants);
+    test_variants_against_rug!(::core::primitive::i16, i16_factorial_variants);
+    test_variants_against_rug!(::core::primitive::u16, u16_factorial_variants);
+    test_variants_against_rug!(::core::primitive::i32, i32_factorial_variants);
+    test_variants_against_rug!(::core::primitive::u32, u32_factorial_variants);
+    test_variants_against_rug!(::core::primitive::i64, i64_factorial_variants);
+    test_variants_against_rug!(::core::primitive::u64, u64_factorial_variants);
+    test_variants_against_rug!(::core::primitive::i128, i128_factorial_variants);
+    test_variants_against_rug!(::core::primitive::u128, u128_factorial_variants);
+    test_variants_against_rug!(::core::primitive::isize, isize_factorial_variants);
+    test_variants_against_rug!(::core::primitive::usize, usize_factorial_variants);
+
+    macro_rules! test_subfactorial_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let max = Integer::from(<$type>::MAX);
+                let expected: ::std::vec::Vec<Integer> =
+                    <Integer as Factorial>::subfactorial_iter()
+                        .take(40)
+                        .take_while(|term| *term <= max)
+                        .collect();
+                let terms: ::std::vec::Vec<Integer> =
+                    <$type>::subfactorial_iter().map(Integer::from).collect();
+                ::core::assert_eq!(terms, expected);
+
+                for n in 0..=40u8 {
+                    let n = n as $type;
+                    ::core::assert_eq!(
+                        <$type>::subfactorial(&n).map(Integer::from),
+                        expected.get(n as usize).cloned()
+                    );
+                }
+                ::core::assert_eq!(
+                    <$type>::subfactorial(&<$type>::MAX),
+                    ::core::option::Option::None
+                );
+            }
+        };
+    }
+
+    test_subfactorial_against_rug!(::core::primitive::i8, i8_subfactorial);
+    test_subfactorial_against_rug!(::core::primitive::u8, u8_subfactorial);
+    test_subfactorial_against_rug!(::core::primitive::i16, i16_subfactorial);
+    test_subfactorial_against_rug!(::core::primitive::u16, u16_subfactorial);
+    test_subfactorial_against_rug!(::core::primitive::i32, i32_subfactorial);
+    test_subfactorial_against_rug!(::core::primitive::u32, u32_subfactorial);
+    test_subfactorial_against_rug!(::core::primitive::i64, i64_subfactorial);
+    test_subfactorial_against_rug!(::core::primitive::u64, u64_subfactorial);
+    test_subfactorial_against_rug!(::core::primitive::i128, i128_subfactorial);
+    test_subfactorial_against_rug!(::core::primitive::u128, u128_subfactorial);
+    test_subfactorial_against_rug!(::core::primitive::isize, isize_subfactorial);
+    test_subfactorial_against_rug!(::core::primitive::usize, usize_subfactorial);
+
+    #[test]
+    fn subfactorials_count_derangements() {
+        // !n = Σ (−1)^k n! / k!, and for n ≥ 1 it is the integer nearest n! / e.
+        for (n, term) in <Integer as Factorial>::subfactorial_iter()
+            .take(200)
+            .enumerate()
+        {
+            let n = n as u32;
+            let mut sum = Integer::new();
+            for k in 0..=n {
+                let quotient =
+                    Integer::from(Integer::factorial(n)) / Integer::from(Integer::factorial(k));
+                if k % 2 == 0 {
+                    sum += quotient;
+                } else {
+                    sum -= quotient;
+                }
+            }
+            assert_eq!(term, sum, "n = {}", n);
+            assert_eq!(
+                <Integer as Factorial>::subfactorial(&Integer::from(n)),
+                Some(sum.clone())
+            );
+            for m in [1, 2, 7, 1000, 1_000_000_007, u64::MAX] {
+                let expected = (sum.clone() % m).to_u64().unwrap();
+                assert_eq!(
+                    subfactorial_mod(n as u64, m),
+                    expected,
+                    "n = {}, m = {}",
+                    n,
+                    m
+                );
+            }
+        }
+        assert_eq!(
+            <Integer as Factorial>::subfactorial(&Integer::from(-1)),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus must be nonzero")]
+    fn subfactorial_mod_zero() {
+        subfactorial_mod(5, 0);
+    }
+
+    #[test]
+    fn valuations_match_rug() {
+        for &p in &[2u64, 3, 5, 7, 97, 211] {
+            let p_integer = Integer::from(p);
+            for n in 0..300u32 {
+                let factorial = Integer::from(Integer::factorial(n));
+                let expected = factorial.clone().remove_factor(&p_integer).1 as u64;
+                assert_eq!(factorial_valuation(n as u64, p), expected);
+                for k in 0..=n {
+                    let binomial = Integer::from(Integer::binomial_u(n, k));
+                    assert_eq!(
+                        binomial_valuation(n as u64, k as u64, p),
+                        Some(binomial.remove_factor(&p_integer).1 as u64),
+                        "n = {}, k = {}, p = {}",
+                        n,
+                        k,
+                        p
+                    );
+                }
+            }
+        }
+        assert_eq!(factorial_valuation(u64::MAX, 2), u64::MAX - 64);
+        assert_eq!(binomial_valuation(u64::MAX, u64::MAX / 2, 2), Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "p must be prime")]
+    fn valuation_of_composite() {
+        factorial_valuation(10, 4);
+    }
+
+    #[test]
+    fn rug_factorial_variants() {
+        for x in -30..30 {
+            let x = Integer::from(x);
+            let mut rising = Integer::from(1);
+            let mut falling = Integer::from(1);
+            for n in 0..40u32 {
+                let n_big = Integer::from(n);
+                assert_eq!(
+                    <Integer as Factorial>::rising_factorial(&x, &n_big),
+                    Some(rising.clone())
+                );
+                assert_eq!(
+                    <Integer as Factorial>::falling_factorial(&x, &n_big),
+                    Some(falling.clone())
+                );
+                rising *= Integer::from(&x + n);
+                falling *= Integer::from(&x - n);
+            }
+        }
+        assert_eq!(
+            <Integer as Factorial>::rising_factorial(&Integer::from(3), &Integer::from(-1)),
+            None
+        );
+        assert_eq!(
+            <Integer as Factorial>::double_factorial(&Integer::from(-2)),
+            None
+        );
+        assert_eq!(
+            <Integer as Factorial>::double_factorial(&Integer::from(2001)),
+            Some(
+                Integer::from(Integer::factorial(2001)) / Integer::from(Integer::factorial_2(2000))
+            )
+        );
+    }
+
+    #[test]
+    fn factorials_match_rug() {
+        for n in 0..2000 {
+            assert_eq!(
+                prime_swing_factorial(n),
+                Integer::from(Integer::factorial(n)),
+                "n = {}",
+                n
+            );
+        }
+        assert_eq!(
+            prime_swing_factorial(100_000),
+            Integer::from(Integer::factorial(100_000))
+        );
+    }
+
+    #[test]
+    fn binomials_match_rug() {
+        for n in 0..200 {
+            for k in 0..=n + 1 {
+                assert_eq!(
+                    prime_swing_binomial(n, k),
+                    Integer::from(Integer::binomial_u(n, k)),
+                    "n = {}, k = {}",
+                    n,
+                    k
+                );
+            }
+        }
+        assert_eq!(
+            prime_swing_binomial(100_000, 37_000),
+            Integer::from(Integer::binomial_u(100_000, 37_000))
+        );
+    }
+
+    #[test]
+    fn primorials_match_rug() {
+        for x in 0..5000 {
+            assert_eq!(
+                primorial_up_to(x),
+                Integer::from(Integer::primorial(x)),
+                "x = {}",
+                x
+            );
+        }
+    }
+
+    #[test]
+    fn rising_factorials_match_products() {
+        for x in -20..20 {
+            let x = Integer::from(x);
+            let mut expected = Integer::from(1);
+            for n in 0..40 {
+                assert_eq!(rising_factorial(&x, n), expected, "x = {}, n = {}", x, n);
+                expected *= Integer::from(&x + n);
+            }
+        }
+        // x (x + 1) ⋯ (x + n − 1) = (x + n − 1)! / (x − 1)!.
+        let x = Integer::from(1000);
+        assert_eq!(
+            rising_factorial(&x, 3000),
+            Integer::from(Integer::factorial(3999)) / Integer::from(Integer::factorial(999))
+        );
+    }
+}
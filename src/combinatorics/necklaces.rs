@@ -0,0 +1,500 @@
+use core::convert::TryFrom;
+
+use crate::number_theory::Factor;
+
+pub trait Necklaces: Sized {
+    /// Returns an `Option` containing either the number of k-ary necklaces
+    /// of length n, the strings of length n over k letters counted up to
+    /// rotation, or `None` if that would cause overflow.
+    ///
+    /// This is (1 / n) Σ φ(d) k<sup>n / d</sup> over the divisors d of n, by
+    /// Burnside's lemma. For `u128`, `None` is also returned when n times the
+    /// result would overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Necklaces;
+    ///
+    /// assert_eq!(u32::necklace_count(&2, &6), Some(14));
+    /// assert_eq!(u32::necklace_count(&3, &4), Some(24));
+    /// assert_eq!(u8::necklace_count(&2, &12), None);
+    /// ```
+    fn necklace_count(k: &Self, n: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the number of k-ary bracelets
+    /// of length n, the strings of length n over k letters counted up to
+    /// rotation and reflection, or `None` if that would cause overflow. For
+    /// `u128`, `None` is also returned when 2n times the result would
+    /// overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Necklaces;
+    ///
+    /// assert_eq!(u32::bracelet_count(&2, &6), Some(13));
+    /// assert_eq!(u32::bracelet_count(&3, &4), Some(21));
+    /// ```
+    fn bracelet_count(k: &Self, n: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the number of k-ary Lyndon
+    /// words of length n, the aperiodic necklaces, or `None` if that would
+    /// cause overflow.
+    ///
+    /// This is (1 / n) Σ μ(d) k<sup>n / d</sup> over the divisors d of n. For
+    /// `u128`, `None` is also returned when n times the result would
+    /// overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Necklaces;
+    ///
+    /// assert_eq!(u32::lyndon_word_count(&2, &6), Some(9));
+    /// assert_eq!(u32::lyndon_word_count(&26, &1), Some(26));
+    /// assert_eq!(u32::lyndon_word_count(&5, &0), Some(0));
+    /// ```
+    fn lyndon_word_count(k: &Self, n: &Self) -> Option<Self>;
+}
+
+/// Returns an iterator over the k-ary necklaces of length n in
+/// lexicographic order, each given by its lexicographically least rotation
+/// as a `Vec` of letters from `0..k`.
+///
+/// The words are generated by the Fredricksen–Kessler–Maiorana extension of
+/// Duval's algorithm in constant amortized time per word.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::combinatorics::necklaces::necklaces;
+///
+/// let words: Vec<_> = necklaces(2, 4).collect();
+/// assert_eq!(
+///     words,
+///     vec![
+///         vec![0, 0, 0, 0],
+///         vec![0, 0, 0, 1],
+///         vec![0, 0, 1, 1],
+///         vec![0, 1, 0, 1],
+///         vec![0, 1, 1, 1],
+///         vec![1, 1, 1, 1],
+///     ]
+/// );
+/// ```
+pub fn necklaces(k: usize, n: usize) -> NecklaceWords {
+    NecklaceWords::new(k, n, false)
+}
+
+/// Returns an iterator over the k-ary Lyndon words of length n, the strings
+/// strictly smaller than all of their other rotations, in lexicographic
+/// order as `Vec`s of letters from `0..k`.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::combinatorics::necklaces::lyndon_words;
+///
+/// let words: Vec<_> = lyndon_words(3, 2).collect();
+/// assert_eq!(words, vec![vec![0, 1], vec![0, 2], vec![1, 2]]);
+///
+/// assert_eq!(lyndon_words(2, 12).count(), 335);
+/// ```
+pub fn lyndon_words(k: usize, n: usize) -> NecklaceWords {
+    NecklaceWords::new(k, n, true)
+}
+
+/// The iterator returned by `necklaces` and `lyndon_words`.
+#[derive(Clone, Debug)]
+pub struct NecklaceWords {
+    k: usize,
+    word: Vec<usize>,
+    period: usize,
+    lyndon_only: bool,
+    done: bool,
+}
+
+impl NecklaceWords {
+    fn new(k: usize, n: usize, lyndon_only: bool) -> Self {
+        Self {
+            k,
+            word: vec![0; n],
+            period: 1,
+            lyndon_only,
+            done: k == 0 && n != 0,
+        }
+    }
+
+    /// Moves on to the next prenecklace, the next prefix of some necklace,
+    /// together with the length of its shortest period.
+    fn advance(&mut self) {
+        let k = self.k;
+        match self.word.iter().rposition(|&letter| letter + 1 < k) {
+            Some(i) => {
+                self.word[i] += 1;
+                for j in i + 1..self.word.len() {
+                    self.word[j] = self.word[j - i - 1];
+                }
+                self.period = i + 1;
+            }
+            None => self.done = true,
+        }
+    }
+}
+
+impl Iterator for NecklaceWords {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            // A prenecklace is a necklace exactly when its period divides its
+            // length and a Lyndon word exactly when its period is its length.
+            let n = self.word.len();
+            let matches = if self.lyndon_only {
+                self.period == n
+            } else {
+                n.is_multiple_of(self.period)
+            };
+            let word = if matches {
+                Some(self.word.clone())
+            } else {
+                None
+            };
+            self.advance();
+            if word.is_some() {
+                return word;
+            }
+        }
+        None
+    }
+}
+
+/// Returns the divisors of n, which must be positive, along with their
+/// totients and Möbius values.
+fn divisors_with_totient_and_mobius(n: u32) -> Vec<(u32, u32, i8)> {
+    u32::factorize(&n)
+        .unwrap()
+        .divisors()
+        .map(|d| {
+            let factorization = u32::factorize(&d).unwrap();
+            let factors = factorization.factors();
+            let mobius = if factors.iter().any(|&(_, e)| e > 1) {
+                0
+            } else if factors.len() % 2 == 1 {
+                -1
+            } else {
+                1
+            };
+            (d, factorization.totient(), mobius)
+        })
+        .collect()
+}
+
+/// Returns n times the number of k-ary necklaces of length n, for n from 1
+/// to 255 and k of at least two, or `None` if that does not fit in a `u128`.
+fn necklace_sum(k: u128, n: u32) -> Option<u128> {
+    let mut sum: u128 = 0;
+    for (d, totient, _) in divisors_with_totient_and_mobius(n) {
+        sum = sum.checked_add(k.checked_pow(n / d)?.checked_mul(totient as u128)?)?;
+    }
+    Some(sum)
+}
+
+fn necklace_count(k: u128, n: u128) -> Option<u128> {
+    if n == 0 {
+        return Some(1);
+    }
+    if k <= 1 {
+        return Some(k);
+    }
+    // Even binary necklaces of length 256 overflow a `u128`.
+    let n = u32::try_from(n).ok().filter(|&n| n < 256)?;
+    Some(necklace_sum(k, n)? / n as u128)
+}
+
+fn bracelet_count(k: u128, n: u128) -> Option<u128> {
+    if n == 0 || k <= 1 {
+        return necklace_count(k, n);
+    }
+    let n = u32::try_from(n).ok().filter(|&n| n < 256)?;
+
+    // Twice the number of bracelets is the number of necklaces plus the
+    // number of strings fixed by some reflection, found here as n times
+    // each.
+    let symmetric = if n % 2 == 1 {
+        k.checked_pow(n / 2 + 1)?
+    } else {
+        k.checked_pow(n / 2)?.checked_mul(k + 1)? / 2
+    };
+    let sum = necklace_sum(k, n)?.checked_add(symmetric.checked_mul(n as u128)?)?;
+    Some(sum / (2 * n as u128))
+}
+
+fn lyndon_word_count(k: u128, n: u128) -> Option<u128> {
+    if n == 0 {
+        return Some(0);
+    }
+    if k <= 1 {
+        return Some(if n == 1 { k } else { 0 });
+    }
+    let n = u32::try_from(n).ok().filter(|&n| n < 256)?;
+
+    // Every negative term is smaller than the positive term for d = 1, so
+    // the two parts are accumulated separately.
+    let (mut positive, mut negative): (u128, u128) = (0, 0);
+    for (d, _, mobius) in divisors_with_totient_and_mobius(n) {
+        match mobius {
+            1 => positive = positive.checked_add(k.checked_pow(n / d)?)?,
+            -1 => negative = negative.checked_add(k.checked_pow(n / d)?)?,
+            _ => {}
+        }
+    }
+    Some((positive - negative) / n as u128)
+}
+
+macro_rules! necklaces_trait_for_unsigned {
+    ($type:ty) => {
+        impl $crate::combinatorics::necklaces::Necklaces for $type {
+            fn necklace_count(k: &Self, n: &Self) -> ::core::option::Option<Self> {
+                let count =
+                    $crate::combinatorics::necklaces::necklace_count(*k as u128, *n as u128)?;
+                <$type as ::core::convert::TryFrom<u128>>::try_from(count).ok()
+            }
+
+            fn bracelet_count(k: &Self, n: &Self) -> ::core::option::Option<Self> {
+                let count =
+                    $crate::combinatorics::necklaces::bracelet_count(*k as u128, *n as u128)?;
+                <$type as ::core::convert::TryFrom<u128>>::try_from(count).ok()
+            }
+
+            fn lyndon_word_count(k: &Self, n: &Self) -> ::core::option::Option<Self> {
+                let count =
+                    $crate::combinatorics::necklaces::lyndon_word_count(*k as u128, *n as u128)?;
+                <$type as ::core::convert::TryFrom<u128>>::try_from(count).ok()
+            }
+        }
+    };
+}
+
+necklaces_trait_for_unsigned!(::core::primitive::u8);
+necklaces_trait_for_unsigned!(::core::primitive::u16);
+necklaces_trait_for_unsigned!(::core::primitive::u32);
+necklaces_trait_for_unsigned!(::core::primitive::u64);
+necklaces_trait_for_unsigned!(::core::primitive::u128);
+necklaces_trait_for_unsigned!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Necklaces for rug::Integer {
+    fn necklace_count(k: &Self, n: &Self) -> Option<Self> {
+        if *k < 0 || *n < 0 {
+            return None;
+        }
+        if *n == 0 {
+            return Some(Self::from(1));
+        }
+        if *k <= 1 {
+            return Some(k.clone());
+        }
+        let n = n.to_u32()?;
+
+        let mut sum = Self::new();
+        for (d, totient, _) in divisors_with_totient_and_mobius(n) {
+            sum += Self::from(rug::ops::Pow::pow(k, n / d)) * totient;
+        }
+        Some(sum / n)
+    }
+
+    fn bracelet_count(k: &Self, n: &Self) -> Option<Self> {
+        let necklaces = Self::necklace_count(k, n)?;
+        if *n == 0 {
+            return Some(necklaces);
+        }
+        let n = n.to_u32()?;
+
+        let symmetric = if n % 2 == 1 {
+            Self::from(rug::ops::Pow::pow(k, n / 2 + 1))
+        } else {
+            Self::from(rug::ops::Pow::pow(k, n / 2)) * (k.clone() + 1u8) / 2u8
+        };
+        Some((necklaces + symmetric) / 2u8)
+    }
+
+    fn lyndon_word_count(k: &Self, n: &Self) -> Option<Self> {
+        if *k < 0 || *n < 0 {
+            return None;
+        }
+        if *n == 0 {
+            return Some(Self::new());
+        }
+        if *k <= 1 {
+            return Some(if *n == 1 { k.clone() } else { Self::new() });
+        }
+        let n = n.to_u32()?;
+
+        let mut sum = Self::new();
+        for (d, _, mobius) in divisors_with_totient_and_mobius(n) {
+            sum += Self::from(rug::ops::Pow::pow(k, n / d)) * mobius;
+        }
+        Some(sum / n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rotations(word: &[usize]) -> impl Iterator<Item = Vec<usize>> + '_ {
+        (0..word.len().max(1)).map(move |i| {
+            let mut rotation = word.to_vec();
+            rotation.rotate_left(i.min(word.len()));
+            rotation
+        })
+    }
+
+    fn brute_force_counts(k: usize, n: usize) -> (usize, usize, usize) {
+        let (mut necklaces, mut bracelets, mut lyndon) = (0, 0, 0);
+        let total = k.pow(n as u32);
+        for mut code in 0..total {
+            let mut word = vec![0; n];
+            for letter in word.iter_mut().rev() {
+                *letter = code % k;
+                code /= k;
+            }
+            if rotations(&word).all(|rotation| word <= rotation) {
+                necklaces += 1;
+                if rotations(&word).skip(1).all(|rotation| word < rotation) {
+                    lyndon += 1;
+                }
+            }
+            let mut reversed = word.clone();
+            reversed.reverse();
+            if rotations(&word)
+                .chain(rotations(&reversed))
+                .all(|rotation| word <= rotation)
+            {
+                bracelets += 1;
+            }
+        }
+        (necklaces, bracelets, lyndon)
+    }
+
+    #[test]
+    fn words_match_brute_force() {
+        for k in 1..=4usize {
+            for n in 1..=7 {
+                if k.pow(n as u32) > 10_000 {
+                    continue;
+                }
+                let (necklace_count, bracelet_count, lyndon_count) = brute_force_counts(k, n);
+                let all: Vec<_> = necklaces(k, n).collect();
+                let lyndon: Vec<_> = lyndon_words(k, n).collect();
+                assert_eq!(all.len(), necklace_count, "k = {}, n = {}", k, n);
+                assert_eq!(lyndon.len(), lyndon_count, "k = {}, n = {}", k, n);
+                assert!(all.windows(2).all(|pair| pair[0] < pair[1]));
+                for word in &all {
+                    assert!(rotations(word).all(|rotation| *word <= rotation));
+                }
+                for word in &lyndon {
+                    assert!(rotations(word).skip(1).all(|rotation| *word < rotation));
+                }
+
+                let (k, n, bracelet_count) = (k as u64, n as u64, bracelet_count as u64);
+                assert_eq!(u64::necklace_count(&k, &n), Some(all.len() as u64));
+                assert_eq!(u64::lyndon_word_count(&k, &n), Some(lyndon.len() as u64));
+                assert_eq!(u64::bracelet_count(&k, &n), Some(bracelet_count));
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_lengths_and_alphabets() {
+        assert_eq!(
+            necklaces(3, 0).collect::<Vec<_>>(),
+            vec![Vec::<usize>::new()]
+        );
+        assert_eq!(lyndon_words(3, 0).count(), 0);
+        assert_eq!(necklaces(0, 3).count(), 0);
+        assert_eq!(necklaces(0, 0).count(), 1);
+        assert_eq!(necklaces(1, 5).collect::<Vec<_>>(), vec![vec![0; 5]]);
+        assert_eq!(lyndon_words(1, 1).collect::<Vec<_>>(), vec![vec![0]]);
+        assert_eq!(lyndon_words(1, 2).count(), 0);
+
+        assert_eq!(u8::necklace_count(&0, &3), Some(0));
+        assert_eq!(u8::necklace_count(&1, &u8::MAX), Some(1));
+        assert_eq!(u8::bracelet_count(&1, &u8::MAX), Some(1));
+        assert_eq!(u8::lyndon_word_count(&1, &1), Some(1));
+        assert_eq!(u64::necklace_count(&2, &u64::MAX), None);
+    }
+
+    macro_rules! test_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                for k in 0..=40u32 {
+                    for n in 0..=140u32 {
+                        let (big_k, big_n) = (rug::Integer::from(k), rug::Integer::from(n));
+                        // Sums past the range of `u128` are only handled by
+                        // the bignum implementation.
+                        let sum = rug::Integer::necklace_count(&big_k, &big_n).unwrap() * 2 * n;
+                        if sum > u128::MAX {
+                            continue;
+                        }
+                        let (k, n) = (k as $type, n as $type);
+                        for (primitive, big) in [
+                            (
+                                <$type>::necklace_count(&k, &n),
+                                rug::Integer::necklace_count(&big_k, &big_n).unwrap(),
+                            ),
+                            (
+                                <$type>::bracelet_count(&k, &n),
+                                rug::Integer::bracelet_count(&big_k, &big_n).unwrap(),
+                            ),
+                            (
+                                <$type>::lyndon_word_count(&k, &n),
+                                rug::Integer::lyndon_word_count(&big_k, &big_n).unwrap(),
+                            ),
+                        ] {
+                            let expected = if big <= <$type>::MAX {
+                                ::core::option::Option::Some(big.to_u128().unwrap() as $type)
+                            } else {
+                                ::core::option::Option::None
+                            };
+                            assert_eq!(primitive, expected, "k = {}, n = {}", k, n);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    test_against_rug!(::core::primitive::u8, test_u8_against_rug);
+    test_against_rug!(::core::primitive::u16, test_u16_against_rug);
+    test_against_rug!(::core::primitive::u32, test_u32_against_rug);
+    test_against_rug!(::core::primitive::u64, test_u64_against_rug);
+    test_against_rug!(::core::primitive::u128, test_u128_against_rug);
+    test_against_rug!(::core::primitive::usize, test_usize_against_rug);
+
+    #[test]
+    fn divisors_near_u32_max() {
+        let p = 4_294_967_291;
+        let mut divisors = divisors_with_totient_and_mobius(p);
+        divisors.sort_unstable();
+        assert_eq!(divisors, vec![(1, 1, 1), (p, p - 1, -1)]);
+
+        // u32::MAX = 3 × 5 × 17 × 257 × 65537, so the totients of its
+        // divisors sum to it and the Möbius values cancel.
+        let divisors = divisors_with_totient_and_mobius(u32::MAX);
+        assert_eq!(divisors.len(), 32);
+        let totients: u64 = divisors.iter().map(|&(_, totient, _)| totient as u64).sum();
+        assert_eq!(totients, u32::MAX as u64);
+        assert_eq!(
+            divisors
+                .iter()
+                .map(|&(_, _, mobius)| mobius as i32)
+                .sum::<i32>(),
+            0
+        );
+    }
+}
@@ -0,0 +1,346 @@
+pub trait Pairing: Sized {
+    /// The type of the single integers that pairs are encoded as: the type
+    /// itself for unsigned types and its unsigned counterpart for signed
+    /// types.
+    type Code;
+
+    /// Returns the Cantor pairing π(x, y) = (x + y)(x + y + 1) / 2 + y, which
+    /// numbers the lattice points diagonal by diagonal.
+    ///
+    /// Signed values are first mapped to unsigned ones by the zigzag
+    /// bijection 0, −1, 1, −2, 2, … ↦ 0, 1, 2, 3, 4, ….
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result would overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Pairing;
+    ///
+    /// assert_eq!(u32::cantor_pair(&0, &0), 0);
+    /// assert_eq!(u32::cantor_pair(&47, &32), 3192);
+    /// assert_eq!(i32::cantor_pair(&-1, &0), 1);
+    /// ```
+    fn cantor_pair(x: &Self, y: &Self) -> Self::Code {
+        Self::checked_cantor_pair(x, y).expect("overflow in Cantor pairing")
+    }
+
+    /// Returns an `Option` containing either the Cantor pairing of x and y
+    /// or `None` if that would cause overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Pairing;
+    ///
+    /// assert_eq!(u8::checked_cantor_pair(&10, &10), Some(220));
+    /// assert_eq!(u8::checked_cantor_pair(&11, &11), None);
+    /// assert_eq!(i8::checked_cantor_pair(&-5, &5), Some(200));
+    /// ```
+    fn checked_cantor_pair(x: &Self, y: &Self) -> Option<Self::Code>;
+
+    /// Returns the `(x, y)` whose Cantor pairing is z. Every z is the
+    /// pairing of exactly one `(x, y)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Pairing;
+    ///
+    /// assert_eq!(u32::cantor_unpair(&3192), (47, 32));
+    /// assert_eq!(i32::cantor_unpair(&1), (-1, 0));
+    /// assert_eq!(u8::cantor_unpair(&255), (20, 2));
+    /// ```
+    fn cantor_unpair(z: &Self::Code) -> (Self, Self);
+
+    /// Returns Szudzik's pairing, y² + x if x < y and x² + x + y otherwise,
+    /// which numbers the lattice points shell by shell and, unlike Cantor's,
+    /// fits every pair of `n`-bit values into `2n` bits.
+    ///
+    /// Signed values are first mapped to unsigned ones by the zigzag
+    /// bijection 0, −1, 1, −2, 2, … ↦ 0, 1, 2, 3, 4, ….
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result would overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Pairing;
+    ///
+    /// assert_eq!(u32::szudzik_pair(&47, &32), 2288);
+    /// assert_eq!(u32::szudzik_pair(&32, &47), 2241);
+    /// assert_eq!(i32::szudzik_pair(&0, &-1), 1);
+    /// ```
+    fn szudzik_pair(x: &Self, y: &Self) -> Self::Code {
+        Self::checked_szudzik_pair(x, y).expect("overflow in Szudzik pairing")
+    }
+
+    /// Returns an `Option` containing either Szudzik's pairing of x and y or
+    /// `None` if that would cause overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Pairing;
+    ///
+    /// assert_eq!(u8::checked_szudzik_pair(&15, &15), Some(255));
+    /// assert_eq!(u8::checked_szudzik_pair(&0, &16), None);
+    /// assert_eq!(i8::checked_szudzik_pair(&-8, &-8), Some(255));
+    /// ```
+    fn checked_szudzik_pair(x: &Self, y: &Self) -> Option<Self::Code>;
+
+    /// Returns the `(x, y)` whose Szudzik pairing is z. Every z is the
+    /// pairing of exactly one `(x, y)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Pairing;
+    ///
+    /// assert_eq!(u32::szudzik_unpair(&2288), (47, 32));
+    /// assert_eq!(u8::szudzik_unpair(&255), (15, 15));
+    /// assert_eq!(i8::szudzik_unpair(&255), (-8, -8));
+    /// ```
+    fn szudzik_unpair(z: &Self::Code) -> (Self, Self);
+}
+
+macro_rules! pairing_trait_for_unsigned {
+    ($type:ty) => {
+        impl $crate::combinatorics::pairing::Pairing for $type {
+            type Code = $type;
+
+            fn checked_cantor_pair(x: &Self, y: &Self) -> ::core::option::Option<Self::Code> {
+                let sum = x.checked_add(*y)?;
+                // Halve whichever of the two consecutive factors is even
+                // before multiplying so that only the result can overflow.
+                let next = sum.checked_add(1)?;
+                let triangle = if sum % 2 == 0 {
+                    (sum / 2).checked_mul(next)?
+                } else {
+                    sum.checked_mul(next / 2)?
+                };
+                triangle.checked_add(*y)
+            }
+
+            fn cantor_unpair(z: &Self::Code) -> (Self, Self) {
+                let z = *z;
+                // The diagonal w is the largest with w(w + 1) / 2 <= z, which
+                // is within one or two of √(2z).
+                let mut w = if z <= <$type>::MAX / 2 {
+                    (2 * z).isqrt()
+                } else {
+                    2 * (z / 2).isqrt()
+                };
+                let triangle = |w: $type| {
+                    if w % 2 == 0 {
+                        (w / 2).checked_mul(w + 1)
+                    } else {
+                        w.checked_mul(w / 2 + 1)
+                    }
+                };
+                while triangle(w).map_or(true, |t| t > z) {
+                    w -= 1;
+                }
+                while triangle(w + 1).map_or(false, |t| t <= z) {
+                    w += 1;
+                }
+                let y = z - triangle(w).unwrap();
+                (w - y, y)
+            }
+
+            fn checked_szudzik_pair(x: &Self, y: &Self) -> ::core::option::Option<Self::Code> {
+                let (x, y) = (*x, *y);
+                if x < y {
+                    y.checked_mul(y)?.checked_add(x)
+                } else {
+                    x.checked_mul(x)?.checked_add(x)?.checked_add(y)
+                }
+            }
+
+            fn szudzik_unpair(z: &Self::Code) -> (Self, Self) {
+                let z = *z;
+                let root = z.isqrt();
+                let remainder = z - root * root;
+                if remainder < root {
+                    (remainder, root)
+                } else {
+                    (root, remainder - root)
+                }
+            }
+        }
+    };
+}
+
+pairing_trait_for_unsigned!(::core::primitive::u8);
+pairing_trait_for_unsigned!(::core::primitive::u16);
+pairing_trait_for_unsigned!(::core::primitive::u32);
+pairing_trait_for_unsigned!(::core::primitive::u64);
+pairing_trait_for_unsigned!(::core::primitive::u128);
+pairing_trait_for_unsigned!(::core::primitive::usize);
+
+macro_rules! pairing_trait_for_signed {
+    ($type:ty, $unsigned:ty) => {
+        impl $crate::combinatorics::pairing::Pairing for $type {
+            type Code = $unsigned;
+
+            fn checked_cantor_pair(x: &Self, y: &Self) -> ::core::option::Option<Self::Code> {
+                <$unsigned as $crate::combinatorics::pairing::Pairing>::checked_cantor_pair(
+                    &Self::fold(*x),
+                    &Self::fold(*y),
+                )
+            }
+
+            fn cantor_unpair(z: &Self::Code) -> (Self, Self) {
+                let (x, y) =
+                    <$unsigned as $crate::combinatorics::pairing::Pairing>::cantor_unpair(z);
+                (Self::unfold(x), Self::unfold(y))
+            }
+
+            fn checked_szudzik_pair(x: &Self, y: &Self) -> ::core::option::Option<Self::Code> {
+                <$unsigned as $crate::combinatorics::pairing::Pairing>::checked_szudzik_pair(
+                    &Self::fold(*x),
+                    &Self::fold(*y),
+                )
+            }
+
+            fn szudzik_unpair(z: &Self::Code) -> (Self, Self) {
+                let (x, y) =
+                    <$unsigned as $crate::combinatorics::pairing::Pairing>::szudzik_unpair(z);
+                (Self::unfold(x), Self::unfold(y))
+            }
+        }
+
+        impl $crate::combinatorics::pairing::Zigzag for $type {
+            type Unsigned = $unsigned;
+
+            fn fold(n: Self) -> $unsigned {
+                ((n << 1) ^ (n >> (<$type>::BITS - 1))) as $unsigned
+            }
+
+            fn unfold(n: $unsigned) -> Self {
+                ((n >> 1) as $type) ^ -((n & 1) as $type)
+            }
+        }
+    };
+}
+
+/// The zigzag bijection between a signed type and its unsigned counterpart.
+trait Zigzag {
+    type Unsigned;
+
+    fn fold(n: Self) -> Self::Unsigned;
+
+    fn unfold(n: Self::Unsigned) -> Self;
+}
+
+pairing_trait_for_signed!(::core::primitive::i8, ::core::primitive::u8);
+pairing_trait_for_signed!(::core::primitive::i16, ::core::primitive::u16);
+pairing_trait_for_signed!(::core::primitive::i32, ::core::primitive::u32);
+pairing_trait_for_signed!(::core::primitive::i64, ::core::primitive::u64);
+pairing_trait_for_signed!(::core::primitive::i128, ::core::primitive::u128);
+pairing_trait_for_signed!(::core::primitive::isize, ::core::primitive::usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_unsigned_bijection {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                // The first codes cover whole diagonals and shells in order.
+                let mut z: $type = 0;
+                for sum in 0..=20 {
+                    for y in 0..=sum {
+                        let x = sum - y;
+                        assert_eq!(<$type>::checked_cantor_pair(&x, &y), Some(z));
+                        assert_eq!(<$type>::cantor_unpair(&z), (x, y));
+                        z += 1;
+                    }
+                }
+                let mut z: $type = 0;
+                for shell in 0..=15 {
+                    let cells = (0..shell)
+                        .map(|x| (x, shell))
+                        .chain((0..=shell).map(|y| (shell, y)));
+                    for (x, y) in cells {
+                        assert_eq!(<$type>::checked_szudzik_pair(&x, &y), Some(z));
+                        assert_eq!(<$type>::szudzik_unpair(&z), (x, y));
+                        z = z.wrapping_add(1);
+                    }
+                }
+
+                // Codes near the top of the range round-trip and the pairs
+                // just past it are reported as overflow.
+                for z in (0..=200).map(|k| <$type>::MAX - k) {
+                    let (x, y) = <$type>::cantor_unpair(&z);
+                    assert_eq!(<$type>::checked_cantor_pair(&x, &y), Some(z));
+                    let (x, y) = <$type>::szudzik_unpair(&z);
+                    assert_eq!(<$type>::checked_szudzik_pair(&x, &y), Some(z));
+                }
+                let (x, y) = <$type>::cantor_unpair(&<$type>::MAX);
+                assert_eq!(<$type>::checked_cantor_pair(&(x + y + 1), &0), None);
+                let root = <$type>::MAX.isqrt();
+                assert_eq!(
+                    <$type>::checked_szudzik_pair(&root, &root),
+                    Some(<$type>::MAX)
+                );
+                assert_eq!(<$type>::checked_szudzik_pair(&0, &(root + 1)), None);
+            }
+        };
+    }
+
+    test_unsigned_bijection!(::core::primitive::u8, test_u8_bijection);
+    test_unsigned_bijection!(::core::primitive::u16, test_u16_bijection);
+    test_unsigned_bijection!(::core::primitive::u32, test_u32_bijection);
+    test_unsigned_bijection!(::core::primitive::u64, test_u64_bijection);
+    test_unsigned_bijection!(::core::primitive::u128, test_u128_bijection);
+    test_unsigned_bijection!(::core::primitive::usize, test_usize_bijection);
+
+    macro_rules! test_signed_bijection {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let mut cantor_codes = ::std::collections::HashSet::new();
+                let mut szudzik_codes = ::std::collections::HashSet::new();
+                for x in -5..5 {
+                    for y in -5..5 {
+                        let z = <$type>::cantor_pair(&x, &y);
+                        assert_eq!(<$type>::cantor_unpair(&z), (x, y));
+                        assert!(cantor_codes.insert(z));
+                    }
+                }
+                for x in -8..8 {
+                    for y in -8..8 {
+                        let z = <$type>::szudzik_pair(&x, &y);
+                        assert_eq!(<$type>::szudzik_unpair(&z), (x, y));
+                        assert!(szudzik_codes.insert(z));
+                    }
+                }
+                // The 16 × 16 square centred on the origin fills the first
+                // 256 Szudzik codes exactly.
+                assert!(szudzik_codes.iter().all(|&z| (z as u128) < 256));
+
+                for &(x, y) in &[
+                    (<$type>::MIN, <$type>::MIN),
+                    (<$type>::MAX, <$type>::MIN),
+                    (<$type>::MIN, <$type>::MAX),
+                ] {
+                    assert_eq!(<$type>::checked_cantor_pair(&x, &y), None);
+                    assert_eq!(<$type>::checked_szudzik_pair(&x, &y), None);
+                }
+            }
+        };
+    }
+
+    test_signed_bijection!(::core::primitive::i8, test_i8_bijection);
+    test_signed_bijection!(::core::primitive::i16, test_i16_bijection);
+    test_signed_bijection!(::core::primitive::i32, test_i32_bijection);
+    test_signed_bijection!(::core::primitive::i64, test_i64_bijection);
+    test_signed_bijection!(::core::primitive::i128, test_i128_bijection);
+    test_signed_bijection!(::core::primitive::isize, test_isize_bijection);
+}
@@ -0,0 +1,768 @@
+use crate::number_theory::primes::{power, Modular, Small};
+
+pub trait StandardYoungTableaux: Sized {
+    /// Returns an `Option` containing either the number of standard Young
+    /// tableaux whose shape is the given partition, or `None` if that would
+    /// cause overflow or the parts are not in nonincreasing order.
+    ///
+    /// The count is n! divided by the product of the hook lengths of the
+    /// cells of the shape. It is assembled from its prime factorization, so
+    /// nothing larger than the result is ever computed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::StandardYoungTableaux;
+    ///
+    /// assert_eq!(u32::count_standard_young_tableaux(&[3, 2]), Some(5));
+    /// assert_eq!(u32::count_standard_young_tableaux(&[4, 3, 1]), Some(70));
+    /// assert_eq!(u32::count_standard_young_tableaux(&[]), Some(1));
+    /// assert_eq!(u8::count_standard_young_tableaux(&[4, 3, 2, 1]), None);
+    /// assert_eq!(u32::count_standard_young_tableaux(&[2, 3]), None);
+    /// ```
+    fn count_standard_young_tableaux(partition: &[usize]) -> Option<Self>;
+}
+
+pub trait RestrictedPartitions: Sized {
+    /// Returns an `Option` containing either the number of partitions of n
+    /// into distinct parts or `None` if that would cause overflow or n does
+    /// not fit in a `usize`.
+    ///
+    /// This takes O(n<sup>3/2</sup>) time and O(n) space, where n is at most
+    /// a few thousand whenever the result fits a primitive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::RestrictedPartitions;
+    ///
+    /// let counts: Vec<_> = (0..10).map(|n| u32::distinct_part_partitions(&n).unwrap()).collect();
+    /// assert_eq!(counts, vec![1, 1, 1, 2, 2, 3, 4, 5, 6, 8]);
+    /// assert_eq!(u8::distinct_part_partitions(&29), None);
+    /// ```
+    fn distinct_part_partitions(n: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the number of partitions of n
+    /// into odd parts or `None` if that would cause overflow or n does not
+    /// fit in a `usize`.
+    ///
+    /// By Euler's partition theorem, this is the number of partitions of n
+    /// into distinct parts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::RestrictedPartitions;
+    ///
+    /// // 7, 5 + 1 + 1, 3 + 3 + 1, 3 + 1 + 1 + 1 + 1, and 1 + 1 + 1 + 1 + 1 + 1 + 1.
+    /// assert_eq!(u32::odd_part_partitions(&7), Some(5));
+    /// ```
+    fn odd_part_partitions(n: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the number of partitions of n
+    /// into at most k parts or `None` if that would cause overflow or n does
+    /// not fit in a `usize`.
+    ///
+    /// For k up to three this is a closed form. Otherwise it takes
+    /// O(n min(n, k)) time and O(min(n, k<sup>2</sup>)) space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::RestrictedPartitions;
+    ///
+    /// assert_eq!(u32::partitions_into_at_most(&10, &3), Some(14));
+    /// assert_eq!(u32::partitions_into_at_most(&10, &10), Some(42));
+    /// assert_eq!(u32::partitions_into_at_most(&0, &0), Some(1));
+    /// assert_eq!(u64::partitions_into_at_most(&(1 << 40), &2), Some((1 << 39) + 1));
+    /// assert_eq!(u64::partitions_into_at_most(&(1 << 40), &4), None);
+    /// ```
+    fn partitions_into_at_most(n: &Self, k: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the number of partitions of n
+    /// into parts no larger than m or `None` if that would cause overflow or
+    /// n does not fit in a `usize`.
+    ///
+    /// By conjugation, this is the number of partitions of n into at most m
+    /// parts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::RestrictedPartitions;
+    ///
+    /// assert_eq!(u32::partitions_with_parts_at_most(&10, &3), Some(14));
+    /// assert_eq!(u64::partitions_with_parts_at_most(&100, &100), Some(190_569_292));
+    /// ```
+    fn partitions_with_parts_at_most(n: &Self, m: &Self) -> Option<Self>;
+}
+
+/// Returns an iterator over the partitions of n, each a `Vec` of parts in
+/// nonincreasing order, going through the partitions in reverse
+/// lexicographic order.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::combinatorics::partitions::partitions;
+///
+/// let all: Vec<_> = partitions(4).collect();
+/// assert_eq!(
+///     all,
+///     vec![vec![4], vec![3, 1], vec![2, 2], vec![2, 1, 1], vec![1, 1, 1, 1]]
+/// );
+///
+/// assert_eq!(partitions(20).count(), 627);
+/// ```
+pub fn partitions(n: usize) -> Partitions {
+    Partitions {
+        parts: if n == 0 { Vec::new() } else { vec![n] },
+        done: false,
+    }
+}
+
+/// The iterator returned by `partitions`.
+#[derive(Clone, Debug)]
+pub struct Partitions {
+    parts: Vec<usize>,
+    done: bool,
+}
+
+impl Iterator for Partitions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self.parts.clone();
+
+        // Take one from the last part greater than one and spread it and the
+        // ones after it as evenly as possible in parts no larger than what
+        // remains of that part.
+        let ones = self
+            .parts
+            .iter()
+            .rev()
+            .take_while(|&&part| part == 1)
+            .count();
+        self.parts.truncate(self.parts.len() - ones);
+        match self.parts.pop() {
+            Some(part) => {
+                let size = part - 1;
+                let mut remaining = ones + part;
+                while remaining >= size {
+                    self.parts.push(size);
+                    remaining -= size;
+                }
+                if remaining > 0 {
+                    self.parts.push(remaining);
+                }
+            }
+            None => self.done = true,
+        }
+        Some(result)
+    }
+}
+
+/// Returns the prime factorization of the number of standard Young tableaux
+/// of the given shape as `(prime, exponent)` pairs, or `None` if the parts are
+/// not in nonincreasing order.
+fn tableaux_factorization(partition: &[usize]) -> Option<Vec<(usize, u32)>> {
+    if partition.windows(2).any(|pair| pair[0] < pair[1]) {
+        return None;
+    }
+    let n = partition
+        .iter()
+        .try_fold(0usize, |sum, &part| sum.checked_add(part))?;
+
+    let sieve = crate::number_theory::SpfSieve::new(n as u64);
+    let mut exponents = vec![0i64; n + 1];
+    let mut add_factors = |m: usize, sign: i64| {
+        for (p, e) in sieve.factorize(m as u64) {
+            exponents[p as usize] += sign * e as i64;
+        }
+    };
+
+    for m in 2..=n {
+        add_factors(m, 1);
+    }
+    // The hook of a cell is itself together with the cells to its right and
+    // below it.
+    let columns = partition.first().copied().unwrap_or(0);
+    let mut column_lengths = vec![0usize; columns];
+    for &part in partition {
+        for length in &mut column_lengths[..part] {
+            *length += 1;
+        }
+    }
+    for (i, &part) in partition.iter().enumerate() {
+        for (j, &column_length) in column_lengths[..part].iter().enumerate() {
+            add_factors((part - j) + (column_length - i) - 1, -1);
+        }
+    }
+
+    Some(
+        exponents
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, exponent)| exponent != 0)
+            .map(|(p, exponent)| (p, exponent as u32))
+            .collect(),
+    )
+}
+
+macro_rules! standard_young_tableaux_trait_for_unsigned {
+    ($type:ty) => {
+        impl $crate::combinatorics::partitions::StandardYoungTableaux for $type {
+            fn count_standard_young_tableaux(partition: &[usize]) -> ::core::option::Option<Self> {
+                let mut count: $type = 1;
+                for (p, exponent) in
+                    $crate::combinatorics::partitions::tableaux_factorization(partition)?
+                {
+                    let p = <$type as ::core::convert::TryFrom<usize>>::try_from(p).ok()?;
+                    count = count.checked_mul(p.checked_pow(exponent)?)?;
+                }
+                ::core::option::Option::Some(count)
+            }
+        }
+    };
+}
+
+standard_young_tableaux_trait_for_unsigned!(::core::primitive::u8);
+standard_young_tableaux_trait_for_unsigned!(::core::primitive::u16);
+standard_young_tableaux_trait_for_unsigned!(::core::primitive::u32);
+standard_young_tableaux_trait_for_unsigned!(::core::primitive::u64);
+standard_young_tableaux_trait_for_unsigned!(::core::primitive::u128);
+standard_young_tableaux_trait_for_unsigned!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl StandardYoungTableaux for rug::Integer {
+    fn count_standard_young_tableaux(partition: &[usize]) -> Option<Self> {
+        let mut count = Self::from(1);
+        for (p, exponent) in tableaux_factorization(partition)? {
+            count *= Self::from(Self::u_pow_u(
+                <u32 as core::convert::TryFrom<usize>>::try_from(p).ok()?,
+                exponent,
+            ));
+        }
+        Some(count)
+    }
+}
+
+/// Returns the number of standard Young tableaux whose shape is the given
+/// partition modulo m.
+///
+/// The count is found from its prime factorization, so m need not be prime.
+///
+/// # Panics
+///
+/// Panics if m is zero or the parts are not in nonincreasing order.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::combinatorics::partitions::count_standard_young_tableaux_mod;
+///
+/// assert_eq!(count_standard_young_tableaux_mod(&[4, 3, 1], 12), 10);
+/// assert_eq!(
+///     count_standard_young_tableaux_mod(&[50, 50, 50], 1_000_000_007),
+///     72_573_045
+/// );
+/// ```
+pub fn count_standard_young_tableaux_mod(partition: &[usize], m: u64) -> u64 {
+    assert!(m != 0, "modulus must be nonzero");
+    let factorization =
+        tableaux_factorization(partition).expect("parts must be in nonincreasing order");
+
+    let modular = Small { n: m };
+    let mut count = 1 % m;
+    for (p, exponent) in factorization {
+        let factor = power(&modular, p as u64 % m, exponent as u128);
+        count = modular.mul(count, factor);
+    }
+    count
+}
+
+/// Returns the number of partitions of n into distinct parts, found with
+/// the given addition, which returns `None` on overflow.
+///
+/// The partitions of j into exactly k distinct parts are counted for each k
+/// in turn. Taking one from each part leaves a partition of j − k into k
+/// distinct parts or, if the smallest part was one, into k − 1 of them.
+fn count_distinct_part_partitions<T: Clone>(
+    n: usize,
+    zero: T,
+    one: T,
+    add: impl Fn(&T, &T) -> Option<T>,
+) -> Option<T> {
+    let mut exact = vec![zero.clone(); n + 1];
+    exact[0] = one.clone();
+    let mut total = if n == 0 { one } else { zero.clone() };
+    let mut k = 1;
+    while k * (k + 1) / 2 <= n {
+        let mut next = vec![zero.clone(); n + 1];
+        for j in k * (k + 1) / 2..=n {
+            next[j] = add(&next[j - k], &exact[j - k])?;
+        }
+        total = add(&total, &next[n])?;
+        exact = next;
+        k += 1;
+    }
+    Some(total)
+}
+
+/// Returns the number of partitions of n into at most k parts, found with
+/// the given addition, which returns `None` on overflow.
+///
+/// These are counted as the partitions of n into parts no larger than k.
+/// When k is small next to n, the counts for each largest part m are kept
+/// for only the last m totals, and otherwise one table of every total up to
+/// n gains one more part size at a time.
+fn count_partitions_into_at_most<T: Clone>(
+    n: usize,
+    k: usize,
+    zero: T,
+    one: T,
+    add: impl Fn(&T, &T) -> Option<T>,
+) -> Option<T> {
+    let k = core::cmp::min(n, k);
+    if k * (k + 1) / 2 <= n {
+        // The partitions of j into parts no larger than m either have none
+        // equal to m or are partitions of j − m with one more m.
+        let mut rows: Vec<Vec<T>> = (1..=k).map(|m| vec![zero.clone(); m]).collect();
+        let mut count = one;
+        for j in 0..=n {
+            if j != 0 {
+                count = zero.clone();
+            }
+            for (m, row) in (1..=k).zip(&mut rows) {
+                let slot = &mut row[j % m];
+                if j >= m {
+                    count = add(&count, slot)?;
+                }
+                *slot = count.clone();
+            }
+        }
+        return Some(count);
+    }
+
+    let mut counts = vec![zero; n + 1];
+    counts[0] = one;
+    for part in 1..=k {
+        for j in part..=n {
+            counts[j] = add(&counts[j], &counts[j - part])?;
+        }
+    }
+    Some(counts.swap_remove(n))
+}
+
+/// Returns an `Option` containing either the number of partitions of n into
+/// at most k parts for k up to three or `None` if that is past u128::MAX.
+fn partitions_into_at_most_three(n: u128, k: u128) -> Option<u128> {
+    match k {
+        0 => Some((n == 0) as u128),
+        1 => Some(1),
+        2 => Some(n / 2 + 1),
+        _ => {
+            // The nearest integer to (n + 3)^2 / 12, with n + 3 = 12 q + r
+            // so that the square is never formed.
+            let m = n.checked_add(3)?;
+            let (q, r) = (m / 12, m % 12);
+            12u128
+                .checked_mul(q.checked_mul(q)?)?
+                .checked_add(q.checked_mul(2 * r)?)?
+                .checked_add((r * r + 6) / 12)
+        }
+    }
+}
+
+/// Returns an `Option` containing either a lower bound on the number of
+/// partitions of n into at most k parts for k of at least three or `None`
+/// if that bound is past u128::MAX.
+fn partitions_into_at_most_lower_bound(n: u128, k: u128) -> Option<u128> {
+    // With parts no larger than m, there are the partitions of n − a m into
+    // parts no larger than m − 1 for each count a of parts equal to m. Those
+    // with up to t = ⌊n / 2m⌋ of them are each at least the count for
+    // n − t m, and fewer part sizes only give fewer partitions.
+    let (mut n, mut bound) = (n, 1u128);
+    for m in (4..=core::cmp::min(k, 128)).rev() {
+        let t = n / (2 * m);
+        bound = bound.checked_mul(t + 1)?;
+        n -= t * m;
+    }
+    bound.checked_mul(partitions_into_at_most_three(n, 3)?)
+}
+
+macro_rules! restricted_partitions_trait_for_unsigned {
+    ($type:ty) => {
+        impl $crate::combinatorics::partitions::RestrictedPartitions for $type {
+            fn distinct_part_partitions(n: &Self) -> ::core::option::Option<Self> {
+                // For b bits, each subset of 1, 2, …, b along with n less its
+                // sum is a partition into distinct parts once n is past
+                // b (b + 3) / 2, and there are 2^b of those.
+                let bits = <$type>::BITS as $type;
+                if *n > bits * (bits + 3) / 2 {
+                    return ::core::option::Option::None;
+                }
+                // Every count in the table is at most the result, so any
+                // overflow means that the result overflows.
+                let n = <usize as ::core::convert::TryFrom<$type>>::try_from(*n).ok()?;
+                $crate::combinatorics::partitions::count_distinct_part_partitions(
+                    n,
+                    0,
+                    1,
+                    |a: &$type, b: &$type| a.checked_add(*b),
+                )
+            }
+
+            fn odd_part_partitions(n: &Self) -> ::core::option::Option<Self> {
+                Self::distinct_part_partitions(n)
+            }
+
+            fn partitions_into_at_most(n: &Self, k: &Self) -> ::core::option::Option<Self> {
+                let k = ::core::cmp::min(*n, *k);
+                if k <= 3 {
+                    let count = $crate::combinatorics::partitions::partitions_into_at_most_three(
+                        *n as u128, k as u128,
+                    )?;
+                    return <$type as ::core::convert::TryFrom<u128>>::try_from(count).ok();
+                }
+                let bound = $crate::combinatorics::partitions::partitions_into_at_most_lower_bound(
+                    *n as u128, k as u128,
+                )?;
+                if bound > <$type>::MAX as u128 {
+                    return ::core::option::Option::None;
+                }
+                let n = <usize as ::core::convert::TryFrom<$type>>::try_from(*n).ok()?;
+                let k = k as usize;
+                $crate::combinatorics::partitions::count_partitions_into_at_most(
+                    n,
+                    k,
+                    0,
+                    1,
+                    |a: &$type, b: &$type| a.checked_add(*b),
+                )
+            }
+
+            fn partitions_with_parts_at_most(n: &Self, m: &Self) -> ::core::option::Option<Self> {
+                Self::partitions_into_at_most(n, m)
+            }
+        }
+    };
+}
+
+restricted_partitions_trait_for_unsigned!(::core::primitive::u8);
+restricted_partitions_trait_for_unsigned!(::core::primitive::u16);
+restricted_partitions_trait_for_unsigned!(::core::primitive::u32);
+restricted_partitions_trait_for_unsigned!(::core::primitive::u64);
+restricted_partitions_trait_for_unsigned!(::core::primitive::u128);
+restricted_partitions_trait_for_unsigned!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl RestrictedPartitions for rug::Integer {
+    fn distinct_part_partitions(n: &Self) -> Option<Self> {
+        count_distinct_part_partitions(n.to_usize()?, Self::new(), Self::from(1), |a, b| {
+            Some(Self::from(a + b))
+        })
+    }
+
+    fn odd_part_partitions(n: &Self) -> Option<Self> {
+        Self::distinct_part_partitions(n)
+    }
+
+    fn partitions_into_at_most(n: &Self, k: &Self) -> Option<Self> {
+        if *k < 0 {
+            return None;
+        }
+        let k = k.to_usize().unwrap_or(usize::MAX);
+        count_partitions_into_at_most(n.to_usize()?, k, Self::new(), Self::from(1), |a, b| {
+            Some(Self::from(a + b))
+        })
+    }
+
+    fn partitions_with_parts_at_most(n: &Self, m: &Self) -> Option<Self> {
+        Self::partitions_into_at_most(n, m)
+    }
+}
+
+/// Returns the number of partitions of n into distinct parts modulo m.
+///
+/// By Euler's partition theorem, this is also the number of partitions of n
+/// into odd parts modulo m. This takes O(n<sup>3/2</sup>) time and O(n)
+/// space.
+///
+/// # Panics
+///
+/// Panics if m is zero.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::combinatorics::partitions::distinct_part_partitions_mod;
+///
+/// assert_eq!(distinct_part_partitions_mod(9, 5), 3);
+/// assert_eq!(distinct_part_partitions_mod(10_000, 1_000_000_007), 883_287_351);
+/// ```
+pub fn distinct_part_partitions_mod(n: u64, m: u64) -> u64 {
+    assert!(m != 0, "modulus must be nonzero");
+    let modular = Small { n: m };
+    count_distinct_part_partitions(n as usize, 0, 1 % m, |&a, &b| Some(modular.add(a, b))).unwrap()
+}
+
+/// Returns the number of partitions of n into at most k parts modulo m.
+///
+/// By conjugation, this is also the number of partitions of n into parts no
+/// larger than k modulo m. This takes O(n min(n, k)) time and
+/// O(min(n, k<sup>2</sup>)) space.
+///
+/// # Panics
+///
+/// Panics if m is zero.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::combinatorics::partitions::partitions_into_at_most_mod;
+///
+/// assert_eq!(partitions_into_at_most_mod(10, 3, 10), 4);
+/// assert_eq!(partitions_into_at_most_mod(1000, 1000, 1_000_000_007), 709_496_666);
+/// ```
+pub fn partitions_into_at_most_mod(n: u64, k: u64, m: u64) -> u64 {
+    assert!(m != 0, "modulus must be nonzero");
+    let k = core::cmp::min(n, k) as usize;
+    let modular = Small { n: m };
+    count_partitions_into_at_most(n as usize, k, 0, 1 % m, |&a, &b| Some(modular.add(a, b)))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partitions_are_complete_and_ordered() {
+        let counts = [1, 1, 2, 3, 5, 7, 11, 15, 22, 30, 42, 56, 77, 101, 135, 176];
+        for (n, &count) in counts.iter().enumerate() {
+            let all: Vec<_> = partitions(n).collect();
+            assert_eq!(all.len(), count);
+            assert!(all.windows(2).all(|pair| pair[0] > pair[1]));
+            for partition in &all {
+                assert_eq!(partition.iter().sum::<usize>(), n);
+                assert!(partition.windows(2).all(|pair| pair[0] >= pair[1]));
+                assert!(partition.iter().all(|&part| part > 0));
+            }
+        }
+    }
+
+    /// Counts standard Young tableaux by removing the cell holding the
+    /// largest entry, which is always at the end of some row and column.
+    fn brute_force_tableaux(partition: &mut Vec<usize>) -> u64 {
+        if partition.is_empty() {
+            return 1;
+        }
+        let mut count = 0;
+        for i in 0..partition.len() {
+            if i + 1 == partition.len() || partition[i] > partition[i + 1] {
+                partition[i] -= 1;
+                let removed_row = partition[i] == 0;
+                if removed_row {
+                    partition.pop();
+                }
+                count += brute_force_tableaux(partition);
+                if removed_row {
+                    partition.push(0);
+                }
+                partition[i] += 1;
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn hook_length_formula_matches_brute_force() {
+        for n in 0..=12 {
+            let mut sum_of_squares = rug::Integer::new();
+            for mut partition in partitions(n) {
+                let count = u64::count_standard_young_tableaux(&partition).unwrap();
+                assert_eq!(count, brute_force_tableaux(&mut partition));
+                sum_of_squares += count * count;
+            }
+            // The shapes of the pairs of tableaux in the Robinson–Schensted
+            // correspondence.
+            assert_eq!(
+                sum_of_squares,
+                rug::Integer::from(rug::Integer::factorial(n as u32))
+            );
+        }
+    }
+
+    macro_rules! test_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                for n in 0..=40 {
+                    for partition in partitions(n).step_by(97) {
+                        let big = rug::Integer::count_standard_young_tableaux(&partition).unwrap();
+                        let expected = if big <= <$type>::MAX {
+                            ::core::option::Option::Some(big.to_u128().unwrap() as $type)
+                        } else {
+                            ::core::option::Option::None
+                        };
+                        assert_eq!(
+                            <$type>::count_standard_young_tableaux(&partition),
+                            expected,
+                            "{:?}",
+                            partition
+                        );
+                        for m in [1u64, 7, 360, 1_000_000_007, u64::MAX] {
+                            assert_eq!(
+                                count_standard_young_tableaux_mod(&partition, m),
+                                rug::Integer::from(&big % m).to_u64().unwrap()
+                            );
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    test_against_rug!(::core::primitive::u8, test_u8_against_rug);
+    test_against_rug!(::core::primitive::u16, test_u16_against_rug);
+    test_against_rug!(::core::primitive::u32, test_u32_against_rug);
+    test_against_rug!(::core::primitive::u64, test_u64_against_rug);
+    test_against_rug!(::core::primitive::u128, test_u128_against_rug);
+    test_against_rug!(::core::primitive::usize, test_usize_against_rug);
+
+    #[test]
+    fn restricted_counts_match_enumeration() {
+        for n in 0..=30u64 {
+            let all: Vec<_> = partitions(n as usize).collect();
+            let distinct = all
+                .iter()
+                .filter(|partition| partition.windows(2).all(|pair| pair[0] > pair[1]))
+                .count() as u64;
+            let odd = all
+                .iter()
+                .filter(|partition| partition.iter().all(|part| part % 2 == 1))
+                .count() as u64;
+            assert_eq!(u64::distinct_part_partitions(&n), Some(distinct));
+            assert_eq!(u64::odd_part_partitions(&n), Some(odd));
+            assert_eq!(distinct_part_partitions_mod(n, 7), distinct % 7);
+            for k in 0..=n + 1 {
+                let at_most = all
+                    .iter()
+                    .filter(|partition| partition.len() as u64 <= k)
+                    .count() as u64;
+                let bounded = all
+                    .iter()
+                    .filter(|partition| partition.iter().all(|&part| part as u64 <= k))
+                    .count() as u64;
+                assert_eq!(u64::partitions_into_at_most(&n, &k), Some(at_most));
+                assert_eq!(u64::partitions_with_parts_at_most(&n, &k), Some(bounded));
+                assert_eq!(partitions_into_at_most_mod(n, k, 7), at_most % 7);
+            }
+        }
+        assert_eq!(u64::partitions_into_at_most(&0, &u64::MAX), Some(1));
+        assert_eq!(u8::partitions_into_at_most(&200, &1), Some(1));
+    }
+
+    #[test]
+    fn restricted_counts_for_large_n() {
+        assert_eq!(
+            u64::partitions_into_at_most(&(1 << 40), &2),
+            Some((1 << 39) + 1)
+        );
+        // The nearest integer to (n + 3)^2 / 12.
+        let expected = (rug::Integer::from(rug::Integer::u_pow_u(2, 64)) + 3u8).square() + 6u8;
+        let expected = expected / 12u8;
+        assert_eq!(
+            u128::partitions_into_at_most(&(1 << 64), &3),
+            expected.to_u128()
+        );
+        assert_eq!(u64::partitions_into_at_most(&(1 << 40), &3), None);
+        assert_eq!(u128::partitions_into_at_most(&u128::MAX, &3), None);
+        assert_eq!(u64::partitions_into_at_most(&(1 << 40), &(1 << 40)), None);
+        assert_eq!(
+            usize::partitions_into_at_most(&usize::MAX, &usize::MAX),
+            None
+        );
+        assert_eq!(u128::partitions_into_at_most(&u128::MAX, &1), Some(1));
+        assert_eq!(
+            u128::partitions_into_at_most(&u128::MAX, &2),
+            Some(1 << 127)
+        );
+        assert_eq!(u64::distinct_part_partitions(&u64::MAX), None);
+        assert_eq!(usize::odd_part_partitions(&usize::MAX), None);
+        // Small k next to n uses the rolling rows, checked against the table.
+        for n in [100u64, 1000, 5000] {
+            for k in 4..=7 {
+                let rolling = partitions_into_at_most_mod(n, k, 1_000_000_007);
+                let mut counts = vec![0u64; n as usize + 1];
+                counts[0] = 1;
+                for part in 1..=k as usize {
+                    for j in part..=n as usize {
+                        counts[j] = (counts[j] + counts[j - part]) % 1_000_000_007;
+                    }
+                }
+                assert_eq!(rolling, counts[n as usize], "n = {}, k = {}", n, k);
+            }
+        }
+    }
+
+    macro_rules! test_restricted_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let expected = |big: rug::Integer| {
+                    if big <= <$type>::MAX {
+                        ::core::option::Option::Some(big.to_u128().unwrap() as $type)
+                    } else {
+                        ::core::option::Option::None
+                    }
+                };
+                let fits = |n: &u32| *n as u128 <= <$type>::MAX as u128;
+                for n in (0..=3000u32).step_by(37).filter(fits) {
+                    let big_n = rug::Integer::from(n);
+                    assert_eq!(
+                        <$type>::distinct_part_partitions(&(n as $type)),
+                        expected(rug::Integer::distinct_part_partitions(&big_n).unwrap()),
+                        "n = {}",
+                        n
+                    );
+                }
+                for n in (0..=600u32).step_by(29).filter(fits) {
+                    for k in [0u32, 1, 2, 5, 17, 60, 600].iter().copied().filter(fits) {
+                        let big =
+                            rug::Integer::partitions_into_at_most(&n.into(), &k.into()).unwrap();
+                        assert_eq!(
+                            <$type>::partitions_into_at_most(&(n as $type), &(k as $type)),
+                            expected(big),
+                            "n = {}, k = {}",
+                            n,
+                            k
+                        );
+                    }
+                }
+            }
+        };
+    }
+
+    test_restricted_against_rug!(::core::primitive::u8, test_u8_restricted_against_rug);
+    test_restricted_against_rug!(::core::primitive::u16, test_u16_restricted_against_rug);
+    test_restricted_against_rug!(::core::primitive::u32, test_u32_restricted_against_rug);
+    test_restricted_against_rug!(::core::primitive::u64, test_u64_restricted_against_rug);
+    test_restricted_against_rug!(::core::primitive::u128, test_u128_restricted_against_rug);
+    test_restricted_against_rug!(::core::primitive::usize, test_usize_restricted_against_rug);
+
+    #[test]
+    fn trailing_zero_parts_are_ignored() {
+        assert_eq!(u32::count_standard_young_tableaux(&[3, 2, 0, 0]), Some(5));
+        assert_eq!(
+            u32::count_standard_young_tableaux(&[0, 0]),
+            u32::count_standard_young_tableaux(&[])
+        );
+    }
+}
@@ -0,0 +1,236 @@
+pub trait Primorial: Sized {
+    /// Returns an `Option` containing either the product of the first n
+    /// primes, p<sub>n</sub>#, or `None` if that would cause overflow or n is
+    /// negative. The product of no primes is one.
+    ///
+    /// For `rug::Integer`, this also returns `None` if the n<sup>th</sup>
+    /// prime does not fit in a `u32`. As `rug::Integer` has an inherent
+    /// `primorial` function taking a `u32` and multiplying the primes up to
+    /// it, call this one as `<rug::Integer as Primorial>::primorial`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Primorial;
+    ///
+    /// assert_eq!(u32::primorial(&0), Some(1));
+    /// assert_eq!(u32::primorial(&5), Some(2310));
+    /// assert_eq!(u32::primorial(&9), Some(223_092_870));
+    /// assert_eq!(u32::primorial(&10), None);
+    /// assert_eq!(u128::primorial(&26), Some(232_862_364_358_497_360_900_063_316_880_507_363_070));
+    /// assert_eq!(i8::primorial(&-1), None);
+    /// ```
+    fn primorial(n: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the product of the primes no
+    /// larger than x, x#, or `None` if that would cause overflow or x is
+    /// negative.
+    ///
+    /// For `rug::Integer`, this also returns `None` if x does not fit in a
+    /// `u32`, and otherwise agrees with `primorial_up_to`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::combinatorics::Primorial;
+    ///
+    /// assert_eq!(u8::primorial_up_to(&1), Some(1));
+    /// assert_eq!(u8::primorial_up_to(&10), Some(210));
+    /// assert_eq!(u8::primorial_up_to(&10), u8::primorial(&4));
+    /// assert_eq!(u8::primorial_up_to(&11), None);
+    /// assert_eq!(u64::primorial_up_to(&52), Some(614_889_782_588_491_410));
+    /// assert_eq!(u64::primorial_up_to(&u64::MAX), None);
+    /// assert_eq!(i8::primorial_up_to(&-1), None);
+    /// ```
+    fn primorial_up_to(x: &Self) -> Option<Self>;
+}
+
+macro_rules! primorial_trait_for_primitive {
+    ($type:ty) => {
+        impl $crate::combinatorics::primorial::Primorial for $type {
+            fn primorial(n: &Self) -> ::core::option::Option<Self> {
+                let n = <u128 as ::core::convert::TryFrom<$type>>::try_from(*n).ok()?;
+                let result = $crate::combinatorics::primorial::primorial(n)?;
+                <$type as ::core::convert::TryFrom<u128>>::try_from(result).ok()
+            }
+
+            fn primorial_up_to(x: &Self) -> ::core::option::Option<Self> {
+                let x = <u128 as ::core::convert::TryFrom<$type>>::try_from(*x).ok()?;
+                let result = $crate::combinatorics::primorial::primorial_up_to(x)?;
+                <$type as ::core::convert::TryFrom<u128>>::try_from(result).ok()
+            }
+        }
+    };
+}
+
+primorial_trait_for_primitive!(::core::primitive::i8);
+primorial_trait_for_primitive!(::core::primitive::u8);
+primorial_trait_for_primitive!(::core::primitive::i16);
+primorial_trait_for_primitive!(::core::primitive::u16);
+primorial_trait_for_primitive!(::core::primitive::i32);
+primorial_trait_for_primitive!(::core::primitive::u32);
+primorial_trait_for_primitive!(::core::primitive::i64);
+primorial_trait_for_primitive!(::core::primitive::u64);
+primorial_trait_for_primitive!(::core::primitive::i128);
+primorial_trait_for_primitive!(::core::primitive::u128);
+primorial_trait_for_primitive!(::core::primitive::isize);
+primorial_trait_for_primitive!(::core::primitive::usize);
+
+/// Returns the product of the first n primes, or `None` if that does not fit
+/// in a `u128`.
+fn primorial(n: u128) -> Option<u128> {
+    // This overflows after 26 primes, so n need not be counted down in full.
+    let mut result: u128 = 1;
+    for p in crate::number_theory::sieve::primes()
+        .map(u128::from)
+        .take(n.min(u8::MAX as u128) as usize)
+    {
+        result = result.checked_mul(p)?;
+    }
+    Some(result)
+}
+
+/// Returns the product of the primes no larger than x, or `None` if that does
+/// not fit in a `u128`.
+fn primorial_up_to(x: u128) -> Option<u128> {
+    // This overflows by the prime 103, so the loop ends quickly however large
+    // x is.
+    let mut result: u128 = 1;
+    for p in crate::number_theory::sieve::primes()
+        .map(u128::from)
+        .take_while(|&p| p <= x)
+    {
+        result = result.checked_mul(p)?;
+    }
+    Some(result)
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Primorial for rug::Integer {
+    fn primorial(n: &Self) -> Option<Self> {
+        if *n < 0 {
+            return None;
+        }
+        // There are 203,280,221 primes below 2³².
+        let n = n.to_usize().filter(|&n| n <= 203_280_221)?;
+        let primes: Vec<_> = crate::number_theory::sieve::primes().take(n).collect();
+        Some(crate::combinatorics::factorial::product(&primes))
+    }
+
+    fn primorial_up_to(x: &Self) -> Option<Self> {
+        if *x < 0 {
+            return None;
+        }
+        x.to_u32()
+            .map(crate::combinatorics::factorial::primorial_up_to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rug::Integer;
+
+    /// Returns the first n primes.
+    fn first_primes(n: usize) -> Vec<u32> {
+        let mut primes = Vec::new();
+        let mut p = Integer::from(1);
+        while primes.len() < n {
+            p.next_prime_mut();
+            primes.push(p.to_u32().unwrap());
+        }
+        primes
+    }
+
+    macro_rules! test_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let max = Integer::from(<$type>::MAX);
+                let primes = first_primes(40);
+                for n in 0..=primes.len() as u8 {
+                    let n = match <$type as ::core::convert::TryFrom<u8>>::try_from(n) {
+                        ::core::result::Result::Ok(n) => n,
+                        ::core::result::Result::Err(_) => break,
+                    };
+                    let expected: Integer = primes[..n as usize].iter().product();
+                    let expected = if expected <= max {
+                        ::core::option::Option::Some(expected)
+                    } else {
+                        ::core::option::Option::None
+                    };
+                    ::core::assert_eq!(<$type>::primorial(&n).map(Integer::from), expected);
+                }
+
+                for x in 0..=200u8 {
+                    let x = match <$type as ::core::convert::TryFrom<u8>>::try_from(x) {
+                        ::core::result::Result::Ok(x) => x,
+                        ::core::result::Result::Err(_) => break,
+                    };
+                    let expected = Integer::from(Integer::primorial(x as u32));
+                    let expected = if expected <= max {
+                        ::core::option::Option::Some(expected)
+                    } else {
+                        ::core::option::Option::None
+                    };
+                    ::core::assert_eq!(<$type>::primorial_up_to(&x).map(Integer::from), expected);
+                }
+
+                ::core::assert_eq!(
+                    <$type>::primorial(&<$type>::MAX),
+                    ::core::option::Option::None
+                );
+                ::core::assert_eq!(
+                    <$type>::primorial_up_to(&<$type>::MAX),
+                    ::core::option::Option::None
+                );
+            }
+        };
+    }
+
+    test_against_rug!(::core::primitive::i8, i8_primorial);
+    test_against_rug!(::core::primitive::u8, u8_primorial);
+    test_against_rug!(::core::primitive::i16, i16_primorial);
+    test_against_rug!(::core::primitive::u16, u16_primorial);
+    test_against_rug!(::core::primitive::i32, i32_primorial);
+    test_against_rug!(::core::primitive::u32, u32_primorial);
+    test_against_rug!(::core::primitive::i64, i64_primorial);
+    test_against_rug!(::core::primitive::u64, u64_primorial);
+    test_against_rug!(::core::primitive::i128, i128_primorial);
+    test_against_rug!(::core::primitive::u128, u128_primorial);
+    test_against_rug!(::core::primitive::isize, isize_primorial);
+    test_against_rug!(::core::primitive::usize, usize_primorial);
+
+    #[test]
+    fn negative_values() {
+        assert_eq!(i32::primorial(&-5), None);
+        assert_eq!(i32::primorial_up_to(&-5), None);
+        assert_eq!(<Integer as Primorial>::primorial(&Integer::from(-1)), None);
+        assert_eq!(Integer::primorial_up_to(&Integer::from(-1)), None);
+    }
+
+    #[test]
+    fn rug_primorial() {
+        let primes = first_primes(2000);
+        let mut expected = Integer::from(1);
+        for n in 0..=primes.len() {
+            assert_eq!(
+                <Integer as Primorial>::primorial(&Integer::from(n)),
+                Some(expected.clone()),
+                "n = {}",
+                n
+            );
+            if n < primes.len() {
+                expected *= primes[n];
+            }
+        }
+        for x in [0u32, 1, 2, 100, 7919, 100_000] {
+            assert_eq!(
+                Integer::primorial_up_to(&Integer::from(x)),
+                Some(Integer::from(Integer::primorial(x)))
+            );
+        }
+        assert_eq!(Integer::primorial_up_to(&Integer::from(u64::MAX)), None);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod champernowne;
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+pub mod spigot;
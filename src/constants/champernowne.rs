@@ -0,0 +1,231 @@
+/// Returns the n<sup>th</sup> digit after the radix point of the base-b
+/// Champernowne constant 0.123…(b − 1)10 11 12…, counting from zero.
+///
+/// The digit is found in O(log n) time by skipping whole blocks of numbers
+/// with the same number of digits.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::constants::champernowne::champernowne_digit;
+///
+/// // 0.12345678910111213…
+/// let digits: Vec<_> = (0..15).map(|n| champernowne_digit(n, 10)).collect();
+/// assert_eq!(digits, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 1, 0, 1, 1, 1, 2]);
+///
+/// // 0.1 10 11 100 101… in binary.
+/// let digits: Vec<_> = (0..8).map(|n| champernowne_digit(n, 2)).collect();
+/// assert_eq!(digits, vec![1, 1, 0, 1, 1, 1, 0, 0]);
+///
+/// assert_eq!(champernowne_digit(999_999_999_999, 10), 1);
+/// ```
+pub fn champernowne_digit(n: u64, base: u32) -> u32 {
+    assert!(base >= 2, "base must be at least two");
+    let (number, position, length) = locate(n.into(), base).unwrap();
+    digit_of(number, length - 1 - position, base)
+}
+
+/// Returns an `Iterator<Item = u32>` implementation that streams the digits
+/// after the radix point of the base-b Champernowne constant.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::constants::champernowne::champernowne_digits;
+///
+/// let digits: Vec<_> = champernowne_digits(16).skip(14).take(6).collect();
+/// assert_eq!(digits, vec![15, 1, 0, 1, 1, 1]);
+/// ```
+pub fn champernowne_digits(base: u32) -> ChampernowneDigits {
+    assert!(base >= 2, "base must be at least two");
+    ChampernowneDigits {
+        base,
+        number: 1,
+        position: 0,
+        length: 1,
+    }
+}
+
+/// The iterator returned by `champernowne_digits`. Its `nth` method takes
+/// O(log n) time.
+#[derive(Clone, Debug)]
+pub struct ChampernowneDigits {
+    base: u32,
+    number: u128,
+    position: u32,
+    length: u32,
+}
+
+impl Iterator for ChampernowneDigits {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.length == 0 {
+            return None;
+        }
+
+        let digit = digit_of(self.number, self.length - 1 - self.position, self.base);
+        self.position += 1;
+        if self.position == self.length {
+            self.position = 0;
+            match self.number.checked_add(1) {
+                Some(number) => {
+                    self.number = number;
+                    self.length = digit_count(number, self.base);
+                }
+                None => self.length = 0,
+            }
+        }
+        Some(digit)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if self.length == 0 {
+            return None;
+        }
+
+        // The offset of the current digit within the constant is recovered
+        // by counting the digits of every smaller number.
+        let offset = digits_before(self.number, self.base) + self.position as u128;
+        match locate(offset + n as u128, self.base) {
+            Some((number, position, length)) => {
+                self.number = number;
+                self.position = position;
+                self.length = length;
+                self.next()
+            }
+            None => {
+                self.length = 0;
+                None
+            }
+        }
+    }
+}
+
+/// Returns an `Option` containing either the number containing the
+/// n<sup>th</sup> digit, the position of the digit within it from the most
+/// significant end, and its digit count, or `None` if that number is past
+/// `u128::MAX`.
+fn locate(mut n: u128, base: u32) -> Option<(u128, u32, u32)> {
+    let base = base as u128;
+    let mut length = 1u32;
+    let mut first = 1u128;
+    // A base-b constant has (b − 1)·b^(k − 1) numbers of k digits, and a
+    // block too long to count in a u128 is sure to hold the n-th digit.
+    while let Some(block) = (base - 1)
+        .checked_mul(first)
+        .and_then(|count| count.checked_mul(length as u128))
+        .filter(|&block| n >= block)
+    {
+        n -= block;
+        first = first.checked_mul(base)?;
+        length += 1;
+    }
+    let number = first.checked_add(n / length as u128)?;
+    Some((number, (n % length as u128) as u32, length))
+}
+
+/// Returns the number of digits in the constant before those of `number`.
+fn digits_before(number: u128, base: u32) -> u128 {
+    let base = base as u128;
+    let mut count = 0;
+    let mut length = 1;
+    let mut first = 1u128;
+    while let Some(next) = first.checked_mul(base).filter(|&next| next <= number) {
+        count += (next - first) * length;
+        first = next;
+        length += 1;
+    }
+    count + (number - first) * length
+}
+
+fn digit_count(number: u128, base: u32) -> u32 {
+    let mut count = 1;
+    let mut number = number / base as u128;
+    while number != 0 {
+        number /= base as u128;
+        count += 1;
+    }
+    count
+}
+
+fn digit_of(number: u128, place: u32, base: u32) -> u32 {
+    ((number / (base as u128).pow(place)) % base as u128) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_digits(base: u32, count: usize) -> Vec<u32> {
+        let mut digits = Vec::new();
+        let mut number = 1u64;
+        while digits.len() < count {
+            let mut reversed = Vec::new();
+            let mut m = number;
+            while m != 0 {
+                reversed.push((m % base as u64) as u32);
+                m /= base as u64;
+            }
+            digits.extend(reversed.into_iter().rev());
+            number += 1;
+        }
+        digits.truncate(count);
+        digits
+    }
+
+    #[test]
+    fn digits_match_concatenation() {
+        for base in 2..=17 {
+            let expected = naive_digits(base, 20_000);
+            let streamed: Vec<_> = champernowne_digits(base).take(expected.len()).collect();
+            assert_eq!(streamed, expected, "base {}", base);
+            for (n, &digit) in expected.iter().enumerate() {
+                assert_eq!(champernowne_digit(n as u64, base), digit);
+            }
+        }
+    }
+
+    #[test]
+    fn nth_skips_ahead() {
+        let expected = naive_digits(10, 5000);
+        let mut digits = champernowne_digits(10);
+        let mut index = 0;
+        for skip in [0usize, 1, 7, 100, 3, 999] {
+            index += skip;
+            assert_eq!(digits.nth(skip), Some(expected[index]));
+            index += 1;
+        }
+
+        // Past digit u64::MAX, nth and next still agree.
+        let mut digits = champernowne_digits(2);
+        digits.nth(usize::MAX);
+        digits.nth(usize::MAX);
+        let mut stepped = digits.clone();
+        for skip in 0..200 {
+            let expected: Vec<_> = stepped.by_ref().take(skip + 1).collect();
+            assert_eq!(expected.len(), skip + 1);
+            assert_eq!(digits.nth(skip), expected.last().copied());
+        }
+    }
+
+    #[test]
+    fn extreme_indices() {
+        for base in [2, 10, 36, u32::MAX] {
+            let digit = champernowne_digit(u64::MAX, base);
+            assert!(digit < base);
+        }
+        // The digits of 10¹¹ start at index 1088888888889.
+        assert_eq!(champernowne_digit(1_088_888_888_889, 10), 1);
+        assert_eq!(champernowne_digit(1_088_888_888_890, 10), 0);
+        assert_eq!(champernowne_digit(1_088_888_888_888, 10), 9);
+    }
+}
@@ -0,0 +1,248 @@
+use rug::Integer;
+
+/// Returns an `Iterator<Item = u32>` implementation that streams the base-b
+/// digits of π after the radix point, whose integer part is 3.
+///
+/// The digits come from Gibbons' unbounded spigot for the series
+/// π = 2 + 1/3 (2 + 2/5 (2 + 3/7 (2 + …))), so there is no need to choose a
+/// precision in advance.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::constants::spigot::pi_digits;
+///
+/// // 3.14159265358979…
+/// let digits: Vec<_> = pi_digits(10).take(14).collect();
+/// assert_eq!(digits, vec![1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9]);
+///
+/// // 3.243f6a88… in hexadecimal.
+/// let digits: Vec<_> = pi_digits(16).take(8).collect();
+/// assert_eq!(digits, vec![2, 4, 3, 15, 6, 10, 8, 8]);
+/// ```
+pub fn pi_digits(base: u32) -> SpigotDigits {
+    // Each step applies x ↦ 2 + kx / (2k + 1), and every tail of the series
+    // lies between 3 and 4.
+    SpigotDigits::new(base, 3, |k| (k, 4 * k + 2, 0, 2 * k + 1), (3, 4))
+}
+
+/// Returns an `Iterator<Item = u32>` implementation that streams the base-b
+/// digits of e after the radix point, whose integer part is 2.
+///
+/// The digits come from an unbounded spigot for the series
+/// e = 1 + 1/1 (1 + 1/2 (1 + 1/3 (1 + …))), so there is no need to choose a
+/// precision in advance.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::constants::spigot::e_digits;
+///
+/// // 2.71828182845904…
+/// let digits: Vec<_> = e_digits(10).take(14).collect();
+/// assert_eq!(digits, vec![7, 1, 8, 2, 8, 1, 8, 2, 8, 4, 5, 9, 0, 4]);
+/// ```
+pub fn e_digits(base: u32) -> SpigotDigits {
+    // Each step applies x ↦ 1 + x / k, and every tail of the series lies
+    // between 1 and 2.
+    SpigotDigits::new(base, 2, |k| (1, k, 0, k), (1, 2))
+}
+
+/// Returns an `Iterator<Item = u32>` implementation that streams the base-b
+/// digits of the golden ratio φ = (1 + √5) / 2 after the radix point, whose
+/// integer part is 1.
+///
+/// The digits come from an unbounded spigot for the continued fraction
+/// φ = 1 + 1/(1 + 1/(1 + …)), so there is no need to choose a precision in
+/// advance.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::constants::spigot::golden_ratio_digits;
+///
+/// // 1.61803398874989…
+/// let digits: Vec<_> = golden_ratio_digits(10).take(14).collect();
+/// assert_eq!(digits, vec![6, 1, 8, 0, 3, 3, 9, 8, 8, 7, 4, 9, 8, 9]);
+///
+/// // 1.1001111000110111… in binary.
+/// let digits: Vec<_> = golden_ratio_digits(2).take(16).collect();
+/// assert_eq!(digits, vec![1, 0, 0, 1, 1, 1, 1, 0, 0, 0, 1, 1, 0, 1, 1, 1]);
+/// ```
+pub fn golden_ratio_digits(base: u32) -> SpigotDigits {
+    // Each step applies x ↦ 1 + 1/x, and every tail lies between 1 and 2.
+    SpigotDigits::new(base, 1, |_| (1, 1, 1, 0), (1, 2))
+}
+
+/// The iterator returned by `pi_digits`, `e_digits`, and
+/// `golden_ratio_digits`.
+///
+/// It holds the fractional linear transformation x ↦ (qx + r) / (sx + t)
+/// that maps the unknown tail of the expansion to the digits not yet
+/// produced. A digit is produced once the transformation sends both bounds on
+/// the tail to the same integer part, and otherwise another step of the
+/// expansion is composed in.
+#[derive(Clone, Debug)]
+pub struct SpigotDigits {
+    base: u32,
+    q: Integer,
+    r: Integer,
+    s: Integer,
+    t: Integer,
+    k: u64,
+    step: fn(u64) -> (u64, u64, u64, u64),
+    bounds: (u32, u32),
+}
+
+impl SpigotDigits {
+    fn new(
+        base: u32,
+        integer_part: u32,
+        step: fn(u64) -> (u64, u64, u64, u64),
+        bounds: (u32, u32),
+    ) -> Self {
+        assert!(base >= 2, "base must be at least two");
+        // Start from x ↦ b(x − integer part), so that the first integer part
+        // found is the first digit after the radix point.
+        Self {
+            base,
+            q: Integer::from(base),
+            r: -(Integer::from(base) * integer_part),
+            s: Integer::new(),
+            t: Integer::from(1),
+            k: 0,
+            step,
+            bounds,
+        }
+    }
+
+    fn floor_at(&self, x: u32) -> Integer {
+        let numerator = Integer::from(&self.q * x) + &self.r;
+        let denominator = Integer::from(&self.s * x) + &self.t;
+        rug::ops::DivRounding::div_floor(numerator, denominator)
+    }
+}
+
+impl Iterator for SpigotDigits {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let digit = self.floor_at(self.bounds.0);
+            if digit == self.floor_at(self.bounds.1) {
+                // Replace the transformation z with b(z − digit).
+                let digit = digit.to_u32().unwrap();
+                self.q = (&self.q - Integer::from(&self.s * digit)) * self.base;
+                self.r = (&self.r - Integer::from(&self.t * digit)) * self.base;
+                return Some(digit);
+            }
+
+            // Compose the next step x ↦ (ax + b) / (cx + d) on the right.
+            self.k += 1;
+            let (a, b, c, d) = (self.step)(self.k);
+            let q = Integer::from(&self.q * a) + Integer::from(&self.r * c);
+            let r = Integer::from(&self.q * b) + Integer::from(&self.r * d);
+            let s = Integer::from(&self.s * a) + Integer::from(&self.t * c);
+            let t = Integer::from(&self.s * b) + Integer::from(&self.t * d);
+            self.q = q;
+            self.r = r;
+            self.s = s;
+            self.t = t;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the first `count` base-b digits after the radix point of the
+    /// number whose scaled floor ⌊x·bⁿ⌋ is given.
+    fn fractional_digits(scaled: Integer, base: u32, count: u32) -> Vec<u32> {
+        let mut digits = Vec::new();
+        let mut scaled = scaled % Integer::from(Integer::u_pow_u(base, count));
+        for _ in 0..count {
+            digits.push(Integer::from(&scaled % base).to_u32().unwrap());
+            scaled /= base;
+        }
+        digits.reverse();
+        digits
+    }
+
+    /// Returns ⌊arctan(1/m)·scale⌋ or one less, by the alternating series.
+    fn arctan_inverse(m: u32, scale: &Integer) -> Integer {
+        let mut sum = Integer::new();
+        let mut power = Integer::from(scale / m);
+        let mut k = 0u32;
+        while power != 0 {
+            let term = Integer::from(&power / (2 * k + 1));
+            if k.is_multiple_of(2) {
+                sum += term;
+            } else {
+                sum -= term;
+            }
+            power /= m * m;
+            k += 1;
+        }
+        sum
+    }
+
+    #[test]
+    fn pi_matches_machin() {
+        for base in [2, 3, 7, 10, 16, 36] {
+            let count = 600;
+            // π = 16 arctan(1/5) − 4 arctan(1/239), with guard bits to absorb
+            // the truncation errors.
+            let scale = Integer::from(Integer::u_pow_u(base, count)) << 64u32;
+            let pi = arctan_inverse(5, &scale) * 16u32 - arctan_inverse(239, &scale) * 4u32;
+            let expected = fractional_digits(pi >> 64u32, base, count);
+            let streamed: Vec<_> = pi_digits(base).take(count as usize).collect();
+            assert_eq!(streamed, expected, "base {}", base);
+        }
+    }
+
+    #[test]
+    fn e_matches_series() {
+        for base in [2, 3, 7, 10, 16, 36] {
+            let count = 600;
+            // Sum 1/k! with guard bits until the terms vanish.
+            let scale = Integer::from(Integer::u_pow_u(base, count)) << 64u32;
+            let mut e = Integer::new();
+            let mut term = scale;
+            let mut k = 1u32;
+            while term != 0 {
+                e += &term;
+                term /= k;
+                k += 1;
+            }
+            let expected = fractional_digits(e >> 64u32, base, count);
+            let streamed: Vec<_> = e_digits(base).take(count as usize).collect();
+            assert_eq!(streamed, expected, "base {}", base);
+        }
+    }
+
+    #[test]
+    fn golden_ratio_matches_square_root() {
+        for base in [2, 3, 7, 10, 16, 36] {
+            let count = 600;
+            // ⌊φ·bⁿ⌋ = ⌊(bⁿ + ⌊√(5 b²ⁿ)⌋) / 2⌋ exactly.
+            let power = Integer::from(Integer::u_pow_u(base, count));
+            let root = (Integer::from(&power * &power) * 5u32).sqrt();
+            let expected = fractional_digits((power + root) >> 1u32, base, count);
+            let streamed: Vec<_> = golden_ratio_digits(base).take(count as usize).collect();
+            assert_eq!(streamed, expected, "base {}", base);
+        }
+    }
+}
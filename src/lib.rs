@@ -1,3 +1,7 @@
 #![feature(doc_cfg)]
 
+pub mod combinatorics;
+pub mod constants;
+pub mod number_theory;
+pub mod search;
 pub mod sequences;
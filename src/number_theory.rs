@@ -0,0 +1,33 @@
+pub mod arithmetic_functions;
+pub mod certificates;
+pub mod continued_fraction;
+pub mod cunningham;
+pub mod divisors;
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+pub mod ecm;
+pub mod factorization;
+pub mod gcd;
+pub mod goldbach;
+pub mod prime_gaps;
+pub mod prime_tuples;
+pub mod primes;
+pub mod quadratic_irrational;
+pub mod quadratic_sieve;
+#[cfg(any(feature = "rand", doc, test))]
+#[doc(cfg(feature = "rand"))]
+pub mod random_primes;
+pub mod sieve;
+pub mod totient;
+pub mod wheel;
+
+pub use arithmetic_functions::{DedekindPsi, Liouville, VonMangoldt};
+pub use certificates::PrimalityCertificate;
+pub use divisors::{DivisorSigma, UnitaryDivisors};
+pub use factorization::{Factor, Factorization, FactorizationConfig};
+pub use gcd::{ExtendedGcd, Gcd};
+pub use primes::Prime;
+pub use quadratic_irrational::QuadraticIrrational;
+pub use sieve::{Sieve, SpfSieve};
+pub use totient::Totient;
+pub use wheel::Wheel;
@@ -0,0 +1,525 @@
+use crate::number_theory::{Sieve, SpfSieve};
+
+pub trait DedekindPsi: Sized {
+    /// Returns an `Option` containing either the Dedekind psi function
+    /// ψ(n) = n ∏ (1 + 1/p), where the product runs over the distinct primes p
+    /// dividing n, or `None` if n is zero or ψ(n) would cause overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::DedekindPsi;
+    ///
+    /// assert_eq!(u32::dedekind_psi(&1), Some(1));
+    /// assert_eq!(u32::dedekind_psi(&12), Some(24));
+    /// assert_eq!(u8::dedekind_psi(&210), None);
+    /// assert_eq!(u32::dedekind_psi(&0), None);
+    /// ```
+    fn dedekind_psi(n: &Self) -> Option<Self>;
+}
+
+pub trait Liouville: Sized {
+    /// Returns an `Option` containing either λ(n) = (−1)<sup>Ω(n)</sup>,
+    /// where Ω(n) is the number of prime factors of n counted with
+    /// multiplicity, or `None` if n is not positive.
+    ///
+    /// This factors n with `Factor::factorize`, so it is quick for any n that
+    /// factors quickly. For λ of every number up to a limit,
+    /// `liouville_table` and `SpfSieve::liouville` sieve them all at once, and
+    /// `liouville_sum` gives their sum without needing a full sieve.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Liouville;
+    ///
+    /// assert_eq!(u32::liouville(&1), Some(1));
+    /// assert_eq!(u32::liouville(&12), Some(-1));
+    /// assert_eq!(u32::liouville(&36), Some(1));
+    /// assert_eq!(u32::liouville(&0), None);
+    /// // 2^64 + 1 = 274 177 × 67 280 421 310 721.
+    /// assert_eq!(u128::liouville(&((1 << 64) + 1)), Some(1));
+    /// ```
+    fn liouville(n: &Self) -> Option<i8>;
+}
+
+pub trait VonMangoldt: Sized {
+    /// Returns an `Option` containing either `(p, k)` when n = p<sup>k</sup>
+    /// for a prime p and positive k, or `None` if n is not a prime power.
+    ///
+    /// This is the exact form of the von Mangoldt function, as Λ(n) is ln p
+    /// in the first case and zero otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::VonMangoldt;
+    ///
+    /// assert_eq!(u32::prime_power(&81), Some((3, 4)));
+    /// assert_eq!(u32::prime_power(&97), Some((97, 1)));
+    /// assert_eq!(u32::prime_power(&12), None);
+    /// assert_eq!(u32::prime_power(&1), None);
+    /// ```
+    fn prime_power(n: &Self) -> Option<(Self, u32)>;
+
+    /// Returns Λ(n), which is ln p if n is a power of the prime p and zero
+    /// otherwise, including when n is zero or one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::VonMangoldt;
+    ///
+    /// assert_eq!(u32::von_mangoldt(&8), 2f64.ln());
+    /// assert_eq!(u32::von_mangoldt(&10), 0.0);
+    /// ```
+    fn von_mangoldt(n: &Self) -> f64;
+}
+
+macro_rules! arithmetic_functions_for_unsigned {
+    ($type:ty) => {
+        impl $crate::number_theory::arithmetic_functions::DedekindPsi for $type {
+            fn dedekind_psi(n: &Self) -> ::core::option::Option<Self> {
+                let factorization = <Self as $crate::number_theory::Factor>::factorize(n)?;
+                let mut result: $type = 1;
+                for &(p, e) in factorization.factors() {
+                    // p^(e − 1) divides n, so only the products can overflow.
+                    let term = p.pow(e - 1).checked_mul(p.checked_add(1)?)?;
+                    result = result.checked_mul(term)?;
+                }
+                ::core::option::Option::Some(result)
+            }
+        }
+
+        impl $crate::number_theory::arithmetic_functions::Liouville for $type {
+            fn liouville(n: &Self) -> ::core::option::Option<i8> {
+                let factorization = <Self as $crate::number_theory::Factor>::factorize(n)?;
+                ::core::option::Option::Some(factorization.liouville())
+            }
+        }
+
+        impl $crate::number_theory::arithmetic_functions::VonMangoldt for $type {
+            fn prime_power(n: &Self) -> ::core::option::Option<(Self, u32)> {
+                if *n < 2 {
+                    return ::core::option::Option::None;
+                }
+                match <Self as $crate::number_theory::Factor>::factorize(n)?.factors() {
+                    &[factor] => ::core::option::Option::Some(factor),
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            fn von_mangoldt(n: &Self) -> f64 {
+                match Self::prime_power(n) {
+                    ::core::option::Option::Some((p, _)) => (p as f64).ln(),
+                    ::core::option::Option::None => 0.0,
+                }
+            }
+        }
+    };
+}
+
+arithmetic_functions_for_unsigned!(::core::primitive::u8);
+arithmetic_functions_for_unsigned!(::core::primitive::u16);
+arithmetic_functions_for_unsigned!(::core::primitive::u32);
+arithmetic_functions_for_unsigned!(::core::primitive::u64);
+arithmetic_functions_for_unsigned!(::core::primitive::u128);
+arithmetic_functions_for_unsigned!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Liouville for rug::Integer {
+    fn liouville(n: &Self) -> Option<i8> {
+        Some(<Self as crate::number_theory::Factor>::factorize(n)?.liouville())
+    }
+}
+
+/// Returns a table whose n<sup>th</sup> element is ψ(n) for every n below
+/// `limit`, with the element for zero set to zero.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::arithmetic_functions::dedekind_psi_table;
+///
+/// assert_eq!(dedekind_psi_table(10), vec![0, 1, 3, 4, 6, 6, 12, 8, 12, 12]);
+/// ```
+pub fn dedekind_psi_table(limit: usize) -> Vec<u64> {
+    let mut table: Vec<u64> = (0..limit as u64).collect();
+    if limit > 2 {
+        let sieve = SpfSieve::new(limit as u64 - 1);
+        for (n, entry) in table.iter_mut().enumerate().skip(2) {
+            // ψ(n) is n times (p + 1) / p for each prime p dividing it.
+            for (p, _) in sieve.factorize(n as u64) {
+                *entry = *entry / p * (p + 1);
+            }
+        }
+    }
+    table
+}
+
+/// Returns a table whose n<sup>th</sup> element is λ(n) for every n below
+/// `limit`, with the element for zero set to zero.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::arithmetic_functions::liouville_table;
+///
+/// assert_eq!(liouville_table(10), vec![0, 1, -1, -1, 1, -1, 1, -1, -1, 1]);
+/// ```
+pub fn liouville_table(limit: usize) -> Vec<i8> {
+    match limit.checked_sub(1) {
+        Some(last) => SpfSieve::new(last as u64).liouville(),
+        None => Vec::new(),
+    }
+}
+
+/// Returns a table whose n<sup>th</sup> element is e<sup>Λ(n)</sup> for
+/// every n below `limit`, that is, the prime p if n is a power of p and one
+/// otherwise, with the element for zero set to one.
+///
+/// Taking the natural logarithm of an element gives Λ(n), and keeping the
+/// table in integers keeps it exact.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::arithmetic_functions::von_mangoldt_table;
+///
+/// assert_eq!(von_mangoldt_table(10), vec![1, 1, 2, 3, 2, 5, 1, 7, 2, 3]);
+/// ```
+pub fn von_mangoldt_table(limit: usize) -> Vec<u64> {
+    let mut table = vec![1u64; limit];
+    if limit > 2 {
+        let sieve = SpfSieve::new(limit as u64 - 1);
+        for (n, entry) in table.iter_mut().enumerate().skip(2) {
+            let mut factors = sieve.factorize(n as u64);
+            if let (Some((p, _)), None) = (factors.next(), factors.next()) {
+                *entry = p;
+            }
+        }
+    }
+    table
+}
+
+/// Returns the first Chebyshev function θ(x), the sum of ln p over the primes
+/// p no larger than x.
+///
+/// The primes are sieved by `Sieve`, so this takes O(x log log x) time and
+/// a bit of space for each odd number up to x.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::arithmetic_functions::chebyshev_theta;
+///
+/// assert!((chebyshev_theta(100) - 83.728_390_399_063_93).abs() < 1e-9);
+/// assert_eq!(chebyshev_theta(1), 0.0);
+/// ```
+pub fn chebyshev_theta(x: u64) -> f64 {
+    Sieve::new(x).primes().map(|p| (p as f64).ln()).sum()
+}
+
+/// Returns the second Chebyshev function ψ(x), the sum of Λ(n) over the
+/// positive n no larger than x.
+///
+/// Each prime p no larger than x contributes ln p once for each of its
+/// powers no larger than x, so only the primes are sieved by `Sieve`, which
+/// takes O(x log log x) time and a bit of space for each odd number up to x.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::arithmetic_functions::chebyshev_psi;
+///
+/// assert!((chebyshev_psi(100) - 94.045_311_229_357_4).abs() < 1e-9);
+/// assert_eq!(chebyshev_psi(1), 0.0);
+/// ```
+pub fn chebyshev_psi(x: u64) -> f64 {
+    Sieve::new(x)
+        .primes()
+        .map(|p| {
+            let mut powers = 0;
+            let mut power = p;
+            while power <= x {
+                powers += 1;
+                power = match power.checked_mul(p) {
+                    Some(next) => next,
+                    None => break,
+                };
+            }
+            powers as f64 * (p as f64).ln()
+        })
+        .sum()
+}
+
+/// Returns the Mertens function M(n), the sum of μ(k) over the positive k no
+/// larger than n.
+///
+/// Only μ up to about n<sup>2/3</sup> is sieved, and the remaining values
+/// come from the identity Σ M(⌊n/d⌋) = 1 over the positive d, so this takes
+/// O(n<sup>2/3</sup>) time and space rather than the O(n) of summing a full
+/// sieve, which puts n around 10<sup>10</sup> well within reach.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::arithmetic_functions::mertens;
+///
+/// assert_eq!(mertens(10), -1);
+/// assert_eq!(mertens(1_000_000), 212);
+/// assert_eq!(mertens(0), 0);
+/// ```
+pub fn mertens(n: u64) -> i64 {
+    MertensTable::new(n).get(n)
+}
+
+/// Returns the summatory Liouville function L(n), the sum of λ(k) over the
+/// positive k no larger than n.
+///
+/// Every k is a square d<sup>2</sup> times a squarefree number, and summing
+/// μ over the squarefree parts gives L(n) = Σ M(⌊n/d<sup>2</sup>⌋) over the
+/// positive d, so this takes the same O(n<sup>2/3</sup>) time and space as
+/// `mertens`.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::arithmetic_functions::liouville_sum;
+///
+/// assert_eq!(liouville_sum(10), 0);
+/// assert_eq!(liouville_sum(1_000_000), -530);
+/// assert_eq!(liouville_sum(0), 0);
+/// ```
+pub fn liouville_sum(n: u64) -> i64 {
+    let table = MertensTable::new(n);
+    // ⌊n/d^2⌋ is ⌊n/k⌋ for k = d^2, so it is in the table.
+    (1..=n.isqrt()).map(|d| table.get(n / (d * d))).sum()
+}
+
+/// The values of the Mertens function at every ⌊n/k⌋ for a fixed n.
+struct MertensTable {
+    n: u64,
+    /// The bound up to which M was summed from a sieve.
+    bound: u64,
+    /// The values M(x) for every x up to the bound.
+    small: Vec<i64>,
+    /// The values M(⌊n/j⌋) for every j with ⌊n/j⌋ above the bound, indexed by
+    /// j.
+    large: Vec<i64>,
+}
+
+impl MertensTable {
+    fn new(n: u64) -> Self {
+        let bound = ((n as f64).cbrt().powi(2) as u64).max(n.isqrt()).min(n);
+        let mut small = Vec::with_capacity(bound as usize + 1);
+        let mut sum = 0;
+        for mu in SpfSieve::new(bound).mobius() {
+            sum += i64::from(mu);
+            small.push(sum);
+        }
+
+        let count = n / (bound + 1);
+        let mut table = Self {
+            n,
+            bound,
+            small,
+            large: vec![0; count as usize + 1],
+        };
+        // M(⌊x/d⌋) is ⌊n/(j d)⌋, so the larger j are needed first.
+        for j in (1..=count).rev() {
+            let x = n / j;
+            let mut sum = 0;
+            let mut d = 2;
+            while d <= x {
+                // Every d up to `last` gives the same quotient.
+                let q = x / d;
+                let last = x / q;
+                sum += (last - d + 1) as i64 * table.get(q);
+                d = last + 1;
+            }
+            table.large[j as usize] = 1 - sum;
+        }
+        table
+    }
+
+    /// Returns M(x), where x must be ⌊n/k⌋ for some positive k.
+    fn get(&self, x: u64) -> i64 {
+        if x <= self.bound {
+            self.small[x as usize]
+        } else {
+            self.large[(self.n / x) as usize]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_against_tables {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let dedekind_psi = dedekind_psi_table(3000);
+                let liouville = liouville_table(3000);
+                let von_mangoldt = von_mangoldt_table(3000);
+                for n in (0..3000usize).take_while(|&n| n as u128 <= <$type>::MAX as u128) {
+                    let expected = if n == 0 || dedekind_psi[n] as u128 > <$type>::MAX as u128 {
+                        ::core::option::Option::None
+                    } else {
+                        ::core::option::Option::Some(dedekind_psi[n] as $type)
+                    };
+                    assert_eq!(<$type>::dedekind_psi(&(n as $type)), expected, "n = {}", n);
+
+                    let expected = if n == 0 {
+                        ::core::option::Option::None
+                    } else {
+                        ::core::option::Option::Some(liouville[n])
+                    };
+                    assert_eq!(<$type>::liouville(&(n as $type)), expected, "n = {}", n);
+
+                    match <$type>::prime_power(&(n as $type)) {
+                        ::core::option::Option::Some((p, k)) => {
+                            assert_eq!(p as u64, von_mangoldt[n], "n = {}", n);
+                            assert_eq!((p as usize).pow(k), n);
+                        }
+                        ::core::option::Option::None => {
+                            assert_eq!(von_mangoldt[n], 1, "n = {}", n)
+                        }
+                    }
+                    assert_eq!(
+                        <$type>::von_mangoldt(&(n as $type)),
+                        (von_mangoldt[n] as f64).ln()
+                    );
+                }
+
+                let max = <$type>::MAX;
+                // Every maximum is 2^b − 1 for even b, which is divisible by 3.
+                assert_eq!(<$type>::prime_power(&max), None);
+            }
+        };
+    }
+
+    test_against_tables!(::core::primitive::u8, u8_against_tables);
+    test_against_tables!(::core::primitive::u16, u16_against_tables);
+    test_against_tables!(::core::primitive::u32, u32_against_tables);
+    test_against_tables!(::core::primitive::u64, u64_against_tables);
+    test_against_tables!(::core::primitive::u128, u128_against_tables);
+    test_against_tables!(::core::primitive::usize, usize_against_tables);
+
+    #[test]
+    fn large_prime_powers() {
+        assert_eq!(u64::prime_power(&(1 << 63)), Some((2, 63)));
+        assert_eq!(u64::prime_power(&3u64.pow(40)), Some((3, 40)));
+        assert_eq!(u64::prime_power(&4_294_967_291), Some((4_294_967_291, 1)));
+        assert_eq!(u64::prime_power(&(4_294_967_291 * 4_294_967_279)), None);
+        assert_eq!(u64::liouville(&(4_294_967_291 * 4_294_967_279)), Some(1));
+        assert_eq!(u128::liouville(&(1 << 127)), Some(-1));
+        // 2^89 − 1 is prime, which is far too large to trial divide.
+        let prime = (1u128 << 89) - 1;
+        assert_eq!(u128::prime_power(&prime), Some((prime, 1)));
+        assert_eq!(u128::prime_power(&(prime * 3)), None);
+        assert_eq!(u128::von_mangoldt(&prime), (prime as f64).ln());
+    }
+
+    #[test]
+    fn dedekind_psi_near_overflow() {
+        // ψ(2^k) = 3 · 2^(k − 1).
+        assert_eq!(u8::dedekind_psi(&128), Some(192));
+        assert_eq!(u64::dedekind_psi(&(1 << 63)), Some(3 << 62));
+        assert_eq!(u64::dedekind_psi(&(3 << 62)), None);
+        // ψ(p) = p + 1 for the largest prime below 2^64.
+        assert_eq!(
+            u64::dedekind_psi(&18_446_744_073_709_551_557),
+            Some(u64::MAX - 57)
+        );
+        assert_eq!(u8::dedekind_psi(&251), Some(252));
+        assert_eq!(u8::dedekind_psi(&255), None);
+        // ψ(p) = p + 1 for the prime 2^89 − 1, which is far too large to
+        // trial divide.
+        assert_eq!(u128::dedekind_psi(&((1 << 89) - 1)), Some(1 << 89));
+        assert_eq!(
+            u128::dedekind_psi(&(u128::MAX - 158)),
+            Some(u128::MAX - 157)
+        );
+        assert_eq!(u128::dedekind_psi(&(u128::MAX - 1)), None);
+    }
+
+    #[test]
+    fn chebyshev_functions_match_tables() {
+        let table = von_mangoldt_table(2000);
+        let mut psi = 0.0;
+        let mut theta = 0.0;
+        for x in 0..2000u64 {
+            let entry = table[x as usize];
+            psi += (entry as f64).ln();
+            if entry == x {
+                theta += (entry as f64).ln();
+            }
+            assert!((chebyshev_psi(x) - psi).abs() < 1e-9, "x = {}", x);
+            assert!((chebyshev_theta(x) - theta).abs() < 1e-9, "x = {}", x);
+        }
+        assert!((chebyshev_psi(1_000_000) - 999_586.597_495_633).abs() < 1e-6);
+        assert!((chebyshev_theta(1_000_000) - 998_484.175_025_634_2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn liouville_sum_matches_sieve() {
+        use crate::number_theory::Factor;
+
+        let liouville = SpfSieve::new(3000).liouville();
+        assert_eq!(liouville.len(), 3001);
+        for n in 1..=3000u64 {
+            assert_eq!(
+                liouville[n as usize],
+                u64::factorize(&n).unwrap().liouville(),
+                "n = {}",
+                n
+            );
+        }
+        let mut sum = 0;
+        for (n, &lambda) in liouville.iter().enumerate() {
+            sum += i64::from(lambda);
+            assert_eq!(liouville_sum(n as u64), sum, "n = {}", n);
+        }
+        let expected = [0, -2, -14, -94, -288, -530, -842, -3884, -25216, -116026];
+        let mut n = 1;
+        for &value in &expected {
+            n *= 10;
+            assert_eq!(liouville_sum(n), value, "n = {}", n);
+        }
+        assert_eq!(liouville_table(0), Vec::<i8>::new());
+    }
+
+    #[test]
+    fn rug_and_large_liouville() {
+        use rug::Integer;
+
+        assert_eq!(Integer::liouville(&Integer::from(12)), Some(-1));
+        assert_eq!(Integer::liouville(&Integer::from(0)), None);
+        assert_eq!(Integer::liouville(&Integer::from(-12)), None);
+        // 2^128 + 1 = 59 649 589 127 497 217 × 5 704 689 200 685 129 054 721.
+        let n = Integer::from(Integer::u_pow_u(2, 128)) + 1u8;
+        assert_eq!(Integer::liouville(&n), Some(1));
+        assert_eq!(Integer::liouville(&(n * 3u8)), Some(-1));
+        assert_eq!(u128::liouville(&(u128::MAX - 158)), Some(-1));
+    }
+
+    #[test]
+    fn mertens_matches_sieve() {
+        let mobius = SpfSieve::new(3000).mobius();
+        let mut sum = 0;
+        for (n, &mu) in mobius.iter().enumerate() {
+            sum += i64::from(mu);
+            assert_eq!(mertens(n as u64), sum, "n = {}", n);
+        }
+        let expected = [-1, 1, 2, -23, -48, 212, 1037, 1928, -222, -33722];
+        let mut n = 1;
+        for &value in &expected {
+            n *= 10;
+            assert_eq!(mertens(n), value, "n = {}", n);
+        }
+    }
+}
@@ -0,0 +1,345 @@
+//! Primality certificates, which let a claim that a number is prime be
+//! checked without trusting whatever test made the claim.
+
+use crate::number_theory::primes::{power, Modular, Montgomery, Small};
+use crate::number_theory::Gcd;
+
+/// A proof that a number is prime, built from the factors of n − 1 and the
+/// certificates for those factors in turn.
+///
+/// `verify` checks a certificate with nothing but modular arithmetic of its
+/// own, so a certificate that verifies shows that n is prime however it was
+/// made.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::certificates::is_prime_certified;
+/// use mathematical::number_theory::PrimalityCertificate;
+///
+/// let certificate = is_prime_certified(1_000_000_007).unwrap();
+/// assert_eq!(certificate.n(), 1_000_000_007);
+/// assert!(certificate.verify());
+///
+/// // 7 − 1 = 2 × 3, and 3 has order 6 modulo 7.
+/// let three = PrimalityCertificate::Pratt {
+///     n: 3,
+///     witness: 2,
+///     factors: vec![(PrimalityCertificate::Pratt { n: 2, witness: 1, factors: vec![] }, 1)],
+/// };
+/// let seven = PrimalityCertificate::Pratt {
+///     n: 7,
+///     witness: 3,
+///     factors: vec![
+///         (PrimalityCertificate::Pratt { n: 2, witness: 1, factors: vec![] }, 1),
+///         (three, 1),
+///     ],
+/// };
+/// assert!(seven.verify());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PrimalityCertificate {
+    /// A Pratt certificate, which lists every prime factor q of n − 1 with
+    /// its exponent, along with a witness a for which a<sup>n − 1</sup> ≡ 1
+    /// and a<sup>(n − 1)/q</sup> ≢ 1 modulo n for each q.
+    ///
+    /// Then a has order n − 1 modulo n, so all of 1, 2, …, n − 1 are units
+    /// and n is prime.
+    Pratt {
+        n: u128,
+        witness: u128,
+        factors: Vec<(PrimalityCertificate, u32)>,
+    },
+    /// A Pocklington certificate, which lists some prime factors q of n − 1
+    /// with their exponents, whose product F has (F + 1)<sup>2</sup> > n,
+    /// each along with a witness a for which a<sup>n − 1</sup> ≡ 1 modulo n
+    /// and gcd(a<sup>(n − 1)/q</sup> − 1, n) = 1.
+    ///
+    /// Then every prime factor of n is 1 modulo F, and so is larger than
+    /// √n, so n is prime. Only about half of n − 1 needs to be factored.
+    Pocklington {
+        n: u128,
+        factors: Vec<(PrimalityCertificate, u32, u128)>,
+    },
+}
+
+impl PrimalityCertificate {
+    /// Returns the number that the certificate claims is prime.
+    pub fn n(&self) -> u128 {
+        match *self {
+            PrimalityCertificate::Pratt { n, .. } | PrimalityCertificate::Pocklington { n, .. } => {
+                n
+            }
+        }
+    }
+
+    /// Returns whether the certificate, along with the certificates for the
+    /// factors of n − 1 inside it, holds up, which proves that n is prime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::PrimalityCertificate;
+    ///
+    /// // 2 has order 4 modulo 5, not 8 modulo 9.
+    /// let two = || PrimalityCertificate::Pratt { n: 2, witness: 1, factors: vec![] };
+    /// let five = PrimalityCertificate::Pratt { n: 5, witness: 2, factors: vec![(two(), 2)] };
+    /// let nine = PrimalityCertificate::Pratt { n: 9, witness: 2, factors: vec![(two(), 3)] };
+    /// assert!(five.verify());
+    /// assert!(!nine.verify());
+    /// ```
+    pub fn verify(&self) -> bool {
+        // Montgomery arithmetic needs an odd modulus, and 2 is the only even
+        // prime.
+        let n = self.n();
+        if n < 2 || n > 2 && n.is_multiple_of(2) {
+            return false;
+        }
+        match <u64 as core::convert::TryFrom<u128>>::try_from(n) {
+            Ok(n) => self.verify_with(&Small { n }),
+            Err(_) => self.verify_with(&Montgomery::new(n)),
+        }
+    }
+
+    fn verify_with<M: Modular>(&self, modular: &M) -> bool {
+        let n = modular.modulus();
+        let one = modular.elem(1);
+        let power_of =
+            |witness: u128, exponent| power(modular, modular.elem(witness % n), exponent);
+        match self {
+            PrimalityCertificate::Pratt {
+                witness, factors, ..
+            } => {
+                product(factors.iter().map(|(certificate, e)| (certificate, *e))) == Some(n - 1)
+                    && power_of(*witness, n - 1) == one
+                    && factors.iter().all(|(certificate, _)| {
+                        let q = certificate.n();
+                        certificate.verify() && power_of(*witness, (n - 1) / q) != one
+                    })
+            }
+            PrimalityCertificate::Pocklington { factors, .. } => {
+                let f = match product(factors.iter().map(|(certificate, e, _)| (certificate, *e))) {
+                    Some(f) => f,
+                    None => return false,
+                };
+                (n - 1).is_multiple_of(f)
+                    && f + 1 > n.isqrt()
+                    && factors.iter().all(|(certificate, _, witness)| {
+                        let q = certificate.n();
+                        let x = power_of(*witness, (n - 1) / q);
+                        certificate.verify()
+                            && power_of(*witness, n - 1) == one
+                            && u128::gcd(&modular.value(modular.sub(x, one)), &n) == 1
+                    })
+            }
+        }
+    }
+}
+
+/// Returns an `Option` containing either a certificate that n is prime or
+/// `None` if n is not prime.
+///
+/// Numbers below 2<sup>64</sup> get Pratt certificates, as n − 1 is quick to
+/// factor in full, and larger numbers get Pocklington certificates, for
+/// which factoring about half of n − 1 is enough. The factors are found by
+/// trial division and `factorization::pollard_rho`, so this can take a while
+/// when what remains of n − 1 after its small factors has two large prime
+/// factors.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::certificates::is_prime_certified;
+/// use mathematical::number_theory::PrimalityCertificate;
+///
+/// let certificate = is_prime_certified(2u128.pow(127) - 1).unwrap();
+/// assert!(matches!(certificate, PrimalityCertificate::Pocklington { .. }));
+/// assert!(certificate.verify());
+///
+/// assert!(matches!(is_prime_certified(101), Some(PrimalityCertificate::Pratt { .. })));
+/// assert_eq!(is_prime_certified(561), None);
+/// ```
+pub fn is_prime_certified(n: u128) -> Option<PrimalityCertificate> {
+    if crate::number_theory::primes::is_prime(n) {
+        Some(certify(n))
+    } else {
+        None
+    }
+}
+
+/// Returns a certificate for n, which must be prime.
+fn certify(n: u128) -> PrimalityCertificate {
+    if n == 2 {
+        return PrimalityCertificate::Pratt {
+            n,
+            witness: 1,
+            factors: Vec::new(),
+        };
+    }
+    let modular = Montgomery::new(n);
+    let one = modular.elem(1);
+    let is_witness = |a: u128, q: u128| power(&modular, modular.elem(a), (n - 1) / q) != one;
+    if n <= u64::MAX as u128 {
+        let factors = factor(n - 1, |_| false);
+        let witness = (2..)
+            .find(|&a| factors.iter().all(|&(q, _)| is_witness(a, q)))
+            .unwrap();
+        PrimalityCertificate::Pratt {
+            n,
+            witness,
+            factors: factors.into_iter().map(|(q, e)| (certify(q), e)).collect(),
+        }
+    } else {
+        let root = n.isqrt();
+        let factors = factor(n - 1, |f| f + 1 > root);
+        PrimalityCertificate::Pocklington {
+            n,
+            factors: factors
+                .into_iter()
+                .map(|(q, e)| {
+                    let witness = (2..).find(|&a| is_witness(a, q)).unwrap();
+                    (certify(q), e, witness)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Returns the product of the primes to the exponents, or `None` if an
+/// exponent is zero or the product overflows.
+fn product<'a>(mut factors: impl Iterator<Item = (&'a PrimalityCertificate, u32)>) -> Option<u128> {
+    factors.try_fold(1u128, |product, (certificate, e)| {
+        if e == 0 {
+            return None;
+        }
+        product.checked_mul(certificate.n().checked_pow(e)?)
+    })
+}
+
+/// Returns the prime factors of m, which must be positive, with their
+/// exponents in increasing order, stopping early once the product of those
+/// found so far is `enough`.
+fn factor(m: u128, enough: impl Fn(u128) -> bool) -> Vec<(u128, u32)> {
+    let mut factors = Vec::new();
+    let (mut rest, mut found) = (m, 1);
+    let mut take = |factors: &mut Vec<_>, rest: &mut u128, q: u128| {
+        let mut e = 0;
+        while rest.is_multiple_of(q) {
+            *rest /= q;
+            found *= q;
+            e += 1;
+        }
+        factors.push((q, e));
+        found
+    };
+    for q in crate::number_theory::Sieve::new(1000).primes() {
+        if rest.is_multiple_of(q as u128) && enough(take(&mut factors, &mut rest, q as u128)) {
+            return factors;
+        }
+    }
+    let mut composites = vec![rest];
+    while let Some(mut c) = composites.pop() {
+        for &(q, _) in &factors {
+            while c.is_multiple_of(q) {
+                c /= q;
+            }
+        }
+        if c == 1 {
+            continue;
+        }
+        if crate::number_theory::primes::is_prime(c) {
+            if enough(take(&mut factors, &mut rest, c)) {
+                break;
+            }
+        } else {
+            let d = crate::number_theory::factorization::pollard_rho(c).unwrap();
+            composites.push(d);
+            composites.push(c / d);
+        }
+    }
+    factors.sort_unstable();
+    factors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pratt(
+        n: u128,
+        witness: u128,
+        factors: Vec<(PrimalityCertificate, u32)>,
+    ) -> PrimalityCertificate {
+        PrimalityCertificate::Pratt {
+            n,
+            witness,
+            factors,
+        }
+    }
+
+    #[test]
+    fn certifies_primes() {
+        let sieve = crate::number_theory::Sieve::new(20_000);
+        for n in 0..20_000 {
+            let certificate = is_prime_certified(n as u128);
+            assert_eq!(certificate.is_some(), sieve.is_prime(n), "n = {}", n);
+            if let Some(certificate) = certificate {
+                assert_eq!(certificate.n(), n as u128);
+                assert!(certificate.verify(), "n = {}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn certifies_large_primes() {
+        for &n in &[
+            18_446_744_073_709_551_557u128,
+            18_446_744_073_709_551_629,
+            (1 << 89) - 1,
+            (1 << 107) - 1,
+            (1 << 127) - 1,
+            170_141_183_460_469_231_731_687_303_715_884_105_757,
+            u128::MAX - 158,
+        ] {
+            let certificate = is_prime_certified(n).unwrap();
+            assert_eq!(certificate.n(), n);
+            assert!(certificate.verify(), "n = {}", n);
+        }
+        assert!(matches!(
+            is_prime_certified(u64::MAX as u128 - 58),
+            Some(PrimalityCertificate::Pratt { .. })
+        ));
+        assert_eq!(is_prime_certified(u128::MAX), None);
+        assert_eq!(is_prime_certified((1 << 67) - 1), None);
+    }
+
+    #[test]
+    fn rejects_bad_certificates() {
+        let two = || pratt(2, 1, vec![]);
+        let three = || pratt(3, 2, vec![(two(), 1)]);
+        assert!(pratt(7, 3, vec![(two(), 1), (three(), 1)]).verify());
+        // Not a generator of the whole group.
+        assert!(!pratt(7, 2, vec![(two(), 1), (three(), 1)]).verify());
+        // Missing or wrong factors of n − 1.
+        assert!(!pratt(7, 3, vec![(two(), 1)]).verify());
+        assert!(!pratt(7, 3, vec![(two(), 1), (three(), 1), (two(), 0)]).verify());
+        assert!(!pratt(7, 3, vec![(two(), 1), (pratt(3, 1, vec![(two(), 1)]), 1)]).verify());
+        // A Fermat liar for a composite.
+        assert!(!pratt(561, 2, vec![(two(), 4), (pratt(35, 2, vec![]), 1)]).verify());
+        assert!(!pratt(1, 1, vec![]).verify());
+        assert!(!pratt(0, 1, vec![]).verify());
+        assert!(!pratt(1 << 100, 3, vec![]).verify());
+        // Witnesses are taken modulo n.
+        assert!(pratt(7, 10, vec![(two(), 1), (three(), 1)]).verify());
+
+        // 1009 − 1 = 2⁴ × 3² × 7, so 2⁴ × 3² alone is enough.
+        let pocklington = |factors| PrimalityCertificate::Pocklington { n: 1009, factors };
+        assert!(pocklington(vec![(two(), 4, 11), (three(), 2, 11)]).verify());
+        // F = 2⁴ is too small.
+        assert!(!pocklington(vec![(two(), 4, 11)]).verify());
+        // F does not divide n − 1.
+        assert!(!pocklington(vec![(two(), 5, 11), (three(), 2, 11)]).verify());
+        // a^((n − 1)/2) ≡ 1 for a square.
+        assert!(!pocklington(vec![(two(), 4, 4), (three(), 2, 11)]).verify());
+    }
+}
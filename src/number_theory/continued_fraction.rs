@@ -0,0 +1,160 @@
+/// Returns the continued fraction expansion of √n as its initial term ⌊√n⌋
+/// and its period, which is empty exactly when n is a perfect square.
+///
+/// When n is not a square, the period ends with 2⌊√n⌋ and the terms before
+/// that read the same backwards. Only the first half of the period is
+/// computed, as the point where the expansion starts to retrace its steps is
+/// detected, and the rest is filled in by reflection. The period can still
+/// have on the order of √n log n terms.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::continued_fraction::sqrt_continued_fraction;
+///
+/// // √14 = [3; 1, 2, 1, 6, 1, 2, 1, 6, …].
+/// assert_eq!(sqrt_continued_fraction(14), (3, vec![1, 2, 1, 6]));
+/// assert_eq!(sqrt_continued_fraction(2), (1, vec![2]));
+/// assert_eq!(sqrt_continued_fraction(16), (4, vec![]));
+/// ```
+pub fn sqrt_continued_fraction(n: u64) -> (u64, Vec<u64>) {
+    let root = n.isqrt();
+    if root * root == n {
+        return (root, Vec::new());
+    }
+
+    let mut period = Vec::new();
+    let odd = half_period(n, root, |term| period.push(term));
+    // An even period has a middle term that is not repeated.
+    let mirrored = if odd { period.len() } else { period.len() - 1 };
+    for i in (0..mirrored).rev() {
+        period.push(period[i]);
+    }
+    period.push(2 * root);
+    (root, period)
+}
+
+/// Returns the length of the period of the continued fraction expansion of
+/// √n, which is zero exactly when n is a perfect square.
+///
+/// This walks only the first half of the period and allocates nothing.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::continued_fraction::sqrt_period_length;
+///
+/// assert_eq!(sqrt_period_length(14), 4);
+/// assert_eq!(sqrt_period_length(13), 5);
+/// assert_eq!(sqrt_period_length(16), 0);
+///
+/// // Of the numbers up to 13, only √2, √5, √10, and √13 have odd periods.
+/// let odd = (2..=13).filter(|&n| sqrt_period_length(n) % 2 == 1).count();
+/// assert_eq!(odd, 4);
+/// ```
+pub fn sqrt_period_length(n: u64) -> usize {
+    let root = n.isqrt();
+    if root * root == n {
+        return 0;
+    }
+
+    let mut half = 0;
+    let odd = half_period(n, root, |_| half += 1);
+    if odd {
+        2 * half + 1
+    } else {
+        2 * half
+    }
+}
+
+/// Passes the terms of the expansion of the nonsquare √n that come before the
+/// middle of its period to `visit` in order, and returns whether the period
+/// has odd length.
+fn half_period(n: u64, root: u64, mut visit: impl FnMut(u64)) -> bool {
+    // The complete quotient (√n + m) / d has the term ⌊(root + m) / d⌋, and
+    // each step takes the reciprocal of its fractional part. The pairs (m, d)
+    // run backwards through the same values after the middle of the period, so
+    // a repeated m marks the middle term of an even period, and a repeated d
+    // marks the middle pair of terms of an odd period. Every value stays below
+    // 2√n.
+    let (mut m, mut d, mut term) = (0, 1, root);
+    loop {
+        let next_m = d * term - m;
+        let next_d = (n - next_m * next_m) / d;
+        if next_m == m {
+            return false;
+        }
+        if next_d == d {
+            return true;
+        }
+        term = (root + next_m) / next_d;
+        visit(term);
+        m = next_m;
+        d = next_d;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Computes the whole period term by term until the final 2⌊√n⌋.
+    fn naive_period(n: u64) -> Vec<u64> {
+        let root = n.isqrt();
+        let mut period = Vec::new();
+        if root * root == n {
+            return period;
+        }
+        let (mut m, mut d, mut term) = (0, 1, root);
+        while term != 2 * root {
+            m = d * term - m;
+            d = (n - m * m) / d;
+            term = (root + m) / d;
+            period.push(term);
+        }
+        period
+    }
+
+    #[test]
+    fn matches_naive_expansion() {
+        for n in 0..20_000 {
+            let expected = naive_period(n);
+            assert_eq!(
+                sqrt_continued_fraction(n),
+                (n.isqrt(), expected.clone()),
+                "n = {}",
+                n
+            );
+            assert_eq!(sqrt_period_length(n), expected.len(), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn near_squares() {
+        let root = u32::MAX as u64;
+        assert_eq!(
+            sqrt_continued_fraction(root * root + 1),
+            (root, vec![2 * root])
+        );
+        assert_eq!(
+            sqrt_continued_fraction(root * root + 2),
+            (root, vec![root, 2 * root])
+        );
+        assert_eq!(
+            sqrt_continued_fraction(root * root - 1),
+            (root - 1, vec![1, 2 * root - 2])
+        );
+        assert_eq!(sqrt_continued_fraction(root * root), (root, vec![]));
+    }
+
+    #[test]
+    fn periods_are_palindromes() {
+        for n in 2..5000 {
+            let (root, period) = sqrt_continued_fraction(n);
+            if let Some((&last, rest)) = period.split_last() {
+                assert_eq!(last, 2 * root);
+                assert!(rest.iter().eq(rest.iter().rev()), "n = {}", n);
+            }
+        }
+    }
+}
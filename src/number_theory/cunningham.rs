@@ -0,0 +1,192 @@
+use crate::number_theory::Prime;
+
+/// The two kinds of Cunningham chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChainKind {
+    /// Chains where each prime p is followed by 2p + 1, so that every prime
+    /// but the last is a Sophie Germain prime.
+    First,
+    /// Chains where each prime p is followed by 2p − 1.
+    Second,
+}
+
+impl ChainKind {
+    /// Returns the prime after p in a chain of this kind, if it fits in a
+    /// `u64`.
+    fn next(self, p: u64) -> Option<u64> {
+        match self {
+            ChainKind::First => p.checked_mul(2)?.checked_add(1),
+            ChainKind::Second => p.checked_mul(2).map(|double| double - 1),
+        }
+    }
+
+    /// Returns the prime before p in a chain of this kind, if p comes from
+    /// an integer.
+    fn previous(self, p: u64) -> Option<u64> {
+        if p.is_multiple_of(2) {
+            return None;
+        }
+        match self {
+            ChainKind::First => Some(p / 2),
+            ChainKind::Second => Some(p / 2 + 1),
+        }
+    }
+
+    /// Returns whether the chain through p starts before p.
+    fn continues_back(self, p: u64) -> bool {
+        self.previous(p).is_some_and(|q| u64::is_prime(&q))
+    }
+}
+
+/// Returns the maximal Cunningham chain of the given kind that goes through
+/// p, from its first prime to its last, or an empty `Vec` if p is not prime.
+///
+/// The chain is followed back from p while the prime before it is prime,
+/// and then forward while the prime after it is prime, stopping early if
+/// the next term does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::cunningham::{cunningham_chain, ChainKind};
+///
+/// assert_eq!(cunningham_chain(11, ChainKind::First), vec![2, 5, 11, 23, 47]);
+/// assert_eq!(
+///     cunningham_chain(1531, ChainKind::Second),
+///     vec![1531, 3061, 6121, 12_241, 24_481]
+/// );
+/// assert_eq!(cunningham_chain(7, ChainKind::First), vec![3, 7]);
+/// assert!(cunningham_chain(9, ChainKind::First).is_empty());
+/// ```
+pub fn cunningham_chain(p: u64, kind: ChainKind) -> Vec<u64> {
+    if !u64::is_prime(&p) {
+        return Vec::new();
+    }
+    let mut first = p;
+    while kind.continues_back(first) {
+        first = kind.previous(first).unwrap();
+    }
+    chain_from(first, kind)
+}
+
+/// Returns an `Iterator<Item = Vec<u64>>` implementation that goes through
+/// the maximal Cunningham chains of the given kind with at least `length`
+/// primes, in increasing order of their first primes, which are below
+/// `limit`.
+///
+/// The later primes of a chain can be past the limit.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::cunningham::{cunningham_chains, ChainKind};
+///
+/// let chains: Vec<_> = cunningham_chains(ChainKind::First, 5, 1000).collect();
+/// assert_eq!(chains, vec![vec![2, 5, 11, 23, 47], vec![89, 179, 359, 719, 1439, 2879]]);
+///
+/// let starts: Vec<_> = cunningham_chains(ChainKind::Second, 4, 2000)
+///     .map(|chain| chain[0])
+///     .collect();
+/// assert_eq!(starts, vec![1531]);
+/// ```
+pub fn cunningham_chains(kind: ChainKind, length: usize, limit: u64) -> CunninghamChains {
+    CunninghamChains {
+        kind,
+        length,
+        limit,
+        primes: crate::number_theory::sieve::primes(),
+    }
+}
+
+/// The iterator returned by `cunningham_chains`.
+#[derive(Clone, Debug)]
+pub struct CunninghamChains {
+    kind: ChainKind,
+    length: usize,
+    limit: u64,
+    primes: crate::number_theory::sieve::Primes,
+}
+
+impl Iterator for CunninghamChains {
+    type Item = Vec<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let p = self.primes.next().filter(|&p| p < self.limit)?;
+            if self.kind.continues_back(p) {
+                continue;
+            }
+            let chain = chain_from(p, self.kind);
+            if chain.len() >= self.length {
+                return Some(chain);
+            }
+        }
+    }
+}
+
+impl core::iter::FusedIterator for CunninghamChains {}
+
+/// Returns the chain of the given kind from the prime p on.
+fn chain_from(p: u64, kind: ChainKind) -> Vec<u64> {
+    let mut chain = vec![p];
+    while let Some(next) = kind.next(*chain.last().unwrap()).filter(u64::is_prime) {
+        chain.push(next);
+    }
+    chain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chains_match_sieve() {
+        let sieve = crate::number_theory::Sieve::new(1 << 20);
+        for kind in [ChainKind::First, ChainKind::Second] {
+            let step = |p: u64| match kind {
+                ChainKind::First => 2 * p + 1,
+                ChainKind::Second => 2 * p - 1,
+            };
+            let chains: Vec<_> = cunningham_chains(kind, 1, 10_000).collect();
+            let mut covered = Vec::new();
+            for chain in &chains {
+                assert!(chain.windows(2).all(|pair| step(pair[0]) == pair[1]));
+                assert!(chain.iter().all(|&p| sieve.is_prime(p)));
+                assert!(!sieve.is_prime(step(*chain.last().unwrap())));
+                for &p in chain {
+                    assert_eq!(&cunningham_chain(p, kind), chain);
+                }
+                covered.extend(chain.iter().copied().filter(|&p| p < 10_000));
+            }
+            // Every prime below the limit is in exactly one chain.
+            covered.sort_unstable();
+            let primes: Vec<_> = sieve.primes().take_while(|&p| p < 10_000).collect();
+            assert_eq!(covered, primes, "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn long_chains() {
+        // The first chains of seven primes of each kind.
+        let first: Vec<_> = cunningham_chains(ChainKind::First, 7, 2_000_000)
+            .map(|chain| chain[0])
+            .collect();
+        assert_eq!(first, vec![1_122_659]);
+        let second: Vec<_> = cunningham_chains(ChainKind::Second, 7, 2_000_000)
+            .map(|chain| chain[0])
+            .collect();
+        assert_eq!(second, vec![16_651, 165_901, 1_768_441]);
+        assert_eq!(
+            cunningham_chain(16_651, ChainKind::Second),
+            vec![16_651, 33_301, 66_601, 133_201, 266_401, 532_801, 1_065_601]
+        );
+        assert_eq!(cunningham_chain(2, ChainKind::Second), vec![2, 3, 5]);
+    }
+
+    #[test]
+    fn chains_end_before_overflow() {
+        let p = 9_223_372_036_854_775_783;
+        assert_eq!(cunningham_chain(p, ChainKind::First), vec![p]);
+        assert_eq!(cunningham_chain(u64::MAX - 58, ChainKind::Second).len(), 1);
+    }
+}
@@ -0,0 +1,492 @@
+pub trait DivisorSigma: Sized {
+    /// Returns an `Option` containing either σ(n), the sum of the positive
+    /// divisors of n, or `None` if n is zero or σ(n) would cause overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::DivisorSigma;
+    ///
+    /// assert_eq!(u32::divisor_sigma(&12), Some(28));
+    /// assert_eq!(u8::divisor_sigma(&128), Some(255));
+    /// assert_eq!(u8::divisor_sigma(&240), None);
+    /// assert_eq!(u8::divisor_sigma(&0), None);
+    /// ```
+    fn divisor_sigma(n: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the abundancy index σ(n)/n as a
+    /// `(numerator, denominator)` fraction in lowest terms or `None` if n
+    /// is zero or σ(n) would cause overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::DivisorSigma;
+    ///
+    /// assert_eq!(u32::abundancy(&6), Some((2, 1)));
+    /// assert_eq!(u32::abundancy(&12), Some((7, 3)));
+    /// assert_eq!(u32::abundancy(&0), None);
+    /// ```
+    fn abundancy(n: &Self) -> Option<(Self, Self)>;
+
+    /// Returns whether n is k-perfect, that is, whether σ(n) = k·n.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::DivisorSigma;
+    ///
+    /// assert!(u32::is_multiperfect(&28, &2));
+    /// assert!(u32::is_multiperfect(&120, &3));
+    /// assert!(!u32::is_multiperfect(&120, &2));
+    /// ```
+    fn is_multiperfect(n: &Self, k: &Self) -> bool;
+
+    /// Returns whether n is weird, that is, whether n is abundant (σ(n) > 2n)
+    /// but not semiperfect (no set of its proper divisors sums to n).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::DivisorSigma;
+    ///
+    /// assert!(u32::is_weird(&70));
+    /// assert!(!u32::is_weird(&12));
+    /// assert!(!u32::is_weird(&71));
+    /// ```
+    fn is_weird(n: &Self) -> bool;
+}
+
+pub trait UnitaryDivisors: Sized {
+    /// Returns the unitary divisors of n in increasing order, that is, the
+    /// divisors d for which d and n / d are coprime, or an empty `Vec` if n is
+    /// zero.
+    ///
+    /// Each unitary divisor is a product of some of the prime powers that
+    /// exactly divide n, so there are 2<sup>ω(n)</sup> of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::UnitaryDivisors;
+    ///
+    /// assert_eq!(u32::unitary_divisors(&60), vec![1, 3, 4, 5, 12, 15, 20, 60]);
+    /// assert_eq!(u32::unitary_divisors(&1), vec![1]);
+    /// assert_eq!(u32::unitary_divisors(&0), Vec::<u32>::new());
+    /// ```
+    fn unitary_divisors(n: &Self) -> Vec<Self>;
+
+    /// Returns an `Option` containing either σ*(n), the sum of the unitary
+    /// divisors of n, or `None` if n is zero or σ*(n) would cause overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::UnitaryDivisors;
+    ///
+    /// assert_eq!(u32::unitary_sigma(&60), Some(120));
+    /// assert_eq!(u32::unitary_sigma(&12), Some(20));
+    /// assert_eq!(u8::unitary_sigma(&128), Some(129));
+    /// assert_eq!(u8::unitary_sigma(&0), None);
+    /// ```
+    fn unitary_sigma(n: &Self) -> Option<Self>;
+
+    /// Returns whether n is unitary perfect, that is, whether σ*(n) = 2n.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::UnitaryDivisors;
+    ///
+    /// let unitary_perfect: Vec<_> = (1..100_000u32).filter(u32::is_unitary_perfect).collect();
+    /// assert_eq!(unitary_perfect, vec![6, 60, 90, 87360]);
+    /// ```
+    fn is_unitary_perfect(n: &Self) -> bool;
+}
+
+macro_rules! divisor_sigma_trait_for_unsigned {
+    ($type:ty) => {
+        impl $crate::number_theory::divisors::DivisorSigma for $type {
+            fn divisor_sigma(n: &Self) -> Option<Self> {
+                <Self as $crate::number_theory::Factor>::factorize(n)?.sum_divisors()
+            }
+
+            fn abundancy(n: &Self) -> Option<(Self, Self)> {
+                let sigma = Self::divisor_sigma(n)?;
+                let g = <Self as $crate::number_theory::Gcd>::gcd(&sigma, n);
+                Some((sigma / g, *n / g))
+            }
+
+            fn is_multiperfect(n: &Self, k: &Self) -> bool {
+                match (Self::divisor_sigma(n), n.checked_mul(*k)) {
+                    (Some(sigma), Some(product)) => sigma == product,
+                    _ => false,
+                }
+            }
+
+            fn is_weird(n: &Self) -> bool {
+                $crate::number_theory::divisors::is_weird(*n as u128)
+            }
+        }
+    };
+}
+
+divisor_sigma_trait_for_unsigned!(::core::primitive::u8);
+divisor_sigma_trait_for_unsigned!(::core::primitive::u16);
+divisor_sigma_trait_for_unsigned!(::core::primitive::u32);
+divisor_sigma_trait_for_unsigned!(::core::primitive::u64);
+divisor_sigma_trait_for_unsigned!(::core::primitive::u128);
+divisor_sigma_trait_for_unsigned!(::core::primitive::usize);
+
+macro_rules! unitary_divisors_trait_for_unsigned {
+    ($type:ty) => {
+        impl $crate::number_theory::divisors::UnitaryDivisors for $type {
+            fn unitary_divisors(n: &Self) -> Vec<Self> {
+                match <Self as $crate::number_theory::Factor>::factorize(n) {
+                    Some(factorization) => {
+                        let mut divisors: Vec<Self> = factorization.unitary_divisors().collect();
+                        divisors.sort_unstable();
+                        divisors
+                    }
+                    None => Vec::new(),
+                }
+            }
+
+            fn unitary_sigma(n: &Self) -> Option<Self> {
+                let factorization = <Self as $crate::number_theory::Factor>::factorize(n)?;
+                let mut result: $type = 1;
+                for &(p, e) in factorization.factors() {
+                    // p^e divides n, so it fits.
+                    result = result.checked_mul(p.pow(e).checked_add(1)?)?;
+                }
+                Some(result)
+            }
+
+            fn is_unitary_perfect(n: &Self) -> bool {
+                match (Self::unitary_sigma(n), n.checked_mul(2)) {
+                    (Some(sigma), Some(double)) => sigma == double,
+                    _ => false,
+                }
+            }
+        }
+    };
+}
+
+unitary_divisors_trait_for_unsigned!(::core::primitive::u8);
+unitary_divisors_trait_for_unsigned!(::core::primitive::u16);
+unitary_divisors_trait_for_unsigned!(::core::primitive::u32);
+unitary_divisors_trait_for_unsigned!(::core::primitive::u64);
+unitary_divisors_trait_for_unsigned!(::core::primitive::u128);
+unitary_divisors_trait_for_unsigned!(::core::primitive::usize);
+
+fn is_weird(n: u128) -> bool {
+    let factorization = match <u128 as crate::number_theory::Factor>::factorize(&n) {
+        Some(factorization) => factorization,
+        None => return false,
+    };
+    let mut divisors: Vec<u128> = factorization.proper_divisors().collect();
+    divisors.sort_unstable_by(|a, b| b.cmp(a));
+
+    // A sum that is past u128::MAX is `None`, which is larger than any n.
+    let mut suffix_sums = vec![Some(0u128); divisors.len() + 1];
+    for i in (0..divisors.len()).rev() {
+        suffix_sums[i] = suffix_sums[i + 1].and_then(|sum| sum.checked_add(divisors[i]));
+    }
+
+    // Weird numbers must be abundant. Deficient and perfect numbers are
+    // rejected here because their proper divisors sum to at most n.
+    !matches!(suffix_sums[0], Some(sum) if sum <= n) && !is_subset_sum(&divisors, &suffix_sums, n)
+}
+
+fn is_subset_sum(values: &[u128], suffix_sums: &[Option<u128>], target: u128) -> bool {
+    match suffix_sums[0] {
+        _ if target == 0 => return true,
+        Some(sum) if sum == target => return true,
+        Some(sum) if sum < target => return false,
+        _ => {}
+    }
+
+    (values[0] <= target && is_subset_sum(&values[1..], &suffix_sums[1..], target - values[0]))
+        || is_subset_sum(&values[1..], &suffix_sums[1..], target)
+}
+
+/// Returns a table whose n<sup>th</sup> element is σ(n) for every n below
+/// `limit`, with the element for zero set to zero.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::divisors::sigma_table;
+///
+/// assert_eq!(sigma_table(7), vec![0, 1, 3, 4, 7, 6, 12]);
+/// ```
+pub fn sigma_table(limit: usize) -> Vec<u64> {
+    let mut table = vec![0u64; limit];
+    for d in 1..limit {
+        for multiple in (d..limit).step_by(d) {
+            table[multiple] += d as u64;
+        }
+    }
+    table
+}
+
+/// Returns an iterator over every k-perfect number below `limit` in
+/// increasing order, found by scanning a sieved σ table.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::divisors::multiperfect_numbers;
+///
+/// let perfect: Vec<_> = multiperfect_numbers(2, 10_000).collect();
+/// assert_eq!(perfect, vec![6, 28, 496, 8128]);
+///
+/// let triperfect: Vec<_> = multiperfect_numbers(3, 1_000).collect();
+/// assert_eq!(triperfect, vec![120, 672]);
+/// ```
+pub fn multiperfect_numbers(k: u64, limit: usize) -> MultiperfectNumbers {
+    MultiperfectNumbers {
+        sigma: sigma_table(limit),
+        k,
+        n: 1,
+    }
+}
+
+/// The iterator returned by `multiperfect_numbers`.
+pub struct MultiperfectNumbers {
+    sigma: Vec<u64>,
+    k: u64,
+    n: usize,
+}
+
+impl Iterator for MultiperfectNumbers {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.n < self.sigma.len() {
+            let n = self.n as u64;
+            self.n += 1;
+            if n.checked_mul(self.k) == Some(self.sigma[n as usize]) {
+                return Some(n);
+            }
+        }
+        None
+    }
+}
+
+/// Returns every untouchable number below `limit` in increasing order, that
+/// is, every number that is not the sum of the proper divisors of any
+/// positive integer.
+///
+/// Every composite m is p k for its smallest prime factor p and its largest
+/// proper divisor k, whose prime factors are all at least p. With r the part
+/// of k prime to p, the sum of the proper divisors of m is
+/// s(p k) = p s(k) + σ(r), which is more than k and grows with p. So only k
+/// below `limit` − 1 and the few p that keep the sum below `limit` are
+/// tried, rather than every number below `limit`².
+///
+/// This takes O(`limit`) memory. The time is dominated by the sums
+/// p + k + 1 of two primes, so it is O(`limit`² / log² `limit`), which is
+/// practical up to a few million.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::divisors::untouchable_numbers_below;
+///
+/// assert_eq!(untouchable_numbers_below(130), vec![2, 5, 52, 88, 96, 120, 124]);
+/// ```
+pub fn untouchable_numbers_below(limit: usize) -> Vec<u64> {
+    let limit_u64 = limit as u64;
+    let mut touched = vec![false; limit];
+    // s(1) = 0 and s(p) = 1 for every prime p.
+    for touched in touched.iter_mut().take(2) {
+        *touched = true;
+    }
+
+    let len = limit.saturating_sub(1);
+    let sigma = sigma_table(len);
+    let sieve = crate::number_theory::SpfSieve::new(len as u64);
+    let primes: Vec<_> = (2..len as u64).filter(|&p| sieve.is_prime(p)).collect();
+    for k in 2..len as u64 {
+        let (sigma_k, aliquot_sum) = (sigma[k as usize], sigma[k as usize] - k);
+        let smallest = sieve.smallest_prime_factor(k).unwrap();
+        for &p in primes.iter().take_while(|&&p| p < smallest) {
+            let sum = p.saturating_mul(aliquot_sum).saturating_add(sigma_k);
+            if sum >= limit_u64 {
+                break;
+            }
+            touched[sum as usize] = true;
+        }
+
+        let mut r = k;
+        while r.is_multiple_of(smallest) {
+            r /= smallest;
+        }
+        let sum = smallest
+            .saturating_mul(aliquot_sum)
+            .saturating_add(sigma[r as usize]);
+        if sum < limit_u64 {
+            touched[sum as usize] = true;
+        }
+    }
+
+    (0..limit_u64).filter(|&n| !touched[n as usize]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_divisor_sigma_against_table {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let table = sigma_table(2000);
+                for n in (0..table.len()).take_while(|&n| n as u128 <= <$type>::MAX as u128) {
+                    let expected = if n == 0 || table[n] as u128 > <$type>::MAX as u128 {
+                        ::core::option::Option::None
+                    } else {
+                        ::core::option::Option::Some(table[n] as $type)
+                    };
+                    ::core::assert_eq!(<$type>::divisor_sigma(&(n as $type)), expected);
+                }
+            }
+        };
+    }
+
+    test_divisor_sigma_against_table!(::core::primitive::u8, u8_divisor_sigma);
+    test_divisor_sigma_against_table!(::core::primitive::u16, u16_divisor_sigma);
+    test_divisor_sigma_against_table!(::core::primitive::u32, u32_divisor_sigma);
+    test_divisor_sigma_against_table!(::core::primitive::u64, u64_divisor_sigma);
+    test_divisor_sigma_against_table!(::core::primitive::u128, u128_divisor_sigma);
+    test_divisor_sigma_against_table!(::core::primitive::usize, usize_divisor_sigma);
+
+    #[test]
+    fn sigma_near_type_bounds() {
+        assert_eq!(u64::divisor_sigma(&u64::MAX), None);
+        assert_eq!(u32::divisor_sigma(&4294967291), Some(4294967292));
+        // 2^89 − 1 is prime, which is far too large to trial divide.
+        let prime = (1u128 << 89) - 1;
+        assert_eq!(u128::divisor_sigma(&prime), Some(1 << 89));
+        assert_eq!(u128::abundancy(&prime), Some((1 << 89, prime)));
+        assert!(!u128::is_multiperfect(&prime, &1));
+        assert_eq!(u128::divisor_sigma(&(3 << 126)), None);
+    }
+
+    macro_rules! test_unitary_divisors_against_definition {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                for n in (0..2000u32).take_while(|&n| n as u128 <= <$type>::MAX as u128) {
+                    let n = n as $type;
+                    let expected: Vec<$type> = (1..=n)
+                        .filter(|&d| n % d == 0)
+                        .filter(|&d| <$type as crate::number_theory::Gcd>::gcd(&d, &(n / d)) == 1)
+                        .collect();
+                    ::core::assert_eq!(<$type>::unitary_divisors(&n), expected);
+                    let sum = expected.iter().map(|&d| d as u128).sum::<u128>();
+                    let expected_sigma = if n == 0 || sum > <$type>::MAX as u128 {
+                        ::core::option::Option::None
+                    } else {
+                        ::core::option::Option::Some(sum as $type)
+                    };
+                    ::core::assert_eq!(<$type>::unitary_sigma(&n), expected_sigma);
+                }
+            }
+        };
+    }
+
+    test_unitary_divisors_against_definition!(::core::primitive::u8, u8_unitary_divisors);
+    test_unitary_divisors_against_definition!(::core::primitive::u16, u16_unitary_divisors);
+    test_unitary_divisors_against_definition!(::core::primitive::u32, u32_unitary_divisors);
+    test_unitary_divisors_against_definition!(::core::primitive::u64, u64_unitary_divisors);
+    test_unitary_divisors_against_definition!(::core::primitive::u128, u128_unitary_divisors);
+    test_unitary_divisors_against_definition!(::core::primitive::usize, usize_unitary_divisors);
+
+    #[test]
+    fn unitary_perfect_numbers() {
+        assert!(u128::is_unitary_perfect(&146_361_946_186_458_562_560_000));
+        assert_eq!(
+            u128::unitary_divisors(&146_361_946_186_458_562_560_000).len(),
+            1 << 12
+        );
+        assert!(!u64::is_unitary_perfect(&0));
+        assert!(!u8::is_unitary_perfect(&255));
+        assert_eq!(u64::unitary_sigma(&u64::MAX), None);
+        // 2^89 − 1 is prime.
+        let prime = (1u128 << 89) - 1;
+        assert_eq!(u128::unitary_divisors(&prime), vec![1, prime]);
+        assert_eq!(u128::unitary_sigma(&prime), Some(1 << 89));
+        assert!(!u128::is_unitary_perfect(&prime));
+    }
+
+    #[test]
+    fn multiperfect_pointwise() {
+        for &n in &[120u64, 672, 523776, 459818240] {
+            assert!(u64::is_multiperfect(&n, &3));
+            assert_eq!(u64::abundancy(&n), Some((3, 1)));
+        }
+        for &n in &[30240u64, 32760, 2178540] {
+            assert!(u64::is_multiperfect(&n, &4));
+        }
+        assert!(u64::is_multiperfect(&1, &1));
+        assert!(!u64::is_multiperfect(&6, &0));
+    }
+
+    #[test]
+    fn multiperfect_search() {
+        let found: Vec<_> = multiperfect_numbers(4, 40_000).collect();
+        assert_eq!(found, vec![30240, 32760]);
+        let found: Vec<_> = multiperfect_numbers(1, 100).collect();
+        assert_eq!(found, vec![1]);
+    }
+
+    #[test]
+    fn weird_numbers() {
+        let weird: Vec<u64> = (1..15000).filter(u64::is_weird).collect();
+        assert_eq!(
+            weird,
+            vec![
+                70, 836, 4030, 5830, 7192, 7912, 9272, 10430, 10570, 10792, 10990, 11410, 11690,
+                12110, 12530, 12670, 13370, 13510, 13790, 13930, 14770
+            ]
+        );
+        assert!(u8::is_weird(&70));
+        assert!(u128::is_weird(&70));
+        assert!(!u128::is_weird(&((1 << 89) - 1)));
+        // The proper divisors of 3 · 2^126 sum to past u128::MAX.
+        assert!(!u128::is_weird(&(3 << 126)));
+    }
+
+    #[test]
+    fn untouchable_numbers() {
+        assert_eq!(
+            untouchable_numbers_below(500),
+            vec![
+                2, 5, 52, 88, 96, 120, 124, 146, 162, 188, 206, 210, 216, 238, 246, 248, 262, 268,
+                276, 288, 290, 292, 304, 306, 322, 324, 326, 336, 342, 372, 406, 408, 426, 430,
+                448, 472, 474, 498
+            ]
+        );
+        assert!(untouchable_numbers_below(0).is_empty());
+        assert!(untouchable_numbers_below(1).is_empty());
+        assert_eq!(untouchable_numbers_below(3), vec![2]);
+
+        // A composite m has a proper divisor of at least √m, so only the
+        // numbers below limit² can have aliquot sums below limit.
+        let limit = 1000;
+        let sigma = sigma_table(limit * limit);
+        let mut touched = vec![false; limit];
+        touched[0] = true;
+        for (m, &sigma) in sigma.iter().enumerate().skip(1) {
+            if let Some(touched) = touched.get_mut(sigma as usize - m) {
+                *touched = true;
+            }
+        }
+        let expected: Vec<_> = (0..limit as u64)
+            .filter(|&n| !touched[n as usize])
+            .collect();
+        assert_eq!(untouchable_numbers_below(limit), expected);
+    }
+}
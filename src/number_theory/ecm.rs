@@ -0,0 +1,334 @@
+//! Lenstra's elliptic curve method, for finding prime factors of up to about
+//! 35 digits in integers too large for `pollard_rho`.
+//!
+//! Pollard's p − 1 method works in the group of units modulo a prime factor
+//! p, whose order p − 1 is fixed. The elliptic curve method instead works in
+//! the group of points on a curve modulo p, whose order is somewhere near p
+//! and changes from curve to curve, so a p whose p − 1 is not smooth can
+//! still be found by trying curves until one has a smooth order. The time
+//! that takes depends on the size of p rather than of n.
+//!
+//! Curves are in Montgomery form By<sup>2</sup> = x<sup>3</sup> +
+//! Ax<sup>2</sup> + x with Suyama's parametrization, which makes each order
+//! a multiple of 12, and points are kept as projective x-coordinates (X : Z)
+//! so that no inverses are needed. Curve i for i = 0, 1, … uses the
+//! parameter σ = i + 6, so the results do not change from run to run.
+//!
+//! # Examples
+//!
+//! ```
+//! use mathematical::number_theory::ecm::ecm;
+//! use rug::Integer;
+//!
+//! // The second curve finds a 20-digit factor of this 60-digit number.
+//! let p = Integer::from(10_560_000_000_000_000_097u128);
+//! let q: Integer = "1000000000000000000000000000000000000003".parse().unwrap();
+//! let n = Integer::from(&p * &q);
+//! assert_eq!(ecm(&n, 11_000, 1_100_000, 1), None);
+//! assert_eq!(ecm(&n, 11_000, 1_100_000, 2), Some(p));
+//! ```
+
+use crate::number_theory::Prime;
+use rug::Integer;
+
+/// The choices of B1 from the GMP-ECM documentation, each with the number of
+/// curves that is expected to find a prime factor of 20, 25, 30, … digits,
+/// which are worked through in turn when `find_factor` has to split a
+/// composite that no configured strategy did.
+const LEVELS: [(u64, u32); 6] = [
+    (2000, 25),
+    (11_000, 90),
+    (50_000, 300),
+    (250_000, 700),
+    (1_000_000, 1800),
+    (3_000_000, 5100),
+];
+
+/// Returns an `Option` containing either a nontrivial factor of n or `None`
+/// if n is prime, less than four, or has no factor found by the given
+/// number of curves with stage-one bound `b1` and stage-two bound `b2`.
+///
+/// Stage one multiplies a point on each curve by the largest power of
+/// each prime up to `b1` that is no larger than `b1`, which reaches the
+/// identity modulo a prime factor p, showing up as a common factor of Z and
+/// n, when the order of the curve modulo p is `b1`-smooth. Stage two then
+/// finds p when the order has one more prime factor up to `b2`, by
+/// comparing a table of small multiples of the point with multiples of it
+/// 210 apart. Each curve takes about 1.44 `b1` steps of a few
+/// multiplications in stage one and about (`b2` − `b1`) / 4 in stage two.
+///
+/// The factor that turns up is not necessarily prime or the smallest one.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::ecm::ecm;
+/// use rug::Integer;
+///
+/// let n = Integer::from(Integer::u_pow_u(2, 128)) + 1u8;
+/// assert_eq!(ecm(&n, 2000, 200_000, 25).unwrap(), 59_649_589_127_497_217u64);
+///
+/// assert_eq!(ecm(&Integer::from(u64::MAX - 58), 2000, 200_000, 25), None);
+/// ```
+pub fn ecm(n: &Integer, b1: u64, b2: u64, curves: u32) -> Option<Integer> {
+    if *n < 4 || Integer::is_prime(n) {
+        return None;
+    }
+    if n.is_even() {
+        return Some(Integer::from(2));
+    }
+    let sieve = crate::number_theory::Sieve::new(b1.max(b2) + D);
+    (6..6 + curves as u64).find_map(|sigma| curve(n, sigma, b1, b2, &sieve))
+}
+
+/// Returns a nontrivial factor of n, which must be odd and composite, trying
+/// more and more curves with larger and larger bounds until one is found.
+pub(crate) fn find_factor(n: &Integer) -> Integer {
+    let mut sigma = 6;
+    let last = LEVELS.len() - 1;
+    for level in (0..).map(|level: usize| level.min(last)) {
+        let (b1, curves) = LEVELS[level];
+        let sieve = crate::number_theory::Sieve::new(100 * b1 + D);
+        for _ in 0..curves {
+            if let Some(d) = curve(n, sigma, b1, 100 * b1, &sieve) {
+                return d;
+            }
+            sigma += 1;
+        }
+    }
+    unreachable!()
+}
+
+/// The spacing of the multiples of the point in stage two, which are
+/// compared with the multiples by the 24 numbers below D / 2 coprime to D.
+const D: u64 = 210;
+
+/// A point on a curve, as the projective x-coordinate (X : Z).
+#[derive(Clone, Debug)]
+struct Point {
+    x: Integer,
+    z: Integer,
+}
+
+/// A Montgomery curve modulo n, kept as (A + 2) / 4 since that is all that
+/// doubling needs.
+struct Curve<'a> {
+    n: &'a Integer,
+    a24: Integer,
+}
+
+impl Curve<'_> {
+    fn reduce(&self, x: Integer) -> Integer {
+        x.modulo(self.n)
+    }
+
+    /// Returns 2P.
+    fn double(&self, p: &Point) -> Point {
+        let sum = self.reduce(Integer::from(&p.x + &p.z).square());
+        let difference = self.reduce(Integer::from(&p.x - &p.z).square());
+        // 4XZ.
+        let product = Integer::from(&sum - &difference);
+        let x = self.reduce(Integer::from(&sum * &difference));
+        let z = self.reduce(Integer::from(&self.a24 * &product) + &difference);
+        let z = self.reduce(z * product);
+        Point { x, z }
+    }
+
+    /// Returns P + Q given P − Q, which must not be the identity.
+    fn add(&self, p: &Point, q: &Point, difference: &Point) -> Point {
+        let u = self.reduce(Integer::from(&p.x - &p.z) * Integer::from(&q.x + &q.z));
+        let v = self.reduce(Integer::from(&p.x + &p.z) * Integer::from(&q.x - &q.z));
+        let x = self.reduce(Integer::from(&u + &v).square());
+        let z = self.reduce(Integer::from(&u - &v).square());
+        Point {
+            x: self.reduce(x * &difference.z),
+            z: self.reduce(z * &difference.x),
+        }
+    }
+
+    /// Returns kP, for positive k, by the Montgomery ladder.
+    fn multiply(&self, p: &Point, k: u64) -> Point {
+        let (mut low, mut high) = (p.clone(), self.double(p));
+        for bit in (0..63 - k.leading_zeros()).rev() {
+            if k >> bit & 1 == 1 {
+                low = self.add(&high, &low, p);
+                high = self.double(&high);
+            } else {
+                high = self.add(&high, &low, p);
+                low = self.double(&low);
+            }
+        }
+        low
+    }
+}
+
+/// Returns an `Option` containing either a nontrivial factor of n that the
+/// curve with Suyama parameter σ finds, or `None` if it finds none or finds
+/// every factor at once, where the sieve must go past `b1` and `b2` + D.
+fn curve(
+    n: &Integer,
+    sigma: u64,
+    b1: u64,
+    b2: u64,
+    sieve: &crate::number_theory::Sieve,
+) -> Option<Integer> {
+    let found = |d: Integer| Some(d).filter(|d| *d != 1 && d != n);
+
+    // u = σ² − 5, v = 4σ, the starting point is (u³ : v³), and
+    // (A + 2) / 4 = (v − u)³ (3u + v) / (16 u³ v).
+    let u = Integer::from(sigma * sigma - 5);
+    let v = Integer::from(4 * sigma);
+    let x = u.clone().pow_mod(&Integer::from(3), n).unwrap();
+    let z = v.clone().pow_mod(&Integer::from(3), n).unwrap();
+    let numerator: Integer = Integer::from(&v - &u)
+        .pow_mod(&Integer::from(3), n)
+        .unwrap()
+        * (3 * u + &v);
+    let denominator: Integer = Integer::from(&x * &v) << 4;
+    let inverse = match denominator.invert_ref(n) {
+        Some(inverse) => Integer::from(inverse),
+        None => return found(Integer::from(denominator.gcd_ref(n))),
+    };
+    let curve = Curve {
+        n,
+        a24: (numerator * inverse).modulo(n),
+    };
+    let mut point = Point { x, z };
+
+    // Stage one.
+    for q in sieve.primes().take_while(|&q| q <= b1) {
+        let mut power = q;
+        while power <= b1 / q {
+            power *= q;
+        }
+        point = curve.multiply(&point, power);
+    }
+    let d = Integer::from(point.z.gcd_ref(n));
+    if d != 1 {
+        return found(d);
+    }
+    if b2 <= b1 {
+        return None;
+    }
+
+    // Stage two, where each prime q up to b2 is kD ± j for some j below D / 2
+    // and coprime to D, and kDP and jP have the same x-coordinate modulo p
+    // exactly when one of (kD ± j)P is the identity modulo p.
+    let is_stage_two = |q: u64| q > b1 && q <= b2 && sieve.is_prime(q);
+    let twice = curve.double(&point);
+    let mut odd = vec![point.clone(), curve.add(&twice, &point, &point)];
+    while odd.len() < (D / 4) as usize {
+        let next = curve.add(&odd[odd.len() - 1], &twice, &odd[odd.len() - 2]);
+        odd.push(next);
+    }
+    let table: Vec<_> = (1..)
+        .step_by(2)
+        .zip(odd)
+        .filter(|&(j, _)| [3, 5, 7].iter().all(|&p| j % p != 0))
+        .collect();
+
+    // The primes below D / 2 are the j themselves.
+    let mut product = Integer::from(1);
+    for (j, small) in &table {
+        if is_stage_two(*j) {
+            product = curve.reduce(product * &small.z);
+        }
+    }
+    // Then kDP and (k + 1)DP, for k from about b1 / D on.
+    let step = curve.multiply(&point, D);
+    let first = (b1 / D).max(1);
+    let (mut multiple, mut next) = (
+        curve.multiply(&point, first * D),
+        curve.multiply(&point, (first + 1) * D),
+    );
+    let mut k = first;
+    while k * D <= b2 + D {
+        for (j, small) in &table {
+            if is_stage_two(k * D - j) || is_stage_two(k * D + j) {
+                let difference = Integer::from(&multiple.x * &small.z) - &small.x * &multiple.z;
+                product = curve.reduce(product * difference);
+            }
+        }
+        let after = curve.add(&next, &step, &multiple);
+        multiple = core::mem::replace(&mut next, after);
+        k += 1;
+    }
+    found(product.gcd(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiples_agree() {
+        let n = Integer::from(1_000_000_007u64) * 998_244_353u64;
+        let curve = Curve {
+            n: &n,
+            a24: Integer::from(12_345),
+        };
+        let point = Point {
+            x: Integer::from(7),
+            z: Integer::from(1),
+        };
+        let same = |p: &Point, q: &Point| {
+            Integer::from(&p.x * &q.z).modulo(&n) == Integer::from(&q.x * &p.z).modulo(&n)
+        };
+        let mut multiples = vec![point.clone(), curve.double(&point)];
+        for k in 2..300 {
+            let next = curve.add(&multiples[k - 1], &point, &multiples[k - 2]);
+            multiples.push(next);
+        }
+        for k in 1..=300 {
+            assert!(same(&curve.multiply(&point, k), &multiples[k as usize - 1]));
+        }
+        assert!(same(
+            &curve.double(&multiples[99]),
+            &curve.multiply(&point, 200)
+        ));
+    }
+
+    #[test]
+    fn factors_are_nontrivial() {
+        let mut missed = 0;
+        for n in (9..5000u32).step_by(2) {
+            let big = Integer::from(n);
+            match ecm(&big, 50, 500, 10) {
+                Some(d) => assert!(d > 1 && d < n && big.is_divisible(&d), "n = {}", n),
+                None => missed += !Integer::is_prime(&big) as u32,
+            }
+        }
+        // Curves modulo tiny primes often find every factor at once, but
+        // most composites still split.
+        assert!(missed < 200, "{}", missed);
+        assert_eq!(ecm(&Integer::from(1 << 20), 50, 500, 1).unwrap(), 2);
+        assert_eq!(ecm(&Integer::from(3), 50, 500, 1), None);
+    }
+
+    #[test]
+    fn stages() {
+        let p = Integer::from(10_560_000_000_000_000_097u128);
+        let q: Integer = "1000000000000000000000000000000000000003".parse().unwrap();
+        let n = Integer::from(&p * &q);
+        let sieve = crate::number_theory::Sieve::new(100_000);
+        // The order of the curve with σ = 7 modulo p has largest prime factor
+        // 79,943, and its other prime power factors are at most 6827.
+        assert_eq!(curve(&n, 7, 79_943, 0, &sieve), Some(p.clone()));
+        assert_eq!(curve(&n, 7, 79_942, 0, &sieve), None);
+        assert_eq!(curve(&n, 7, 6827, 79_943, &sieve), Some(p.clone()));
+        assert_eq!(curve(&n, 7, 6827, 79_942, &sieve), None);
+        assert_eq!(curve(&n, 7, 6826, 79_943, &sieve), None);
+        // Modulo 10^18 + 3 the order is 20,000-smooth, so both factors are
+        // found at once.
+        let n = Integer::from(&p * 1_000_000_000_000_000_003u64);
+        assert_eq!(
+            curve(&n, 7, 20_000, 0, &sieve),
+            Some(Integer::from(1_000_000_000_000_000_003u64))
+        );
+        assert_eq!(curve(&n, 7, 79_943, 0, &sieve), None);
+        assert_eq!(
+            find_factor(&Integer::from(4_294_967_291u64 * 4_294_967_279)),
+            4_294_967_291u64
+        );
+    }
+}
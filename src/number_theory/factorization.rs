@@ -0,0 +1,1654 @@
+use crate::number_theory::Gcd;
+
+pub trait Factor: Sized {
+    /// Returns an `Option` containing either the factorization of n into
+    /// primes or `None` if n is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Factor;
+    ///
+    /// let factorization = u32::factorize(&360).unwrap();
+    /// assert_eq!(factorization.factors(), &[(2, 3), (3, 2), (5, 1)]);
+    /// assert_eq!(factorization.value(), 360);
+    ///
+    /// assert!(u64::factorize(&1).unwrap().factors().is_empty());
+    /// assert_eq!(u8::factorize(&0), None);
+    /// ```
+    fn factorize(n: &Self) -> Option<Factorization<Self>>;
+
+    /// Returns an `Option` containing either the factorization of n into
+    /// primes or `None` if n is zero, trying the configured strategies on
+    /// whatever trial division leaves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::factorization::{FactorizationConfig, Strategy};
+    /// use mathematical::number_theory::Factor;
+    ///
+    /// // p − 1 = 2 × 7 × 11 × 23 × 67 × 79 × 317 × 359 × 641 × 40009, while
+    /// // q is near 2^60, which would take rho a long time.
+    /// let (p, q) = (54_716_698_845_660_031_643u128, 1_634_716_391_129_166_659u128);
+    /// let config = FactorizationConfig::new(vec![Strategy::PollardPMinusOne {
+    ///     b1: 1000,
+    ///     b2: 50_000,
+    /// }]);
+    /// let factorization = u128::factorize_with(&(p * q), &config).unwrap();
+    /// assert_eq!(factorization.factors(), &[(q, 1), (p, 1)]);
+    /// ```
+    fn factorize_with(n: &Self, config: &FactorizationConfig) -> Option<Factorization<Self>>;
+}
+
+/// A method for splitting the composites that trial division leaves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Strategy {
+    /// `pollard_rho`.
+    PollardRho,
+    /// `squfof`, for composites from 2<sup>48</sup> to 2<sup>62</sup> only,
+    /// where it keeps up with Pollard's rho.
+    Squfof,
+    /// `quadratic_sieve::quadratic_sieve`, for composites of 2<sup>64</sup>
+    /// or more only, where it overtakes Pollard's rho.
+    QuadraticSieve,
+    /// `fermat` with the given number of steps.
+    Fermat { steps: u64 },
+    /// `pollard_p_minus_one` with the given bounds.
+    PollardPMinusOne { b1: u64, b2: u64 },
+    /// `ecm::ecm` with the given bounds and number of curves.
+    #[cfg(any(feature = "rug", doc, test))]
+    #[doc(cfg(feature = "rug"))]
+    Ecm { b1: u64, b2: u64, curves: u32 },
+}
+
+/// The strategies to try, in order, on each composite that trial division
+/// leaves when factoring.
+///
+/// The first strategy to split a composite wins, and anything that none of
+/// them split is left to `pollard_rho`, so every configuration factors
+/// completely. The default is SQUFOF, then the quadratic sieve, and then
+/// Pollard's rho, which suits numbers with no particular structure, as each
+/// of the first two only takes the sizes of composite where it is quickest.
+///
+/// When factoring a `rug::Integer`, SQUFOF, the quadratic sieve, and
+/// Pollard's rho and p − 1 methods are skipped for composites of
+/// 2<sup>128</sup> or more, and anything that no `Strategy::Ecm` splits is left
+/// to the elliptic curve method with bounds that grow until a factor turns up.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::factorization::{FactorizationConfig, Strategy};
+///
+/// let config = FactorizationConfig::default();
+/// assert_eq!(
+///     config.strategies(),
+///     &[Strategy::Squfof, Strategy::QuadraticSieve, Strategy::PollardRho]
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FactorizationConfig {
+    strategies: Vec<Strategy>,
+}
+
+impl FactorizationConfig {
+    /// Returns the configuration that tries the strategies in order.
+    pub fn new(strategies: Vec<Strategy>) -> Self {
+        Self { strategies }
+    }
+
+    /// Returns the strategies in the order that they are tried.
+    pub fn strategies(&self) -> &[Strategy] {
+        &self.strategies
+    }
+
+    /// Returns a nontrivial factor of n, which must be composite.
+    fn split(&self, n: u128) -> u128 {
+        self.strategies
+            .iter()
+            .find_map(|strategy| match *strategy {
+                Strategy::PollardRho => pollard_rho(n),
+                Strategy::Squfof => <u64 as core::convert::TryFrom<u128>>::try_from(n)
+                    .ok()
+                    .filter(|&n| n >> 48 != 0 && n < 1 << 62)
+                    .and_then(squfof)
+                    .map(u128::from),
+                Strategy::QuadraticSieve => Some(n)
+                    .filter(|&n| n >> 64 != 0)
+                    .and_then(crate::number_theory::quadratic_sieve::quadratic_sieve),
+                Strategy::Fermat { steps } => fermat(n, steps),
+                Strategy::PollardPMinusOne { b1, b2 } => pollard_p_minus_one(n, b1, b2),
+                #[cfg(any(feature = "rug", doc, test))]
+                Strategy::Ecm { b1, b2, curves } => {
+                    crate::number_theory::ecm::ecm(&n.into(), b1, b2, curves)
+                        .map(|d| d.to_u128().unwrap())
+                }
+            })
+            .or_else(|| pollard_rho(n))
+            .unwrap()
+    }
+
+    /// Returns a nontrivial factor of n, which must be odd, composite, and
+    /// at least 2<sup>128</sup>.
+    #[cfg(any(feature = "rug", doc, test))]
+    fn split_big(&self, n: &rug::Integer) -> rug::Integer {
+        use rug::ops::Pow;
+
+        // The elliptic curve method does badly on prime powers.
+        if n.is_perfect_power() {
+            for k in 2.. {
+                let root = rug::Integer::from(n.root_ref(k));
+                if root.clone().pow(k) == *n {
+                    return root;
+                }
+            }
+        }
+        self.strategies
+            .iter()
+            .find_map(|strategy| match *strategy {
+                Strategy::Ecm { b1, b2, curves } => {
+                    crate::number_theory::ecm::ecm(n, b1, b2, curves)
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| crate::number_theory::ecm::find_factor(n))
+    }
+}
+
+impl Default for FactorizationConfig {
+    fn default() -> Self {
+        Self::new(vec![
+            Strategy::Squfof,
+            Strategy::QuadraticSieve,
+            Strategy::PollardRho,
+        ])
+    }
+}
+
+/// The factorization of a positive integer into primes, as each prime
+/// factor in increasing order along with its exponent.
+///
+/// The functions of n that only depend on its prime factors are quick to
+/// find from here, so `Factorization` holds them rather than each needing
+/// to factor n again.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::Factor;
+///
+/// let factorization = u64::factorize(&720).unwrap();
+/// assert_eq!(factorization.num_divisors(), 30);
+/// assert_eq!(factorization.sum_divisors(), Some(2418));
+/// assert_eq!(factorization.totient(), 192);
+/// assert_eq!(factorization.radical(), 30);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Factorization<T> {
+    factors: Vec<(T, u32)>,
+}
+
+/// The iterator returned by `Factorization::divisors` and the other
+/// divisor functions of `Factorization`.
+///
+/// The divisors come as the exponents count up with the exponent of the
+/// smallest prime changing fastest, so n itself is last.
+#[derive(Clone, Debug)]
+pub struct Divisors<T> {
+    factors: Vec<(T, u32)>,
+    exponents: Vec<u32>,
+    /// The product of the current powers of the i<sup>th</sup> prime and
+    /// those after it, with the current divisor first.
+    products: Vec<T>,
+    done: bool,
+    proper: bool,
+}
+
+impl<T: Clone> Divisors<T> {
+    fn new(factors: Vec<(T, u32)>, one: T, proper: bool) -> Self {
+        let products = vec![one; factors.len() + 1];
+        Self {
+            exponents: vec![0; factors.len()],
+            factors,
+            products,
+            done: false,
+            proper,
+        }
+    }
+}
+
+impl<T> Iterator for Divisors<T>
+where
+    T: Clone + for<'a> core::ops::MulAssign<&'a T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+        let divisor = self.products[0].clone();
+        let exponents = &self.exponents;
+        match (0..self.factors.len()).find(|&i| exponents[i] < self.factors[i].1) {
+            Some(i) => {
+                self.exponents[i] += 1;
+                self.products[i] *= &self.factors[i].0;
+                for j in 0..i {
+                    self.exponents[j] = 0;
+                    self.products[j] = self.products[i].clone();
+                }
+            }
+            None => {
+                self.done = true;
+                if self.proper {
+                    return None;
+                }
+            }
+        }
+        Some(divisor)
+    }
+}
+
+impl<T> core::iter::FusedIterator for Divisors<T> where
+    T: Clone + for<'a> core::ops::MulAssign<&'a T>
+{
+}
+
+macro_rules! factor_trait_for_unsigned {
+    ($type:ty) => {
+        impl $crate::number_theory::factorization::Factor for $type {
+            fn factorize(
+                n: &Self,
+            ) -> ::core::option::Option<$crate::number_theory::factorization::Factorization<Self>>
+            {
+                Self::factorize_with(
+                    n,
+                    &$crate::number_theory::factorization::FactorizationConfig::default(),
+                )
+            }
+
+            fn factorize_with(
+                n: &Self,
+                config: &$crate::number_theory::factorization::FactorizationConfig,
+            ) -> ::core::option::Option<$crate::number_theory::factorization::Factorization<Self>>
+            {
+                if *n == 0 {
+                    return ::core::option::Option::None;
+                }
+                ::core::option::Option::Some($crate::number_theory::factorization::Factorization {
+                    factors: $crate::number_theory::factorization::factorize(*n as u128, config)
+                        .into_iter()
+                        .map(|(p, e)| (p as $type, e))
+                        .collect(),
+                })
+            }
+        }
+
+        impl $crate::number_theory::factorization::Factorization<$type> {
+            /// Returns the prime factors in increasing order, each along
+            /// with its exponent.
+            pub fn factors(&self) -> &[($type, u32)] {
+                &self.factors
+            }
+
+            /// Returns the number that was factored, the product of the
+            /// prime powers.
+            pub fn value(&self) -> $type {
+                self.factors.iter().map(|&(p, e)| p.pow(e)).product()
+            }
+
+            /// Returns d(n), the number of positive divisors, which is the
+            /// product of e + 1 over the exponents e.
+            pub fn num_divisors(&self) -> u64 {
+                self.factors.iter().map(|&(_, e)| e as u64 + 1).product()
+            }
+
+            /// Returns an `Option` containing either σ(n), the sum of the
+            /// positive divisors, or `None` if that would cause overflow.
+            pub fn sum_divisors(&self) -> ::core::option::Option<$type> {
+                let mut sum: $type = 1;
+                for &(p, e) in &self.factors {
+                    // 1 + p + … + p^e.
+                    let (mut power, mut term): ($type, $type) = (1, 1);
+                    for _ in 0..e {
+                        power *= p;
+                        term = term.checked_add(power)?;
+                    }
+                    sum = sum.checked_mul(term)?;
+                }
+                ::core::option::Option::Some(sum)
+            }
+
+            /// Returns Euler's totient φ(n), the number of positive integers
+            /// up to n that are coprime to it.
+            pub fn totient(&self) -> $type {
+                self.factors
+                    .iter()
+                    .map(|&(p, e)| p.pow(e - 1) * (p - 1))
+                    .product()
+            }
+
+            /// Returns an `Option` containing either the Jordan totient
+            /// J<sub>k</sub>(n), the number of k-tuples of integers from 1 to
+            /// n whose greatest common divisor with n is one, or `None` if
+            /// that would cause overflow.
+            ///
+            /// This is the product of p<sup>k(e − 1)</sup> (p<sup>k</sup> − 1)
+            /// over the prime powers, so J<sub>1</sub> is φ.
+            pub fn jordan_totient(&self, k: u32) -> ::core::option::Option<$type> {
+                let mut product: $type = 1;
+                for &(p, e) in &self.factors {
+                    let power = p.checked_pow(k)?;
+                    let term = power.checked_pow(e - 1)?.checked_mul(power - 1)?;
+                    product = product.checked_mul(term)?;
+                }
+                ::core::option::Option::Some(product)
+            }
+
+            /// Returns the Carmichael function λ(n), the smallest positive m
+            /// with a<sup>m</sup> ≡ 1 (mod n) for every a coprime to n.
+            ///
+            /// This is the least common multiple over the prime powers of
+            /// λ(p<sup>e</sup>), which is φ(p<sup>e</sup>) except that
+            /// λ(2<sup>e</sup>) is 2<sup>e − 2</sup> from e = 3 on.
+            pub fn carmichael_lambda(&self) -> $type {
+                self.factors.iter().fold(1, |lambda, &(p, e)| {
+                    let term = if p == 2 && e >= 3 {
+                        p.pow(e - 2)
+                    } else {
+                        p.pow(e - 1) * (p - 1)
+                    };
+                    // λ(n) divides φ(n), which is at most n, so this fits.
+                    <$type as $crate::number_theory::Gcd>::lcm(&lambda, &term)
+                })
+            }
+
+            /// Returns the radical, the product of the distinct prime
+            /// factors, which is the largest squarefree divisor.
+            pub fn radical(&self) -> $type {
+                self.factors.iter().map(|&(p, _)| p).product()
+            }
+
+            /// Returns the Liouville function λ(n) = (−1)<sup>Ω(n)</sup>,
+            /// where Ω(n) is the sum of the exponents.
+            pub fn liouville(&self) -> i8 {
+                let omega: u32 = self.factors.iter().map(|&(_, e)| e).sum();
+                if omega.is_multiple_of(2) {
+                    1
+                } else {
+                    -1
+                }
+            }
+
+            /// Returns a `Divisors<T>` iterator over the positive divisors,
+            /// which are not in increasing order.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use mathematical::number_theory::Factor;
+            ///
+            /// let factorization = u32::factorize(&12).unwrap();
+            /// let divisors: Vec<_> = factorization.divisors().collect();
+            /// assert_eq!(divisors, vec![1, 2, 4, 3, 6, 12]);
+            /// assert_eq!(factorization.sorted_divisors(), vec![1, 2, 3, 4, 6, 12]);
+            ///
+            /// let proper: Vec<_> = factorization.proper_divisors().collect();
+            /// assert_eq!(proper, vec![1, 2, 4, 3, 6]);
+            /// let unitary: Vec<_> = factorization.unitary_divisors().collect();
+            /// assert_eq!(unitary, vec![1, 4, 3, 12]);
+            /// ```
+            pub fn divisors(&self) -> $crate::number_theory::factorization::Divisors<$type> {
+                $crate::number_theory::factorization::Divisors::new(self.factors.clone(), 1, false)
+            }
+
+            /// Returns the positive divisors in increasing order.
+            pub fn sorted_divisors(&self) -> ::std::vec::Vec<$type> {
+                let mut divisors: ::std::vec::Vec<$type> = self.divisors().collect();
+                divisors.sort_unstable();
+                divisors
+            }
+
+            /// Returns a `Divisors<T>` iterator over the proper divisors, the
+            /// positive divisors other than n itself, so there are none for
+            /// one.
+            pub fn proper_divisors(&self) -> $crate::number_theory::factorization::Divisors<$type> {
+                $crate::number_theory::factorization::Divisors::new(self.factors.clone(), 1, true)
+            }
+
+            /// Returns a `Divisors<T>` iterator over the unitary divisors,
+            /// the divisors d for which d and n / d are coprime, which are
+            /// the products of some of the prime powers.
+            pub fn unitary_divisors(
+                &self,
+            ) -> $crate::number_theory::factorization::Divisors<$type> {
+                $crate::number_theory::factorization::Divisors::new(
+                    self.factors.iter().map(|&(p, e)| (p.pow(e), 1)).collect(),
+                    1,
+                    false,
+                )
+            }
+        }
+    };
+}
+
+factor_trait_for_unsigned!(::core::primitive::u8);
+factor_trait_for_unsigned!(::core::primitive::u16);
+factor_trait_for_unsigned!(::core::primitive::u32);
+factor_trait_for_unsigned!(::core::primitive::u64);
+factor_trait_for_unsigned!(::core::primitive::u128);
+factor_trait_for_unsigned!(::core::primitive::usize);
+
+/// Negative integers have no factorization into primes, so they give `None`
+/// as zero does.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::factorization::{FactorizationConfig, Strategy};
+/// use mathematical::number_theory::Factor;
+/// use rug::Integer;
+///
+/// let n = Integer::from(Integer::u_pow_u(2, 128)) + 1u8;
+/// let config = FactorizationConfig::new(vec![Strategy::Ecm {
+///     b1: 2000,
+///     b2: 200_000,
+///     curves: 25,
+/// }]);
+/// let factorization = Integer::factorize_with(&n, &config).unwrap();
+/// assert_eq!(
+///     factorization.factors(),
+///     &[
+///         (Integer::from(59_649_589_127_497_217u64), 1),
+///         (Integer::from(5_704_689_200_685_129_054_721u128), 1)
+///     ]
+/// );
+/// assert_eq!(factorization.value(), n);
+/// ```
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Factor for rug::Integer {
+    fn factorize(n: &Self) -> Option<Factorization<Self>> {
+        Self::factorize_with(n, &FactorizationConfig::default())
+    }
+
+    fn factorize_with(n: &Self, config: &FactorizationConfig) -> Option<Factorization<Self>> {
+        if *n <= 0 {
+            return None;
+        }
+        Some(Factorization {
+            factors: factorize_big(n.clone(), config),
+        })
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Factorization<rug::Integer> {
+    /// Returns the prime factors in increasing order, each along with its
+    /// exponent.
+    pub fn factors(&self) -> &[(rug::Integer, u32)] {
+        &self.factors
+    }
+
+    /// Returns the number that was factored, the product of the prime
+    /// powers.
+    pub fn value(&self) -> rug::Integer {
+        self.product(|p, e| rug::Integer::from(rug::ops::Pow::pow(p, e)))
+    }
+
+    /// Returns d(n), the number of positive divisors, which is the product
+    /// of e + 1 over the exponents e.
+    pub fn num_divisors(&self) -> rug::Integer {
+        self.product(|_, e| rug::Integer::from(e) + 1u8)
+    }
+
+    /// Returns σ(n), the sum of the positive divisors.
+    pub fn sum_divisors(&self) -> rug::Integer {
+        // (p^(e + 1) − 1) / (p − 1) = 1 + p + … + p^e.
+        self.product(|p, e| {
+            let power = rug::Integer::from(rug::ops::Pow::pow(p, e + 1)) - 1u8;
+            power.div_exact(&rug::Integer::from(p - 1u8))
+        })
+    }
+
+    /// Returns Euler's totient φ(n), the number of positive integers up to n
+    /// that are coprime to it.
+    pub fn totient(&self) -> rug::Integer {
+        self.product(|p, e| {
+            rug::Integer::from(rug::ops::Pow::pow(p, e - 1)) * rug::Integer::from(p - 1u8)
+        })
+    }
+
+    /// Returns the Jordan totient J<sub>k</sub>(n), the number of k-tuples of
+    /// integers from 1 to n whose greatest common divisor with n is one.
+    pub fn jordan_totient(&self, k: u32) -> rug::Integer {
+        self.product(|p, e| {
+            let power = rug::Integer::from(rug::ops::Pow::pow(p, k));
+            rug::Integer::from(rug::ops::Pow::pow(&power, e - 1)) * (power - 1u8)
+        })
+    }
+
+    /// Returns the Carmichael function λ(n), the smallest positive m with
+    /// a<sup>m</sup> ≡ 1 (mod n) for every a coprime to n.
+    pub fn carmichael_lambda(&self) -> rug::Integer {
+        self.factors
+            .iter()
+            .fold(rug::Integer::from(1), |lambda, (p, e)| {
+                let term = if *p == 2 && *e >= 3 {
+                    rug::Integer::from(rug::ops::Pow::pow(p, e - 2))
+                } else {
+                    rug::Integer::from(rug::ops::Pow::pow(p, e - 1)) * rug::Integer::from(p - 1u8)
+                };
+                lambda.lcm(&term)
+            })
+    }
+
+    /// Returns the radical, the product of the distinct prime factors, which
+    /// is the largest squarefree divisor.
+    pub fn radical(&self) -> rug::Integer {
+        self.product(|p, _| p.clone())
+    }
+
+    /// Returns the Liouville function λ(n) = (−1)<sup>Ω(n)</sup>, where Ω(n)
+    /// is the sum of the exponents.
+    pub fn liouville(&self) -> i8 {
+        let omega: u32 = self.factors.iter().map(|(_, e)| e).sum();
+        if omega.is_multiple_of(2) {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Returns a `Divisors<T>` iterator over the positive divisors, which are
+    /// not in increasing order.
+    pub fn divisors(&self) -> Divisors<rug::Integer> {
+        Divisors::new(self.factors.clone(), rug::Integer::from(1), false)
+    }
+
+    /// Returns the positive divisors in increasing order.
+    pub fn sorted_divisors(&self) -> Vec<rug::Integer> {
+        let mut divisors: Vec<_> = self.divisors().collect();
+        divisors.sort_unstable();
+        divisors
+    }
+
+    /// Returns a `Divisors<T>` iterator over the proper divisors, the
+    /// positive divisors other than n itself, so there are none for one.
+    pub fn proper_divisors(&self) -> Divisors<rug::Integer> {
+        Divisors::new(self.factors.clone(), rug::Integer::from(1), true)
+    }
+
+    /// Returns a `Divisors<T>` iterator over the unitary divisors, the
+    /// divisors d for which d and n / d are coprime, which are the products of
+    /// some of the prime powers.
+    pub fn unitary_divisors(&self) -> Divisors<rug::Integer> {
+        let powers = self
+            .factors
+            .iter()
+            .map(|(p, e)| (rug::Integer::from(rug::ops::Pow::pow(p, *e)), 1))
+            .collect();
+        Divisors::new(powers, rug::Integer::from(1), false)
+    }
+
+    fn product(&self, term: impl Fn(&rug::Integer, u32) -> rug::Integer) -> rug::Integer {
+        self.factors
+            .iter()
+            .map(|(p, e)| term(p, *e))
+            .fold(rug::Integer::from(1), |product, term| product * term)
+    }
+}
+
+/// Returns the factorizations of the numbers in the range, in order.
+///
+/// The smallest prime factor of every number up to the end of the range is
+/// found first by the linear sieve of `SpfSieve`, after which each number
+/// factors in O(log n) divisions, so this is far quicker than calling
+/// `factorize` on each number. The sieve takes four bytes for each number up
+/// to the end, so the range should start low. With the `parallel` feature,
+/// the numbers are factored on one thread for each available core.
+///
+/// # Panics
+///
+/// Panics if the range contains zero or if the sieve up to the end of the
+/// range would not fit in memory.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::factorization::factorize_range;
+/// use mathematical::number_theory::Factor;
+///
+/// let factorizations = factorize_range(1..=10);
+/// assert_eq!(factorizations[5].factors(), &[(2, 1), (3, 1)]);
+/// assert_eq!(factorizations[7].factors(), &[(2, 3)]);
+///
+/// let totients: Vec<_> = factorize_range(90..=96)
+///     .iter()
+///     .map(|factorization| factorization.totient())
+///     .collect();
+/// assert_eq!(totients, vec![24, 72, 44, 60, 46, 72, 32]);
+/// assert_eq!(factorize_range(96..=96)[0], u64::factorize(&96).unwrap());
+/// ```
+pub fn factorize_range(range: core::ops::RangeInclusive<u64>) -> Vec<Factorization<u64>> {
+    if range.is_empty() {
+        return Vec::new();
+    }
+    let (start, end) = (*range.start(), *range.end());
+    assert!(start != 0, "the range must not contain zero");
+    let sieve = crate::number_theory::SpfSieve::new(end);
+    let factorize = |n| Factorization {
+        factors: sieve.factorize(n).collect(),
+    };
+
+    #[cfg(feature = "parallel")]
+    {
+        let threads = crate::number_theory::sieve::threads() as u64;
+        // At most one part for each thread.
+        let chunk = (end - start + 1).div_ceil(threads);
+        std::thread::scope(|scope| {
+            let factorize = &factorize;
+            let parts: Vec<_> = (start..=end)
+                .step_by(chunk as usize)
+                .map(|first| {
+                    let last = first.saturating_add(chunk - 1).min(end);
+                    scope.spawn(move || (first..=last).map(factorize).collect::<Vec<_>>())
+                })
+                .collect();
+            parts
+                .into_iter()
+                .flat_map(|part| part.join().unwrap())
+                .collect()
+        })
+    }
+    #[cfg(not(feature = "parallel"))]
+    (start..=end).map(factorize).collect()
+}
+
+/// Returns an `Option` containing either a nontrivial factor of n or `None`
+/// if n is prime or less than four, found by Brent's version of Pollard's
+/// rho method.
+///
+/// The sequence x<sub>i + 1</sub> = x<sub>i</sub><sup>2</sup> + c modulo n
+/// falls into a cycle modulo each prime factor p after about √p steps, at
+/// which point the gcd of n with the difference of two terms is a multiple
+/// of p. Brent's cycle detection compares each term against the last power
+/// of two index, and the differences are multiplied together so that only
+/// one gcd is needed for every 128 of them. Products modulo n below
+/// 2<sup>64</sup> are taken in a `u128`, and above that in Montgomery form.
+/// A factor takes about n<sup>1/4</sup> steps to find when n is a product
+/// of two primes of about the same size, so this is quick up to about
+/// 2<sup>100</sup>.
+///
+/// The factor that turns up is not necessarily prime or the smallest one.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::factorization::pollard_rho;
+///
+/// let n = 1_000_000_007u128 * 998_244_353;
+/// let d = pollard_rho(n).unwrap();
+/// assert!(d == 1_000_000_007 || d == 998_244_353);
+///
+/// assert_eq!(pollard_rho(1_000_000_007), None);
+/// assert_eq!(pollard_rho(1), None);
+/// ```
+pub fn pollard_rho(n: u128) -> Option<u128> {
+    use crate::number_theory::primes::{Montgomery, Small};
+
+    if n < 4 || crate::number_theory::primes::is_prime(n) {
+        return None;
+    }
+    if n.is_multiple_of(2) {
+        return Some(2);
+    }
+    // The sequence modulo a square's root cycles in step with it modulo the
+    // square, so squares are dealt with first.
+    let root = n.isqrt();
+    if root * root == n {
+        return Some(root);
+    }
+    Some(match <u64 as core::convert::TryFrom<u128>>::try_from(n) {
+        Ok(n) => brent(&Small { n }),
+        Err(_) => brent(&Montgomery::new(n)),
+    })
+}
+
+/// Returns a nontrivial factor of the modulus, which must be odd,
+/// composite, and not a square.
+fn brent<M: crate::number_theory::primes::Modular>(modular: &M) -> u128
+where
+    M::Elem: Into<u128>,
+{
+    let n = modular.modulus();
+    for c in 1.. {
+        let c = modular.elem(c);
+        let f = |x| modular.add(modular.mul(x, x), c);
+        let (mut x, mut y, mut saved) = (modular.elem(2), modular.elem(2), modular.elem(2));
+        let (mut length, mut divisor) = (1u64, 1);
+        while divisor == 1 {
+            x = y;
+            for _ in 0..length {
+                y = f(y);
+            }
+            let mut k = 0;
+            while k < length && divisor == 1 {
+                saved = y;
+                let mut batch = modular.elem(1);
+                for _ in 0..(length - k).min(128) {
+                    y = f(y);
+                    batch = modular.mul(batch, modular.sub(x, y));
+                }
+                divisor = u128::gcd(&batch.into(), &n);
+                k += 128;
+            }
+            length *= 2;
+        }
+        if divisor == n {
+            // The batch went past the factor, so back up and go one at a
+            // time.
+            loop {
+                saved = f(saved);
+                divisor = u128::gcd(&modular.sub(x, saved).into(), &n);
+                if divisor != 1 {
+                    break;
+                }
+            }
+        }
+        // Otherwise every prime factor cycled at once, so try another c.
+        if divisor != n {
+            return divisor;
+        }
+    }
+    unreachable!()
+}
+
+/// Returns an `Option` containing either a nontrivial factor of n or `None`
+/// if n is prime, less than four, or has no factor that Pollard's p − 1
+/// method finds with the given bounds.
+///
+/// For a prime factor p of n, a<sup>M</sup> ≡ 1 modulo p whenever
+/// p − 1 divides M, so gcd(a<sup>M</sup> − 1, n) picks out every such p.
+/// The first stage takes M to be the product of the largest power of each
+/// prime up to `b1` that is no larger than `b1`, which finds p when p − 1
+/// is `b1`-smooth. The second stage then tries M times each prime q up to
+/// `b2` in turn, stepping from one to the next by multiplying by a cached
+/// a<sup>M d</sup> for the gap d, which finds p when p − 1 has one more
+/// prime factor up to `b2`. This works however large the other factors of n
+/// are, where `pollard_rho` would take about √p steps.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::factorization::pollard_p_minus_one;
+///
+/// // p − 1 = 2 × 11 × 383 × 439 × 523 × 751 × 941.
+/// let (p, q) = (1_367_153_329_098_503u128, 1_634_716_391_129_166_659u128);
+/// assert_eq!(pollard_p_minus_one(p * q, 1000, 1000), Some(p));
+/// assert_eq!(pollard_p_minus_one(p * q, 900, 900), None);
+/// assert_eq!(pollard_p_minus_one(p * q, 900, 1000), Some(p));
+/// ```
+pub fn pollard_p_minus_one(n: u128, b1: u64, b2: u64) -> Option<u128> {
+    use crate::number_theory::primes::{Montgomery, Small};
+
+    if n < 4 || crate::number_theory::primes::is_prime(n) {
+        return None;
+    }
+    if n.is_multiple_of(2) {
+        return Some(2);
+    }
+    match <u64 as core::convert::TryFrom<u128>>::try_from(n) {
+        Ok(n) => p_minus_one(&Small { n }, b1, b2),
+        Err(_) => p_minus_one(&Montgomery::new(n), b1, b2),
+    }
+}
+
+/// Returns a nontrivial factor of the modulus, which must be odd, found by
+/// both stages of Pollard's p − 1 method, or `None` if neither finds one.
+fn p_minus_one<M: crate::number_theory::primes::Modular>(
+    modular: &M,
+    b1: u64,
+    b2: u64,
+) -> Option<u128>
+where
+    M::Elem: Into<u128>,
+{
+    use crate::number_theory::primes::power;
+
+    let n = modular.modulus();
+    let one = modular.elem(1);
+    let mut primes = crate::number_theory::sieve::primes().peekable();
+
+    // Stage one, with a gcd after each batch of primes and the batch gone
+    // back over one prime at a time if it found every factor at once.
+    let mut a = modular.elem(2);
+    let prime_power = |q: u64| {
+        let mut power = q;
+        while power <= b1 / q {
+            power *= q;
+        }
+        power as u128
+    };
+    loop {
+        let batch: Vec<_> = primes.by_ref().take_while(|&q| q <= b1).take(64).collect();
+        if batch.is_empty() {
+            break;
+        }
+        let saved = a;
+        for &q in &batch {
+            a = power(modular, a, prime_power(q));
+        }
+        match u128::gcd(&modular.sub(a, one).into(), &n) {
+            1 => {}
+            divisor if divisor != n => return Some(divisor),
+            _ => {
+                a = saved;
+                for &q in &batch {
+                    a = power(modular, a, prime_power(q));
+                    match u128::gcd(&modular.sub(a, one).into(), &n) {
+                        1 => {}
+                        divisor if divisor != n => return Some(divisor),
+                        _ => return None,
+                    }
+                }
+            }
+        }
+        if batch.len() < 64 {
+            break;
+        }
+    }
+
+    // Stage two, with a^q for successive primes q found from the last by the
+    // power for the gap between them, where the gaps are even and small.
+    let mut q = match primes.next() {
+        Some(q) if q <= b2 => q,
+        _ => return None,
+    };
+    let mut steps: Vec<Option<M::Elem>> = Vec::new();
+    let mut x = power(modular, a, q as u128);
+    let mut product = modular.sub(x, one);
+    let mut count = 0;
+    loop {
+        let next = primes.next().filter(|&next| next <= b2);
+        count += 1;
+        if next.is_none() || count % 128 == 0 {
+            match u128::gcd(&product.into(), &n) {
+                1 => {}
+                divisor if divisor != n => return Some(divisor),
+                _ => return None,
+            }
+        }
+        let next = next?;
+        let gap = ((next - q) / 2) as usize;
+        if steps.len() <= gap {
+            steps.resize(gap + 1, None);
+        }
+        let step = *steps[gap].get_or_insert_with(|| power(modular, a, 2 * gap as u128));
+        x = modular.mul(x, step);
+        product = modular.mul(product, modular.sub(x, one));
+        q = next;
+    }
+}
+
+/// Returns an `Option` containing either a nontrivial factor of n or `None`
+/// if n is prime, less than four, or has no factor that Shanks' square
+/// forms factorization finds.
+///
+/// SQUFOF goes through the continued fraction of √(kn) for a small
+/// multiplier k until it reaches a square denominator Q = r<sup>2</sup>,
+/// and then goes through the continued fraction of the quadratic form with
+/// r in it until two terms repeat, at which point gcd(n, Q) is usually a
+/// factor. Squares whose roots turned up among the small Q are skipped, as
+/// they only lead back to a trivial factor.
+///
+/// This takes about n<sup>1/4</sup> steps, each a division of numbers near
+/// √(kn) rather than a modular multiplication, which fit in a `u64` for n
+/// below 2<sup>62</sup>. The multipliers k are the 16 products of 3, 5, 7,
+/// and 11, which take turns a few hundred steps at a time, with the steps
+/// of each turn interleaved so that their divisions overlap. Numbers from
+/// 2<sup>62</sup> on only get the multipliers that keep √(kn) below
+/// 2<sup>32</sup>, so their factors turn up less often.
+///
+/// The factor that turns up is not necessarily prime or the smallest one.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::factorization::squfof;
+///
+/// let d = squfof(1_000_000_007 * 998_244_353).unwrap();
+/// assert!(d == 1_000_000_007 || d == 998_244_353);
+/// assert_eq!(squfof(1_000_000_007), None);
+/// ```
+pub fn squfof(n: u64) -> Option<u64> {
+    if n < 4 || crate::number_theory::primes::is_prime(n as u128) {
+        return None;
+    }
+    if n.is_multiple_of(2) {
+        return Some(2);
+    }
+    let root = n.isqrt();
+    if root * root == n {
+        return Some(root);
+    }
+    const MULTIPLIERS: [u64; 16] = [
+        1, 3, 5, 7, 11, 15, 21, 33, 35, 55, 77, 105, 165, 231, 385, 1155,
+    ];
+    let mut forms = Vec::new();
+    for &k in &MULTIPLIERS {
+        let kn = k as u128 * n as u128;
+        if kn >> 64 != 0 && n >= 1 << 62 {
+            break;
+        }
+        let root = kn.isqrt();
+        if root * root == kn {
+            let d = u128::gcd(&(n as u128), &root) as u64;
+            if d != 1 && d != n {
+                return Some(d);
+            }
+        } else {
+            forms.push(SquareForms::new(n, k, kn));
+        }
+    }
+
+    // Whether a multiplier finds a factor, and how soon, varies a lot, so
+    // they take turns rather than going one after another.
+    while !forms.is_empty() {
+        for _ in 0..256 {
+            for form in &mut forms {
+                if let Some(d) = form.step() {
+                    return Some(d);
+                }
+            }
+        }
+        forms.retain(|form| form.steps < form.limit);
+    }
+    None
+}
+
+/// The forward cycle of SQUFOF for one multiplier k, partway through the
+/// continued fraction of √(kn), where √(kn) must fit in a `u64` with a few
+/// bits to spare.
+struct SquareForms {
+    n: u64,
+    kn: u128,
+    root: u64,
+    p: u64,
+    q_previous: u64,
+    q: u64,
+    steps: u64,
+    limit: u64,
+    /// The Q below `bound` so far, divided by their gcd with 2k, which are
+    /// the roots of the squares that only lead back to a trivial factor.
+    improper: Vec<u64>,
+    bound: u64,
+    twice_k: u64,
+}
+
+impl SquareForms {
+    fn new(n: u64, k: u64, kn: u128) -> Self {
+        let root = kn.isqrt() as u64;
+        Self {
+            n,
+            kn,
+            root,
+            p: root,
+            q_previous: 1,
+            q: (kn - root as u128 * root as u128) as u64,
+            steps: 0,
+            // A square turns up after about (kn)^(1/4) steps if it does at
+            // all, so the search gives up at a few times that.
+            limit: 6 * (2 * root).isqrt(),
+            improper: Vec::new(),
+            bound: (2 * root).isqrt(),
+            twice_k: 2 * k,
+        }
+    }
+
+    /// Returns an `Option` containing either a nontrivial factor of n found
+    /// by taking one more step, or `None` if none is.
+    fn step(&mut self) -> Option<u64> {
+        if self.steps < self.limit {
+            self.steps += 1;
+            let b = (self.root + self.p) / self.q;
+            let p = b * self.q - self.p;
+            let q = if self.p >= p {
+                self.q_previous + b * (self.p - p)
+            } else {
+                self.q_previous - b * (p - self.p)
+            };
+            self.q_previous = core::mem::replace(&mut self.q, q);
+            self.p = p;
+            if q < 2 * self.bound {
+                let reduced = q / u64::gcd(&q, &self.twice_k);
+                if reduced < self.bound {
+                    self.improper.push(reduced);
+                }
+            }
+            if self.steps % 2 == 1 && SQUARES_MOD_64 >> (q % 64) & 1 == 1 {
+                let r = q.isqrt();
+                if r * r == q {
+                    if r == 1 {
+                        // The whole period has gone by.
+                        self.steps = self.limit;
+                    } else if !self.improper.contains(&r) {
+                        let d = self.reverse(r);
+                        if d.is_some() {
+                            return d;
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns an `Option` containing either the factor of n from the
+    /// square Q = r<sup>2</sup>, found by going through the reverse cycle
+    /// from the form with r until P repeats, or `None` if it is trivial.
+    fn reverse(&self, r: u64) -> Option<u64> {
+        let (root, kn) = (self.root, self.kn);
+        let mut p = (root - self.p) / r * r + self.p;
+        let (mut q_previous, mut q) = (r, ((kn - p as u128 * p as u128) / r as u128) as u64);
+        loop {
+            let b = (root + p) / q;
+            let p_next = b * q - p;
+            if p_next == p {
+                break;
+            }
+            let q_next = if p >= p_next {
+                q_previous + b * (p - p_next)
+            } else {
+                q_previous - b * (p_next - p)
+            };
+            q_previous = q;
+            q = q_next;
+            p = p_next;
+        }
+        Some(u64::gcd(&self.n, &q)).filter(|&d| d != 1 && d != self.n)
+    }
+}
+
+/// Bit r is set when r is a square modulo 64. Only 12 of the 64 residues
+/// are, which rules out most numbers without a square root.
+const SQUARES_MOD_64: u64 = 0x0202_0212_0203_0213;
+
+/// Returns an `Option` containing either a nontrivial factor of n or `None`
+/// if n is prime or less than four or no factor turns up in the given total
+/// number of steps, found by Fermat's difference of squares method with
+/// multipliers.
+///
+/// Fermat's method counts x up from √n until x<sup>2</sup> − n is a square
+/// y<sup>2</sup>, so that n = (x − y)(x + y), which takes one step when the
+/// two factors are as close as they can be and many more as they move
+/// apart. Running it on 4kn for k up to 16 instead also finds a factor p
+/// quickly when its cofactor q is near up / v for some u v = k, since then
+/// 4kn = (2up)(2vq) with the two near each other. The multipliers
+/// take their steps in turn, and any that would not fit in a `u128` are
+/// left out.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::factorization::fermat;
+///
+/// // Two primes near 10^18 that differ by 14.
+/// let (p, q) = (999_999_999_999_999_989u128, 1_000_000_000_000_000_003u128);
+/// assert!(matches!(fermat(p * q, 1), Some(d) if d == p || d == q));
+///
+/// // q is about 3p, which takes the multiplier three.
+/// let (p, q) = (1_000_000_007u128, 3_000_000_019u128);
+/// assert!(matches!(fermat(p * q, 100), Some(d) if d == p || d == q));
+///
+/// assert_eq!(fermat(1_000_000_007, 1000), None);
+/// ```
+pub fn fermat(n: u128, steps: u64) -> Option<u128> {
+    if n < 4 || crate::number_theory::primes::is_prime(n) {
+        return None;
+    }
+    if n.is_multiple_of(2) {
+        return Some(2);
+    }
+    // For each multiplier, N along with x and x² − N, which is small enough
+    // to work out with wrapping arithmetic.
+    let mut states: Vec<(u128, u128, u128)> = (1..=16u128)
+        .filter_map(|k| match (4 * k).checked_mul(n) {
+            Some(product) => Some(product),
+            None if k % 2 == 1 => k.checked_mul(n),
+            None => None,
+        })
+        .map(|product| {
+            let root = product.isqrt();
+            let x = if root * root == product {
+                root
+            } else {
+                root + 1
+            };
+            (product, x, x.wrapping_mul(x).wrapping_sub(product))
+        })
+        .collect();
+    let mut steps_left = steps;
+    while steps_left != 0 {
+        for (_, x, r) in &mut states {
+            if steps_left == 0 {
+                break;
+            }
+            steps_left -= 1;
+            if SQUARES_MOD_64 >> (*r % 64) & 1 == 1 {
+                let y = r.isqrt();
+                if y * y == *r {
+                    let d = u128::gcd(&(*x - y), &n);
+                    if d != 1 && d != n {
+                        return Some(d);
+                    }
+                }
+            }
+            *r = r.wrapping_add(2 * *x + 1);
+            *x += 1;
+        }
+    }
+    None
+}
+
+/// Returns the prime factors of n, which must be positive, in increasing
+/// order along with their exponents.
+///
+/// Trial division by 2, 3, and the numbers below 1000 that are 1 or 5
+/// modulo 6 takes out the small factors, and then the configured strategies
+/// split what is left until every piece is prime.
+pub(crate) fn factorize(mut n: u128, config: &FactorizationConfig) -> Vec<(u128, u32)> {
+    let mut factors = Vec::new();
+    let mut divide_out = |n: &mut u128, p: u128| {
+        let mut e = 0;
+        while n.is_multiple_of(p) {
+            *n /= p;
+            e += 1;
+        }
+        if e > 0 {
+            factors.push((p, e));
+        }
+    };
+    divide_out(&mut n, 2);
+    divide_out(&mut n, 3);
+    let mut p = 5;
+    while p < 1000 && p <= n / p {
+        divide_out(&mut n, p);
+        divide_out(&mut n, p + 2);
+        p += 6;
+    }
+
+    // The rest has no factors below 1000.
+    let mut large = Vec::new();
+    let mut pieces = vec![n];
+    while let Some(piece) = pieces.pop() {
+        if piece == 1 {
+            continue;
+        }
+        if crate::number_theory::primes::is_prime(piece) {
+            large.push(piece);
+        } else {
+            let d = config.split(piece);
+            pieces.extend([d, piece / d].iter().copied());
+        }
+    }
+    merge(&mut factors, large);
+    factors
+}
+
+/// Returns the prime factors of n, which must be positive, in increasing
+/// order along with their exponents.
+///
+/// Trial division by the primes below 1000 takes out the small factors, and
+/// then the pieces are split until each is prime or small enough for
+/// `factorize`.
+#[cfg(any(feature = "rug", doc, test))]
+fn factorize_big(mut n: rug::Integer, config: &FactorizationConfig) -> Vec<(rug::Integer, u32)> {
+    use crate::number_theory::Prime;
+    use rug::Integer;
+
+    let small = |n: u128| {
+        factorize(n, config)
+            .into_iter()
+            .map(|(p, e)| (Integer::from(p), e))
+    };
+    if let Some(n) = n.to_u128() {
+        return small(n).collect();
+    }
+    let mut factors = Vec::new();
+    for p in crate::number_theory::sieve::primes().take_while(|&p| p < 1000) {
+        let mut e = 0;
+        while n.is_divisible_u(p as u32) {
+            n.div_exact_u_mut(p as u32);
+            e += 1;
+        }
+        if e > 0 {
+            factors.push((Integer::from(p), e));
+        }
+    }
+
+    // The rest has no factors below 1000.
+    let mut large = Vec::new();
+    let mut pieces = vec![n];
+    while let Some(piece) = pieces.pop() {
+        if let Some(piece) = piece.to_u128() {
+            for (p, e) in small(piece) {
+                large.extend(core::iter::repeat_n(p, e as usize));
+            }
+        } else if <Integer as Prime>::is_prime(&piece) {
+            large.push(piece);
+        } else {
+            let d = config.split_big(&piece);
+            let rest = Integer::from(piece.div_exact_ref(&d));
+            pieces.extend([d, rest]);
+        }
+    }
+    merge(&mut factors, large);
+    factors
+}
+
+/// Sorts the primes and appends them to the factors as prime powers, where
+/// the primes must all be larger than those already in the factors.
+fn merge<T: Ord>(factors: &mut Vec<(T, u32)>, mut large: Vec<T>) {
+    large.sort_unstable();
+    for p in large {
+        match factors.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_against_tables {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let sigma = crate::number_theory::divisors::sigma_table(3000);
+                let phi = crate::number_theory::totient::totient_table(3000);
+                for n in (1..3000u32).take_while(|&n| n as u128 <= <$type>::MAX as u128) {
+                    let factorization = <$type>::factorize(&(n as $type)).unwrap();
+                    let factors = factorization.factors();
+                    ::core::assert!(factors.windows(2).all(|pair| pair[0].0 < pair[1].0));
+                    ::core::assert!(factors
+                        .iter()
+                        .all(|&(p, e)| e > 0 && crate::number_theory::primes::is_prime(p as u128)));
+                    ::core::assert_eq!(factorization.value(), n as $type);
+
+                    let divisors = (1..=n).filter(|d| n % d == 0).count();
+                    ::core::assert_eq!(factorization.num_divisors(), divisors as u64);
+                    let expected = if sigma[n as usize] as u128 > <$type>::MAX as u128 {
+                        ::core::option::Option::None
+                    } else {
+                        ::core::option::Option::Some(sigma[n as usize] as $type)
+                    };
+                    ::core::assert_eq!(factorization.sum_divisors(), expected);
+                    ::core::assert_eq!(factorization.totient(), phi[n as usize] as $type);
+                    let radical: u32 = (2..=n)
+                        .filter(|&p| {
+                            n % p == 0 && crate::number_theory::primes::is_prime(p as u128)
+                        })
+                        .product();
+                    ::core::assert_eq!(factorization.radical(), radical as $type);
+                }
+                ::core::assert_eq!(<$type>::factorize(&0), ::core::option::Option::None);
+            }
+        };
+    }
+
+    test_against_tables!(::core::primitive::u8, u8_factorize);
+    test_against_tables!(::core::primitive::u16, u16_factorize);
+    test_against_tables!(::core::primitive::u32, u32_factorize);
+    test_against_tables!(::core::primitive::u64, u64_factorize);
+    test_against_tables!(::core::primitive::u128, u128_factorize);
+    test_against_tables!(::core::primitive::usize, usize_factorize);
+
+    #[test]
+    fn large_values() {
+        let factorization = u64::factorize(&u64::MAX).unwrap();
+        assert_eq!(
+            factorization.factors(),
+            &[
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65_537, 1),
+                (6_700_417, 1)
+            ]
+        );
+        assert_eq!(factorization.totient(), 9_208_981_628_670_443_520);
+        assert_eq!(factorization.sum_divisors(), None);
+
+        let p = 18_446_744_073_709_551_557u128;
+        assert_eq!(
+            u128::factorize(&(p * 1024)).unwrap().factors(),
+            &[(2, 10), (p, 1)]
+        );
+        assert_eq!(u128::factorize(&(1 << 127)).unwrap().factors(), &[(2, 127)]);
+        assert_eq!(u8::factorize(&255).unwrap().radical(), 255);
+    }
+
+    #[test]
+    fn rho_splits_composites() {
+        for &(p, q) in &[
+            (1009u128, 1013u128),
+            (1_000_003, 1_000_033),
+            (4_294_967_291, 4_294_967_279),
+            (1_000_000_007, 1_000_000_007),
+            (18_446_744_073_709_551_557, 1_000_000_007),
+            (1_099_511_627_689, 1_099_511_627_791),
+        ] {
+            let d = pollard_rho(p * q).unwrap();
+            assert!(d == p || d == q, "{} × {} gave {}", p, q, d);
+        }
+        for n in 0..10_000u128 {
+            match pollard_rho(n) {
+                Some(d) => assert!(d > 1 && d < n && n % d == 0, "n = {}", n),
+                None => assert!(n < 4 || crate::number_theory::primes::is_prime(n)),
+            }
+        }
+    }
+
+    #[test]
+    fn fermat_splits_composites() {
+        for n in 4..5000u128 {
+            match fermat(n, 1 << 20) {
+                Some(d) => assert!(d > 1 && d < n && n % d == 0, "n = {}", n),
+                None => assert!(crate::number_theory::primes::is_prime(n), "n = {}", n),
+            }
+        }
+        // Factors near each other or near a ratio of small numbers, up to
+        // the top of the type where only the multiplier one fits.
+        let next_prime = |n: u128| crate::number_theory::primes::next_prime(n).unwrap();
+        for &(bits, u, v) in &[(30, 1, 1), (40, 3, 1), (50, 5, 2), (56, 16, 1), (63, 1, 1)] {
+            let p = next_prime(1 << bits);
+            let q = next_prime(p * u / v + 1000);
+            let d = fermat(p * q, 1000).unwrap();
+            assert!(d == p || d == q, "{} × {}", p, q);
+        }
+        let (p, q) = (
+            18_446_744_073_709_551_533u128,
+            18_446_744_073_709_551_557u128,
+        );
+        assert!(matches!(fermat(p * q, 1), Some(d) if d == p || d == q));
+        // Factors far apart are out of reach.
+        assert_eq!(fermat(1_000_003 * next_prime(1 << 60), 10_000), None);
+    }
+
+    #[test]
+    fn squfof_splits_composites() {
+        for n in 4..20_000u64 {
+            match squfof(n) {
+                Some(d) => assert!(d > 1 && d < n && n % d == 0, "n = {}", n),
+                None => assert!(
+                    crate::number_theory::primes::is_prime(n as u128),
+                    "n = {}",
+                    n
+                ),
+            }
+        }
+        // Products of two primes up to 2^62, and two near 2^63 that only get
+        // the first multiplier or two.
+        for bits in 20..=30 {
+            let p = crate::number_theory::primes::prev_prime(1 << bits).unwrap() as u64;
+            for q in crate::number_theory::primes::primes_from((3 << bits) + 12_345).take(20) {
+                let d = squfof(p * q).unwrap();
+                assert!(d == p || d == q, "{} × {}", p, q);
+            }
+        }
+        let (p, q) = (2_147_483_647u64, 4_294_967_291u64);
+        assert!(matches!(squfof(p * q), Some(d) if d == p || d == q));
+        assert_eq!(squfof(65_537 * 65_537), Some(65_537));
+        assert_eq!(squfof(1 << 61), Some(2));
+    }
+
+    #[test]
+    fn p_minus_one_stages() {
+        let (p, q) = (1_367_153_329_098_503u128, 1_634_716_391_129_166_659u128);
+        // The largest prime factor of p − 1 is 941.
+        assert_eq!(pollard_p_minus_one(p * q, 941, 0), Some(p));
+        assert_eq!(pollard_p_minus_one(p * q, 940, 0), None);
+        assert_eq!(pollard_p_minus_one(p * q, 10, 941), None);
+        assert_eq!(pollard_p_minus_one(p * q, 751, 941), Some(p));
+        // Below 2^64 too, where p − 1 = 2 × 29 × 101 × 179.
+        let (p, q) = (1_048_583u128, 4_294_967_291u128);
+        assert_eq!(pollard_p_minus_one(p * q, 179, 0), Some(p));
+        assert_eq!(pollard_p_minus_one(p * q, 101, 179), Some(p));
+        assert_eq!(pollard_p_minus_one(p * q, 100, 179), None);
+        assert_eq!(pollard_p_minus_one(q, 1000, 1000), None);
+        assert_eq!(pollard_p_minus_one(1 << 40, 10, 10), Some(2));
+        // Found one prime at a time after the batch finds every factor, but
+        // not when the same prime finds them all.
+        assert_eq!(pollard_p_minus_one(3 * 5 * 7, 100, 100), Some(15));
+        assert_eq!(pollard_p_minus_one(3 * 5 * 17, 100, 100), None);
+
+        for n in 4..3000u128 {
+            if let Some(d) = pollard_p_minus_one(n, 20, 200) {
+                assert!(d > 1 && d < n && n % d == 0, "n = {}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn divisors_match_trial_division() {
+        use crate::number_theory::UnitaryDivisors;
+
+        for n in 1..3000u32 {
+            let factorization = u32::factorize(&n).unwrap();
+            let expected: Vec<_> = (1..=n).filter(|d| n % d == 0).collect();
+            assert_eq!(factorization.sorted_divisors(), expected);
+            assert_eq!(
+                factorization.divisors().count() as u64,
+                factorization.num_divisors()
+            );
+            assert_eq!(
+                factorization.divisors().sum::<u32>(),
+                factorization.sum_divisors().unwrap()
+            );
+            let mut proper: Vec<_> = factorization.proper_divisors().collect();
+            proper.sort_unstable();
+            assert_eq!(proper, &expected[..expected.len() - 1]);
+            let mut unitary: Vec<_> = factorization.unitary_divisors().collect();
+            unitary.sort_unstable();
+            assert_eq!(unitary, u32::unitary_divisors(&n));
+        }
+
+        // The largest divisor of a number near the top of the type is
+        // reached without overflow.
+        let n = u64::MAX - 1;
+        let divisors = u64::factorize(&n).unwrap().sorted_divisors();
+        assert_eq!(divisors.len(), 192);
+        assert_eq!(divisors.last(), Some(&n));
+        assert_eq!(u8::factorize(&1).unwrap().proper_divisors().next(), None);
+    }
+
+    #[test]
+    fn range_matches_factorize() {
+        for (start, end) in [(1, 5000), (1_000_000, 1_002_000), (7, 7)] {
+            let factorizations = factorize_range(start..=end);
+            assert_eq!(factorizations.len() as u64, end - start + 1);
+            for (n, factorization) in (start..=end).zip(&factorizations) {
+                assert_eq!(factorization, &u64::factorize(&n).unwrap());
+            }
+        }
+        let mut exhausted = 9..=9;
+        exhausted.next();
+        assert!(factorize_range(exhausted).is_empty());
+        assert!(factorize_range(1..=1)[0].factors().is_empty());
+    }
+
+    #[test]
+    fn integer_factorize() {
+        use rug::Integer;
+
+        let sigma = crate::number_theory::divisors::sigma_table(3000);
+        let phi = crate::number_theory::totient::totient_table(3000);
+        for n in 1..3000u64 {
+            let factorization = Integer::factorize(&n.into()).unwrap();
+            let expected = u64::factorize(&n).unwrap();
+            let factors: Vec<_> = factorization
+                .factors()
+                .iter()
+                .map(|(p, e)| (p.to_u64().unwrap(), *e))
+                .collect();
+            assert_eq!(factors, expected.factors());
+            assert_eq!(factorization.value(), n);
+            assert_eq!(factorization.num_divisors(), expected.num_divisors());
+            assert_eq!(factorization.sum_divisors(), sigma[n as usize]);
+            assert_eq!(factorization.totient(), phi[n as usize]);
+            assert_eq!(factorization.radical(), expected.radical());
+            assert_eq!(factorization.sorted_divisors(), expected.sorted_divisors());
+            let unitary: Vec<_> = factorization.unitary_divisors().collect();
+            assert_eq!(unitary, expected.unitary_divisors().collect::<Vec<_>>());
+            assert_eq!(
+                factorization.proper_divisors().count() as u64,
+                expected.num_divisors() - 1
+            );
+        }
+        assert_eq!(Integer::factorize(&Integer::new()), None);
+        assert_eq!(Integer::factorize(&Integer::from(-12)), None);
+
+        // The default configuration leaves 2^128 + 1 to the elliptic curve
+        // method too.
+        let n =
+            (Integer::from(Integer::u_pow_u(2, 128)) + 1u8) * 12u8 * 1_000_003u64 * 1_000_003u64;
+        let factorization = Integer::factorize(&n).unwrap();
+        let factors: Vec<_> = factorization
+            .factors()
+            .iter()
+            .map(|(p, e)| (p.to_u128().unwrap(), *e))
+            .collect();
+        assert_eq!(
+            factors,
+            vec![
+                (2, 2),
+                (3, 1),
+                (1_000_003, 2),
+                (59_649_589_127_497_217, 1),
+                (5_704_689_200_685_129_054_721, 1)
+            ]
+        );
+        assert_eq!(factorization.value(), n);
+        assert_eq!(factorization.num_divisors(), 72);
+        let sum = "9527972969254056422206635370743600694546618629876144";
+        assert_eq!(
+            factorization.sum_divisors(),
+            sum.parse::<Integer>().unwrap()
+        );
+        let totient = "1361136273339259026609760724131396806484970679828480";
+        assert_eq!(factorization.totient(), totient.parse::<Integer>().unwrap());
+        assert_eq!(factorization.radical(), Integer::from(&n / 2_000_006u64));
+
+        // A prime power, and a 20-digit factor of a 60-digit number.
+        let p = Integer::from(u64::MAX - 58);
+        let cube = Integer::from(&p * &p) * &p;
+        assert_eq!(Integer::factorize(&cube).unwrap().factors(), &[(p, 3)]);
+        let p = Integer::from(10_560_000_000_000_000_097u128);
+        let q: Integer = "1000000000000000000000000000000000000003".parse().unwrap();
+        let config = FactorizationConfig::new(vec![
+            Strategy::PollardRho,
+            Strategy::Ecm {
+                b1: 11_000,
+                b2: 1_100_000,
+                curves: 2,
+            },
+        ]);
+        let factorization = Integer::factorize_with(&Integer::from(&p * &q), &config).unwrap();
+        assert_eq!(factorization.factors(), &[(p, 1), (q, 1)]);
+    }
+
+    #[test]
+    fn factors_with_strategies() {
+        let config = FactorizationConfig::new(vec![
+            Strategy::PollardPMinusOne { b1: 100, b2: 1000 },
+            Strategy::PollardRho,
+        ]);
+        for n in (1..1_000_000u64).step_by(997) {
+            let factorization = u64::factorize_with(&n, &config).unwrap();
+            assert_eq!(factorization, u64::factorize(&n).unwrap());
+        }
+        let fermat = FactorizationConfig::new(vec![Strategy::Fermat { steps: 100 }]);
+        let (p, q) = (999_999_999_999_999_989u64, 1_000_000_000_000_000_003u64);
+        let close = u128::factorize_with(&(p as u128 * q as u128), &fermat).unwrap();
+        assert_eq!(close.factors(), &[(p as u128, 1), (q as u128, 1)]);
+        let squfof = FactorizationConfig::new(vec![Strategy::Squfof]);
+        for n in (1u64 << 47..1 << 63).step_by(1 << 56).map(|n| n + 12_345) {
+            let factorization = u64::factorize_with(&n, &squfof).unwrap();
+            assert_eq!(factorization.value(), n);
+            assert_eq!(factorization, u64::factorize(&n).unwrap());
+        }
+        let (p, q) = (1_099_511_627_689u128, 1_099_511_627_791u128);
+        let ecm = FactorizationConfig::new(vec![Strategy::Ecm {
+            b1: 2000,
+            b2: 200_000,
+            curves: 50,
+        }]);
+        assert_eq!(
+            u128::factorize_with(&(p * q), &ecm).unwrap().factors(),
+            &[(p, 1), (q, 1)]
+        );
+        let empty = FactorizationConfig::new(vec![]);
+        assert_eq!(
+            u128::factorize_with(&(p * q), &empty).unwrap().factors(),
+            &[(p, 1), (q, 1)]
+        );
+        let sieve = FactorizationConfig::new(vec![Strategy::QuadraticSieve]);
+        let factorization = u128::factorize_with(&(p * p * q), &sieve).unwrap();
+        assert_eq!(factorization.factors(), &[(p, 2), (q, 1)]);
+        // The two largest primes below 2^64, which only the quadratic sieve
+        // splits quickly.
+        let (p, q) = (
+            18_446_744_073_709_551_533u128,
+            18_446_744_073_709_551_557u128,
+        );
+        assert_eq!(
+            u128::factorize(&(p * q)).unwrap().factors(),
+            &[(p, 1), (q, 1)]
+        );
+        assert_eq!(u128::factorize_with(&0, &config), None);
+    }
+
+    #[test]
+    fn factors_with_rho() {
+        // Two primes near 2^40, and the square of one near 2^32 times a prime
+        // near 2^30.
+        let (p, q) = (1_099_511_627_689u128, 1_099_511_627_791u128);
+        assert_eq!(
+            u128::factorize(&(p * q)).unwrap().factors(),
+            &[(p, 1), (q, 1)]
+        );
+        let (p, q) = (4_294_967_291u128, 1_073_741_789u128);
+        assert_eq!(
+            u128::factorize(&(p * p * q * 6)).unwrap().factors(),
+            &[(2, 1), (3, 1), (q, 1), (p, 2)]
+        );
+        let n = 18_446_744_073_709_551_557u128 * 1009 * 1013;
+        assert_eq!(
+            u128::factorize(&n).unwrap().factors(),
+            &[(1009, 1), (1013, 1), (18_446_744_073_709_551_557, 1)]
+        );
+        // 2^128 − 1 = 3 × 5 × 17 × 257 × 641 × 65537 × 274177 × 6700417 ×
+        // 67280421310721.
+        assert_eq!(
+            u128::factorize(&u128::MAX).unwrap().factors(),
+            &[
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65_537, 1),
+                (274_177, 1),
+                (6_700_417, 1),
+                (67_280_421_310_721, 1)
+            ]
+        );
+    }
+}
@@ -0,0 +1,369 @@
+pub trait Gcd: Sized {
+    /// Returns the greatest common divisor of a and b, which is never
+    /// negative and is zero only when both are.
+    ///
+    /// This is Stein's binary algorithm, which takes out the common power of
+    /// two and then subtracts the smaller odd number from the larger, so it
+    /// needs no division. As `rug::Integer` has an inherent `gcd` of its own,
+    /// this one is called on it as `<rug::Integer as Gcd>::gcd`, which uses
+    /// GMP's.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the greatest common divisor does not fit in the type, which
+    /// only happens for signed types when each of a and b is zero or the
+    /// minimum value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Gcd;
+    ///
+    /// assert_eq!(u32::gcd(&48, &180), 12);
+    /// assert_eq!(i32::gcd(&-48, &180), 12);
+    /// assert_eq!(u8::gcd(&0, &7), 7);
+    /// assert_eq!(u8::gcd(&0, &0), 0);
+    /// assert_eq!(i8::gcd(&i8::MIN, &6), 2);
+    /// ```
+    fn gcd(a: &Self, b: &Self) -> Self;
+
+    /// Returns the least common multiple of a and b, which is never negative
+    /// and is zero when either is.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the least common multiple does not fit in the type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Gcd;
+    ///
+    /// assert_eq!(u32::lcm(&4, &6), 12);
+    /// assert_eq!(i32::lcm(&-4, &6), 12);
+    /// assert_eq!(u32::lcm(&0, &6), 0);
+    /// ```
+    fn lcm(a: &Self, b: &Self) -> Self;
+
+    /// Returns an `Option` containing either the least common multiple of a
+    /// and b or `None` if it would cause overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Gcd;
+    ///
+    /// assert_eq!(u8::checked_lcm(&12, &20), Some(60));
+    /// assert_eq!(u8::checked_lcm(&16, &17), None);
+    /// assert_eq!(i8::checked_lcm(&-8, &16), Some(16));
+    /// assert_eq!(i8::checked_lcm(&i8::MIN, &1), None);
+    /// ```
+    fn checked_lcm(a: &Self, b: &Self) -> Option<Self>;
+}
+
+pub trait ExtendedGcd: Sized {
+    /// Returns an `Option` containing either the greatest common divisor g
+    /// of a and b along with Bézout coefficients x and y for which
+    /// a x + b y = g, as `(g, x, y)`, or `None` if any of them would not fit
+    /// in the type.
+    ///
+    /// This is the extended Euclidean algorithm on |a| and |b|, whose
+    /// coefficients alternate in sign, so they are kept as magnitudes in
+    /// the unsigned type of the same size and cannot overflow on the way.
+    /// The coefficients it finds are the smallest: when neither a nor b
+    /// divides the other, |x| ≤ |b| / 2g and |y| ≤ |a| / 2g. g is never
+    /// negative, and the coefficients are (0, 0) when a and b are both
+    /// zero. Only the minimum value of the type ever gives `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::ExtendedGcd;
+    ///
+    /// assert_eq!(i32::extended_gcd(&240, &46), Some((2, -9, 47)));
+    /// assert_eq!(i32::extended_gcd(&-240, &46), Some((2, 9, 47)));
+    /// assert_eq!(i32::extended_gcd(&0, &-5), Some((5, 0, -1)));
+    /// assert_eq!(i8::extended_gcd(&i8::MIN, &0), None);
+    /// assert_eq!(i8::extended_gcd(&i8::MIN, &i8::MAX), Some((1, -1, -1)));
+    /// ```
+    fn extended_gcd(a: &Self, b: &Self) -> Option<(Self, Self, Self)>;
+}
+
+macro_rules! gcd_trait_for_unsigned {
+    ($type:ty) => {
+        impl $crate::number_theory::gcd::Gcd for $type {
+            fn gcd(a: &Self, b: &Self) -> Self {
+                let (mut a, mut b) = (*a, *b);
+                if a == 0 || b == 0 {
+                    return a | b;
+                }
+                let shift = (a | b).trailing_zeros();
+                a >>= a.trailing_zeros();
+                loop {
+                    // a and b are both odd here, so b − a is even.
+                    b >>= b.trailing_zeros();
+                    if a > b {
+                        ::core::mem::swap(&mut a, &mut b);
+                    }
+                    b -= a;
+                    if b == 0 {
+                        return a << shift;
+                    }
+                }
+            }
+
+            fn lcm(a: &Self, b: &Self) -> Self {
+                Self::checked_lcm(a, b).expect("least common multiple overflows")
+            }
+
+            fn checked_lcm(a: &Self, b: &Self) -> ::core::option::Option<Self> {
+                if *a == 0 || *b == 0 {
+                    return ::core::option::Option::Some(0);
+                }
+                (*a / Self::gcd(a, b)).checked_mul(*b)
+            }
+        }
+    };
+}
+
+macro_rules! gcd_trait_for_signed {
+    ($type:ty, $unsigned:ty) => {
+        impl $crate::number_theory::gcd::Gcd for $type {
+            fn gcd(a: &Self, b: &Self) -> Self {
+                let gcd = <$unsigned as $crate::number_theory::gcd::Gcd>::gcd(
+                    &a.unsigned_abs(),
+                    &b.unsigned_abs(),
+                );
+                <$type as ::core::convert::TryFrom<$unsigned>>::try_from(gcd)
+                    .expect("greatest common divisor overflows")
+            }
+
+            fn lcm(a: &Self, b: &Self) -> Self {
+                Self::checked_lcm(a, b).expect("least common multiple overflows")
+            }
+
+            fn checked_lcm(a: &Self, b: &Self) -> ::core::option::Option<Self> {
+                let lcm = <$unsigned as $crate::number_theory::gcd::Gcd>::checked_lcm(
+                    &a.unsigned_abs(),
+                    &b.unsigned_abs(),
+                )?;
+                <$type as ::core::convert::TryFrom<$unsigned>>::try_from(lcm).ok()
+            }
+        }
+    };
+}
+
+macro_rules! extended_gcd_trait_for_signed {
+    ($type:ty, $unsigned:ty) => {
+        impl $crate::number_theory::gcd::ExtendedGcd for $type {
+            fn extended_gcd(a: &Self, b: &Self) -> ::core::option::Option<(Self, Self, Self)> {
+                // The magnitudes of the remainders and of the coefficients of
+                // a and b in them, where the coefficient of a is positive
+                // when the number of steps is even and that of b when it is
+                // odd.
+                let (mut r0, mut r1) = (a.unsigned_abs(), b.unsigned_abs());
+                let (mut s0, mut s1): ($unsigned, $unsigned) = (1, 0);
+                let (mut t0, mut t1): ($unsigned, $unsigned) = (0, 1);
+                let mut even = true;
+                while r1 != 0 {
+                    let q = r0 / r1;
+                    (r0, r1) = (r1, r0 - q * r1);
+                    (s0, s1) = (s1, s0 + q * s1);
+                    (t0, t1) = (t1, t0 + q * t1);
+                    even = !even;
+                }
+
+                let signed = |magnitude: $unsigned, negative: bool| {
+                    let value =
+                        <$type as ::core::convert::TryFrom<$unsigned>>::try_from(magnitude).ok()?;
+                    ::core::option::Option::Some(if negative { -value } else { value })
+                };
+                let g = <$type as ::core::convert::TryFrom<$unsigned>>::try_from(r0).ok()?;
+                let x = signed(s0, (*a < 0) == even)?;
+                let y = signed(t0, (*b < 0) != even)?;
+                ::core::option::Option::Some((g, x, y))
+            }
+        }
+    };
+}
+
+gcd_trait_for_unsigned!(::core::primitive::u8);
+gcd_trait_for_unsigned!(::core::primitive::u16);
+gcd_trait_for_unsigned!(::core::primitive::u32);
+gcd_trait_for_unsigned!(::core::primitive::u64);
+gcd_trait_for_unsigned!(::core::primitive::u128);
+gcd_trait_for_unsigned!(::core::primitive::usize);
+gcd_trait_for_signed!(::core::primitive::i8, ::core::primitive::u8);
+gcd_trait_for_signed!(::core::primitive::i16, ::core::primitive::u16);
+gcd_trait_for_signed!(::core::primitive::i32, ::core::primitive::u32);
+gcd_trait_for_signed!(::core::primitive::i64, ::core::primitive::u64);
+gcd_trait_for_signed!(::core::primitive::i128, ::core::primitive::u128);
+gcd_trait_for_signed!(::core::primitive::isize, ::core::primitive::usize);
+extended_gcd_trait_for_signed!(::core::primitive::i8, ::core::primitive::u8);
+extended_gcd_trait_for_signed!(::core::primitive::i16, ::core::primitive::u16);
+extended_gcd_trait_for_signed!(::core::primitive::i32, ::core::primitive::u32);
+extended_gcd_trait_for_signed!(::core::primitive::i64, ::core::primitive::u64);
+extended_gcd_trait_for_signed!(::core::primitive::i128, ::core::primitive::u128);
+extended_gcd_trait_for_signed!(::core::primitive::isize, ::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Gcd for rug::Integer {
+    fn gcd(a: &Self, b: &Self) -> Self {
+        rug::Integer::from(a.gcd_ref(b))
+    }
+
+    fn lcm(a: &Self, b: &Self) -> Self {
+        rug::Integer::from(a.lcm_ref(b))
+    }
+
+    fn checked_lcm(a: &Self, b: &Self) -> Option<Self> {
+        Some(<Self as Gcd>::lcm(a, b))
+    }
+}
+
+/// The coefficients come from GMP, which keeps them as small as the
+/// Euclidean algorithm does. As `rug::Integer` has an inherent
+/// `extended_gcd` of its own, this one is called on it as
+/// `<rug::Integer as ExtendedGcd>::extended_gcd`.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::ExtendedGcd;
+/// use rug::Integer;
+///
+/// let a = Integer::from(Integer::u_pow_u(2, 100)) + 1u8;
+/// let b = Integer::from(Integer::u_pow_u(3, 50));
+/// let (g, x, y) = <Integer as ExtendedGcd>::extended_gcd(&a, &b).unwrap();
+/// assert_eq!(g, 1);
+/// assert_eq!(a * x + b * y, 1);
+/// ```
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl ExtendedGcd for rug::Integer {
+    fn extended_gcd(a: &Self, b: &Self) -> Option<(Self, Self, Self)> {
+        Some(a.extended_gcd_ref(b).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rug::Integer;
+
+    macro_rules! test_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let small = (<$type>::MIN..=<$type>::MAX).take(100);
+                let large = (<$type>::MIN..=<$type>::MAX).rev().take(100);
+                let spread = (0..100u32).map(|i| <$type>::MAX / 100 * i as $type + 7);
+                let values: ::std::vec::Vec<$type> = small.chain(large).chain(spread).collect();
+                for &a in &values {
+                    for &b in &values {
+                        let (x, y) = (Integer::from(a), Integer::from(b));
+                        let gcd = Integer::from(x.gcd_ref(&y));
+                        let lcm = Integer::from(x.lcm_ref(&y));
+                        match <$type as ::core::convert::TryFrom<&Integer>>::try_from(&gcd) {
+                            ::core::result::Result::Ok(expected) => {
+                                ::core::assert_eq!(<$type>::gcd(&a, &b), expected, "{} {}", a, b)
+                            }
+                            ::core::result::Result::Err(_) => ::core::assert!(
+                                ::std::panic::catch_unwind(|| <$type>::gcd(&a, &b)).is_err()
+                            ),
+                        }
+                        ::core::assert_eq!(
+                            <$type>::checked_lcm(&a, &b),
+                            <$type as ::core::convert::TryFrom<&Integer>>::try_from(&lcm).ok(),
+                            "{} {}",
+                            a,
+                            b
+                        );
+                    }
+                }
+            }
+        };
+    }
+
+    macro_rules! test_extended_gcd {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let small = (<$type>::MIN..=<$type>::MAX).take(60);
+                let large = (<$type>::MIN..=<$type>::MAX).rev().take(60);
+                let middle = -30..30;
+                let spread = (0..60).map(|i| <$type>::MAX / 60 * (i - 30) + 7);
+                let values: ::std::vec::Vec<$type> =
+                    small.chain(large).chain(middle).chain(spread).collect();
+                for &a in &values {
+                    for &b in &values {
+                        let (x_big, y_big) = (Integer::from(a), Integer::from(b));
+                        let gcd = Integer::from(x_big.gcd_ref(&y_big));
+                        let result = <$type>::extended_gcd(&a, &b);
+                        let (g, x, y) = match result {
+                            ::core::option::Option::Some(result) => result,
+                            ::core::option::Option::None => {
+                                ::core::assert!(a == <$type>::MIN || b == <$type>::MIN);
+                                continue;
+                            }
+                        };
+                        ::core::assert_eq!(Integer::from(g), gcd, "{} {}", a, b);
+                        ::core::assert_eq!(
+                            Integer::from(&x_big * x) + Integer::from(&y_big * y),
+                            gcd,
+                            "{} {}",
+                            a,
+                            b
+                        );
+                        if g != 0 {
+                            let bound = |n: &Integer| {
+                                Integer::from(n.abs_ref())
+                                    .div_exact(&gcd)
+                                    .max(Integer::from(1))
+                            };
+                            ::core::assert!(Integer::from(x).abs() <= bound(&y_big));
+                            ::core::assert!(Integer::from(y).abs() <= bound(&x_big));
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    test_extended_gcd!(::core::primitive::i8, i8_extended_gcd);
+    test_extended_gcd!(::core::primitive::i16, i16_extended_gcd);
+    test_extended_gcd!(::core::primitive::i32, i32_extended_gcd);
+    test_extended_gcd!(::core::primitive::i64, i64_extended_gcd);
+    test_extended_gcd!(::core::primitive::i128, i128_extended_gcd);
+    test_extended_gcd!(::core::primitive::isize, isize_extended_gcd);
+
+    test_against_rug!(::core::primitive::i8, i8_gcd);
+    test_against_rug!(::core::primitive::u8, u8_gcd);
+    test_against_rug!(::core::primitive::i16, i16_gcd);
+    test_against_rug!(::core::primitive::u16, u16_gcd);
+    test_against_rug!(::core::primitive::i32, i32_gcd);
+    test_against_rug!(::core::primitive::u32, u32_gcd);
+    test_against_rug!(::core::primitive::i64, i64_gcd);
+    test_against_rug!(::core::primitive::u64, u64_gcd);
+    test_against_rug!(::core::primitive::i128, i128_gcd);
+    test_against_rug!(::core::primitive::u128, u128_gcd);
+    test_against_rug!(::core::primitive::isize, isize_gcd);
+    test_against_rug!(::core::primitive::usize, usize_gcd);
+
+    #[test]
+    fn rug_gcd_trait() {
+        let a: Integer = "-123456789012345678901234567890".parse().unwrap();
+        let b = Integer::from(Integer::u_pow_u(10, 40)) * 9u8;
+        assert_eq!(<Integer as Gcd>::gcd(&a, &b), 90);
+        assert_eq!(<Integer as Gcd>::gcd(&a, &Integer::new()), a.clone().abs());
+        let lcm = <Integer as Gcd>::checked_lcm(&a, &b).unwrap();
+        assert_eq!(lcm, Integer::from(a.abs_ref()) * &b / 90u8);
+    }
+
+    #[test]
+    #[should_panic(expected = "least common multiple overflows")]
+    fn lcm_overflow() {
+        u32::lcm(&65_536, &65_537);
+    }
+}
@@ -0,0 +1,254 @@
+use crate::number_theory::Sieve;
+
+/// Returns the number of ways to write n as a sum p + q of two primes with
+/// p ≤ q.
+///
+/// For even n, the odd primes up to n are sieved into a bitset by `Sieve`,
+/// and the count is found 64 candidates at a time by intersecting the bitset
+/// with its own reversal. This takes O(n log log n) time and O(n) bits of
+/// space. Odd n can only be 2 + (n − 2), which is checked with
+/// `Prime::is_prime`.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::goldbach::goldbach_count;
+///
+/// // 3 + 7 and 5 + 5.
+/// assert_eq!(goldbach_count(10), 2);
+/// assert_eq!(goldbach_count(100), 6);
+/// assert_eq!(goldbach_count(1_000_000), 5402);
+///
+/// // Odd numbers only have 2 + (n − 2).
+/// assert_eq!(goldbach_count(9), 1);
+/// assert_eq!(goldbach_count(11), 0);
+/// ```
+pub fn goldbach_count(n: u64) -> u64 {
+    use crate::number_theory::Prime;
+
+    if n % 2 == 1 {
+        return n.checked_sub(2).filter(u64::is_prime).is_some() as u64;
+    }
+    if n < 6 {
+        return goldbach_partitions(n).count() as u64;
+    }
+
+    // The odd numbers 2i + 1 and 2j + 1 sum to n exactly when
+    // i + j = n / 2 − 1, so the pairs with i ≤ j are the set bits of the
+    // bitset ANDed with its reversal, up to the middle.
+    let sieve = Sieve::new(n);
+    let bits = sieve.odd_bits();
+    let half = (n / 2) as i64;
+    let last = ((half - 1) / 2) as usize;
+    let mut count = 0;
+    for (k, &word) in bits.iter().enumerate().take(last / 64 + 1) {
+        let reversed = bits_at(bits, half - 64 - 64 * k as i64).reverse_bits();
+        let mut pairs = word & reversed;
+        if k == last / 64 && last % 64 != 63 {
+            pairs &= (1 << (last % 64 + 1)) - 1;
+        }
+        count += pairs.count_ones() as u64;
+    }
+    count
+}
+
+/// Returns an iterator over the pairs `(p, q)` of primes with p + q = n and
+/// p ≤ q in increasing order of p.
+///
+/// For even n, the primes up to n are sieved by `Sieve`. Odd n can only be
+/// 2 + (n − 2), which is checked with `Prime::is_prime`.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::goldbach::goldbach_partitions;
+///
+/// let pairs: Vec<_> = goldbach_partitions(100).collect();
+/// assert_eq!(pairs, vec![(3, 97), (11, 89), (17, 83), (29, 71), (41, 59), (47, 53)]);
+///
+/// assert_eq!(goldbach_partitions(4).collect::<Vec<_>>(), vec![(2, 2)]);
+///
+/// // Odd numbers only have 2 + (n − 2), so no sieve up to n is needed.
+/// let mut pairs = goldbach_partitions(u64::MAX - 56);
+/// assert_eq!(pairs.next(), Some((2, u64::MAX - 58)));
+/// assert_eq!(pairs.next(), None);
+/// ```
+pub fn goldbach_partitions(n: u64) -> GoldbachPartitions {
+    GoldbachPartitions {
+        n,
+        p: 2,
+        sieve: if n.is_multiple_of(2) { Some(Sieve::new(n)) } else { None },
+    }
+}
+
+/// Returns an `Option` containing either the pair `(p, q)` of primes with
+/// p + q = n and p as small as possible, or `None` if there is no such pair.
+///
+/// Rather than sieving up to n, this goes up through the primes p and tests
+/// whether n − p is prime with `Prime::is_prime`, which finds a pair after a
+/// few dozen primes even for n near 2<sup>64</sup>, so ruling out a pair
+/// is the only slow case.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::goldbach::goldbach_any;
+///
+/// assert_eq!(goldbach_any(100), Some((3, 97)));
+/// assert_eq!(goldbach_any(11), None);
+///
+/// let (p, q) = goldbach_any(u64::MAX - 1).unwrap();
+/// assert_eq!(p + q, u64::MAX - 1);
+/// ```
+pub fn goldbach_any(n: u64) -> Option<(u64, u64)> {
+    use crate::number_theory::Prime;
+
+    if n % 2 == 1 {
+        return n.checked_sub(2).filter(u64::is_prime).map(|q| (2, q));
+    }
+    crate::number_theory::sieve::primes()
+        .take_while(|&p| p <= n / 2)
+        .find(|&p| u64::is_prime(&(n - p)))
+        .map(|p| (p, n - p))
+}
+
+/// Returns an `Option` containing either the first even number from 4 up to
+/// and including `limit` that is not a sum of two primes, or `None` if
+/// Goldbach's conjecture holds that far.
+///
+/// The primes up to `limit` are sieved once, and each even number is then
+/// checked by going up through the primes p until n − p is one too.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::goldbach::goldbach_counterexample;
+///
+/// assert_eq!(goldbach_counterexample(1_000_000), None);
+/// ```
+pub fn goldbach_counterexample(limit: u64) -> Option<u64> {
+    let sieve = Sieve::new(limit);
+    (4..=limit).step_by(2).find(|&n| {
+        !sieve
+            .primes()
+            .take_while(|&p| p <= n / 2)
+            .any(|p| sieve.is_prime(n - p))
+    })
+}
+
+/// The iterator returned by `goldbach_partitions`.
+#[derive(Clone, Debug)]
+pub struct GoldbachPartitions {
+    n: u64,
+    p: u64,
+    // There is no sieve for odd n.
+    sieve: Option<Sieve>,
+}
+
+impl Iterator for GoldbachPartitions {
+    type Item = (u64, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use crate::number_theory::Prime;
+
+        let sieve = match &self.sieve {
+            Some(sieve) => sieve,
+            None => {
+                let first = self.p == 2;
+                self.p = 3;
+                return self
+                    .n
+                    .checked_sub(2)
+                    .filter(|q| first && u64::is_prime(q))
+                    .map(|q| (2, q));
+            }
+        };
+        while self.p <= self.n / 2 {
+            let p = self.p;
+            // After 2, only odd numbers can be prime.
+            self.p += if p == 2 { 1 } else { 2 };
+            if sieve.is_prime(p) && sieve.is_prime(self.n - p) {
+                return Some((p, self.n - p));
+            }
+        }
+        None
+    }
+}
+
+/// Returns bits `start` to `start + 63` of the bitset, treating bits outside
+/// it as zero.
+fn bits_at(bits: &[u64], start: i64) -> u64 {
+    let word = |w: i64| {
+        if w < 0 || w as usize >= bits.len() {
+            0
+        } else {
+            bits[w as usize]
+        }
+    };
+    let (w, offset) = (start.div_euclid(64), start.rem_euclid(64));
+    if offset == 0 {
+        word(w)
+    } else {
+        word(w) >> offset | word(w + 1) << (64 - offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::number_theory::Prime;
+
+    fn is_prime(n: u64) -> bool {
+        n >= 2
+            && (2..)
+                .take_while(|d| d * d <= n)
+                .all(|d| !n.is_multiple_of(d))
+    }
+
+    #[test]
+    fn counts_match_pairs_and_trial_division() {
+        for n in 0..3000 {
+            let expected: Vec<_> = (2..=n / 2)
+                .filter(|&p| is_prime(p) && is_prime(n - p))
+                .map(|p| (p, n - p))
+                .collect();
+            assert_eq!(
+                goldbach_partitions(n).collect::<Vec<_>>(),
+                expected,
+                "n = {}",
+                n
+            );
+            assert_eq!(goldbach_count(n), expected.len() as u64, "n = {}", n);
+            assert_eq!(goldbach_any(n), expected.first().copied(), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn counts_across_word_boundaries() {
+        for n in (100_000..100_600).step_by(2) {
+            assert_eq!(goldbach_count(n), goldbach_partitions(n).count() as u64);
+        }
+    }
+
+    #[test]
+    fn large_partitions() {
+        for n in (u64::MAX - 2001..u64::MAX)
+            .step_by(2)
+            .chain([1 << 40, 1 << 63])
+        {
+            let (p, q) = goldbach_any(n).unwrap();
+            assert!(is_prime(p) && u64::is_prime(&q) && p + q == n, "n = {}", n);
+            // No smaller prime works.
+            assert!(crate::number_theory::sieve::primes()
+                .take_while(|&r| r < p)
+                .all(|r| !u64::is_prime(&(n - r))));
+        }
+        assert_eq!(goldbach_any(u64::MAX), None);
+        // Odd numbers only need n − 2 tested, not a sieve up to n.
+        assert_eq!(goldbach_count(18_446_744_073_709_551_559), 1);
+        assert_eq!(goldbach_count(u64::MAX), 0);
+        assert_eq!(goldbach_partitions(u64::MAX).next(), None);
+        assert_eq!(goldbach_counterexample(3_000_000), None);
+        assert_eq!(goldbach_counterexample(0), None);
+    }
+}
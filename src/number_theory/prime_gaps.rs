@@ -0,0 +1,120 @@
+/// Returns an `Iterator<Item = (u64, u64)>` implementation that goes through
+/// each prime p along with the gap q − p to the next prime q.
+///
+/// The primes come from `sieve::primes`, so this ends with the gap before
+/// the largest prime below 2<sup>64</sup>.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::prime_gaps::prime_gaps;
+///
+/// let gaps: Vec<_> = prime_gaps().take(6).collect();
+/// assert_eq!(gaps, vec![(2, 1), (3, 2), (5, 2), (7, 4), (11, 2), (13, 4)]);
+///
+/// // The first gap of at least 100 follows 370,261.
+/// assert_eq!(prime_gaps().find(|&(_, gap)| gap >= 100), Some((370_261, 112)));
+/// ```
+pub fn prime_gaps() -> PrimeGaps {
+    let mut primes = crate::number_theory::sieve::primes();
+    PrimeGaps {
+        previous: primes.next(),
+        primes,
+    }
+}
+
+/// Returns an `Option` containing either the first prime p for which the
+/// gap to the next prime q is the largest among the consecutive primes below
+/// n, along with that gap, or `None` if there are fewer than two primes below
+/// n.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::prime_gaps::max_gap_below;
+///
+/// assert_eq!(max_gap_below(100), Some((89, 8)));
+/// assert_eq!(max_gap_below(1_000_000), Some((492_113, 114)));
+/// assert_eq!(max_gap_below(3), None);
+/// ```
+pub fn max_gap_below(n: u64) -> Option<(u64, u64)> {
+    prime_gaps()
+        .take_while(|&(p, gap)| p.checked_add(gap).is_some_and(|q| q < n))
+        .fold(None, |best: Option<(u64, u64)>, (p, gap)| match best {
+            Some((_, record)) if record >= gap => best,
+            _ => Some((p, gap)),
+        })
+}
+
+/// The iterator returned by `prime_gaps`.
+#[derive(Clone, Debug)]
+pub struct PrimeGaps {
+    previous: Option<u64>,
+    primes: crate::number_theory::sieve::Primes,
+}
+
+impl Iterator for PrimeGaps {
+    type Item = (u64, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let p = self.previous?;
+        self.previous = self.primes.next();
+        self.previous.map(|q| (p, q - p))
+    }
+}
+
+impl core::iter::FusedIterator for PrimeGaps {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_sieve() {
+        let primes: Vec<_> = crate::number_theory::Sieve::new(200_000).primes().collect();
+        let gaps: Vec<_> = prime_gaps().take(primes.len() - 1).collect();
+        let expected: Vec<_> = primes
+            .windows(2)
+            .map(|pair| (pair[0], pair[1] - pair[0]))
+            .collect();
+        assert_eq!(gaps, expected);
+
+        for n in (0..200_000)
+            .step_by(1009)
+            .chain([0, 1, 2, 3, 4, 5, 6, 7, 8])
+        {
+            let best = expected.iter().filter(|&&(p, gap)| p + gap < n).fold(
+                None,
+                |best: Option<(u64, u64)>, &(p, gap)| match best {
+                    Some((_, record)) if record >= gap => best,
+                    _ => Some((p, gap)),
+                },
+            );
+            assert_eq!(max_gap_below(n), best, "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn maximal_gaps() {
+        // The record gaps and where they start, up to 10⁷.
+        let mut record = 0;
+        let records: Vec<_> = prime_gaps()
+            .take_while(|&(p, _)| p < 10_000_000)
+            .filter(|&(_, gap)| {
+                let new = gap > record;
+                record = record.max(gap);
+                new
+            })
+            .collect();
+        assert_eq!(
+            &records[records.len() - 4..],
+            &[
+                (1_349_533, 118),
+                (1_357_201, 132),
+                (2_010_733, 148),
+                (4_652_353, 154)
+            ]
+        );
+        assert_eq!(max_gap_below(10_000_000), Some((4_652_353, 154)));
+    }
+}
@@ -0,0 +1,172 @@
+/// Returns an `Iterator<Item = u64>` implementation that goes through the
+/// primes p for which p + 2 is also prime, the smaller of each pair of twin
+/// primes.
+///
+/// This is `prime_tuples(&[0, 2])`.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::prime_tuples::twin_primes;
+///
+/// let terms: Vec<_> = twin_primes().take(8).collect();
+/// assert_eq!(terms, vec![3, 5, 11, 17, 29, 41, 59, 71]);
+/// ```
+pub fn twin_primes() -> PrimeTuples {
+    prime_tuples(&[0, 2])
+}
+
+/// Returns an `Iterator<Item = u64>` implementation that goes through the
+/// primes p for which p + 4 is also prime, the smaller of each pair of
+/// cousin primes.
+///
+/// This is `prime_tuples(&[0, 4])`.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::prime_tuples::cousin_primes;
+///
+/// let terms: Vec<_> = cousin_primes().take(8).collect();
+/// assert_eq!(terms, vec![3, 7, 13, 19, 37, 43, 67, 79]);
+/// ```
+pub fn cousin_primes() -> PrimeTuples {
+    prime_tuples(&[0, 4])
+}
+
+/// Returns an `Iterator<Item = u64>` implementation that goes through the
+/// primes p for which p + d is prime for every offset d in the pattern, in
+/// increasing order.
+///
+/// The pattern must be admissible, that is, for each prime q the offsets
+/// must miss some residue modulo q, as otherwise one of the p + d would
+/// always be divisible by q and there would be at most one such tuple. Only
+/// the primes up to the number of offsets need checking. The primes come
+/// from `sieve::primes`, with those up to p plus the largest offset kept for
+/// looking up the rest of each tuple.
+///
+/// # Panics
+///
+/// Panics if the offsets do not start at zero and strictly increase, or if
+/// the pattern is not admissible.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::prime_tuples::prime_tuples;
+///
+/// // Prime triplets of both kinds.
+/// let terms: Vec<_> = prime_tuples(&[0, 2, 6]).take(6).collect();
+/// assert_eq!(terms, vec![5, 11, 17, 41, 101, 107]);
+/// let terms: Vec<_> = prime_tuples(&[0, 4, 6]).take(6).collect();
+/// assert_eq!(terms, vec![7, 13, 37, 67, 97, 103]);
+///
+/// // Prime quadruplets.
+/// let terms: Vec<_> = prime_tuples(&[0, 2, 6, 8]).take(5).collect();
+/// assert_eq!(terms, vec![5, 11, 101, 191, 821]);
+/// ```
+pub fn prime_tuples(offsets: &[u64]) -> PrimeTuples {
+    assert!(
+        offsets.first() == Some(&0) && offsets.windows(2).all(|pair| pair[0] < pair[1]),
+        "offsets must start at zero and increase"
+    );
+    let k = offsets.len() as u64;
+    let admissible = crate::number_theory::Sieve::new(k)
+        .primes()
+        .all(|q| (0..q).any(|r| offsets.iter().all(|d| d % q != r)));
+    assert!(admissible, "pattern must be admissible");
+    PrimeTuples {
+        offsets: offsets.to_vec(),
+        primes: crate::number_theory::sieve::primes(),
+        window: std::collections::VecDeque::new(),
+        exhausted: false,
+    }
+}
+
+/// The iterator returned by `prime_tuples`, `twin_primes`, and
+/// `cousin_primes`.
+#[derive(Clone, Debug)]
+pub struct PrimeTuples {
+    offsets: Vec<u64>,
+    primes: crate::number_theory::sieve::Primes,
+    // The primes from the next candidate up to at least the candidate plus
+    // the largest offset.
+    window: std::collections::VecDeque<u64>,
+    exhausted: bool,
+}
+
+impl Iterator for PrimeTuples {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let span = *self.offsets.last().unwrap();
+        loop {
+            if self.window.is_empty() {
+                self.window.push_back(self.primes.next()?);
+            }
+            let p = self.window[0];
+            let end = p.checked_add(span);
+            while !self.exhausted && end.is_none_or(|end| *self.window.back().unwrap() < end) {
+                match self.primes.next() {
+                    Some(q) => self.window.push_back(q),
+                    None => self.exhausted = true,
+                }
+            }
+            self.window.pop_front();
+            let found = self.offsets[1..].iter().all(|&d| {
+                p.checked_add(d)
+                    .is_some_and(|q| self.window.binary_search(&q).is_ok())
+            });
+            if found {
+                return Some(p);
+            }
+        }
+    }
+}
+
+impl core::iter::FusedIterator for PrimeTuples {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_sieve() {
+        let sieve = crate::number_theory::Sieve::new(1_000_100);
+        for offsets in [
+            &[0, 2][..],
+            &[0, 4],
+            &[0, 6],
+            &[0, 2, 6],
+            &[0, 4, 6],
+            &[0, 2, 6, 8],
+            &[0, 2, 6, 8, 12],
+            &[0],
+        ] {
+            let expected: Vec<_> = sieve
+                .primes()
+                .take_while(|&p| p < 1_000_000)
+                .filter(|&p| offsets.iter().all(|&d| sieve.is_prime(p + d)))
+                .collect();
+            let terms: Vec<_> = prime_tuples(offsets)
+                .take_while(|&p| p < 1_000_000)
+                .collect();
+            assert_eq!(terms, expected, "{:?}", offsets);
+        }
+        // There are 8169 twin prime pairs below 10⁶.
+        assert_eq!(twin_primes().take_while(|&p| p < 1_000_000).count(), 8169);
+        assert_eq!(cousin_primes().take_while(|&p| p < 1_000_000).count(), 8144);
+    }
+
+    #[test]
+    #[should_panic(expected = "pattern must be admissible")]
+    fn inadmissible() {
+        prime_tuples(&[0, 2, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "offsets must start at zero and increase")]
+    fn unsorted() {
+        prime_tuples(&[0, 6, 2]);
+    }
+}
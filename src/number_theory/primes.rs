@@ -0,0 +1,1242 @@
+pub trait Prime: Sized {
+    /// Returns whether n is prime.
+    ///
+    /// This is the Baillie–PSW test: trial division by the primes below 64,
+    /// then a strong Fermat test to base two, then a strong Lucas test with
+    /// Selfridge's parameters. No composite number is known to pass it, and
+    /// none below 2<sup>64</sup> does, so for those it is exact. Larger
+    /// numbers are worked with in Montgomery form, so each test takes a few
+    /// hundred multiplications for `u128` and time quasi-quadratic in the
+    /// number of bits for `rug::Integer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Prime;
+    ///
+    /// let primes: Vec<_> = (0..30).filter(u8::is_prime).collect();
+    /// assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    ///
+    /// assert!(u64::is_prime(&18_446_744_073_709_551_557));
+    /// // A strong pseudoprime to base two.
+    /// assert!(!u32::is_prime(&3_215_031_751));
+    /// assert!(u128::is_prime(&(u128::MAX >> 1)));
+    /// assert!(!i32::is_prime(&-7));
+    /// ```
+    fn is_prime(n: &Self) -> bool;
+
+    /// Returns an `Option` containing either the smallest prime larger than
+    /// n or `None` if that would cause overflow.
+    ///
+    /// Only the candidates coprime to 2 × 3 × 5 × 7 are tested, with
+    /// `is_prime`. As `rug::Integer` has an inherent `next_prime` of its own,
+    /// this one is called on it as `<rug::Integer as Prime>::next_prime`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Prime;
+    ///
+    /// assert_eq!(u32::next_prime(&113), Some(127));
+    /// assert_eq!(u32::next_prime(&0), Some(2));
+    /// assert_eq!(i32::next_prime(&-20), Some(2));
+    /// assert_eq!(u8::next_prime(&251), None);
+    /// assert_eq!(u64::next_prime(&1_000_000_000_000), Some(1_000_000_000_039));
+    /// ```
+    fn next_prime(n: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the largest prime smaller than
+    /// n or `None` if n is at most two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Prime;
+    ///
+    /// assert_eq!(u32::prev_prime(&127), Some(113));
+    /// assert_eq!(u32::prev_prime(&3), Some(2));
+    /// assert_eq!(u32::prev_prime(&2), None);
+    /// assert_eq!(u64::prev_prime(&u64::MAX), Some(18_446_744_073_709_551_557));
+    /// ```
+    fn prev_prime(n: &Self) -> Option<Self>;
+}
+
+macro_rules! prime_trait_for_primitive {
+    ($type:ty) => {
+        impl $crate::number_theory::primes::Prime for $type {
+            fn is_prime(n: &Self) -> bool {
+                match <u128 as ::core::convert::TryFrom<$type>>::try_from(*n) {
+                    ::core::result::Result::Ok(n) => $crate::number_theory::primes::is_prime(n),
+                    ::core::result::Result::Err(_) => false,
+                }
+            }
+
+            fn next_prime(n: &Self) -> ::core::option::Option<Self> {
+                // Every negative number is followed by the prime 2.
+                let n = <u128 as ::core::convert::TryFrom<$type>>::try_from(*n).unwrap_or(0);
+                let p = $crate::number_theory::primes::next_prime(n)?;
+                <$type as ::core::convert::TryFrom<u128>>::try_from(p).ok()
+            }
+
+            fn prev_prime(n: &Self) -> ::core::option::Option<Self> {
+                let n = <u128 as ::core::convert::TryFrom<$type>>::try_from(*n).ok()?;
+                $crate::number_theory::primes::prev_prime(n).map(|p| p as $type)
+            }
+        }
+    };
+}
+
+prime_trait_for_primitive!(::core::primitive::i8);
+prime_trait_for_primitive!(::core::primitive::u8);
+prime_trait_for_primitive!(::core::primitive::i16);
+prime_trait_for_primitive!(::core::primitive::u16);
+prime_trait_for_primitive!(::core::primitive::i32);
+prime_trait_for_primitive!(::core::primitive::u32);
+prime_trait_for_primitive!(::core::primitive::i64);
+prime_trait_for_primitive!(::core::primitive::u64);
+prime_trait_for_primitive!(::core::primitive::i128);
+prime_trait_for_primitive!(::core::primitive::u128);
+prime_trait_for_primitive!(::core::primitive::isize);
+prime_trait_for_primitive!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Prime for rug::Integer {
+    fn is_prime(n: &Self) -> bool {
+        use rug::Integer;
+
+        if let Some(n) = n.to_u128() {
+            return is_prime(n);
+        }
+        if *n < 0 || SMALL_PRIMES.iter().any(|&p| n.is_divisible_u(p as u32)) {
+            return false;
+        }
+
+        // The strong Fermat test to base two.
+        let minus_one = Integer::from(n - 1u8);
+        let s = minus_one.find_one(0).unwrap();
+        let d = Integer::from(&minus_one >> s);
+        let mut x = Integer::from(2).pow_mod(&d, n).unwrap();
+        if x != 1 && x != minus_one {
+            let mut passed = false;
+            for _ in 1..s {
+                x.square_mut();
+                x.modulo_mut(n);
+                if x == minus_one {
+                    passed = true;
+                    break;
+                }
+            }
+            if !passed {
+                return false;
+            }
+        }
+
+        // The strong Lucas test.
+        if n.is_perfect_square() {
+            return false;
+        }
+        let discriminant = match selfridge(|d| Integer::from(d).jacobi(n)) {
+            Some(discriminant) => discriminant,
+            None => return false,
+        };
+        let q = Integer::from((1 - discriminant) / 4).modulo(n);
+        let discriminant = Integer::from(discriminant).modulo(n);
+        let half = |x: &mut Integer| {
+            if x.is_odd() {
+                *x += n;
+            }
+            *x >>= 1;
+        };
+
+        let plus_one = Integer::from(n + 1u8);
+        let s = plus_one.find_one(0).unwrap();
+        let d = Integer::from(&plus_one >> s);
+        let (mut u, mut v, mut q_power) = (Integer::from(1), Integer::from(1), q.clone());
+        for bit in (0..d.significant_bits() - 1).rev() {
+            u *= &v;
+            u.modulo_mut(n);
+            v.square_mut();
+            v -= Integer::from(&q_power << 1);
+            v.modulo_mut(n);
+            q_power.square_mut();
+            q_power.modulo_mut(n);
+            if d.get_bit(bit) {
+                let mut next_u = Integer::from(&u + &v);
+                half(&mut next_u);
+                v += Integer::from(&discriminant * &u);
+                v.modulo_mut(n);
+                half(&mut v);
+                u = next_u.modulo(n);
+                q_power *= &q;
+                q_power.modulo_mut(n);
+            }
+        }
+        if u == 0 || v == 0 {
+            return true;
+        }
+        for _ in 1..s {
+            v.square_mut();
+            v -= Integer::from(&q_power << 1);
+            v.modulo_mut(n);
+            if v == 0 {
+                return true;
+            }
+            q_power.square_mut();
+            q_power.modulo_mut(n);
+        }
+        false
+    }
+
+    fn next_prime(n: &Self) -> Option<Self> {
+        if let Some(p) = n.to_u128().and_then(next_prime) {
+            return Some(p.into());
+        }
+        if *n < 2 {
+            return Some(2.into());
+        }
+        let residues = wheel_residues();
+        let remainder = n.mod_u(WHEEL_MODULUS as u32) as u128;
+        let mut base = rug::Integer::from(n - remainder);
+        let mut index = residues.partition_point(|&r| r as u128 <= remainder);
+        loop {
+            if index == residues.len() {
+                base += WHEEL_MODULUS;
+                index = 0;
+            }
+            let candidate = rug::Integer::from(&base + residues[index]);
+            if Self::is_prime(&candidate) {
+                return Some(candidate);
+            }
+            index += 1;
+        }
+    }
+
+    fn prev_prime(n: &Self) -> Option<Self> {
+        if let Some(n) = n.to_u128() {
+            return prev_prime(n).map(Into::into);
+        }
+        if *n < 0 {
+            return None;
+        }
+        let residues = wheel_residues();
+        let remainder = n.mod_u(WHEEL_MODULUS as u32) as u128;
+        let mut base = rug::Integer::from(n - remainder);
+        let mut index = residues.partition_point(|&r| (r as u128) < remainder);
+        loop {
+            if index == 0 {
+                base -= WHEEL_MODULUS;
+                index = residues.len();
+            }
+            index -= 1;
+            let candidate = rug::Integer::from(&base + residues[index]);
+            if Self::is_prime(&candidate) {
+                return Some(candidate);
+            }
+        }
+    }
+}
+
+/// Returns an `Iterator<Item = u64>` implementation that goes through the
+/// primes from n on in increasing order, ending after the largest one below
+/// 2<sup>64</sup>.
+///
+/// Each candidate is tested with `Prime::is_prime`, and only those coprime
+/// to 2 × 3 × 5 × 7 are tried, so unlike `sieve::primes` this takes constant
+/// memory and starts anywhere at once, at the cost of more time per prime.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::primes::primes_from;
+///
+/// let terms: Vec<_> = primes_from(0).take(10).collect();
+/// assert_eq!(terms, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+///
+/// let terms: Vec<_> = primes_from(1_000_000_000_000).take(3).collect();
+/// assert_eq!(terms, vec![1_000_000_000_039, 1_000_000_000_061, 1_000_000_000_063]);
+/// assert_eq!(primes_from(u64::MAX - 100).count(), 3);
+/// ```
+pub fn primes_from(n: u64) -> PrimesFrom {
+    let wheel = crate::number_theory::Wheel::new(&[2, 3, 5, 7]);
+    PrimesFrom {
+        small: wheel.primes().iter().position(|&p| p >= n).unwrap_or(4),
+        position: Some(wheel.position(n)),
+        wheel,
+    }
+}
+
+/// The iterator returned by `primes_from`.
+#[derive(Clone, Debug)]
+pub struct PrimesFrom {
+    wheel: crate::number_theory::Wheel,
+    // The index of the next of the wheel's own primes, which come first.
+    small: usize,
+    // The next candidate, or `None` once past every u64.
+    position: Option<crate::number_theory::wheel::Position>,
+}
+
+impl Iterator for PrimesFrom {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(&p) = self.wheel.primes().get(self.small) {
+            self.small += 1;
+            return Some(p);
+        }
+        loop {
+            let position = self.position?;
+            let candidate = self.wheel.value(position);
+            self.position = candidate.and_then(|_| self.wheel.step(position));
+            let candidate = candidate?;
+            if candidate != 1 && is_prime(candidate as u128) {
+                return Some(candidate);
+            }
+        }
+    }
+}
+
+impl core::iter::FusedIterator for PrimesFrom {}
+
+/// Returns an `Option` containing either the n<sup>th</sup> prime, counting
+/// two as the first, or `None` if n is zero or the prime does not fit in a
+/// `u64`.
+///
+/// This sieves up to the prime a segment at a time, counting the primes in
+/// each segment rather than going through them, so it takes memory
+/// proportional to the square root of the answer.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::primes::nth_prime;
+///
+/// assert_eq!(nth_prime(1), Some(2));
+/// assert_eq!(nth_prime(10_000), Some(104_729));
+/// assert_eq!(nth_prime(1_000_000), Some(15_485_863));
+/// assert_eq!(nth_prime(0), None);
+/// ```
+pub fn nth_prime(n: u64) -> Option<u64> {
+    // The number of primes below 2^64.
+    if n == 0 || n > 425_656_284_035_217_743 {
+        return None;
+    }
+    let index = <usize as core::convert::TryFrom<u64>>::try_from(n - 1).ok()?;
+    crate::number_theory::sieve::primes().nth(index)
+}
+
+/// Returns an estimate of the n<sup>th</sup> prime, counting two as the first,
+/// or zero if n is zero.
+///
+/// This is the inverse of the logarithmic integral li(x) =
+/// ∫<sub>0</sub><sup>x</sup> dt / ln t at n, found by Newton's method, which by
+/// the prime number theorem is within a small fraction of a percent of the
+/// n<sup>th</sup> prime for large n. It takes constant time, so it suits sizing
+/// buffers and sieves.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::primes::nth_prime_estimate;
+///
+/// // The millionth prime is 15,485,863.
+/// let estimate = nth_prime_estimate(1_000_000);
+/// assert!(15_400_000 < estimate && estimate < 15_500_000);
+/// assert_eq!(nth_prime_estimate(3), 5);
+/// assert_eq!(nth_prime_estimate(25), 97);
+/// ```
+pub fn nth_prime_estimate(n: u64) -> u64 {
+    // The estimate is poor for the very first primes.
+    const FIRST: [u64; 26] = [
+        0, 2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83,
+        89, 97,
+    ];
+    if let Some(&p) = FIRST.get(n as usize) {
+        return p;
+    }
+    let target = n as f64;
+    let mut x = target * (target.ln() + target.ln().ln());
+    for _ in 0..100 {
+        let step = (logarithmic_integral(x) - target) * x.ln();
+        x -= step;
+        if step.abs() < 0.25 {
+            break;
+        }
+    }
+    x.round() as u64
+}
+
+/// Returns li(x) for x > 1 by Ramanujan's series.
+fn logarithmic_integral(x: f64) -> f64 {
+    // The Euler–Mascheroni constant.
+    const GAMMA: f64 = 0.577_215_664_901_532_9;
+    let log = x.ln();
+    let (mut term, mut inner, mut sum) = (-1.0, 0.0, 0.0);
+    for n in 1..=200 {
+        // term = (−1)^(n − 1) (ln x)^n / (n! 2^(n − 1)).
+        term *= -log / (n as f64 * if n == 1 { 1.0 } else { 2.0 });
+        if n % 2 == 1 {
+            inner += 1.0 / n as f64;
+        }
+        let next = sum + term * inner;
+        if next == sum {
+            break;
+        }
+        sum = next;
+    }
+    GAMMA + log.ln() + x.sqrt() * sum
+}
+
+/// Returns π(n), the number of primes no larger than n.
+///
+/// This is Lucy Hedgehog's method: with S(v) starting as the count of the
+/// numbers from 2 to v, crossing off the multiples of each prime p up to √n
+/// takes S(v) to S(v) − (S(v / p) − S(p − 1)), and only the values of
+/// S at the 2√n distinct values of n / i are ever needed. So this takes
+/// O(n<sup>3/4</sup>) time and O(√n) memory, and π(10<sup>12</sup>) takes
+/// a few seconds. With the `parallel` feature, the values changed by each
+/// prime are split between a thread for each core.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::primes::prime_pi;
+///
+/// assert_eq!(prime_pi(100), 25);
+/// assert_eq!(prime_pi(1_000_000_000), 50_847_534);
+/// assert_eq!(prime_pi(1), 0);
+/// ```
+pub fn prime_pi(n: u64) -> u64 {
+    lucy_hedgehog(n, |v| v.saturating_sub(1), |_| 1)
+}
+
+/// Returns the sum of the primes no larger than n.
+///
+/// This uses the same method as `prime_pi`, with S(v) starting as the sum
+/// of the numbers from 2 to v and each crossing off by p taking
+/// p (S(v / p) − S(p − 1)) from it, so it also takes O(n<sup>3/4</sup>)
+/// time and O(√n) memory. The sum of the primes below 2<sup>64</sup> is below
+/// 2<sup>127</sup>, so the result always fits.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::primes::prime_sum;
+///
+/// assert_eq!(prime_sum(10), 17);
+/// assert_eq!(prime_sum(2_000_000), 142_913_828_922);
+/// assert_eq!(prime_sum(1_000_000_000), 24_739_512_092_254_535);
+/// ```
+pub fn prime_sum(n: u64) -> u128 {
+    lucy_hedgehog(
+        n,
+        |v| {
+            let v = v as u128;
+            (v * (v + 1) / 2).saturating_sub(1)
+        },
+        |p| p as u128,
+    )
+}
+
+/// Returns the total weight of the primes no larger than n by Lucy
+/// Hedgehog's method, given the total weight of the numbers from 2 to each v
+/// and a completely multiplicative weight.
+fn lucy_hedgehog<T>(n: u64, initial: impl Fn(u64) -> T, weight: impl Fn(u64) -> T) -> T
+where
+    T: Copy
+        + PartialEq
+        + Send
+        + Sync
+        + core::ops::Sub<Output = T>
+        + core::ops::Mul<Output = T>
+        + core::ops::SubAssign,
+{
+    #[cfg(feature = "parallel")]
+    let threads = crate::number_theory::sieve::threads();
+    #[cfg(not(feature = "parallel"))]
+    let threads = 1;
+    lucy_hedgehog_with(n, threads, initial, weight)
+}
+
+/// The fewest values changed by a prime in `lucy_hedgehog_with` for which
+/// the changes are split between threads.
+const PARALLEL_LENGTH: usize = 1 << 14;
+
+/// Returns `lucy_hedgehog` of n with the values changed by each prime split
+/// between the given number of threads.
+fn lucy_hedgehog_with<T>(
+    n: u64,
+    threads: usize,
+    initial: impl Fn(u64) -> T,
+    weight: impl Fn(u64) -> T,
+) -> T
+where
+    T: Copy
+        + PartialEq
+        + Send
+        + Sync
+        + core::ops::Sub<Output = T>
+        + core::ops::Mul<Output = T>
+        + core::ops::SubAssign,
+{
+    if n < 2 {
+        return initial(n);
+    }
+    let root = n.isqrt() as usize;
+    // small[v] = S(v) for v ≤ √n and large[i] = S(n / i) for i ≤ √n.
+    let mut small: Vec<T> = (0..=root as u64).map(&initial).collect();
+    let mut large: Vec<T> = (0..=root as u64)
+        .map(|i| initial(n.checked_div(i).unwrap_or(0)))
+        .collect();
+    for p in 2..=root {
+        if small[p] == small[p - 1] {
+            continue;
+        }
+        let (below, w) = (small[p - 1], weight(p as u64));
+        let square = p * p;
+        let end = root.min((n / square as u64) as usize);
+        // S(n / (i p)) is among the large values exactly when i p ≤ √n.
+        let quotient = |large: &[T], small: &[T], i: usize| {
+            if i * p <= root {
+                large[i * p]
+            } else {
+                small[(n / (i * p) as u64) as usize]
+            }
+        };
+        // Each new value only reads values that are yet to change, so the
+        // threads find every change before any is made.
+        if threads > 1 && end >= PARALLEL_LENGTH {
+            let changes = map_parallel(1..=end, threads, |i| {
+                w * (quotient(&large, &small, i) - below)
+            });
+            for (value, change) in large[1..=end].iter_mut().zip(changes) {
+                *value -= change;
+            }
+        } else {
+            for i in 1..=end {
+                let change = w * (quotient(&large, &small, i) - below);
+                large[i] -= change;
+            }
+        }
+        if threads > 1 && square + PARALLEL_LENGTH <= root {
+            let changes = map_parallel(square..=root, threads, |v| w * (small[v / p] - below));
+            for (value, change) in small[square..=root].iter_mut().zip(changes) {
+                *value -= change;
+            }
+        } else {
+            for v in (square..=root).rev() {
+                let quotient = small[v / p];
+                small[v] -= w * (quotient - below);
+            }
+        }
+    }
+    large[1]
+}
+
+/// Returns f of each number in the range, in order, with the range split
+/// evenly between the given number of threads.
+fn map_parallel<T: Send>(
+    range: core::ops::RangeInclusive<usize>,
+    threads: usize,
+    f: impl Fn(usize) -> T + Sync,
+) -> Vec<T> {
+    let (start, end) = range.into_inner();
+    let chunk = (end + 1 - start).div_ceil(threads);
+    let f = &f;
+    std::thread::scope(|scope| {
+        let parts: Vec<_> = (start..=end)
+            .step_by(chunk)
+            .map(|low| {
+                let high = end.min(low + chunk - 1);
+                scope.spawn(move || (low..=high).map(f).collect::<Vec<_>>())
+            })
+            .collect();
+        parts
+            .into_iter()
+            .flat_map(|part| part.join().unwrap())
+            .collect()
+    })
+}
+
+/// The modulus of the wheel that candidates are stepped through.
+const WHEEL_MODULUS: u64 = 2 * 3 * 5 * 7;
+
+/// Returns the residues modulo `WHEEL_MODULUS` that are coprime to it.
+fn wheel_residues() -> Vec<u64> {
+    crate::number_theory::Wheel::new(&[2, 3, 5, 7])
+        .residues()
+        .to_vec()
+}
+
+/// Returns the smallest prime larger than n, or `None` if that does not fit
+/// in a `u128`.
+pub(crate) fn next_prime(n: u128) -> Option<u128> {
+    if let Some(p) = [2, 3, 5, 7].iter().copied().find(|&p| p > n) {
+        return Some(p);
+    }
+    let residues = wheel_residues();
+    let modulus = WHEEL_MODULUS as u128;
+    let (mut quotient, remainder) = (n / modulus, (n % modulus) as u64);
+    let mut index = residues.partition_point(|&r| r <= remainder);
+    loop {
+        if index == residues.len() {
+            quotient += 1;
+            index = 0;
+        }
+        let candidate = (quotient * modulus).checked_add(residues[index] as u128)?;
+        if is_prime(candidate) {
+            return Some(candidate);
+        }
+        index += 1;
+    }
+}
+
+/// Returns the largest prime smaller than n, or `None` if n is at most two.
+pub(crate) fn prev_prime(n: u128) -> Option<u128> {
+    if n <= 11 {
+        return [7, 5, 3, 2].iter().copied().find(|&p| p < n);
+    }
+    let residues = wheel_residues();
+    let modulus = WHEEL_MODULUS as u128;
+    let (mut quotient, remainder) = (n / modulus, (n % modulus) as u64);
+    let mut index = residues.partition_point(|&r| r < remainder);
+    loop {
+        if index == 0 {
+            quotient -= 1;
+            index = residues.len();
+        }
+        index -= 1;
+        // Eleven is a candidate, so this ends before the quotient runs out.
+        let candidate = quotient * modulus + residues[index] as u128;
+        if is_prime(candidate) {
+            return Some(candidate);
+        }
+    }
+}
+
+const SMALL_PRIMES: [u8; 18] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61,
+];
+
+/// Returns whether n is prime by the Baillie–PSW test.
+pub(crate) fn is_prime(n: u128) -> bool {
+    for &p in &SMALL_PRIMES {
+        if n.is_multiple_of(p as u128) {
+            return n == p as u128;
+        }
+    }
+    if n < 64 * 64 {
+        return n > 1;
+    }
+    match <u64 as core::convert::TryFrom<u128>>::try_from(n) {
+        Ok(n) => bpsw(&Small { n }),
+        Err(_) => bpsw(&Montgomery::new(n)),
+    }
+}
+
+/// Returns the first of 5, −7, 9, −11, … whose Jacobi symbol is −1, or `None`
+/// if one of them has Jacobi symbol zero first, which for a number with no
+/// factor below 64 means that it is composite.
+fn selfridge(jacobi: impl Fn(i64) -> i32) -> Option<i64> {
+    let mut discriminant = 5i64;
+    loop {
+        match jacobi(discriminant) {
+            -1 => return Some(discriminant),
+            0 => return None,
+            _ => {
+                discriminant = if discriminant > 0 {
+                    -(discriminant + 2)
+                } else {
+                    -discriminant + 2
+                }
+            }
+        }
+    }
+}
+
+/// Returns the Jacobi symbol (a/n) for odd n.
+pub(crate) fn jacobi(mut a: u128, mut n: u128) -> i32 {
+    let mut result = 1;
+    a %= n;
+    while a != 0 {
+        let zeros = a.trailing_zeros();
+        a >>= zeros;
+        if zeros & 1 == 1 && (n % 8 == 3 || n % 8 == 5) {
+            result = -result;
+        }
+        if a % 4 == 3 && n % 4 == 3 {
+            result = -result;
+        }
+        core::mem::swap(&mut a, &mut n);
+        a %= n;
+    }
+    if n == 1 {
+        result
+    } else {
+        0
+    }
+}
+
+/// Arithmetic modulo a positive number, in whatever representation suits
+/// it. Even moduli are fine except where an implementation says otherwise,
+/// though `half` only makes sense for odd ones.
+pub(crate) trait Modular {
+    type Elem: Copy + PartialEq;
+
+    fn modulus(&self) -> u128;
+
+    /// Returns the residue of x, which must be less than the modulus.
+    fn elem(&self, x: u128) -> Self::Elem;
+
+    /// Returns the number below the modulus that a stands for.
+    fn value(&self, a: Self::Elem) -> u128;
+
+    fn add(&self, a: Self::Elem, b: Self::Elem) -> Self::Elem;
+
+    fn sub(&self, a: Self::Elem, b: Self::Elem) -> Self::Elem;
+
+    fn mul(&self, a: Self::Elem, b: Self::Elem) -> Self::Elem;
+
+    /// Returns a / 2, for an odd modulus.
+    fn half(&self, a: Self::Elem) -> Self::Elem;
+}
+
+/// Arithmetic modulo a number below 2<sup>64</sup>.
+pub(crate) struct Small {
+    pub(crate) n: u64,
+}
+
+impl Modular for Small {
+    type Elem = u64;
+
+    fn modulus(&self) -> u128 {
+        self.n as u128
+    }
+
+    fn elem(&self, x: u128) -> u64 {
+        x as u64
+    }
+
+    fn value(&self, a: u64) -> u128 {
+        a as u128
+    }
+
+    fn add(&self, a: u64, b: u64) -> u64 {
+        let (sum, carry) = a.overflowing_add(b);
+        if carry || sum >= self.n {
+            sum.wrapping_sub(self.n)
+        } else {
+            sum
+        }
+    }
+
+    fn sub(&self, a: u64, b: u64) -> u64 {
+        if a >= b {
+            a - b
+        } else {
+            a.wrapping_sub(b).wrapping_add(self.n)
+        }
+    }
+
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        (a as u128 * b as u128 % self.n as u128) as u64
+    }
+
+    fn half(&self, a: u64) -> u64 {
+        if a & 1 == 0 {
+            a >> 1
+        } else {
+            (a >> 1) + (self.n >> 1) + 1
+        }
+    }
+}
+
+/// Montgomery arithmetic modulo an odd number, where x is represented by
+/// x 2<sup>128</sup> mod n so that products are reduced without division.
+/// The modulus has to be odd so that 2<sup>128</sup> has an inverse.
+pub(crate) struct Montgomery {
+    n: u128,
+    // The inverse of n modulo 2^128, and 2^256 mod n.
+    inverse: u128,
+    r_squared: u128,
+}
+
+impl Montgomery {
+    pub(crate) fn new(n: u128) -> Self {
+        // Each step of Newton's iteration doubles the number of correct low
+        // bits, and n is its own inverse modulo eight.
+        let mut inverse = n;
+        for _ in 0..6 {
+            inverse = inverse.wrapping_mul(2u128.wrapping_sub(n.wrapping_mul(inverse)));
+        }
+        let mut montgomery = Self {
+            n,
+            inverse,
+            r_squared: 0,
+        };
+        let mut r_squared = (u128::MAX % n + 1) % n;
+        for _ in 0..128 {
+            r_squared = montgomery.add(r_squared, r_squared);
+        }
+        montgomery.r_squared = r_squared;
+        montgomery
+    }
+
+    /// Returns t / 2<sup>128</sup> mod n for t given as its high and low
+    /// halves, which must be less than 2<sup>128</sup> n.
+    fn reduce(&self, (high, low): (u128, u128)) -> u128 {
+        let m = low.wrapping_mul(self.inverse);
+        // m n has the same low half as t, so the difference is a multiple of
+        // 2^128.
+        let (product_high, _) = wide_mul(m, self.n);
+        if high >= product_high {
+            high - product_high
+        } else {
+            high.wrapping_sub(product_high).wrapping_add(self.n)
+        }
+    }
+}
+
+impl Modular for Montgomery {
+    type Elem = u128;
+
+    fn modulus(&self) -> u128 {
+        self.n
+    }
+
+    fn elem(&self, x: u128) -> u128 {
+        self.reduce(wide_mul(x, self.r_squared))
+    }
+
+    fn value(&self, a: u128) -> u128 {
+        self.reduce((0, a))
+    }
+
+    fn add(&self, a: u128, b: u128) -> u128 {
+        let (sum, carry) = a.overflowing_add(b);
+        if carry || sum >= self.n {
+            sum.wrapping_sub(self.n)
+        } else {
+            sum
+        }
+    }
+
+    fn sub(&self, a: u128, b: u128) -> u128 {
+        if a >= b {
+            a - b
+        } else {
+            a.wrapping_sub(b).wrapping_add(self.n)
+        }
+    }
+
+    fn mul(&self, a: u128, b: u128) -> u128 {
+        self.reduce(wide_mul(a, b))
+    }
+
+    fn half(&self, a: u128) -> u128 {
+        if a & 1 == 0 {
+            a >> 1
+        } else {
+            (a >> 1) + (self.n >> 1) + 1
+        }
+    }
+}
+
+/// Returns the 256-bit product a b as its high and low halves.
+fn wide_mul(a: u128, b: u128) -> (u128, u128) {
+    let (a_high, a_low) = (a >> 64, a & u64::MAX as u128);
+    let (b_high, b_low) = (b >> 64, b & u64::MAX as u128);
+    let low = a_low * b_low;
+    let (middle, carry) = (a_low * b_high).overflowing_add(a_high * b_low);
+    let (low, low_carry) = low.overflowing_add(middle << 64);
+    let high = a_high * b_high + (middle >> 64) + ((carry as u128) << 64) + low_carry as u128;
+    (high, low)
+}
+
+/// Returns whether n, which is odd, larger than 64<sup>2</sup>, and free of
+/// the factors in `SMALL_PRIMES`, passes the Baillie–PSW test.
+fn bpsw<M: Modular>(modular: &M) -> bool {
+    let n = modular.modulus();
+    let one = modular.elem(1);
+    let minus_one = modular.elem(n - 1);
+
+    // The strong Fermat test to base two.
+    let s = (n - 1).trailing_zeros();
+    let mut x = power(modular, modular.elem(2), (n - 1) >> s);
+    if x != one && x != minus_one {
+        let mut passed = false;
+        for _ in 1..s {
+            x = modular.mul(x, x);
+            if x == minus_one {
+                passed = true;
+                break;
+            }
+        }
+        if !passed {
+            return false;
+        }
+    }
+
+    // The strong Lucas test with P = 1 and Q = (1 − D)/4, which has no
+    // suitable D when n is a square.
+    let root = n.isqrt();
+    if root * root == n {
+        return false;
+    }
+    let elem = |x: i64| {
+        let magnitude = modular.elem(x.unsigned_abs() as u128);
+        if x < 0 {
+            modular.sub(modular.elem(0), magnitude)
+        } else {
+            magnitude
+        }
+    };
+    let discriminant = match selfridge(|d| {
+        let d = if d < 0 {
+            n - d.unsigned_abs() as u128
+        } else {
+            d as u128
+        };
+        jacobi(d, n)
+    }) {
+        Some(discriminant) => discriminant,
+        None => return false,
+    };
+    let q = elem((1 - discriminant) / 4);
+    let discriminant = elem(discriminant);
+
+    // n + 1 = d 2^s, and U_d, V_d, and Q^d are found through the bits of d
+    // with U_2k = U_k V_k, V_2k = V_k² − 2Q^k, U_(k+1) = (U_k + V_k)/2, and
+    // V_(k+1) = (D U_k + V_k)/2.
+    let s = (n + 1).trailing_zeros();
+    let d = (n + 1) >> s;
+    let (mut u, mut v, mut q_power) = (one, one, q);
+    for bit in (0..127 - d.leading_zeros()).rev() {
+        u = modular.mul(u, v);
+        v = modular.sub(modular.mul(v, v), modular.add(q_power, q_power));
+        q_power = modular.mul(q_power, q_power);
+        if d >> bit & 1 == 1 {
+            let next_u = modular.half(modular.add(u, v));
+            v = modular.half(modular.add(modular.mul(discriminant, u), v));
+            u = next_u;
+            q_power = modular.mul(q_power, q);
+        }
+    }
+    let zero = modular.elem(0);
+    if u == zero || v == zero {
+        return true;
+    }
+    for _ in 1..s {
+        v = modular.sub(modular.mul(v, v), modular.add(q_power, q_power));
+        if v == zero {
+            return true;
+        }
+        q_power = modular.mul(q_power, q_power);
+    }
+    false
+}
+
+pub(crate) fn power<M: Modular>(modular: &M, base: M::Elem, exponent: u128) -> M::Elem {
+    let mut result = modular.elem(1);
+    for bit in (0..128 - exponent.leading_zeros()).rev() {
+        result = modular.mul(result, result);
+        if exponent >> bit & 1 == 1 {
+            result = modular.mul(result, base);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rug_is_prime(n: &rug::Integer) -> bool {
+        *n > 0 && n.is_probably_prime(40) != rug::integer::IsPrime::No
+    }
+
+    macro_rules! test_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let small = (<$type>::MIN..=<$type>::MAX).take(5000);
+                let large = (<$type>::MIN..=<$type>::MAX).rev().take(5000);
+                for n in small.chain(large) {
+                    ::core::assert_eq!(
+                        <$type>::is_prime(&n),
+                        rug_is_prime(&rug::Integer::from(n)),
+                        "n = {}",
+                        n
+                    );
+                }
+            }
+        };
+    }
+
+    test_against_rug!(::core::primitive::i8, i8_is_prime);
+    test_against_rug!(::core::primitive::u8, u8_is_prime);
+    test_against_rug!(::core::primitive::i16, i16_is_prime);
+    test_against_rug!(::core::primitive::u16, u16_is_prime);
+    test_against_rug!(::core::primitive::i32, i32_is_prime);
+    test_against_rug!(::core::primitive::u32, u32_is_prime);
+    test_against_rug!(::core::primitive::i64, i64_is_prime);
+    test_against_rug!(::core::primitive::u64, u64_is_prime);
+    test_against_rug!(::core::primitive::i128, i128_is_prime);
+    test_against_rug!(::core::primitive::u128, u128_is_prime);
+    test_against_rug!(::core::primitive::isize, isize_is_prime);
+    test_against_rug!(::core::primitive::usize, usize_is_prime);
+
+    #[test]
+    fn matches_sieve() {
+        let limit = 1_000_000;
+        let mut composite = vec![false; limit];
+        for i in 2..limit {
+            if !composite[i] {
+                for multiple in (i * i..limit).step_by(i) {
+                    composite[multiple] = true;
+                }
+            }
+            assert_eq!(is_prime(i as u128), !composite[i], "n = {}", i);
+        }
+        assert!(!is_prime(0) && !is_prime(1));
+    }
+
+    #[test]
+    fn pseudoprimes() {
+        // Strong pseudoprimes to base two, strong Lucas pseudoprimes, and
+        // Carmichael numbers.
+        let composites: [u128; 15] = [
+            2047,
+            3277,
+            4033,
+            4681,
+            8321,
+            3_215_031_751,
+            5459,
+            5777,
+            10_877,
+            16_109,
+            18_971,
+            561,
+            41_041,
+            825_265,
+            3_825_123_056_546_413_051,
+        ];
+        for &n in &composites {
+            assert!(!is_prime(n), "n = {}", n);
+        }
+        // Products of two primes near 2^64, which need Montgomery arithmetic.
+        let p = 18_446_744_073_709_551_557u128;
+        let q = 18_446_744_073_709_551_533u128;
+        assert!(is_prime(p) && is_prime(q));
+        assert!(!is_prime(p * q));
+        assert!(!is_prime(p * p));
+    }
+
+    #[test]
+    fn wide_values() {
+        let mut n = rug::Integer::from(u128::MAX);
+        for _ in 0..3000 {
+            let value = n.to_u128().unwrap();
+            assert_eq!(is_prime(value), rug_is_prime(&n), "n = {}", n);
+            n -= 0x1234_5678_9abc_def1u64;
+        }
+        for p in [61, 89, 107, 127] {
+            assert!(is_prime((1u128 << p) - 1));
+        }
+        assert!(!is_prime((1u128 << 101) - 1));
+        assert_eq!(wide_mul(u128::MAX, u128::MAX), (u128::MAX - 1, 1));
+    }
+
+    #[test]
+    fn primes_from_matches_sieve() {
+        let sieve = crate::number_theory::Sieve::new(100_000);
+        for start in [0, 1, 2, 3, 7, 8, 10, 11, 209, 210, 211, 99_000] {
+            let expected: Vec<_> = sieve.primes().filter(|&p| p >= start).collect();
+            let terms: Vec<_> = primes_from(start).take(expected.len()).collect();
+            assert_eq!(terms, expected, "start = {}", start);
+        }
+        let mut iter = primes_from(u64::MAX - 100);
+        assert_eq!(iter.next(), Some(18_446_744_073_709_551_521));
+        assert_eq!(iter.next(), Some(18_446_744_073_709_551_533));
+        assert_eq!(iter.next(), Some(18_446_744_073_709_551_557));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn next_and_prev_match_sieve() {
+        let primes: Vec<u64> = crate::number_theory::Sieve::new(100_000).primes().collect();
+        for n in 0..99_000u64 {
+            let next = primes[primes.partition_point(|&p| p <= n)];
+            assert_eq!(u64::next_prime(&n), Some(next), "n = {}", n);
+            let index = primes.partition_point(|&p| p < n);
+            let prev = index.checked_sub(1).map(|i| primes[i]);
+            assert_eq!(u64::prev_prime(&n), prev, "n = {}", n);
+            if n % 97 == 0 {
+                let big = rug::Integer::from(n);
+                assert_eq!(
+                    <rug::Integer as Prime>::next_prime(&big),
+                    Some(rug::Integer::from(next))
+                );
+                assert_eq!(
+                    <rug::Integer as Prime>::prev_prime(&big),
+                    prev.map(rug::Integer::from)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn next_and_prev_near_limits() {
+        assert_eq!(i8::next_prime(&113), Some(127));
+        assert_eq!(i8::next_prime(&127), None);
+        assert_eq!(i8::prev_prime(&127), Some(113));
+        assert_eq!(i8::prev_prime(&-5), None);
+        assert_eq!(u8::next_prime(&250), Some(251));
+        assert_eq!(u8::prev_prime(&u8::MAX), Some(251));
+        assert_eq!(u64::next_prime(&18_446_744_073_709_551_557), None);
+        assert_eq!(
+            u128::next_prime(&18_446_744_073_709_551_557),
+            Some(18_446_744_073_709_551_629)
+        );
+        assert_eq!(
+            u128::prev_prime(&18_446_744_073_709_551_629),
+            Some(18_446_744_073_709_551_557)
+        );
+        // The largest prime below 2^128 is 2^128 − 159.
+        let largest = u128::MAX - 158;
+        assert_eq!(u128::prev_prime(&u128::MAX), Some(largest));
+        assert_eq!(u128::next_prime(&largest), None);
+        let big = rug::Integer::from(largest);
+        let next = <rug::Integer as Prime>::next_prime(&big).unwrap();
+        assert_eq!(next, (rug::Integer::from(1) << 128) + 51);
+        assert_eq!(<rug::Integer as Prime>::prev_prime(&next), Some(big));
+        for k in [200, 521, 1000] {
+            let n = rug::Integer::from(1) << k;
+            let next = <rug::Integer as Prime>::next_prime(&n).unwrap();
+            assert_eq!(next, n.clone().next_prime());
+            let prev = <rug::Integer as Prime>::prev_prime(&n).unwrap();
+            assert_eq!(<rug::Integer as Prime>::next_prime(&prev), Some(next));
+        }
+    }
+
+    #[test]
+    fn nth_prime_matches_sieve() {
+        let primes: Vec<u64> = crate::number_theory::Sieve::new(1_000_000)
+            .primes()
+            .collect();
+        for (i, &p) in primes.iter().enumerate().step_by(997) {
+            assert_eq!(nth_prime(i as u64 + 1), Some(p));
+        }
+        assert_eq!(nth_prime(10_000_000), Some(179_424_673));
+        assert_eq!(nth_prime(425_656_284_035_217_744), None);
+    }
+
+    #[test]
+    fn nth_prime_estimates() {
+        assert_eq!(nth_prime_estimate(0), 0);
+        // The estimate is a little low, by about √p, falling from 1.5% at the
+        // thousandth prime.
+        for (n, p, tolerance) in [
+            (1000u64, 7919u64, 0.02),
+            (1_000_000, 15_485_863, 0.001),
+            (1_000_000_000, 22_801_763_489, 0.000_01),
+            (1_000_000_000_000, 29_996_224_275_833, 0.000_001),
+        ] {
+            let estimate = nth_prime_estimate(n) as f64;
+            let error = (estimate - p as f64).abs() / p as f64;
+            assert!(error < tolerance, "n = {}, estimate = {}", n, estimate);
+        }
+        for n in (1000..100_000).step_by(4999) {
+            let estimate = nth_prime_estimate(n) as f64;
+            let p = nth_prime(n).unwrap() as f64;
+            assert!((estimate - p).abs() / p < 0.02, "n = {}", n);
+        }
+        for n in 0..26 {
+            assert_eq!(nth_prime_estimate(n), nth_prime(n).unwrap_or(0));
+        }
+        // li(10^6) is 78,627.55, against the 78,498 primes below 10^6.
+        assert!((logarithmic_integral(1e6) - 78_627.549_159_5).abs() < 1e-6);
+        assert!((logarithmic_integral(2.0) - 1.045_163_780_117_492).abs() < 1e-12);
+    }
+
+    #[test]
+    fn prime_pi_matches_sieve() {
+        let sieve = crate::number_theory::Sieve::new(100_000);
+        let mut count = 0;
+        for n in 0..=100_000 {
+            if sieve.is_prime(n) {
+                count += 1;
+            }
+            if n < 5000 || n % 191 == 0 {
+                assert_eq!(prime_pi(n), count, "n = {}", n);
+            }
+        }
+        // Values from tables of π(x), around squares and powers of ten.
+        for (n, expected) in [
+            (10_000_000u64, 664_579u64),
+            (1 << 32, 203_280_221),
+            (10_000_000_000, 455_052_511),
+            (99_999_999_999, 4_118_054_813),
+        ] {
+            assert_eq!(prime_pi(n), expected, "n = {}", n);
+        }
+        for k in [1_000u64, 1_001, 4_096] {
+            let square = k * k;
+            let primes = crate::number_theory::Sieve::new(square + 1).count() as u64;
+            assert_eq!(prime_pi(square + 1), primes);
+        }
+    }
+
+    #[test]
+    fn parallel_lucy_hedgehog_matches_serial() {
+        let count = |v: u64| v.saturating_sub(1);
+        let sum = |v: u64| {
+            let v = v as u128;
+            (v * (v + 1) / 2).saturating_sub(1)
+        };
+        for n in [1u64 << 28, 1_000_000_000, 10_000_000_007, 1 << 34] {
+            let serial = lucy_hedgehog_with(n, 1, count, |_| 1);
+            assert_eq!(serial, prime_pi(n));
+            for threads in [2, 3, 4] {
+                assert_eq!(lucy_hedgehog_with(n, threads, count, |_| 1), serial);
+            }
+            let serial = lucy_hedgehog_with(n, 1, sum, |p| p as u128);
+            assert_eq!(serial, prime_sum(n));
+            assert_eq!(lucy_hedgehog_with(n, 3, sum, |p| p as u128), serial);
+        }
+        assert_eq!(
+            lucy_hedgehog_with(1_000_000_000, 4, count, |_| 1),
+            50_847_534
+        );
+    }
+
+    #[test]
+    fn prime_sum_matches_sieve() {
+        let sieve = crate::number_theory::Sieve::new(100_000);
+        let mut sum = 0;
+        for n in 0..=100_000 {
+            if sieve.is_prime(n) {
+                sum += n as u128;
+            }
+            if n < 5000 || n % 193 == 0 {
+                assert_eq!(prime_sum(n), sum, "n = {}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn rug_values() {
+        let start = rug::Integer::from(1) << 200;
+        for i in 0..2000u16 {
+            let n = rug::Integer::from(&start + i);
+            assert_eq!(rug::Integer::is_prime(&n), rug_is_prime(&n), "n = {}", n);
+        }
+        for p in [521, 607, 1279] {
+            assert!(rug::Integer::is_prime(&((rug::Integer::from(1) << p) - 1)));
+        }
+        let p: rug::Integer = (rug::Integer::from(1) << 127) - 1;
+        assert!(!rug::Integer::is_prime(&rug::Integer::from(&p * &p)));
+        assert!(!rug::Integer::is_prime(&rug::Integer::from(-7)));
+    }
+}
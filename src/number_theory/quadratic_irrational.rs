@@ -0,0 +1,212 @@
+use core::convert::TryFrom;
+
+use crate::number_theory::Gcd;
+
+/// An exactly represented real quadratic irrational (a + b√d) / c, where d is
+/// a positive nonsquare, b is nonzero, and c is positive.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::QuadraticIrrational;
+///
+/// let phi = QuadraticIrrational::golden_ratio();
+/// assert_eq!(phi.floor(), Some(1));
+/// assert_eq!(phi.floor_of_multiple(100), Some(161));
+///
+/// let sqrt_2 = QuadraticIrrational::sqrt(2).unwrap();
+/// assert_eq!(sqrt_2.floor_of_multiple(1_000_000), Some(1_414_213));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct QuadraticIrrational {
+    a: i128,
+    b: i128,
+    d: i128,
+    c: i128,
+}
+
+impl QuadraticIrrational {
+    /// Returns an `Option` containing either (a + b√d) / c or `None` if that
+    /// is not a quadratic irrational, that is, if b or c is zero or d is not
+    /// a positive nonsquare.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::QuadraticIrrational;
+    ///
+    /// assert!(QuadraticIrrational::new(1, 1, 5, 2).is_some());
+    /// assert!(QuadraticIrrational::new(1, 1, 4, 2).is_none());
+    /// assert!(QuadraticIrrational::new(1, 0, 5, 2).is_none());
+    /// ```
+    pub fn new(a: i128, b: i128, d: i128, c: i128) -> Option<Self> {
+        if b == 0 || c == 0 || d <= 0 {
+            return None;
+        }
+        let root = (d as u128).isqrt();
+        if root * root == d as u128 {
+            return None;
+        }
+
+        // Reduce the fraction by the greatest common divisor of the
+        // magnitudes and then make the denominator positive, which only
+        // fails when a part would be −i128::MIN.
+        let g = u128::gcd(
+            &u128::gcd(&a.unsigned_abs(), &b.unsigned_abs()),
+            &c.unsigned_abs(),
+        );
+        let reduce = |x: i128| {
+            let magnitude = x.unsigned_abs() / g;
+            if (x < 0) != (c < 0) {
+                0i128.checked_sub_unsigned(magnitude)
+            } else {
+                i128::try_from(magnitude).ok()
+            }
+        };
+        Some(Self {
+            a: reduce(a)?,
+            b: reduce(b)?,
+            d,
+            c: reduce(c)?,
+        })
+    }
+
+    /// Returns √d, or `None` if d is not a positive nonsquare.
+    pub fn sqrt(d: i128) -> Option<Self> {
+        Self::new(0, 1, d, 1)
+    }
+
+    /// Returns the golden ratio φ = (1 + √5) / 2.
+    pub fn golden_ratio() -> Self {
+        Self {
+            a: 1,
+            b: 1,
+            d: 5,
+            c: 2,
+        }
+    }
+
+    /// Returns the `(a, b, d, c)` for which this number is (a + b√d) / c,
+    /// with c positive and the fraction in lowest terms.
+    pub fn parts(&self) -> (i128, i128, i128, i128) {
+        (self.a, self.b, self.d, self.c)
+    }
+
+    /// Returns an `Option` containing either ⌊self⌋ or `None` if computing
+    /// it would cause overflow.
+    pub fn floor(&self) -> Option<i128> {
+        self.floor_of_multiple(1)
+    }
+
+    /// Returns an `Option` containing either ⌊n · self⌋ or `None` if
+    /// computing it would cause overflow. No floating-point arithmetic is
+    /// involved, so the result is exact.
+    pub fn floor_of_multiple(&self, n: i128) -> Option<i128> {
+        if n == 0 {
+            return Some(0);
+        }
+
+        let a = self.a.checked_mul(n)?;
+        let b = self.b.checked_mul(n)?;
+        // b√d = ±√(b²d), which is never an integer, so its floor is found
+        // exactly from the integer square root.
+        let square = (b.unsigned_abs())
+            .checked_mul(b.unsigned_abs())?
+            .checked_mul(self.d as u128)?;
+        let root = i128::try_from(square.isqrt()).ok()?;
+        let floor_of_irrational_part = if b > 0 { root } else { -root - 1 };
+
+        // The numerator lies strictly between two consecutive integers, so
+        // dividing the lower one by the positive denominator gives the floor.
+        Some(a.checked_add(floor_of_irrational_part)?.div_euclid(self.c))
+    }
+
+    /// Returns an `Option` containing either self / (self − 1) or `None` if
+    /// that would cause overflow.
+    ///
+    /// When self is greater than one, this is the number whose Beatty
+    /// sequence is complementary to that of self.
+    pub fn complement(&self) -> Option<Self> {
+        // (a + b√d) / (a − c + b√d), rationalized by multiplying through by
+        // (a − c − b√d).
+        let (a, b, d, c) = (self.a, self.b, self.d, self.c);
+        let a_minus_c = a.checked_sub(c)?;
+        let b_squared_d = b.checked_mul(b)?.checked_mul(d)?;
+        let numerator_rational = a.checked_mul(a_minus_c)?.checked_sub(b_squared_d)?;
+        let numerator_irrational = b.checked_mul(c)?.checked_neg()?;
+        let denominator = a_minus_c.checked_mul(a_minus_c)?.checked_sub(b_squared_d)?;
+        Self::new(numerator_rational, numerator_irrational, d, denominator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn floor_matches_integer_square_roots() {
+        for d in 2..200i128 {
+            if let Some(root) = QuadraticIrrational::sqrt(d) {
+                for n in -50..50i128 {
+                    let expected = if n >= 0 {
+                        ((n * n * d) as u128).isqrt() as i128
+                    } else {
+                        -(((n * n * d) as u128).isqrt() as i128) - 1
+                    };
+                    assert_eq!(root.floor_of_multiple(n), Some(expected));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn normalization_and_complement() {
+        let phi = QuadraticIrrational::golden_ratio();
+        assert_eq!(
+            QuadraticIrrational::new(-2, -2, 5, -4).unwrap().parts(),
+            phi.parts()
+        );
+        // φ / (φ − 1) = φ² = (3 + √5) / 2.
+        assert_eq!(phi.complement().unwrap().parts(), (3, 1, 5, 2));
+        assert_eq!(
+            QuadraticIrrational::sqrt(2)
+                .unwrap()
+                .complement()
+                .unwrap()
+                .parts(),
+            (2, 1, 2, 1)
+        );
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        let root = QuadraticIrrational::sqrt(3).unwrap();
+        assert_eq!(root.floor_of_multiple(i128::MAX), None);
+        assert!(root.floor_of_multiple(1 << 62).is_some());
+    }
+
+    #[test]
+    fn extreme_parts_are_normalized() {
+        assert!(QuadraticIrrational::new(i128::MIN, 1, 2, -1).is_none());
+        assert!(QuadraticIrrational::new(1, i128::MIN, 2, -1).is_none());
+        assert!(QuadraticIrrational::new(1, 1, 2, i128::MIN).is_none());
+        assert_eq!(
+            QuadraticIrrational::new(i128::MIN, 2, 2, -2)
+                .unwrap()
+                .parts(),
+            (1 << 126, -1, 2, 1)
+        );
+        assert_eq!(
+            QuadraticIrrational::new(i128::MIN, i128::MIN, 2, i128::MIN)
+                .unwrap()
+                .parts(),
+            (1, 1, 2, 1)
+        );
+        assert_eq!(
+            QuadraticIrrational::new(i128::MIN, 1, 2, 1)
+                .unwrap()
+                .parts(),
+            (i128::MIN, 1, 2, 1)
+        );
+    }
+}
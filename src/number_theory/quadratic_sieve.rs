@@ -0,0 +1,639 @@
+//! The self-initializing quadratic sieve, for splitting composites up to
+//! 2<sup>128</sup> whose factors are too large for `pollard_rho`.
+//!
+//! The sieve looks for many x whose Q(x) = ((ax + b)<sup>2</sup> − kn) / a
+//! have only small prime factors, from a factor base of the primes p for
+//! which kn is a square modulo p. Each such x gives a relation
+//! (ax + b)<sup>2</sup> ≡ a Q(x) (mod n), and once there are more relations
+//! than primes in the factor base, some of them multiply together to a
+//! congruence of squares X<sup>2</sup> ≡ Y<sup>2</sup> (mod n), where
+//! gcd(X − Y, n) is a factor of n half the time or so.
+//!
+//! The values of x with smooth Q(x) are found by sieving: for each prime p in
+//! the factor base, Q(x) is divisible by p exactly when x is one of two
+//! residues modulo p, so log p is added at those x throughout an interval,
+//! and the x where the sums come near log |Q(x)| are then factored by trial
+//! division. Self-initialization picks a as a product of s primes from the
+//! factor base, which gives 2<sup>s − 1</sup> values of b, and each switch of
+//! b only takes one addition per prime to update the residues. A value of
+//! Q(x) that is left with one prime a little past the factor base is kept
+//! too, and two of those with the same prime make a relation. The
+//! multiplier k is picked by the Knuth–Schroeppel function so that many
+//! small primes are in the factor base.
+
+use crate::number_theory::primes::{jacobi, power, Modular, Montgomery, Small};
+use crate::number_theory::{ExtendedGcd, Gcd};
+
+/// Returns an `Option` containing either a nontrivial factor of n or `None`
+/// if n is prime or less than four, found by the self-initializing quadratic
+/// sieve.
+///
+/// The running time grows as about exp(√(ln n ln ln n)), so unlike
+/// `pollard_rho` it does not depend on the size of the factors, and it is
+/// the quicker of the two for products of two primes from about
+/// 2<sup>80</sup> on. It finds the factors of a product of two 64-bit
+/// primes in well under a second when compiled with optimizations. Numbers
+/// below 2<sup>40</sup> are too small to sieve and are passed on to
+/// `pollard_rho`, and perfect powers give their smallest root.
+///
+/// The factor that turns up is not necessarily prime or the smallest one.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::quadratic_sieve::quadratic_sieve;
+///
+/// let (p, q) = (1_000_000_000_039u128, 1_000_000_000_000_000_003u128);
+/// let d = quadratic_sieve(p * q).unwrap();
+/// assert!(d == p || d == q);
+///
+/// assert_eq!(quadratic_sieve(q), None);
+/// assert_eq!(quadratic_sieve(q * q), Some(q));
+/// ```
+pub fn quadratic_sieve(n: u128) -> Option<u128> {
+    if n < 4 || crate::number_theory::primes::is_prime(n) {
+        return None;
+    }
+    if n.is_multiple_of(2) {
+        return Some(2);
+    }
+    if let Some(root) = perfect_power(n) {
+        return Some(root);
+    }
+    if n >> 40 == 0 {
+        return crate::number_theory::factorization::pollard_rho(n);
+    }
+    match <u64 as core::convert::TryFrom<u128>>::try_from(n) {
+        Ok(n) => Some(siqs(&Small { n })),
+        Err(_) => Some(siqs(&Montgomery::new(n))),
+    }
+}
+
+/// Returns the smallest r with r<sup>j</sup> = n for some j ≥ 2, if there is
+/// one.
+fn perfect_power(n: u128) -> Option<u128> {
+    let bits = 128 - n.leading_zeros();
+    (2..bits).rev().find_map(|j| {
+        let estimate = if j == 2 {
+            n.isqrt()
+        } else {
+            (n as f64).powf(1.0 / j as f64).round() as u128
+        };
+        (estimate.saturating_sub(1)..=estimate + 1).find(|&r| r > 1 && r.checked_pow(j) == Some(n))
+    })
+}
+
+/// A leftover prime from trial division is kept if it is below this multiple
+/// of the largest prime in the factor base.
+const LARGE_MULTIPLE: u128 = 40;
+
+/// The number of primes in the factor base and half the width of the sieve
+/// interval for numbers of the given number of bits, with the number of
+/// primes interpolated between the rows of the table.
+fn parameters(bits: u32) -> (usize, usize) {
+    const TABLE: [(u32, usize, usize); 6] = [
+        (40, 40, 2048),
+        (64, 100, 8192),
+        (80, 180, 16_384),
+        (96, 260, 32_768),
+        (112, 360, 32_768),
+        (128, 500, 32_768),
+    ];
+    let i = TABLE.iter().rposition(|row| row.0 <= bits).unwrap_or(0);
+    let (low, high) = (TABLE[i], TABLE[(i + 1).min(TABLE.len() - 1)]);
+    let primes = if high.0 == low.0 {
+        low.1
+    } else {
+        low.1 + (high.1 - low.1) * (bits - low.0) as usize / (high.0 - low.0) as usize
+    };
+    (primes, low.2)
+}
+
+/// Returns the multiplier k from the odd squarefree numbers below 64 that
+/// has the largest Knuth–Schroeppel value, which measures how much the small
+/// primes of the resulting factor base are expected to contribute.
+fn multiplier(n: u128) -> u64 {
+    const MULTIPLIERS: [u64; 26] = [
+        1, 3, 5, 7, 11, 13, 15, 17, 19, 21, 23, 29, 31, 33, 35, 37, 39, 41, 43, 47, 51, 53, 55, 57,
+        59, 61,
+    ];
+    let primes: Vec<u64> = crate::number_theory::sieve::primes()
+        .skip(1)
+        .take_while(|&p| p < 1000)
+        .collect();
+    let value = |k: u64| {
+        let mut value = -0.5 * (k as f64).ln();
+        value += core::f64::consts::LN_2
+            * match (k as u128 * (n % 8)) % 8 {
+                1 => 2.0,
+                5 => 1.0,
+                _ => 0.5,
+            };
+        for &p in &primes {
+            let contribution = (p as f64).ln() / (p - 1) as f64;
+            if k.is_multiple_of(p) {
+                value += contribution;
+            } else if legendre((k % p) * (n % p as u128) as u64 % p, p) == 1 {
+                value += 2.0 * contribution;
+            }
+        }
+        value
+    };
+    let mut best = (1, value(1));
+    for &k in &MULTIPLIERS[1..] {
+        let v = value(k);
+        if v > best.1 {
+            best = (k, v);
+        }
+    }
+    best.0
+}
+
+/// kn as its high and low halves.
+#[derive(Clone, Copy)]
+struct Wide {
+    high: u128,
+    low: u128,
+}
+
+impl Wide {
+    fn new(n: u128, k: u64) -> Self {
+        let (k, mask) = (k as u128, u64::MAX as u128);
+        let low = (n & mask) * k;
+        let high = (n >> 64) * k + (low >> 64);
+        Self {
+            high: high >> 64,
+            low: high << 64 | (low & mask),
+        }
+    }
+
+    /// Returns self mod m.
+    fn rem(self, m: u64) -> u64 {
+        let m = m as u128;
+        let high = (self.high % m) << 64 | self.low >> 64;
+        (((high % m) << 64 | (self.low & u64::MAX as u128)) % m) as u64
+    }
+
+    /// Returns (self − x) / a, which must be exact, where x must not be more
+    /// than self.
+    fn sub_div(self, x: u128, a: u64) -> u128 {
+        let (low, borrow) = self.low.overflowing_sub(x);
+        let high = self.high - borrow as u128;
+        let a = a as u128;
+        let mut remainder = high % a;
+        let mut quotient = 0;
+        for limb in [low >> 64, low & u64::MAX as u128] {
+            let current = remainder << 64 | limb;
+            quotient = (quotient << 64) | (current / a);
+            remainder = current % a;
+        }
+        debug_assert_eq!(remainder, 0);
+        quotient
+    }
+
+    fn as_f64(self) -> f64 {
+        self.high as f64 * 2f64.powi(128) + self.low as f64
+    }
+}
+
+/// Returns the Legendre symbol (a/p) for an odd prime p.
+fn legendre(a: u64, p: u64) -> i32 {
+    jacobi(a.into(), p.into())
+}
+
+/// Returns a square root of a modulo the odd prime p, where a must be a
+/// square, by the Tonelli–Shanks algorithm.
+fn sqrt_mod(a: u64, p: u64) -> u64 {
+    let a = a % p;
+    if a == 0 {
+        return 0;
+    }
+    let (mut q, mut s) = (p - 1, 0);
+    while q.is_multiple_of(2) {
+        q /= 2;
+        s += 1;
+    }
+    let z = (2..).find(|&z| legendre(z, p) == -1).unwrap();
+    let modular = Small { n: p };
+    let (mut m, mut c, mut t, mut r) = (
+        s,
+        power(&modular, z, q.into()),
+        power(&modular, a, q.into()),
+        power(&modular, a, q.div_ceil(2).into()),
+    );
+    while t != 1 {
+        let mut i = 0;
+        let mut t_power = t;
+        while t_power != 1 {
+            t_power = modular.mul(t_power, t_power);
+            i += 1;
+        }
+        let b = power(&modular, c, 1 << (m - i - 1));
+        m = i;
+        c = modular.mul(b, b);
+        t = modular.mul(t, c);
+        r = modular.mul(r, b);
+    }
+    r
+}
+
+/// Returns the inverse of a modulo m, which must be coprime to it.
+fn inverse_mod(a: u64, m: u64) -> u64 {
+    // Neither value is i128::MIN, so the coefficients always fit.
+    let (_, x, _) = i128::extended_gcd(&((a % m) as i128), &(m as i128)).unwrap();
+    x.rem_euclid(m as i128) as u64
+}
+
+/// A prime p of the factor base, along with a square root of kn modulo p
+/// and the rounded base-two logarithm of p.
+#[derive(Clone, Copy)]
+struct BasePrime {
+    p: u64,
+    root: u64,
+    log: u8,
+}
+
+/// A relation (X<sub>1</sub> ⋯ X<sub>j</sub>)<sup>2</sup> ≡ L<sup>2</sup>
+/// times the product of the primes (mod n), with the primes given by their
+/// indices in the factor base, and index zero for −1.
+struct Relation {
+    lefts: Vec<u128>,
+    factors: Vec<usize>,
+    large: u128,
+}
+
+/// Returns a nontrivial factor of the modulus, which must be an odd
+/// composite of at least 2<sup>40</sup> that is not a perfect power.
+fn siqs<M: Modular>(modular: &M) -> u128
+where
+    M::Elem: Into<u128>,
+{
+    let n = modular.modulus();
+    let (primes, half_width) = parameters(128 - n.leading_zeros());
+    let k = multiplier(n);
+    let kn = Wide::new(n, k);
+
+    // The factor base, starting with 2 and then the odd primes where kn is a
+    // nonzero square, or zero from the multiplier. A prime factor of n
+    // found on the way is returned at once.
+    let mut base = vec![BasePrime {
+        p: 2,
+        root: kn.rem(2),
+        log: 1,
+    }];
+    for p in crate::number_theory::sieve::primes().skip(1) {
+        if base.len() == primes {
+            break;
+        }
+        if n.is_multiple_of(p as u128) {
+            return p as u128;
+        }
+        let residue = kn.rem(p);
+        if residue == 0 || legendre(residue, p) == 1 {
+            base.push(BasePrime {
+                p,
+                root: sqrt_mod(residue, p),
+                log: (p as f64).log2().round() as u8,
+            });
+        }
+    }
+    let largest = base.last().unwrap().p;
+    let large_bound = largest as u128 * LARGE_MULTIPLE;
+
+    // a should be near √(2kn) / M, so that |Q(x)| is at most about M √(kn / 2)
+    // over the interval, and is made from s primes of the factor base near
+    // the s-th root of that.
+    let target = (2.0 * kn.as_f64()).sqrt() / half_width as f64;
+    let s = (2..)
+        .find(|&s| target.powf(1.0 / s as f64) <= largest as f64 / 2.0)
+        .unwrap();
+    let ideal = target.powf(1.0 / s as f64);
+    let eligible = |prime: &BasePrime| prime.p > 2 && !k.is_multiple_of(prime.p);
+    let mut pool: Vec<usize> = (1..base.len())
+        .filter(|&i| eligible(&base[i]))
+        .filter(|&i| (ideal / 4.0..=ideal * 4.0).contains(&(base[i].p as f64)))
+        .collect();
+    if pool.len() < 2 * s + 2 {
+        pool = (1..base.len()).filter(|&i| eligible(&base[i])).collect();
+    }
+
+    // log2 |Q(x)| is about this over most of the interval, and a sum of logs
+    // that comes within the logs of a large prime and of the small primes,
+    // which are not sieved, is worth a trial division.
+    const SMALL: u64 = 30;
+    let log_q = (half_width as f64 * (kn.as_f64() / 2.0).sqrt()).log2();
+    let threshold = (log_q - (large_bound as f64).log2() - 4.0).max(1.0) as u8;
+
+    let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+    let mut random = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed
+    };
+    let mut used_a = std::collections::HashSet::new();
+    let mut relations = Vec::new();
+    let mut partials = std::collections::HashMap::new();
+    let mut sieve = vec![0u8; 2 * half_width];
+    let mut roots = vec![(0, 0); base.len()];
+    let mut wanted = base.len() + 20;
+    loop {
+        // Choose a, with all but its last prime at random from the pool and
+        // the last one chosen to bring the product nearest the target.
+        let mut a_primes = Vec::new();
+        while a_primes.len() < s - 1 {
+            let i = pool[random() as usize % pool.len()];
+            if !a_primes.contains(&i) {
+                a_primes.push(i);
+            }
+        }
+        let partial: f64 = a_primes.iter().map(|&i| base[i].p as f64).product();
+        let with = |i: usize| {
+            let mut primes = a_primes.clone();
+            primes.push(i);
+            primes.sort_unstable();
+            primes
+        };
+        let last = (1..base.len())
+            .filter(|&i| eligible(&base[i]) && !a_primes.contains(&i))
+            .filter(|&i| !used_a.contains(&with(i)))
+            .min_by(|&i, &j| {
+                let distance = |i: usize| (partial * base[i].p as f64 / target).ln().abs();
+                distance(i).partial_cmp(&distance(j)).unwrap()
+            });
+        let a_primes = match last {
+            Some(last) => with(last),
+            None => continue,
+        };
+        used_a.insert(a_primes.clone());
+        let a: u64 = a_primes.iter().map(|&i| base[i].p).product();
+
+        // b² ≡ kn (mod a) for b = ±B_1 ± ⋯ ± B_s, where B_l is a multiple of
+        // a / q_l that is a square root of kn modulo q_l.
+        let b_parts: Vec<u64> = a_primes
+            .iter()
+            .map(|&i| {
+                let q = base[i].p;
+                let cofactor = a / q;
+                let mut gamma = Small { n: q }.mul(base[i].root, inverse_mod(cofactor % q, q));
+                if gamma > q / 2 {
+                    gamma = q - gamma;
+                }
+                cofactor * gamma
+            })
+            .collect();
+        let mut b: i128 = b_parts.iter().map(|&part| part as i128).sum();
+
+        // The roots of Q modulo each prime, as indices into the sieve, and
+        // the amounts by which each B_l moves them.
+        let mut steps = vec![Vec::new(); s];
+        for (i, prime) in base.iter().enumerate() {
+            let p = prime.p;
+            if p < SMALL || a.is_multiple_of(p) || k.is_multiple_of(p) {
+                continue;
+            }
+            let modular = Small { n: p };
+            let a_inverse = inverse_mod(a % p, p);
+            let b_mod = b.rem_euclid(p as i128) as u64;
+            let shift = half_width as u64 % p;
+            let root = |t: u64| (modular.mul(a_inverse, (t + p - b_mod) % p) + shift) % p;
+            roots[i] = (root(prime.root), root(p - prime.root));
+            for (l, &part) in b_parts.iter().enumerate() {
+                steps[l].push((i, modular.mul(2 * (part % p), a_inverse)));
+            }
+        }
+
+        for index in 0..1u64 << (s - 1) {
+            if index > 0 {
+                // Gray code order, so that each b differs from the last by
+                // 2B_l for one l.
+                let l = index.trailing_zeros() as usize;
+                let plus = (index >> l).div_ceil(2).is_multiple_of(2);
+                let delta = 2 * b_parts[l] as i128;
+                b += if plus { delta } else { -delta };
+                for &(i, step) in &steps[l] {
+                    let p = base[i].p;
+                    let (r1, r2) = roots[i];
+                    roots[i] = if plus {
+                        ((r1 + p - step) % p, (r2 + p - step) % p)
+                    } else {
+                        ((r1 + step) % p, (r2 + step) % p)
+                    };
+                }
+            }
+
+            // Q(x) = a x² + 2 b x + c.
+            let c = -(kn.sub_div((b * b) as u128, a) as i128);
+            sieve.iter_mut().for_each(|value| *value = 0);
+            for (i, prime) in base.iter().enumerate() {
+                if prime.p < SMALL || a.is_multiple_of(prime.p) || k.is_multiple_of(prime.p) {
+                    continue;
+                }
+                let p = prime.p as usize;
+                let (r1, r2) = roots[i];
+                for start in [r1 as usize, r2 as usize] {
+                    let mut j = start;
+                    while j < sieve.len() {
+                        sieve[j] = sieve[j].wrapping_add(prime.log);
+                        j += p;
+                    }
+                }
+            }
+
+            for (j, _) in sieve.iter().enumerate().filter(|(_, &v)| v >= threshold) {
+                let x = j as i128 - half_width as i128;
+                let value = (a as i128 * x + 2 * b) * x + c;
+                if value == 0 {
+                    continue;
+                }
+                let mut rest = value.unsigned_abs();
+                let mut factors: Vec<usize> = a_primes.iter().map(|&i| i + 1).collect();
+                if value < 0 {
+                    factors.push(0);
+                }
+                for (i, prime) in base.iter().enumerate() {
+                    let p = prime.p as u128;
+                    while rest.is_multiple_of(p) {
+                        rest /= p;
+                        factors.push(i + 1);
+                    }
+                }
+                let left = (a as i128 * x + b).unsigned_abs() % n;
+                if rest == 1 {
+                    relations.push(Relation {
+                        lefts: vec![left],
+                        factors,
+                        large: 1,
+                    });
+                } else if rest < large_bound {
+                    match partials.remove(&rest) {
+                        Some(Relation {
+                            lefts,
+                            factors: others,
+                            ..
+                        }) => {
+                            factors.extend(others);
+                            relations.push(Relation {
+                                lefts: vec![left, lefts[0]],
+                                factors,
+                                large: rest,
+                            });
+                        }
+                        None => {
+                            partials.insert(
+                                rest,
+                                Relation {
+                                    lefts: vec![left],
+                                    factors,
+                                    large: 1,
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+
+            if relations.len() >= wanted {
+                if let Some(d) = combine(modular, &base, &relations) {
+                    return d;
+                }
+                wanted += 20;
+            }
+        }
+    }
+}
+
+/// Returns an `Option` containing either a nontrivial factor of the modulus
+/// from a product of the relations that is a congruence of squares, or
+/// `None` if every such product gives a trivial one.
+fn combine<M: Modular>(modular: &M, base: &[BasePrime], relations: &[Relation]) -> Option<u128>
+where
+    M::Elem: Into<u128>,
+{
+    let n = modular.modulus();
+    let columns = base.len() + 1;
+    let words = columns.div_ceil(64);
+    let history_words = relations.len().div_ceil(64);
+
+    // Gaussian elimination over GF(2) on the parities of the exponents, with
+    // each row carrying the relations that it is the sum of.
+    let mut rows: Vec<(Vec<u64>, Vec<u64>)> = relations
+        .iter()
+        .enumerate()
+        .map(|(r, relation)| {
+            let mut bits = vec![0u64; words];
+            for &i in &relation.factors {
+                bits[i / 64] ^= 1 << (i % 64);
+            }
+            let mut history = vec![0u64; history_words];
+            history[r / 64] |= 1 << (r % 64);
+            (bits, history)
+        })
+        .collect();
+    let mut pivoted = vec![false; rows.len()];
+    for column in 0..columns {
+        let (word, bit) = (column / 64, 1 << (column % 64));
+        let pivot = match (0..rows.len()).find(|&r| !pivoted[r] && rows[r].0[word] & bit != 0) {
+            Some(pivot) => pivot,
+            None => continue,
+        };
+        pivoted[pivot] = true;
+        let (pivot_bits, pivot_history) = rows[pivot].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r != pivot && row.0[word] & bit != 0 {
+                row.0.iter_mut().zip(&pivot_bits).for_each(|(x, y)| *x ^= y);
+                row.1
+                    .iter_mut()
+                    .zip(&pivot_history)
+                    .for_each(|(x, y)| *x ^= y);
+            }
+        }
+    }
+
+    for (_, history) in rows
+        .iter()
+        .filter(|(bits, _)| bits.iter().all(|&word| word == 0))
+    {
+        let mut x = modular.elem(1);
+        let mut y = modular.elem(1);
+        let mut exponents = vec![0u32; columns];
+        for r in (0..relations.len()).filter(|&r| history[r / 64] >> (r % 64) & 1 == 1) {
+            let relation = &relations[r];
+            for &left in &relation.lefts {
+                x = modular.mul(x, modular.elem(left));
+            }
+            y = modular.mul(y, modular.elem(relation.large % n));
+            for &i in &relation.factors {
+                exponents[i] += 1;
+            }
+        }
+        for (i, &exponent) in exponents.iter().enumerate().skip(1) {
+            let p = modular.elem(base[i - 1].p as u128 % n);
+            y = modular.mul(y, power(modular, p, (exponent / 2) as u128));
+        }
+        let d = u128::gcd(&modular.sub(x, y).into(), &n);
+        if d != 1 && d != n {
+            return Some(d);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modular_helpers() {
+        let sieve = crate::number_theory::Sieve::new(2000);
+        for p in sieve.primes().skip(1) {
+            for a in 0..p {
+                let squares = (a * a) % p;
+                let root = sqrt_mod(squares, p);
+                assert_eq!(root * root % p, squares, "{} mod {}", a, p);
+                if a != 0 {
+                    assert_eq!(a * inverse_mod(a, p) % p, 1);
+                }
+            }
+        }
+        let n = u128::MAX / 3;
+        let kn = Wide::new(n, 61);
+        for m in [3, 1000, 4_294_967_291, u64::MAX] {
+            let expected = (n % m as u128) * 61 % m as u128;
+            assert_eq!(kn.rem(m) as u128, expected);
+        }
+        // 61n − 61 × 7 = 61 (n − 7).
+        assert_eq!(kn.sub_div(61 * 7, 61), n - 7);
+    }
+
+    #[test]
+    fn perfect_powers() {
+        assert_eq!(perfect_power(1 << 126), Some(2));
+        assert_eq!(perfect_power(3u128.pow(80)), Some(3));
+        let p = 18_446_744_073_709_551_557u128;
+        assert_eq!(perfect_power(p * p), Some(p));
+        assert_eq!(perfect_power(p * p - 1), None);
+        assert_eq!(perfect_power(1_000_003u128.pow(5)), Some(1_000_003));
+        assert_eq!(perfect_power(36u128.pow(3)), Some(6));
+    }
+
+    #[test]
+    fn splits_semiprimes() {
+        for &(p, q) in &[
+            (1_048_583u128, 1_099_511_627_791u128),
+            (4_294_967_291, 4_294_967_311),
+            (1_000_000_000_039, 1_000_000_000_000_000_003),
+            (4_398_046_511_093, 1_099_511_627_689),
+        ] {
+            let d = quadratic_sieve(p * q).unwrap();
+            assert!(d == p || d == q, "{} × {}", p, q);
+        }
+        for n in (1u128 << 40..1 << 41).step_by(1 << 34).map(|n| n | 1) {
+            if let Some(d) = quadratic_sieve(n) {
+                assert!(d > 1 && d < n && n % d == 0, "n = {}", n);
+            } else {
+                assert!(crate::number_theory::primes::is_prime(n));
+            }
+        }
+    }
+}
@@ -0,0 +1,267 @@
+//! Random primes of a given size, drawn with a `rug::rand::RandState`.
+//!
+//! Each candidate must pass `Prime::is_prime`, the Baillie–PSW test, and then
+//! `rounds` further Miller–Rabin tests to random bases. Baillie–PSW has no
+//! known counterexample, so `rounds` only matters to those who want a bound
+//! on the chance of error that holds even if one exists, at 4<sup>−rounds</sup>
+//! per candidate.
+//!
+//! Nothing here is constant-time, so these should not be used to make keys
+//! where an attacker can time the generation.
+
+use crate::number_theory::Prime;
+use rug::{rand::RandState, Integer};
+
+/// Returns a random prime with exactly the given number of bits.
+///
+/// # Panics
+///
+/// Panics if `bits` is less than two.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::random_primes::random_prime;
+/// use mathematical::number_theory::Prime;
+/// use rug::rand::RandState;
+///
+/// let mut rng = RandState::new();
+/// let p = random_prime(256, 0, &mut rng);
+/// assert_eq!(p.significant_bits(), 256);
+/// assert!(rug::Integer::is_prime(&p));
+/// ```
+pub fn random_prime(bits: u32, rounds: u32, rng: &mut RandState) -> Integer {
+    assert!(bits >= 2, "bits must be at least two");
+    if bits == 2 {
+        return Integer::from(2).random_below(rng) + 2u8;
+    }
+    loop {
+        let candidate = random_odd(bits, rng);
+        if passes(&candidate, rounds, rng) {
+            return candidate;
+        }
+    }
+}
+
+/// Returns a random safe prime with exactly the given number of bits, that
+/// is, a prime p for which (p − 1)/2 is also prime.
+///
+/// The Sophie Germain prime q = (p − 1)/2 must be 5 modulo 6 once p is past
+/// 7, as otherwise 3 divides q or p, so only such q are tried, and p is
+/// checked for small factors before q is tested in full.
+///
+/// # Panics
+///
+/// Panics if `bits` is less than three.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::random_primes::random_safe_prime;
+/// use mathematical::number_theory::Prime;
+/// use rug::rand::RandState;
+///
+/// let mut rng = RandState::new();
+/// let p = random_safe_prime(128, 2, &mut rng);
+/// assert_eq!(p.significant_bits(), 128);
+/// assert!(rug::Integer::is_prime(&p));
+/// assert!(rug::Integer::is_prime(&(p >> 1)));
+/// ```
+pub fn random_safe_prime(bits: u32, rounds: u32, rng: &mut RandState) -> Integer {
+    assert!(bits >= 3, "bits must be at least three");
+    if bits <= 4 {
+        // 5 and 7, then 11, as 13 and 15 do not qualify.
+        let choices: &[u8] = if bits == 3 { &[5, 7] } else { &[11] };
+        let index = Integer::from(choices.len()).random_below(rng);
+        return Integer::from(choices[index.to_usize().unwrap()]);
+    }
+    loop {
+        let mut q = random_odd(bits - 1, rng);
+        // Round q up to 5 modulo 6, staying within its number of bits.
+        q += (11 - q.mod_u(6)) % 6;
+        if q.significant_bits() != bits - 1 {
+            continue;
+        }
+        let p = Integer::from(&q << 1) + 1u8;
+        if SMALL_PRIMES.iter().any(|&r| p.is_divisible_u(r) && p != r) {
+            continue;
+        }
+        if passes(&q, rounds, rng) && passes(&p, rounds, rng) {
+            return p;
+        }
+    }
+}
+
+/// Returns a random strong prime with exactly the given number of bits, in
+/// the sense of Gordon: a prime p for which p − 1 has a large prime factor
+/// r, p + 1 has a large prime factor s, and r − 1 has a large prime factor t.
+///
+/// Following Gordon's algorithm, s and t are random primes of a little under
+/// half the bits, r is the first prime of the form 2it + 1, and p is the
+/// first prime of the form p<sub>0</sub> + 2jrs with the right number of
+/// bits, where p<sub>0</sub> = 2(s<sup>r − 2</sup> mod r)s − 1 is 1 modulo r
+/// and −1 modulo s.
+///
+/// # Panics
+///
+/// Panics if `bits` is less than 32.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::random_primes::random_strong_prime;
+/// use mathematical::number_theory::Prime;
+/// use rug::rand::RandState;
+///
+/// let mut rng = RandState::new();
+/// let p = random_strong_prime(256, 0, &mut rng);
+/// assert_eq!(p.significant_bits(), 256);
+/// assert!(rug::Integer::is_prime(&p));
+/// ```
+pub fn random_strong_prime(bits: u32, rounds: u32, rng: &mut RandState) -> Integer {
+    assert!(bits >= 32, "bits must be at least 32");
+    // r s takes up all but about 12 bits, which leaves room for j.
+    let (s_bits, t_bits) = (bits / 2 - 6, bits / 2 - 12);
+    loop {
+        let s = random_prime(s_bits, rounds, rng);
+        let t = random_prime(t_bits, rounds, rng);
+
+        let step = Integer::from(&t << 1);
+        let mut r = Integer::from(&step + 1u8);
+        while !passes(&r, rounds, rng) {
+            r += &step;
+        }
+
+        let exponent = Integer::from(&r - 2u8);
+        let inverse = Integer::from(s.pow_mod_ref(&exponent, &r).unwrap());
+        let p0 = Integer::from(&inverse * &s) * 2u8 - 1u8;
+        let step = Integer::from(&r * &s) * 2u8;
+
+        // The first p0 + j step with the top bit set, then onwards while
+        // the bits stay the same.
+        let bottom = Integer::from(1) << (bits - 1);
+        let mut p = p0.clone();
+        if p < bottom {
+            let j = Integer::from(&bottom - &p0).div_rem_ceil(step.clone()).0;
+            p += j * &step;
+        }
+        while p.significant_bits() == bits {
+            if passes(&p, rounds, rng) {
+                return p;
+            }
+            p += &step;
+        }
+    }
+}
+
+const SMALL_PRIMES: [u32; 10] = [3, 5, 7, 11, 13, 17, 19, 23, 29, 31];
+
+/// Returns a random odd number with exactly the given number of bits, which
+/// must be at least two.
+fn random_odd(bits: u32, rng: &mut RandState) -> Integer {
+    let mut n = Integer::from(Integer::random_bits(bits, rng));
+    n.set_bit(bits - 1, true);
+    n.set_bit(0, true);
+    n
+}
+
+/// Returns whether n passes the Baillie–PSW test and the given number of
+/// Miller–Rabin tests to random bases.
+fn passes(n: &Integer, rounds: u32, rng: &mut RandState) -> bool {
+    if !Integer::is_prime(n) {
+        return false;
+    }
+    if rounds == 0 || *n < 5 {
+        return true;
+    }
+    // n − 1 = d 2^s.
+    let minus_one = Integer::from(n - 1u8);
+    let s = minus_one.find_one(0).unwrap();
+    let d = Integer::from(&minus_one >> s);
+    let below = Integer::from(n - 3u8);
+    (0..rounds).all(|_| {
+        let base = Integer::from(below.random_below_ref(rng)) + 2u8;
+        let mut x = base.pow_mod(&d, n).unwrap();
+        if x == 1 || x == minus_one {
+            return true;
+        }
+        for _ in 1..s {
+            x.square_mut();
+            x.modulo_mut(n);
+            if x == minus_one {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rug::integer::IsPrime;
+
+    fn is_prime(n: &Integer) -> bool {
+        n.is_probably_prime(40) != IsPrime::No
+    }
+
+    #[test]
+    fn random_primes() {
+        let mut rng = RandState::new();
+        for bits in (2..70).chain([127, 128, 500]) {
+            for _ in 0..5 {
+                let p = random_prime(bits, 3, &mut rng);
+                assert_eq!(p.significant_bits(), bits);
+                assert!(is_prime(&p), "p = {}", p);
+            }
+        }
+    }
+
+    #[test]
+    fn safe_primes() {
+        let mut rng = RandState::new();
+        for bits in (3..40).chain([64, 200]) {
+            let p = random_safe_prime(bits, 1, &mut rng);
+            assert_eq!(p.significant_bits(), bits, "p = {}", p);
+            assert!(
+                is_prime(&p) && is_prime(&Integer::from(&p >> 1)),
+                "p = {}",
+                p
+            );
+        }
+    }
+
+    #[test]
+    fn strong_primes() {
+        let mut rng = RandState::new();
+        for bits in [32, 33, 64, 100, 256, 512] {
+            let p = random_strong_prime(bits, 1, &mut rng);
+            assert_eq!(p.significant_bits(), bits, "p = {}", p);
+            assert!(is_prime(&p));
+            if bits < 64 {
+                continue;
+            }
+            // p − 1 and p + 1 each have a prime factor at least as large as
+            // t, well past the trial division.
+            for neighbor in [Integer::from(&p - 1u8), Integer::from(&p + 1u8)] {
+                let mut rest = neighbor.clone();
+                for q in 2u32..1000 {
+                    while rest.is_divisible_u(q) {
+                        rest /= q;
+                    }
+                }
+                assert!(rest.significant_bits() > bits / 2 - 12, "p = {}", p);
+            }
+        }
+    }
+
+    #[test]
+    fn miller_rabin_rounds() {
+        let mut rng = RandState::new();
+        // A strong pseudoprime to the bases 2, 3, 5, and 7.
+        let carmichael = Integer::from(3_215_031_751u64);
+        assert!(!passes(&carmichael, 10, &mut rng));
+        let p = Integer::from(1_000_000_007);
+        assert!(passes(&p, 20, &mut rng));
+    }
+}
@@ -0,0 +1,948 @@
+/// The primes up to a limit, found by the sieve of Eratosthenes.
+///
+/// Only the odd numbers are stored, one bit each, so sieving up to 10⁹ takes
+/// about 60 MB. The sieving is done in blocks that fit in the cache, each
+/// crossed off by the primes up to the square root of the limit, and with
+/// the `parallel` feature the blocks, along with the counting in `count`,
+/// are split between a thread for each core. The sieve of Atkin is available
+/// instead through `Sieve::atkin`.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::Sieve;
+///
+/// let sieve = Sieve::new(100);
+/// assert_eq!(sieve.count(), 25);
+/// assert!(sieve.is_prime(97));
+/// assert!(!sieve.is_prime(91));
+/// let primes: Vec<_> = sieve.primes().take(10).collect();
+/// assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Sieve {
+    limit: u64,
+    // Bit i is set when 2i + 1 is prime.
+    bits: Vec<u64>,
+}
+
+impl Sieve {
+    /// Returns the primes up to and including the limit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bits for the odd numbers up to the limit would not fit
+    /// in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Sieve;
+    ///
+    /// assert_eq!(Sieve::new(1_000_000).count(), 78_498);
+    /// assert_eq!(Sieve::new(1).count(), 0);
+    /// ```
+    pub fn new(limit: u64) -> Self {
+        // The odd numbers 1, 3, …, up to the limit.
+        let len = <usize as core::convert::TryFrom<u64>>::try_from(limit / 2 + limit % 2)
+            .expect("sieve limit too large");
+        let mut bits = vec![u64::MAX; len.div_ceil(64)];
+        if !len.is_multiple_of(64) {
+            *bits.last_mut().unwrap() = (1 << (len % 64)) - 1;
+        }
+        if let Some(first) = bits.first_mut() {
+            // One is not prime.
+            *first &= !1;
+        }
+
+        // The odd primes up to the square root, from a plain sieve over the
+        // start of the bits.
+        let root = limit.isqrt() as usize;
+        let mut base = Vec::new();
+        let mut i = 1;
+        while 2 * i < root {
+            if bits[i / 64] >> (i % 64) & 1 == 1 {
+                let p = 2 * i + 1;
+                base.push(p);
+                let mut j = (p * p) / 2;
+                while 2 * j < root {
+                    bits[j / 64] &= !(1 << (j % 64));
+                    j += p;
+                }
+            }
+            i += 1;
+        }
+
+        // Then the rest a block at a time.
+        #[cfg(feature = "parallel")]
+        cross_off_parallel(&mut bits, &base);
+        #[cfg(not(feature = "parallel"))]
+        cross_off(&mut bits, 0, &base);
+
+        Self { limit, bits }
+    }
+
+    /// Returns the primes up to and including the limit, found by the sieve
+    /// of Atkin rather than that of Eratosthenes.
+    ///
+    /// The odd squarefree numbers with an odd number of representations by
+    /// 4x² + y², 3x² + y², or 3x² − y² with x > y, each chosen by the
+    /// residue modulo 12, are the primes from five on. This takes time
+    /// linear in the limit rather than the n log log n of `new`, though its
+    /// scattered writes make it slower in practice at most sizes. The result
+    /// is the same and is stored the same way.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bits for the odd numbers up to the limit would not fit
+    /// in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Sieve;
+    ///
+    /// let sieve = Sieve::atkin(1_000_000);
+    /// assert_eq!(sieve.count(), 78_498);
+    /// assert!(sieve.primes().eq(Sieve::new(1_000_000).primes()));
+    /// ```
+    pub fn atkin(limit: u64) -> Self {
+        let len = <usize as core::convert::TryFrom<u64>>::try_from(limit / 2 + limit % 2)
+            .expect("sieve limit too large");
+        let mut bits = vec![0u64; len.div_ceil(64)];
+        let flip = |bits: &mut [u64], n: u64| {
+            let i = (n / 2) as usize;
+            bits[i / 64] ^= 1 << (i % 64);
+        };
+
+        // Each form in turn, with x and y running over the values that keep
+        // it within the limit.
+        let mut x = 1u64;
+        while 4 * x * x < limit {
+            let mut y = 1u64;
+            while 4 * x * x + y * y <= limit {
+                let n = 4 * x * x + y * y;
+                if n % 12 == 1 || n % 12 == 5 {
+                    flip(&mut bits, n);
+                }
+                y += 1;
+            }
+            x += 1;
+        }
+        let mut x = 1u64;
+        while 3 * x * x < limit {
+            let mut y = 1u64;
+            while 3 * x * x + y * y <= limit {
+                let n = 3 * x * x + y * y;
+                if n % 12 == 7 {
+                    flip(&mut bits, n);
+                }
+                y += 1;
+            }
+            x += 1;
+        }
+        // The smallest value of 3x² − y² for y < x is 2x² + 2x − 1.
+        let mut x = 2u64;
+        while 2 * x * x + 2 * x - 1 <= limit {
+            let x3 = 3 * x * x;
+            let mut y = if x3 > limit {
+                (x3 - limit - 1).isqrt() + 1
+            } else {
+                1
+            };
+            while y < x {
+                let n = x3 - y * y;
+                if n % 12 == 11 {
+                    flip(&mut bits, n);
+                }
+                y += 1;
+            }
+            x += 1;
+        }
+
+        // Remove the multiples of the squares of the primes found.
+        let mut r = 5;
+        while r * r <= limit {
+            let i = (r / 2) as usize;
+            if bits[i / 64] >> (i % 64) & 1 == 1 {
+                // The odd multiples of r².
+                let square = r * r;
+                let mut multiple = square;
+                while multiple <= limit {
+                    let i = (multiple / 2) as usize;
+                    bits[i / 64] &= !(1 << (i % 64));
+                    multiple += 2 * square;
+                }
+            }
+            r += 2;
+        }
+        if limit >= 3 {
+            bits[0] |= 1 << 1;
+        }
+
+        Self { limit, bits }
+    }
+
+    /// Returns the limit that the primes were sieved up to.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Returns whether i is prime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if i is larger than the limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Sieve;
+    ///
+    /// let sieve = Sieve::new(1000);
+    /// let primes: Vec<_> = (990..=1000).filter(|&i| sieve.is_prime(i)).collect();
+    /// assert_eq!(primes, vec![991, 997]);
+    /// assert!(sieve.is_prime(2));
+    /// assert!(!sieve.is_prime(0));
+    /// ```
+    pub fn is_prime(&self, i: u64) -> bool {
+        assert!(i <= self.limit, "i must be at most the limit");
+        if i.is_multiple_of(2) {
+            return i == 2;
+        }
+        let i = (i / 2) as usize;
+        self.bits[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    /// Returns an `Iterator<Item = u64>` implementation that goes through
+    /// the primes up to the limit in increasing order.
+    pub fn primes(&self) -> SievePrimes<'_> {
+        SievePrimes {
+            two: self.limit >= 2,
+            bits: &self.bits,
+            index: 0,
+            word: self.bits.first().copied().unwrap_or(0),
+        }
+    }
+
+    /// Returns the bitset whose bit i is set exactly when 2i + 1 is a prime
+    /// no larger than the limit.
+    pub(crate) fn odd_bits(&self) -> &[u64] {
+        &self.bits
+    }
+
+    /// Returns the number of primes up to the limit.
+    pub fn count(&self) -> usize {
+        #[cfg(feature = "parallel")]
+        let odd = count_ones_parallel(&self.bits);
+        #[cfg(not(feature = "parallel"))]
+        let odd = count_ones(&self.bits);
+        odd + (self.limit >= 2) as usize
+    }
+}
+
+/// The number of bits sieved at a time, which fits in the cache.
+const BLOCK: usize = 1 << 18;
+
+/// Crosses the odd multiples of the base primes from their squares on off
+/// the words, whose first bit is bit `start` of the sieve, a block at a time.
+fn cross_off(words: &mut [u64], start: usize, base: &[usize]) {
+    for (k, block) in words.chunks_mut(BLOCK / 64).enumerate() {
+        let begin = start + k * BLOCK;
+        let end = begin + block.len() * 64;
+        for &p in base {
+            // Bit j is for 2j + 1, an odd multiple of p exactly when
+            // j ≡ (p − 1)/2 modulo p.
+            let mut j = (p * p / 2).max(begin + (p / 2 + p - begin % p) % p);
+            while j < end {
+                block[(j - begin) / 64] &= !(1 << ((j - begin) % 64));
+                j += p;
+            }
+        }
+    }
+}
+
+/// Crosses off the words as `cross_off` does, with the blocks split evenly
+/// between one thread for each available core.
+#[cfg(any(feature = "parallel", test))]
+fn cross_off_parallel(bits: &mut [u64], base: &[usize]) {
+    let words = BLOCK / 64 * bits.len().div_ceil(BLOCK / 64).div_ceil(threads()).max(1);
+    std::thread::scope(|scope| {
+        for (k, chunk) in bits.chunks_mut(words).enumerate() {
+            scope.spawn(move || cross_off(chunk, 64 * words * k, base));
+        }
+    });
+}
+
+fn count_ones(words: &[u64]) -> usize {
+    words.iter().map(|word| word.count_ones() as usize).sum()
+}
+
+/// Returns `count_ones` of the words, with the words split evenly between
+/// one thread for each available core.
+#[cfg(any(feature = "parallel", test))]
+fn count_ones_parallel(words: &[u64]) -> usize {
+    let chunk = words.len().div_ceil(threads()).max(1);
+    std::thread::scope(|scope| {
+        let counts: Vec<_> = words
+            .chunks(chunk)
+            .map(|chunk| scope.spawn(move || count_ones(chunk)))
+            .collect();
+        counts.into_iter().map(|count| count.join().unwrap()).sum()
+    })
+}
+
+/// Returns the number of threads to split work between.
+#[cfg(any(feature = "parallel", test))]
+pub(crate) fn threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// The iterator returned by `Sieve::primes`.
+#[derive(Clone, Debug)]
+pub struct SievePrimes<'a> {
+    two: bool,
+    bits: &'a [u64],
+    // The index of the current word and its bits that are yet to be used.
+    index: usize,
+    word: u64,
+}
+
+impl Iterator for SievePrimes<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.two {
+            self.two = false;
+            return Some(2);
+        }
+        while self.word == 0 {
+            self.index += 1;
+            self.word = *self.bits.get(self.index)?;
+        }
+        let bit = self.word.trailing_zeros() as u64;
+        self.word &= self.word - 1;
+        Some(2 * (64 * self.index as u64 + bit) + 1)
+    }
+}
+
+impl core::iter::FusedIterator for SievePrimes<'_> {}
+
+/// Returns an `Iterator<Item = u64>` implementation that goes through all of
+/// the primes in increasing order, ending after the largest one below
+/// 2<sup>64</sup>.
+///
+/// The odd numbers are sieved a segment of 2<sup>18</sup> at a time by the
+/// primes up to the square root of the end of the segment, which come from a
+/// `Sieve` that is replaced by one twice as large whenever it runs short. So
+/// going through the primes up to n takes memory proportional to
+/// √n rather than to n. With the `parallel` feature, a segment for each core
+/// is sieved at once, each on its own thread.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::sieve::primes;
+///
+/// let terms: Vec<_> = primes().take(10).collect();
+/// assert_eq!(terms, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+/// assert_eq!(primes().nth(999_999), Some(15_485_863));
+/// ```
+pub fn primes() -> Primes {
+    #[cfg(feature = "parallel")]
+    let segments = threads();
+    #[cfg(not(feature = "parallel"))]
+    let segments = 1;
+    Primes::with_segments(segments)
+}
+
+/// The iterator returned by `primes`.
+#[derive(Clone, Debug)]
+pub struct Primes {
+    two: bool,
+    // The number of segments sieved at once, each on its own thread.
+    segments: usize,
+    // The odd primes up to the limit of the sieve that found them.
+    base: Vec<u64>,
+    base_limit: u64,
+    // The first number of the current segments, whose bit k is set when
+    // low + 2k is prime, or `None` before the first segments.
+    low: Option<u64>,
+    bits: Vec<u64>,
+    index: usize,
+    word: u64,
+}
+
+impl Primes {
+    const SEGMENT: u64 = 1 << 18;
+
+    fn with_segments(segments: usize) -> Self {
+        Self {
+            two: true,
+            segments,
+            base: Vec::new(),
+            base_limit: 0,
+            low: None,
+            bits: Vec::new(),
+            index: 0,
+            word: 0,
+        }
+    }
+
+    /// Sieves the segments after the current ones, returning `false` if
+    /// there are none.
+    fn advance(&mut self) -> bool {
+        let width = 2 * Self::SEGMENT * self.segments as u64;
+        let low = match self.low {
+            None => 1,
+            Some(low) => match low.checked_add(width) {
+                Some(low) => low,
+                None => return false,
+            },
+        };
+        // The odd numbers from low up to and including high.
+        let high = low.saturating_add(width - 2);
+        let len = ((high - low) / 2 + 1) as usize;
+
+        let root = high.isqrt();
+        if self.base_limit < root {
+            self.base_limit = root.max(2 * self.base_limit);
+            self.base = Sieve::new(self.base_limit).primes().skip(1).collect();
+        }
+        let base = &self.base[..self.base.partition_point(|&p| p <= root)];
+
+        let bits = &mut self.bits;
+        bits.clear();
+        bits.resize(len.div_ceil(64), u64::MAX);
+        if !len.is_multiple_of(64) {
+            *bits.last_mut().unwrap() = (1 << (len % 64)) - 1;
+        }
+        if low == 1 {
+            bits[0] &= !1;
+        }
+        if self.segments > 1 {
+            let words = (Self::SEGMENT / 64) as usize;
+            std::thread::scope(|scope| {
+                for (k, chunk) in bits.chunks_mut(words).enumerate() {
+                    let start = low + 2 * Self::SEGMENT * k as u64;
+                    scope.spawn(move || cross_off_segment(chunk, start, base));
+                }
+            });
+        } else {
+            cross_off_segment(bits, low, base);
+        }
+
+        self.low = Some(low);
+        self.index = 0;
+        self.word = self.bits[0];
+        true
+    }
+}
+
+/// Crosses the odd multiples of the base primes from their squares on off
+/// the words, whose bit k is for the odd number low + 2k.
+fn cross_off_segment(words: &mut [u64], low: u64, base: &[u64]) {
+    let len = 64 * words.len();
+    for &p in base {
+        // The first odd multiple of p from p² that is in the segment.
+        let mut multiple = (p * p).max(low.div_ceil(p).saturating_mul(p));
+        if multiple.is_multiple_of(2) {
+            multiple = multiple.saturating_add(p);
+        }
+        let mut k = ((multiple - low) / 2) as usize;
+        while k < len {
+            words[k / 64] &= !(1 << (k % 64));
+            k += p as usize;
+        }
+    }
+}
+
+impl Iterator for Primes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.two {
+            self.two = false;
+            return Some(2);
+        }
+        while self.word == 0 {
+            self.index += 1;
+            match self.bits.get(self.index) {
+                Some(&word) => self.word = word,
+                None => {
+                    if !self.advance() {
+                        self.bits.clear();
+                        return None;
+                    }
+                }
+            }
+        }
+        let bit = self.word.trailing_zeros() as u64;
+        self.word &= self.word - 1;
+        Some(self.low? + 2 * (64 * self.index as u64 + bit))
+    }
+
+    fn nth(&mut self, mut n: usize) -> Option<Self::Item> {
+        if self.two {
+            if n == 0 {
+                return self.next();
+            }
+            self.two = false;
+            n -= 1;
+        }
+        // Whole words and segments are skipped by counting their bits.
+        loop {
+            let count = self.word.count_ones() as usize;
+            if n < count {
+                for _ in 0..n {
+                    self.word &= self.word - 1;
+                }
+                return self.next();
+            }
+            n -= count;
+            self.word = 0;
+            while let Some(&word) = self.bits.get(self.index + 1) {
+                self.index += 1;
+                let count = word.count_ones() as usize;
+                if n < count {
+                    self.word = word;
+                    break;
+                }
+                n -= count;
+            }
+            if self.word == 0 && !self.advance() {
+                self.bits.clear();
+                return None;
+            }
+        }
+    }
+}
+
+impl core::iter::FusedIterator for Primes {}
+
+/// The smallest prime factor of every number up to a limit, found by a
+/// linear sieve, which crosses off each composite exactly once.
+///
+/// With the smallest prime factor of everything up to the limit on hand, a
+/// sieved number n factors in O(log n) steps by dividing it out again and
+/// again, and multiplicative functions such as φ and μ fill a table in one
+/// pass. Each number takes four bytes, so sieving up to 10⁹ takes about
+/// 4 GB.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::SpfSieve;
+///
+/// let sieve = SpfSieve::new(1000);
+/// assert_eq!(sieve.smallest_prime_factor(91), Some(7));
+/// assert_eq!(sieve.smallest_prime_factor(97), Some(97));
+/// let factors: Vec<_> = sieve.factorize(360).collect();
+/// assert_eq!(factors, vec![(2, 3), (3, 2), (5, 1)]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SpfSieve {
+    limit: u64,
+    // The smallest prime factor of each composite, which is at most the
+    // square root of the limit, and zero for zero, one, and the primes.
+    factors: Vec<u32>,
+}
+
+impl SpfSieve {
+    /// Returns the smallest prime factors of the numbers up to and
+    /// including the limit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the factors for the numbers up to the limit would not fit
+    /// in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::SpfSieve;
+    ///
+    /// let sieve = SpfSieve::new(100);
+    /// assert_eq!(sieve.limit(), 100);
+    /// let primes: Vec<_> = (80..=100).filter(|&n| sieve.is_prime(n)).collect();
+    /// assert_eq!(primes, vec![83, 89, 97]);
+    /// ```
+    pub fn new(limit: u64) -> Self {
+        let len = <usize as core::convert::TryFrom<u64>>::try_from(limit)
+            .ok()
+            .and_then(|limit| limit.checked_add(1))
+            .expect("sieve limit too large");
+        let mut factors = vec![0u32; len];
+        let mut primes: Vec<u32> = Vec::new();
+        for n in 2..len {
+            let smallest = match factors[n] {
+                0 => {
+                    // Primes above the square root never divide a
+                    // composite up to the limit, so they need not be kept.
+                    if n.checked_mul(n).is_some_and(|square| square < len) {
+                        primes.push(n as u32);
+                    }
+                    n
+                }
+                p => p as usize,
+            };
+            // Each composite m p with p no larger than the smallest prime
+            // factor of m is reached only from m.
+            for &p in &primes {
+                let p = p as usize;
+                if p > smallest {
+                    break;
+                }
+                match n.checked_mul(p) {
+                    Some(multiple) if multiple < len => factors[multiple] = p as u32,
+                    _ => break,
+                }
+            }
+        }
+        Self { limit, factors }
+    }
+
+    /// Returns the limit that the factors were sieved up to.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Returns an `Option` containing either the smallest prime factor of n
+    /// or `None` if n is zero or one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if n is larger than the limit.
+    pub fn smallest_prime_factor(&self, n: u64) -> Option<u64> {
+        assert!(n <= self.limit, "n must be at most the limit");
+        match self.factors[n as usize] {
+            0 if n < 2 => None,
+            0 => Some(n),
+            p => Some(p as u64),
+        }
+    }
+
+    /// Returns whether n is prime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if n is larger than the limit.
+    pub fn is_prime(&self, n: u64) -> bool {
+        self.smallest_prime_factor(n) == Some(n)
+    }
+
+    /// Returns an `Iterator<Item = (u64, u32)>` implementation that goes
+    /// through the prime factors of n in increasing order, each along with
+    /// its exponent, so that there are none for one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if n is zero or larger than the limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::SpfSieve;
+    ///
+    /// let sieve = SpfSieve::new(1_000_000);
+    /// let factors: Vec<_> = sieve.factorize(999_999).collect();
+    /// assert_eq!(factors, vec![(3, 3), (7, 1), (11, 1), (13, 1), (37, 1)]);
+    /// assert_eq!(sieve.factorize(1).count(), 0);
+    /// ```
+    pub fn factorize(&self, n: u64) -> SpfFactors<'_> {
+        assert!(n != 0, "n must be positive");
+        assert!(n <= self.limit, "n must be at most the limit");
+        SpfFactors { sieve: self, n }
+    }
+
+    /// Returns a table whose n<sup>th</sup> element is φ(n) for every n up
+    /// to the limit, with the element for zero set to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::SpfSieve;
+    ///
+    /// let totients = SpfSieve::new(9).totients();
+    /// assert_eq!(totients, vec![0, 1, 1, 2, 2, 4, 2, 6, 4, 6]);
+    /// ```
+    pub fn totients(&self) -> Vec<u64> {
+        // φ(p m) is φ(m) p when p divides m and φ(m) (p − 1) otherwise.
+        self.table(0, 1, |previous, p, divides| {
+            previous * if divides { p } else { p - 1 }
+        })
+    }
+
+    /// Returns a table whose n<sup>th</sup> element is the Möbius function
+    /// μ(n) for every n up to the limit, with the element for zero set to
+    /// zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::SpfSieve;
+    ///
+    /// let mobius = SpfSieve::new(12).mobius();
+    /// assert_eq!(mobius, vec![0, 1, -1, -1, 0, -1, 1, -1, 0, 0, 1, -1, 0]);
+    /// ```
+    pub fn mobius(&self) -> Vec<i8> {
+        self.table(
+            0,
+            1,
+            |previous, _, divides| {
+                if divides {
+                    0
+                } else {
+                    -previous
+                }
+            },
+        )
+    }
+
+    /// Returns a table whose n<sup>th</sup> element is the Liouville
+    /// function λ(n) = (−1)<sup>Ω(n)</sup> for every n up to the limit, with
+    /// the element for zero set to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::SpfSieve;
+    ///
+    /// let liouville = SpfSieve::new(12).liouville();
+    /// assert_eq!(liouville, vec![0, 1, -1, -1, 1, -1, 1, -1, -1, 1, 1, -1, -1]);
+    /// ```
+    pub fn liouville(&self) -> Vec<i8> {
+        // Every prime factor flips the sign, whether or not it repeats.
+        self.table(0, 1, |previous, _, _| -previous)
+    }
+
+    /// Returns a table of a multiplicative function f, given f(0), f(1),
+    /// and f(p m) from f(m), p, and whether p divides m, where p is the
+    /// smallest prime factor of p m.
+    fn table<T: Copy>(&self, zero: T, one: T, step: impl Fn(T, u64, bool) -> T) -> Vec<T> {
+        let mut table = Vec::with_capacity(self.factors.len());
+        table.extend([zero, one].iter().copied().take(self.factors.len()));
+        for n in 2..self.factors.len() {
+            let p = match self.factors[n] {
+                0 => n,
+                p => p as usize,
+            };
+            let m = n / p;
+            table.push(step(table[m], p as u64, m.is_multiple_of(p)));
+        }
+        table
+    }
+}
+
+/// The iterator returned by `SpfSieve::factorize`.
+#[derive(Clone, Debug)]
+pub struct SpfFactors<'a> {
+    sieve: &'a SpfSieve,
+    // What is left to factor.
+    n: u64,
+}
+
+impl Iterator for SpfFactors<'_> {
+    type Item = (u64, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let p = self.sieve.smallest_prime_factor(self.n)?;
+        let mut exponent = 0;
+        while self.n.is_multiple_of(p) {
+            self.n /= p;
+            exponent += 1;
+        }
+        Some((p, exponent))
+    }
+}
+
+impl core::iter::FusedIterator for SpfFactors<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::number_theory::{Factor, Prime};
+
+    #[test]
+    fn matches_is_prime() {
+        for limit in (0..300).chain([4095, 4096, 4097, 8191, 1_000_000]) {
+            let sieve = Sieve::new(limit);
+            assert_eq!(sieve.limit(), limit);
+            let expected: Vec<_> = (0..=limit).filter(u64::is_prime).collect();
+            let primes: Vec<_> = sieve.primes().collect();
+            assert_eq!(primes, expected, "limit = {}", limit);
+            assert_eq!(sieve.count(), expected.len());
+            for i in 0..=limit.min(5000) {
+                assert_eq!(sieve.is_prime(i), u64::is_prime(&i));
+            }
+        }
+    }
+
+    #[test]
+    fn odd_bits_match_is_prime() {
+        for n in [0, 1, 2, 3, 127, 128, 129, 130, 10_000] {
+            let sieve = Sieve::new(n);
+            let bits = sieve.odd_bits();
+            for i in 0..bits.len() * 64 {
+                let m = 2 * i as u64 + 1;
+                let expected = m <= n && u64::is_prime(&m);
+                assert_eq!(
+                    bits[i / 64] >> (i % 64) & 1 == 1,
+                    expected,
+                    "{} in {}",
+                    m,
+                    n
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn atkin_matches_eratosthenes() {
+        for limit in (0..300).chain([4095, 4096, 4097, 1_000_000, 3_000_001]) {
+            let (atkin, eratosthenes) = (Sieve::atkin(limit), Sieve::new(limit));
+            assert_eq!(atkin.limit(), limit);
+            assert_eq!(atkin.bits, eratosthenes.bits, "limit = {}", limit);
+            assert_eq!(atkin.count(), eratosthenes.count());
+        }
+    }
+
+    #[test]
+    fn block_boundaries() {
+        // Several blocks of 2^18 odd numbers, ending on and off a boundary.
+        for limit in [1 << 19, (1 << 20) + 1, 3_000_000] {
+            let sieve = Sieve::new(limit);
+            let primes: Vec<_> = sieve.primes().collect();
+            assert_eq!(primes.len(), sieve.count());
+            assert!(primes.windows(2).all(|pair| pair[0] < pair[1]));
+            for &p in primes.iter().step_by(101) {
+                assert!(u64::is_prime(&p));
+            }
+            for i in (limit - 2000)..=limit {
+                assert_eq!(sieve.is_prime(i), u64::is_prime(&i), "i = {}", i);
+            }
+        }
+        assert_eq!(Sieve::new(10_000_000).count(), 664_579);
+    }
+
+    #[test]
+    fn parallel_matches_serial() {
+        for limit in [0, 1, 1000, 1 << 19, (1 << 22) + 1, 30_000_001] {
+            let sieve = Sieve::new(limit);
+            let mut serial = sieve.bits.clone();
+            let mut parallel = sieve.bits.clone();
+            let root = limit.isqrt();
+            let base: Vec<_> = sieve
+                .primes()
+                .skip(1)
+                .take_while(|&p| p <= root)
+                .map(|p| p as usize)
+                .collect();
+            // Crossing off again changes nothing, and the two ways of doing
+            // it cross off the same bits from a fresh start too.
+            cross_off(&mut serial, 0, &base);
+            cross_off_parallel(&mut parallel, &base);
+            assert_eq!(serial, sieve.bits);
+            assert_eq!(parallel, sieve.bits);
+            let mut serial = vec![u64::MAX; sieve.bits.len()];
+            let mut parallel = serial.clone();
+            cross_off(&mut serial, 0, &base);
+            cross_off_parallel(&mut parallel, &base);
+            assert_eq!(serial, parallel, "limit = {}", limit);
+            assert_eq!(count_ones_parallel(&sieve.bits), count_ones(&sieve.bits));
+        }
+    }
+
+    #[test]
+    fn unbounded_primes() {
+        let sieve = Sieve::new(3_000_000);
+        let terms: Vec<_> = primes().take(sieve.count()).collect();
+        assert_eq!(terms, sieve.primes().collect::<Vec<_>>());
+
+        for n in (0..terms.len()).step_by(997) {
+            assert_eq!(primes().nth(n), Some(terms[n]), "n = {}", n);
+        }
+        let mut iter = primes();
+        assert_eq!(iter.nth(100_000), Some(terms[100_000]));
+        assert_eq!(iter.next(), Some(terms[100_001]));
+        assert_eq!(iter.nth(64), Some(terms[100_066]));
+
+        // Far along, where the base primes have been resieved several times.
+        let mut iter = primes();
+        assert_eq!(iter.nth(9_999_999), Some(179_424_673));
+        assert_eq!(iter.next(), Some(179_424_691));
+    }
+
+    #[test]
+    fn parallel_segments_match_serial() {
+        for segments in [2, 3, 4] {
+            let serial = Primes::with_segments(1).take(2_000_000);
+            assert!(
+                serial.eq(Primes::with_segments(segments).take(2_000_000)),
+                "segments = {}",
+                segments
+            );
+            let mut serial = Primes::with_segments(1);
+            let mut parallel = Primes::with_segments(segments);
+            for n in [0, 1, 63, 64, 1_000, 100_000, 3_000_000] {
+                assert_eq!(parallel.nth(n), serial.nth(n), "n = {}", n);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "i must be at most the limit")]
+    fn beyond_limit() {
+        Sieve::new(100).is_prime(101);
+    }
+
+    #[test]
+    fn smallest_prime_factors() {
+        for limit in (0..100).chain([65_535, 65_536, 1_000_000]) {
+            let sieve = SpfSieve::new(limit);
+            assert_eq!(sieve.limit(), limit);
+            for n in 0..=limit.min(20_000) {
+                let expected = (2..=n).find(|p| n.is_multiple_of(*p));
+                assert_eq!(sieve.smallest_prime_factor(n), expected, "n = {}", n);
+                assert_eq!(sieve.is_prime(n), u64::is_prime(&n));
+            }
+        }
+        let sieve = SpfSieve::new(1_000_000);
+        let primes = Sieve::new(1_000_000);
+        for n in 1..=1_000_000 {
+            assert_eq!(sieve.is_prime(n), primes.is_prime(n), "n = {}", n);
+            let product: u64 = sieve.factorize(n).map(|(p, e)| p.pow(e)).product();
+            assert_eq!(product, n);
+        }
+    }
+
+    #[test]
+    fn totients_and_mobius() {
+        let sieve = SpfSieve::new(10_000);
+        let totients = sieve.totients();
+        assert_eq!(totients.len(), 10_001);
+        for n in 1..=10_000u64 {
+            assert_eq!(
+                totients[n as usize],
+                u64::factorize(&n).unwrap().totient(),
+                "n = {}",
+                n
+            );
+        }
+        let mobius = sieve.mobius();
+        assert_eq!(mobius.len(), 10_001);
+        for n in 1..=10_000 {
+            let factors: Vec<_> = sieve.factorize(n).collect();
+            let expected = if factors.iter().any(|&(_, e)| e > 1) {
+                0
+            } else if factors.len() % 2 == 0 {
+                1
+            } else {
+                -1
+            };
+            assert_eq!(mobius[n as usize], expected, "n = {}", n);
+        }
+        assert_eq!(SpfSieve::new(0).totients(), vec![0]);
+        assert_eq!(SpfSieve::new(1).mobius(), vec![0, 1]);
+    }
+}
@@ -0,0 +1,490 @@
+pub trait Totient: Sized {
+    /// Returns an `Option` containing either Euler's totient φ(n), the number
+    /// of positive integers up to n that are coprime to it, or `None` if n is
+    /// not positive.
+    ///
+    /// This factors n with `Factor::factorize` and takes the product of
+    /// p<sup>e − 1</sup> (p − 1) over its prime powers, so it is quick for
+    /// any n that factors quickly. For φ of every number up to a limit,
+    /// `totient_table` and `SpfSieve::totients` sieve them all at once, and
+    /// `factorization::factorize_range` gives the factorizations of a range
+    /// to take `Factorization::totient` of.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Totient;
+    ///
+    /// assert_eq!(u32::totient(&36), Some(12));
+    /// assert_eq!(u8::totient(&1), Some(1));
+    /// assert_eq!(u8::totient(&0), None);
+    /// assert_eq!(i32::totient(&-36), None);
+    /// // 2^64 + 1 = 274 177 × 67 280 421 310 721.
+    /// assert_eq!(
+    ///     u128::totient(&((1 << 64) + 1)),
+    ///     Some(274_176 * 67_280_421_310_720)
+    /// );
+    /// ```
+    fn totient(n: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the Jordan totient
+    /// J<sub>k</sub>(n) or `None` if n is not positive or J<sub>k</sub>(n)
+    /// would cause overflow.
+    ///
+    /// J<sub>k</sub>(n) counts the k-tuples of integers from 1 to n whose
+    /// greatest common divisor with n is one, and is
+    /// n<sup>k</sup> ∏ (1 − p<sup>−k</sup>) over the primes p dividing n.
+    /// J<sub>1</sub> is φ, and the sum of J<sub>k</sub>(d) over the
+    /// divisors d of n is n<sup>k</sup>. This comes from the factorization
+    /// of n, as `totient` does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Totient;
+    ///
+    /// assert_eq!(u32::jordan_totient(&12, 2), Some(96));
+    /// assert_eq!(u32::jordan_totient(&12, 1), u32::totient(&12));
+    /// assert_eq!(u32::jordan_totient(&1, 5), Some(1));
+    /// assert_eq!(u32::jordan_totient(&12, 0), Some(0));
+    /// assert_eq!(u8::jordan_totient(&16, 2), Some(192));
+    /// assert_eq!(u8::jordan_totient(&16, 3), None);
+    /// assert_eq!(u32::jordan_totient(&0, 2), None);
+    /// ```
+    fn jordan_totient(n: &Self, k: u32) -> Option<Self>;
+
+    /// Returns an `Option` containing either the Carmichael function λ(n),
+    /// the smallest positive m for which a<sup>m</sup> ≡ 1 (mod n) for every
+    /// a coprime to n, or `None` if n is not positive.
+    ///
+    /// λ(n) is the exponent of the multiplicative group modulo n, so the
+    /// multiplicative order of every unit divides it, and it divides φ(n),
+    /// with equality exactly when the group is cyclic. It is the least
+    /// common multiple of λ over the prime powers of the factorization of
+    /// n, where λ(p<sup>e</sup>) = φ(p<sup>e</sup>) except that
+    /// λ(2<sup>e</sup>) = 2<sup>e − 2</sup> for e ≥ 3.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Totient;
+    ///
+    /// assert_eq!(u32::carmichael_lambda(&8), Some(2));
+    /// assert_eq!(u32::carmichael_lambda(&15), Some(4));
+    /// // 561 = 3 × 11 × 17 is a Carmichael number, as λ(561) divides 560.
+    /// assert_eq!(u32::carmichael_lambda(&561), Some(80));
+    /// assert_eq!(u32::carmichael_lambda(&1), Some(1));
+    /// assert_eq!(i32::carmichael_lambda(&-8), None);
+    /// ```
+    fn carmichael_lambda(n: &Self) -> Option<Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through
+    /// n, φ(n), φ(φ(n)), … down to and including one, or through nothing if
+    /// n is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Totient;
+    ///
+    /// let chain: Vec<_> = u32::totient_chain(&100).collect();
+    /// assert_eq!(chain, vec![100, 40, 16, 8, 4, 2, 1]);
+    /// assert_eq!(i32::totient_chain(&-100).count(), 0);
+    /// ```
+    fn totient_chain(n: &Self) -> TotientChain<Self>;
+
+    /// Returns the number of terms in the chain of iterated totients from n
+    /// down to one, which is zero if n is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Totient;
+    ///
+    /// assert_eq!(u32::totient_chain_length(&100), 7);
+    /// assert_eq!(u32::totient_chain_length(&1), 1);
+    /// assert_eq!(u64::totient_chain_length(&u64::MAX), 62);
+    /// ```
+    fn totient_chain_length(n: &Self) -> usize;
+
+    /// Returns whether n is a perfect totient number, that is, whether n is
+    /// the sum φ(n) + φ(φ(n)) + … + 1 of its iterated totients.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Totient;
+    ///
+    /// // 327 = 216 + 72 + 24 + 8 + 4 + 2 + 1.
+    /// assert!(u32::is_perfect_totient(&327));
+    /// assert!(!u32::is_perfect_totient(&328));
+    /// ```
+    fn is_perfect_totient(n: &Self) -> bool;
+}
+
+/// The iterator returned by `Totient::totient_chain`.
+pub struct TotientChain<T> {
+    next: Option<T>,
+}
+
+macro_rules! totient_trait_for_primitive {
+    ($type:ty) => {
+        impl $crate::number_theory::totient::Totient for $type {
+            fn totient(n: &Self) -> ::core::option::Option<Self> {
+                if *n <= 0 {
+                    return ::core::option::Option::None;
+                }
+                let n = *n as u128;
+                let factorization = <u128 as $crate::number_theory::Factor>::factorize(&n)?;
+                // φ(n) is at most n, so it fits.
+                ::core::option::Option::Some(factorization.totient() as $type)
+            }
+
+            fn jordan_totient(n: &Self, k: u32) -> ::core::option::Option<Self> {
+                if *n <= 0 {
+                    return ::core::option::Option::None;
+                }
+                let n = *n as u128;
+                let factorization = <u128 as $crate::number_theory::Factor>::factorize(&n)?;
+                let jordan = factorization.jordan_totient(k)?;
+                <$type as ::core::convert::TryFrom<u128>>::try_from(jordan).ok()
+            }
+
+            fn carmichael_lambda(n: &Self) -> ::core::option::Option<Self> {
+                if *n <= 0 {
+                    return ::core::option::Option::None;
+                }
+                let n = *n as u128;
+                let factorization = <u128 as $crate::number_theory::Factor>::factorize(&n)?;
+                ::core::option::Option::Some(factorization.carmichael_lambda() as $type)
+            }
+
+            fn totient_chain(n: &Self) -> $crate::number_theory::totient::TotientChain<Self> {
+                $crate::number_theory::totient::TotientChain {
+                    next: if *n > 0 {
+                        ::core::option::Option::Some(*n)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                }
+            }
+
+            fn totient_chain_length(n: &Self) -> usize {
+                Self::totient_chain(n).count()
+            }
+
+            fn is_perfect_totient(n: &Self) -> bool {
+                let mut sum: $type = 0;
+                for term in Self::totient_chain(n).skip(1) {
+                    match sum.checked_add(term) {
+                        ::core::option::Option::Some(total) if total <= *n => sum = total,
+                        _ => return false,
+                    }
+                }
+                *n > 1 && sum == *n
+            }
+        }
+
+        impl ::core::iter::Iterator for $crate::number_theory::totient::TotientChain<$type> {
+            type Item = $type;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                let term = self.next?;
+                self.next = if term == 1 {
+                    ::core::option::Option::None
+                } else {
+                    <$type as $crate::number_theory::totient::Totient>::totient(&term)
+                };
+                ::core::option::Option::Some(term)
+            }
+        }
+    };
+}
+
+totient_trait_for_primitive!(::core::primitive::i8);
+totient_trait_for_primitive!(::core::primitive::u8);
+totient_trait_for_primitive!(::core::primitive::i16);
+totient_trait_for_primitive!(::core::primitive::u16);
+totient_trait_for_primitive!(::core::primitive::i32);
+totient_trait_for_primitive!(::core::primitive::u32);
+totient_trait_for_primitive!(::core::primitive::i64);
+totient_trait_for_primitive!(::core::primitive::u64);
+totient_trait_for_primitive!(::core::primitive::i128);
+totient_trait_for_primitive!(::core::primitive::u128);
+totient_trait_for_primitive!(::core::primitive::isize);
+totient_trait_for_primitive!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Totient for rug::Integer {
+    fn totient(n: &Self) -> Option<Self> {
+        Some(<Self as crate::number_theory::Factor>::factorize(n)?.totient())
+    }
+
+    fn jordan_totient(n: &Self, k: u32) -> Option<Self> {
+        Some(<Self as crate::number_theory::Factor>::factorize(n)?.jordan_totient(k))
+    }
+
+    fn carmichael_lambda(n: &Self) -> Option<Self> {
+        Some(<Self as crate::number_theory::Factor>::factorize(n)?.carmichael_lambda())
+    }
+
+    fn totient_chain(n: &Self) -> TotientChain<Self> {
+        TotientChain {
+            next: Some(n.clone()).filter(|n| *n > 0),
+        }
+    }
+
+    fn totient_chain_length(n: &Self) -> usize {
+        Self::totient_chain(n).count()
+    }
+
+    fn is_perfect_totient(n: &Self) -> bool {
+        let mut sum = rug::Integer::new();
+        for term in Self::totient_chain(n).skip(1) {
+            sum += term;
+            if sum > *n {
+                return false;
+            }
+        }
+        *n > 1 && sum == *n
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for TotientChain<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let term = self.next.take()?;
+        if term != 1 {
+            self.next = <rug::Integer as Totient>::totient(&term);
+        }
+        Some(term)
+    }
+}
+
+/// Returns a table whose n<sup>th</sup> element is φ(n) for every n below
+/// `limit`, with the element for zero set to zero, from
+/// `SpfSieve::totients`.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::totient::totient_table;
+///
+/// assert_eq!(totient_table(10), vec![0, 1, 1, 2, 2, 4, 2, 6, 4, 6]);
+/// ```
+pub fn totient_table(limit: usize) -> Vec<u64> {
+    match limit.checked_sub(1) {
+        Some(last) => crate::number_theory::SpfSieve::new(last as u64).totients(),
+        None => Vec::new(),
+    }
+}
+
+/// Returns an iterator over every perfect totient number below `limit` in
+/// increasing order, found from a sieved φ table.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::totient::perfect_totient_numbers;
+///
+/// let found: Vec<_> = perfect_totient_numbers(500).collect();
+/// assert_eq!(found, vec![3, 9, 15, 27, 39, 81, 111, 183, 243, 255, 327, 363, 471]);
+/// ```
+pub fn perfect_totient_numbers(limit: usize) -> PerfectTotientNumbers {
+    let totients = totient_table(limit);
+    // The sum of the iterated totients below n, built up from smaller n as
+    // φ(n) < n for n > 1.
+    let mut sums = vec![0u64; limit];
+    for n in 2..limit {
+        let phi = totients[n];
+        sums[n] = phi + sums[phi as usize];
+    }
+    PerfectTotientNumbers { sums, n: 2 }
+}
+
+/// The iterator returned by `perfect_totient_numbers`.
+pub struct PerfectTotientNumbers {
+    sums: Vec<u64>,
+    n: usize,
+}
+
+impl Iterator for PerfectTotientNumbers {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.n < self.sums.len() {
+            let n = self.n;
+            self.n += 1;
+            if self.sums[n] == n as u64 {
+                return Some(n as u64);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::number_theory::Gcd;
+
+    macro_rules! test_chain_against_table {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let table = totient_table(3000);
+                for n in (0..table.len()).take_while(|&n| n as u128 <= <$type>::MAX as u128) {
+                    let mut expected = ::std::vec::Vec::new();
+                    let mut m = n;
+                    while m != 0 {
+                        expected.push(m as $type);
+                        m = if m == 1 { 0 } else { table[m] as usize };
+                    }
+                    if n > 0 {
+                        ::core::assert_eq!(
+                            <$type>::totient(&(n as $type)),
+                            ::core::option::Option::Some(table[n] as $type)
+                        );
+                    }
+                    let chain: ::std::vec::Vec<_> = <$type>::totient_chain(&(n as $type)).collect();
+                    ::core::assert_eq!(chain, expected);
+                    ::core::assert_eq!(
+                        <$type>::totient_chain_length(&(n as $type)),
+                        expected.len()
+                    );
+                    let sum: usize = expected.iter().skip(1).map(|&term| term as usize).sum();
+                    ::core::assert_eq!(
+                        <$type>::is_perfect_totient(&(n as $type)),
+                        n > 1 && sum == n
+                    );
+                }
+            }
+        };
+    }
+
+    test_chain_against_table!(::core::primitive::i8, i8_totient_chain);
+    test_chain_against_table!(::core::primitive::u8, u8_totient_chain);
+    test_chain_against_table!(::core::primitive::i16, i16_totient_chain);
+    test_chain_against_table!(::core::primitive::u16, u16_totient_chain);
+    test_chain_against_table!(::core::primitive::i32, i32_totient_chain);
+    test_chain_against_table!(::core::primitive::u32, u32_totient_chain);
+    test_chain_against_table!(::core::primitive::i64, i64_totient_chain);
+    test_chain_against_table!(::core::primitive::u64, u64_totient_chain);
+    test_chain_against_table!(::core::primitive::i128, i128_totient_chain);
+    test_chain_against_table!(::core::primitive::u128, u128_totient_chain);
+    test_chain_against_table!(::core::primitive::isize, isize_totient_chain);
+    test_chain_against_table!(::core::primitive::usize, usize_totient_chain);
+
+    #[test]
+    fn signed_and_rug_totients() {
+        use rug::Integer;
+
+        assert!(totient_table(0).is_empty());
+        assert_eq!(totient_table(1), vec![0]);
+        let table = totient_table(3000);
+        for (n, &phi) in table.iter().enumerate().skip(1) {
+            assert_eq!(
+                <Integer as Totient>::totient(&Integer::from(n)).unwrap(),
+                phi
+            );
+        }
+        for n in [i64::MIN, -1, 0] {
+            assert_eq!(i64::totient(&n), None);
+            assert_eq!(i64::totient_chain_length(&n), 0);
+        }
+        assert_eq!(i8::totient(&i8::MAX), Some(126));
+        assert_eq!(<Integer as Totient>::totient(&Integer::from(-5)), None);
+
+        // φ(3^k) = 2 × 3^(k − 1), so every power of three is a perfect
+        // totient number.
+        let power = Integer::from(Integer::u_pow_u(3, 200));
+        assert!(<Integer as Totient>::is_perfect_totient(&power));
+        assert_eq!(<Integer as Totient>::totient_chain_length(&power), 202);
+        assert!(!<Integer as Totient>::is_perfect_totient(&(power + 2u8)));
+    }
+
+    #[test]
+    fn jordan_totients_sum_to_powers() {
+        use crate::number_theory::Factor;
+        use rug::Integer;
+
+        // The sum of J_k(d) over the divisors d of n is n^k.
+        for k in 0..4 {
+            for n in 1..500u64 {
+                let divisors = u64::factorize(&n).unwrap().sorted_divisors();
+                let sum: u64 = divisors
+                    .iter()
+                    .map(|d| u64::jordan_totient(d, k).unwrap())
+                    .sum();
+                assert_eq!(sum, n.pow(k), "n = {}, k = {}", n, k);
+                assert_eq!(
+                    <Integer as Totient>::jordan_totient(&Integer::from(n), k).unwrap(),
+                    u64::jordan_totient(&n, k).unwrap()
+                );
+                assert_eq!(
+                    i16::jordan_totient(&(n as i16), k),
+                    u64::jordan_totient(&n, k)
+                        .and_then(|j| <i16 as core::convert::TryFrom<u64>>::try_from(j).ok())
+                );
+            }
+        }
+        assert_eq!(u64::jordan_totient(&2, 64), Some(u64::MAX));
+        assert_eq!(u64::jordan_totient(&3, 41), None);
+        assert_eq!(u128::jordan_totient(&3, 41), Some(3u128.pow(41) - 1));
+        assert_eq!(i64::jordan_totient(&-2, 1), None);
+    }
+
+    #[test]
+    fn carmichael_lambda_is_the_exponent() {
+        use rug::Integer;
+
+        // The largest multiplicative order of the units modulo n.
+        for n in 1..600u64 {
+            let mut lambda = 1;
+            for a in (1..n).filter(|&a| u64::gcd(&a, &n) == 1) {
+                let (mut power, mut order) = (a, 1);
+                while power != 1 {
+                    power = power * a % n;
+                    order += 1;
+                }
+                lambda = u64::lcm(&lambda, &order);
+            }
+            assert_eq!(u64::carmichael_lambda(&n), Some(lambda), "n = {}", n);
+            assert_eq!(
+                <Integer as Totient>::carmichael_lambda(&Integer::from(n)).unwrap(),
+                lambda
+            );
+        }
+        // 2^128 − 1 = 3 × 5 × 17 × 257 × 641 × 65537 × 274177 × 6700417 ×
+        // 67280421310721, so λ is the least common multiple of one less than
+        // each.
+        assert_eq!(
+            u128::carmichael_lambda(&u128::MAX),
+            Some(321_876_063_366_081_731_297_280)
+        );
+        assert_eq!(u8::carmichael_lambda(&0), None);
+    }
+
+    #[test]
+    fn perfect_totient_search() {
+        let found: Vec<_> = perfect_totient_numbers(10_000).collect();
+        assert_eq!(
+            found,
+            vec![
+                3, 9, 15, 27, 39, 81, 111, 183, 243, 255, 327, 363, 471, 729, 2187, 2199, 3063,
+                4359, 4375, 5571, 6561, 8751
+            ]
+        );
+        for &n in &found {
+            assert!(u64::is_perfect_totient(&n));
+        }
+        // Every power of three is a perfect totient number.
+        assert!(u128::is_perfect_totient(&3u128.pow(80)));
+        assert!(!u128::is_perfect_totient(&u128::MAX));
+        assert_eq!(perfect_totient_numbers(0).count(), 0);
+    }
+}
@@ -0,0 +1,224 @@
+/// A factorization wheel: the residues modulo a product of small primes
+/// that are coprime to it, which are the only places that any larger prime
+/// can be.
+///
+/// Stepping through the numbers coprime to 2 × 3 × 5 × 7 = 210 skips all
+/// but 48 of every 210, or about 77%, of the candidates for primality.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::Wheel;
+///
+/// let wheel = Wheel::new(&[2, 3, 5, 7]);
+/// assert_eq!(wheel.modulus(), 210);
+/// assert_eq!(wheel.residues().len(), 48);
+///
+/// let candidates: Vec<_> = wheel.candidates_from(100).take(8).collect();
+/// assert_eq!(candidates, vec![101, 103, 107, 109, 113, 121, 127, 131]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Wheel {
+    primes: Vec<u64>,
+    modulus: u64,
+    residues: Vec<u64>,
+}
+
+impl Wheel {
+    /// Returns the wheel for the given primes.
+    ///
+    /// The wheel keeps one entry for each residue coprime to the product of
+    /// the primes, so that product should stay small.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the primes are not all distinct primes or if their product
+    /// does not fit in a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::number_theory::Wheel;
+    ///
+    /// let wheel = Wheel::new(&[2, 3]);
+    /// assert_eq!(wheel.residues(), &[1, 5]);
+    /// assert_eq!(Wheel::new(&[]).residues(), &[0]);
+    /// ```
+    pub fn new(primes: &[u64]) -> Self {
+        assert!(
+            primes.iter().all(<u64 as crate::number_theory::Prime>::is_prime),
+            "wheel primes must be prime"
+        );
+        assert!(
+            (1..primes.len()).all(|i| !primes[..i].contains(&primes[i])),
+            "wheel primes must be distinct"
+        );
+        let modulus = primes
+            .iter()
+            .try_fold(1u64, |product, &p| product.checked_mul(p))
+            .expect("wheel modulus must fit in a u64");
+        let residues = (0..modulus)
+            .filter(|r| primes.iter().all(|p| !r.is_multiple_of(*p)))
+            .collect();
+        Self {
+            primes: primes.to_vec(),
+            modulus,
+            residues,
+        }
+    }
+
+    /// Returns the primes that the wheel was made from.
+    pub fn primes(&self) -> &[u64] {
+        &self.primes
+    }
+
+    /// Returns the product of the primes.
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// Returns the residues modulo the modulus that are coprime to it, in
+    /// increasing order.
+    pub fn residues(&self) -> &[u64] {
+        &self.residues
+    }
+
+    /// Returns whether n is coprime to the modulus, that is, whether n is
+    /// on one of the spokes of the wheel.
+    pub fn is_candidate(&self, n: u64) -> bool {
+        self.residues.binary_search(&(n % self.modulus)).is_ok()
+    }
+
+    /// Returns an `Iterator<Item = u64>` implementation that goes through
+    /// the numbers from n on that are coprime to the modulus, in increasing
+    /// order until just before overflow.
+    pub fn candidates_from(&self, n: u64) -> WheelCandidates<'_> {
+        WheelCandidates {
+            wheel: self,
+            position: Some(self.position(n)),
+        }
+    }
+
+    /// Returns the position of the first candidate from n on.
+    pub(crate) fn position(&self, n: u64) -> Position {
+        let (quotient, remainder) = (n / self.modulus, n % self.modulus);
+        let index = self.residues.partition_point(|&r| r < remainder);
+        if index == self.residues.len() {
+            Position {
+                quotient: quotient + 1,
+                index: 0,
+            }
+        } else {
+            Position { quotient, index }
+        }
+    }
+
+    /// Returns the candidate at a position, or `None` if it does not fit in
+    /// a `u64`.
+    pub(crate) fn value(&self, position: Position) -> Option<u64> {
+        position
+            .quotient
+            .checked_mul(self.modulus)?
+            .checked_add(self.residues[position.index])
+    }
+
+    /// Returns the position of the next candidate, or `None` if that is
+    /// past every `u64`.
+    pub(crate) fn step(&self, position: Position) -> Option<Position> {
+        if position.index + 1 == self.residues.len() {
+            Some(Position {
+                quotient: position.quotient.checked_add(1)?,
+                index: 0,
+            })
+        } else {
+            Some(Position {
+                index: position.index + 1,
+                ..position
+            })
+        }
+    }
+}
+
+/// A candidate on a wheel, as the number of full turns and the index of its
+/// residue.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Position {
+    quotient: u64,
+    index: usize,
+}
+
+/// The iterator returned by `Wheel::candidates_from`.
+#[derive(Clone, Debug)]
+pub struct WheelCandidates<'a> {
+    wheel: &'a Wheel,
+    // `None` once the candidates have overflowed.
+    position: Option<Position>,
+}
+
+impl Iterator for WheelCandidates<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let position = self.position?;
+        let value = self.wheel.value(position);
+        self.position = value.and_then(|_| self.wheel.step(position));
+        value
+    }
+}
+
+impl core::iter::FusedIterator for WheelCandidates<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates_match_gcd() {
+        for primes in [&[2, 3, 5, 7][..], &[2], &[3, 5], &[2, 3, 5, 7, 11], &[]] {
+            let wheel = Wheel::new(primes);
+            for start in [0, 1, 2, 209, 210, 211, 2309, 2310, 10_000] {
+                let expected: Vec<_> = (start..start + 3000)
+                    .filter(|n| primes.iter().all(|p| n % p != 0))
+                    .collect();
+                let candidates: Vec<_> = wheel
+                    .candidates_from(start)
+                    .take_while(|&n| n < start + 3000)
+                    .collect();
+                assert_eq!(candidates, expected, "{:?} from {}", primes, start);
+                for n in start..start + 300 {
+                    assert_eq!(wheel.is_candidate(n), expected.contains(&n));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn candidates_end_before_overflow() {
+        let wheel = Wheel::new(&[2, 3, 5, 7]);
+        let candidates: Vec<_> = wheel.candidates_from(u64::MAX - 30).collect();
+        let expected: Vec<_> = (u64::MAX - 30..=u64::MAX)
+            .filter(|&n| wheel.is_candidate(n))
+            .collect();
+        assert_eq!(candidates, expected);
+        assert_eq!(wheel.primes(), &[2, 3, 5, 7]);
+    }
+
+    #[test]
+    #[should_panic(expected = "wheel modulus must fit in a u64")]
+    fn modulus_too_large() {
+        // The primes on either side of 2^32.
+        Wheel::new(&[4_294_967_291, 4_294_967_311]);
+    }
+
+    #[test]
+    #[should_panic(expected = "wheel primes must be prime")]
+    fn composite_prime() {
+        Wheel::new(&[2, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "wheel primes must be distinct")]
+    fn repeated_prime() {
+        Wheel::new(&[2, 3, 2]);
+    }
+}
@@ -0,0 +1,115 @@
+use core::cmp::Ordering;
+
+/// Searches the sorted slice for x with Fibonacci search, returning
+/// `Result<usize, usize>` just like `slice::binary_search`.
+///
+/// If x is found, this returns `Ok` containing the index of a matching
+/// element, any one of them if there are several. Otherwise, it returns `Err`
+/// containing the index where x could be inserted while keeping the slice
+/// sorted.
+///
+/// Fibonacci search splits what is left of the slice into two parts whose
+/// lengths are consecutive Fibonacci numbers, so it finds its probes with
+/// additions and subtractions alone and takes O(log n) comparisons.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::search::fibonacci_search;
+///
+/// let primes = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29];
+/// assert_eq!(fibonacci_search(&primes, &13), Ok(5));
+/// assert_eq!(fibonacci_search(&primes, &14), Err(6));
+/// assert_eq!(fibonacci_search(&primes, &1), Err(0));
+/// assert_eq!(fibonacci_search(&primes, &30), Err(10));
+/// ```
+pub fn fibonacci_search<T: Ord>(slice: &[T], x: &T) -> Result<usize, usize> {
+    fibonacci_search_by(slice, |element| element.cmp(x))
+}
+
+/// Searches the slice, which must be sorted in the order given by f, with
+/// Fibonacci search, returning `Result<usize, usize>` just like
+/// `slice::binary_search_by`.
+///
+/// The function f returns whether an element is less than, equal to, or
+/// greater than the one being searched for.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::search::fibonacci_search_by;
+///
+/// let words = ["fig", "kiwi", "apple", "banana", "cherry"];
+/// assert_eq!(fibonacci_search_by(&words, |word| word.len().cmp(&4)), Ok(1));
+/// assert_eq!(fibonacci_search_by(&words, |word| word.len().cmp(&7)), Err(5));
+/// ```
+pub fn fibonacci_search_by<T, F>(slice: &[T], mut f: F) -> Result<usize, usize>
+where
+    F: FnMut(&T) -> Ordering,
+{
+    let table = crate::sequences::fibonacci::usize_table();
+    let len = slice.len();
+    // What is left to search runs from lo for F(k) − 1 elements of the slice
+    // padded with elements greater than everything. That splits into
+    // F(k − 1) − 1 elements, a probe, and F(k − 2) − 1 elements. Only the
+    // longest slices of zero-sized types need a k past the table, and then
+    // F(k) itself is never looked at.
+    let mut k = table.partition_point(|&term| term <= len);
+    let mut lo = 0usize;
+    while k > 2 {
+        let probe = lo.saturating_add(table[k - 1] - 1);
+        let ordering = if probe < len {
+            f(&slice[probe])
+        } else {
+            Ordering::Greater
+        };
+        match ordering {
+            Ordering::Less => {
+                lo = probe + 1;
+                k -= 2;
+            }
+            Ordering::Greater => k -= 1,
+            Ordering::Equal => return Ok(probe),
+        }
+    }
+    Err(lo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_binary_search() {
+        for len in 0..200 {
+            // Every value appears twice, so matches may be found at either.
+            let slice: Vec<_> = (0..len).map(|i| i / 2 * 3).collect();
+            for x in -2..len * 3 / 2 + 3 {
+                match (fibonacci_search(&slice, &x), slice.binary_search(&x)) {
+                    (Ok(i), Ok(_)) => assert_eq!(slice[i], x, "len = {}, x = {}", len, x),
+                    (result, expected) => assert_eq!(result, expected, "len = {}, x = {}", len, x),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn finds_every_element() {
+        let slice: Vec<u32> = (1..10_000).map(|i| i * i).collect();
+        for (i, x) in slice.iter().enumerate() {
+            assert_eq!(fibonacci_search(&slice, x), Ok(i));
+            assert_eq!(fibonacci_search(&slice, &(x + 1)), Err(i + 1));
+        }
+    }
+
+    #[test]
+    fn longest_slices() {
+        let units = vec![(); usize::MAX];
+        assert!(fibonacci_search(&units, &()).is_ok());
+        assert_eq!(
+            fibonacci_search_by(&units, |_| Ordering::Less),
+            Err(usize::MAX)
+        );
+        assert_eq!(fibonacci_search_by(&units, |_| Ordering::Greater), Err(0));
+    }
+}
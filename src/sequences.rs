@@ -1,3 +1,45 @@
+pub mod apery;
+pub mod automatic_sequences;
+pub mod beatty;
+pub mod calkin_wilf;
+pub mod catalan;
+pub mod collatz;
+pub mod digit_dynamics;
+pub mod fermat;
 pub mod fibonacci;
+pub mod fibonacci_word;
+pub mod highly_composite;
+pub mod k_bonacci;
+pub mod kolakoski;
+pub mod lucky;
+pub mod markov;
+pub mod mersenne;
+pub mod perfect;
+pub mod polygonal;
+pub mod proth;
+pub mod smooth;
+pub mod stirling;
+pub mod streaming;
+pub mod triangular;
+pub mod ulam;
+pub mod wedderburn_etherington;
+pub mod wythoff;
+pub mod zigzag;
 
-pub use fibonacci::Fibonacci;
+pub use apery::Apery;
+pub use catalan::Catalan;
+pub use collatz::Collatz;
+pub use fermat::Fermat;
+pub use fibonacci::{Fibonacci, FibonacciApprox, FibonacciMod, Negafibonacci};
+pub use k_bonacci::KBonacci;
+pub use markov::Markov;
+pub use mersenne::Mersenne;
+pub use perfect::Perfect;
+pub use polygonal::Polygonal;
+pub use smooth::Smooth;
+pub use stirling::Stirling;
+pub use streaming::StreamingSequence;
+pub use triangular::Triangular;
+pub use wedderburn_etherington::WedderburnEtherington;
+pub use wythoff::Wythoff;
+pub use zigzag::Zigzag;
@@ -0,0 +1,267 @@
+use crate::number_theory::primes::{Modular, Small};
+
+pub trait Apery: Sized {
+    /// Returns an `Option` containing either the n<sup>th</sup> Apéry number
+    /// for ζ(3), Σ<sub>k</sub> C(n, k)² C(n + k, k)², or `None` if that
+    /// would cause overflow or, for `rug::Integer`, n is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Apery;
+    ///
+    /// assert_eq!(u32::nth_apery(&3), Some(1445));
+    /// assert_eq!(u16::nth_apery(&5), None);
+    /// ```
+    fn nth_apery(n: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the n<sup>th</sup> Apéry number
+    /// for ζ(2), Σ<sub>k</sub> C(n, k)² C(n + k, k), or `None` if that would
+    /// cause overflow or, for `rug::Integer`, n is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Apery;
+    ///
+    /// assert_eq!(u32::nth_apery_zeta2(&4), Some(1251));
+    /// assert_eq!(u8::nth_apery_zeta2(&4), None);
+    /// ```
+    fn nth_apery_zeta2(n: &Self) -> Option<Self>;
+}
+
+macro_rules! apery_trait_for_unsigned {
+    ($type:ty) => {
+        impl $crate::sequences::apery::Apery for $type {
+            fn nth_apery(n: &Self) -> Option<Self> {
+                let mut sum: $type = 0;
+                for k in 0..=*n {
+                    let term = Self::binomial_sum_factor(*n, k)?;
+                    sum = sum.checked_add(term.checked_mul(term)?)?;
+                }
+                Some(sum)
+            }
+
+            fn nth_apery_zeta2(n: &Self) -> Option<Self> {
+                let mut sum: $type = 0;
+                for k in 0..=*n {
+                    let term =
+                        Self::binomial_sum_factor(*n, k)?.checked_mul(Self::binomial(*n, k)?)?;
+                    sum = sum.checked_add(term)?;
+                }
+                Some(sum)
+            }
+        }
+
+        impl $crate::sequences::apery::AperyHelpers for $type {
+            fn binomial_sum_factor(n: Self, k: Self) -> Option<Self> {
+                // C(n, k) C(n + k, k) = C(n + k, 2k) C(2k, k), each factor of
+                // which is at most the product.
+                Self::binomial(n.checked_add(k)?, k.checked_mul(2)?)?
+                    .checked_mul(Self::binomial(k.checked_mul(2)?, k)?)
+            }
+
+            fn binomial(n: Self, k: Self) -> Option<Self> {
+                let k = ::core::cmp::min(k, n - k);
+                let mut result: $type = 1;
+                for i in 1..=k {
+                    // result · (n − k + i) / i is computed after cancelling the
+                    // common factor of result and i, so it only overflows when
+                    // C(n − k + i, i) does.
+                    let g = <$type as $crate::number_theory::Gcd>::gcd(&result, &i);
+                    result = (result / g).checked_mul((n - k + i) / (i / g))?;
+                }
+                Some(result)
+            }
+        }
+    };
+}
+
+trait AperyHelpers: Sized {
+    fn binomial_sum_factor(n: Self, k: Self) -> Option<Self>;
+
+    fn binomial(n: Self, k: Self) -> Option<Self>;
+}
+
+apery_trait_for_unsigned!(::core::primitive::u8);
+apery_trait_for_unsigned!(::core::primitive::u16);
+apery_trait_for_unsigned!(::core::primitive::u32);
+apery_trait_for_unsigned!(::core::primitive::u64);
+apery_trait_for_unsigned!(::core::primitive::u128);
+apery_trait_for_unsigned!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Apery for rug::Integer {
+    fn nth_apery(n: &Self) -> Option<Self> {
+        let n = n.to_u32()?;
+        Some(
+            (0..=n)
+                .map(|k| {
+                    let term = rug::Integer::from(rug::Integer::binomial_u(n, k))
+                        * rug::Integer::from(rug::Integer::binomial_u(n + k, k));
+                    term.square()
+                })
+                .sum(),
+        )
+    }
+
+    fn nth_apery_zeta2(n: &Self) -> Option<Self> {
+        let n = n.to_u32()?;
+        Some(
+            (0..=n)
+                .map(|k| {
+                    rug::Integer::from(rug::Integer::binomial_u(n, k)).square()
+                        * rug::Integer::from(rug::Integer::binomial_u(n + k, k))
+                })
+                .sum(),
+        )
+    }
+}
+
+/// Returns the n<sup>th</sup> Apéry number for ζ(3) modulo m.
+///
+/// The binomial coefficients are taken from rows of Pascal's triangle reduced
+/// modulo m, so m need not be prime. This takes O(n²) time and O(n) space.
+///
+/// # Panics
+///
+/// Panics if m is zero.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::apery::apery_mod;
+///
+/// assert_eq!(apery_mod(3, 1000), 445);
+/// assert_eq!(apery_mod(100, 1_000_000_007), 157_217_438);
+/// ```
+pub fn apery_mod(n: u64, m: u64) -> u64 {
+    binomial_sum_mod(n, m, |modular, a, b| {
+        let product = modular.mul(a, b);
+        modular.mul(product, product)
+    })
+}
+
+/// Returns the n<sup>th</sup> Apéry number for ζ(2) modulo m.
+///
+/// The binomial coefficients are taken from rows of Pascal's triangle reduced
+/// modulo m, so m need not be prime. This takes O(n²) time and O(n) space.
+///
+/// # Panics
+///
+/// Panics if m is zero.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::apery::apery_zeta2_mod;
+///
+/// assert_eq!(apery_zeta2_mod(4, 1000), 251);
+/// ```
+pub fn apery_zeta2_mod(n: u64, m: u64) -> u64 {
+    binomial_sum_mod(n, m, |modular, a, b| modular.mul(modular.mul(a, a), b))
+}
+
+/// Returns the sum over k of `term(modular, C(n, k), C(n + k, k))` modulo m.
+fn binomial_sum_mod(n: u64, m: u64, term: impl Fn(&Small, u64, u64) -> u64) -> u64 {
+    assert!(m != 0, "modulus must be nonzero");
+    let modular = Small { n: m };
+
+    let n = n as usize;
+    let mut row = vec![0u64; 2 * n + 1];
+    row[0] = 1 % m;
+    let mut row_n = Vec::new();
+    let mut sum = 0;
+    for r in 0..=2 * n {
+        if r > 0 {
+            for j in (1..=r).rev() {
+                row[j] = modular.add(row[j], row[j - 1]);
+            }
+        }
+        if r == n {
+            row_n = row[..=n].to_vec();
+        }
+        if r >= n {
+            let k = r - n;
+            sum = modular.add(sum, term(&modular, row_n[k], row[k]));
+        }
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_apery_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let max = rug::Integer::from(<$type>::MAX);
+                for n in 0..40u8 {
+                    let rug_n = rug::Integer::from(n);
+                    let exact = rug::Integer::nth_apery(&rug_n).unwrap();
+                    let expected = if exact <= max {
+                        ::core::option::Option::Some(rug::Integer::from(&exact))
+                    } else {
+                        ::core::option::Option::None
+                    };
+                    ::core::assert_eq!(
+                        <$type>::nth_apery(&(n as $type)).map(rug::Integer::from),
+                        expected
+                    );
+
+                    let exact = rug::Integer::nth_apery_zeta2(&rug_n).unwrap();
+                    let expected = if exact <= max {
+                        ::core::option::Option::Some(rug::Integer::from(&exact))
+                    } else {
+                        ::core::option::Option::None
+                    };
+                    ::core::assert_eq!(
+                        <$type>::nth_apery_zeta2(&(n as $type)).map(rug::Integer::from),
+                        expected
+                    );
+                }
+            }
+        };
+    }
+
+    test_apery_against_rug!(::core::primitive::u8, u8_apery);
+    test_apery_against_rug!(::core::primitive::u16, u16_apery);
+    test_apery_against_rug!(::core::primitive::u32, u32_apery);
+    test_apery_against_rug!(::core::primitive::u64, u64_apery);
+    test_apery_against_rug!(::core::primitive::u128, u128_apery);
+    test_apery_against_rug!(::core::primitive::usize, usize_apery);
+
+    #[test]
+    fn rug_apery_values() {
+        let zeta3: Vec<_> = (0..8u8)
+            .map(|n| rug::Integer::nth_apery(&rug::Integer::from(n)).unwrap())
+            .collect();
+        assert_eq!(zeta3, [1, 5, 73, 1445, 33001, 819005, 21460825, 584307365]);
+        let zeta2: Vec<_> = (0..8u8)
+            .map(|n| rug::Integer::nth_apery_zeta2(&rug::Integer::from(n)).unwrap())
+            .collect();
+        assert_eq!(zeta2, [1, 3, 19, 147, 1251, 11253, 104959, 1004307]);
+        assert_eq!(rug::Integer::nth_apery(&rug::Integer::from(-1)), None);
+    }
+
+    #[test]
+    fn modular_apery() {
+        for &m in &[1u64, 2, 12, 1_000_000_007, u64::MAX] {
+            let rug_m = rug::Integer::from(m);
+            for n in 0..30u64 {
+                let rug_n = rug::Integer::from(n);
+                assert_eq!(
+                    apery_mod(n, m),
+                    rug::Integer::nth_apery(&rug_n).unwrap() % &rug_m
+                );
+                assert_eq!(
+                    apery_zeta2_mod(n, m),
+                    rug::Integer::nth_apery_zeta2(&rug_n).unwrap() % &rug_m
+                );
+            }
+        }
+    }
+}
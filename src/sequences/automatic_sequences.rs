@@ -0,0 +1,192 @@
+/// Returns the n<sup>th</sup> term of the Thue–Morse sequence, the parity of
+/// the number of ones in the binary representation of n.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::automatic_sequences::thue_morse;
+///
+/// let terms: Vec<_> = (0..8).map(thue_morse).collect();
+/// assert_eq!(terms, vec![0, 1, 1, 0, 1, 0, 0, 1]);
+/// ```
+pub fn thue_morse(n: u64) -> u8 {
+    (n.count_ones() & 1) as u8
+}
+
+/// Returns the n<sup>th</sup> term of the Rudin–Shapiro sequence, which is
+/// −1 raised to the number of possibly overlapping occurrences of `11` in the
+/// binary representation of n.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::automatic_sequences::rudin_shapiro;
+///
+/// let terms: Vec<_> = (0..8).map(rudin_shapiro).collect();
+/// assert_eq!(terms, vec![1, 1, 1, -1, 1, 1, -1, 1]);
+/// ```
+pub fn rudin_shapiro(n: u64) -> i8 {
+    // Each set bit of n & (n >> 1) marks one occurrence of `11`.
+    if (n & (n >> 1)).count_ones() & 1 == 0 {
+        1
+    } else {
+        -1
+    }
+}
+
+/// Returns the n<sup>th</sup> term of the Baum–Sweet sequence, which is one if
+/// the binary representation of n contains no block of consecutive zeros of
+/// odd length and zero otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::automatic_sequences::baum_sweet;
+///
+/// let terms: Vec<_> = (0..10).map(baum_sweet).collect();
+/// assert_eq!(terms, vec![1, 1, 0, 1, 1, 0, 0, 1, 0, 1]);
+/// ```
+pub fn baum_sweet(n: u64) -> u8 {
+    if n == 0 {
+        return 1;
+    }
+
+    // Check the length of the lowest run of zeros, then strip it and the run
+    // of ones above it.
+    let mut n = n;
+    while n != 0 {
+        let zeros = n.trailing_zeros();
+        if zeros & 1 == 1 {
+            return 0;
+        }
+        n >>= zeros;
+        n = n.checked_shr(n.trailing_ones()).unwrap_or(0);
+    }
+    1
+}
+
+/// Returns an `Iterator<Item = u8>` implementation that goes through the
+/// Thue–Morse sequence from its zeroth term.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::automatic_sequences::thue_morse_iter;
+///
+/// let mut iter = thue_morse_iter();
+/// assert_eq!(iter.next(), Some(0));
+/// assert_eq!(iter.next(), Some(1));
+/// assert_eq!(iter.nth(1_000_000), Some(0));
+/// ```
+pub fn thue_morse_iter() -> AutomaticSequence<u8> {
+    AutomaticSequence::new(thue_morse)
+}
+
+/// Returns an `Iterator<Item = i8>` implementation that goes through the
+/// Rudin–Shapiro sequence from its zeroth term.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::automatic_sequences::rudin_shapiro_iter;
+///
+/// let sum: i64 = rudin_shapiro_iter().take(1 << 10).map(i64::from).sum();
+/// assert_eq!(sum, 32);
+/// ```
+pub fn rudin_shapiro_iter() -> AutomaticSequence<i8> {
+    AutomaticSequence::new(rudin_shapiro)
+}
+
+/// Returns an `Iterator<Item = u8>` implementation that goes through the
+/// Baum–Sweet sequence from its zeroth term.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::automatic_sequences::baum_sweet_iter;
+///
+/// let ones = baum_sweet_iter().take(16).filter(|&term| term == 1).count();
+/// assert_eq!(ones, 8);
+/// ```
+pub fn baum_sweet_iter() -> AutomaticSequence<u8> {
+    AutomaticSequence::new(baum_sweet)
+}
+
+/// The iterator returned by `thue_morse_iter`, `rudin_shapiro_iter`, and
+/// `baum_sweet_iter`. Its `nth` method takes constant time.
+#[derive(Clone)]
+pub struct AutomaticSequence<T> {
+    term: fn(u64) -> T,
+    n: Option<u64>,
+}
+
+impl<T> AutomaticSequence<T> {
+    fn new(term: fn(u64) -> T) -> Self {
+        Self { term, n: Some(0) }
+    }
+}
+
+impl<T> Iterator for AutomaticSequence<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.n?;
+        self.n = n.checked_add(1);
+        Some((self.term)(n))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.n = self.n.and_then(|m| m.checked_add(n as u64));
+        self.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thue_morse_recurrence() {
+        // t(2n) = t(n) and t(2n + 1) = 1 − t(n).
+        let terms: Vec<_> = thue_morse_iter().take(1 << 12).collect();
+        for n in 0..terms.len() / 2 {
+            assert_eq!(terms[2 * n], terms[n]);
+            assert_eq!(terms[2 * n + 1], 1 - terms[n]);
+        }
+    }
+
+    #[test]
+    fn rudin_shapiro_recurrence() {
+        // r(2n) = r(n) and r(2n + 1) = (−1)ⁿ r(n).
+        let terms: Vec<_> = rudin_shapiro_iter().take(1 << 12).collect();
+        for n in 0..terms.len() / 2 {
+            assert_eq!(terms[2 * n], terms[n]);
+            let sign = if n % 2 == 0 { 1 } else { -1 };
+            assert_eq!(terms[2 * n + 1], sign * terms[n]);
+        }
+        assert_eq!(rudin_shapiro(u64::MAX), -1);
+    }
+
+    #[test]
+    fn baum_sweet_against_digits() {
+        for n in 0..1u64 << 14 {
+            let digits = format!("{:b}", n);
+            let odd_zero_block = digits
+                .split('1')
+                .filter(|block| !block.is_empty())
+                .any(|block| block.len() % 2 == 1);
+            let expected = if n == 0 || !odd_zero_block { 1 } else { 0 };
+            assert_eq!(baum_sweet(n), expected, "n = {}", n);
+        }
+        assert_eq!(baum_sweet(u64::MAX), 1);
+        assert_eq!(baum_sweet(1 << 63), 0);
+    }
+
+    #[test]
+    fn iterators_end_at_the_index_limit() {
+        let mut iter = thue_morse_iter();
+        assert_eq!(iter.nth(usize::MAX), Some(thue_morse(usize::MAX as u64)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.nth(5), None);
+    }
+}
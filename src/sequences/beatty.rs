@@ -0,0 +1,123 @@
+use crate::number_theory::QuadraticIrrational;
+
+/// Returns an iterator over the Beatty sequence ⌊r⌋, ⌊2r⌋, ⌊3r⌋, … of the
+/// quadratic irrational r, going until just before overflow.
+///
+/// The terms are computed exactly, so there is no floating-point error even
+/// for very large multiples.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::QuadraticIrrational;
+/// use mathematical::sequences::beatty::beatty_sequence;
+///
+/// let phi = QuadraticIrrational::golden_ratio();
+/// let lower_wythoff: Vec<_> = beatty_sequence(phi).take(8).collect();
+/// assert_eq!(lower_wythoff, vec![1, 3, 4, 6, 8, 9, 11, 12]);
+///
+/// assert_eq!(beatty_sequence(phi).nth(999_999_999), Some(1_618_033_988));
+/// ```
+pub fn beatty_sequence(r: QuadraticIrrational) -> BeattySequence {
+    BeattySequence { r, n: 1 }
+}
+
+/// Returns an `Option` containing either an iterator over the Beatty sequence
+/// of r / (r − 1), which together with the Beatty sequence of r contains
+/// every positive integer exactly once, or `None` if r is not greater than
+/// one or the complement cannot be represented.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::number_theory::QuadraticIrrational;
+/// use mathematical::sequences::beatty::complementary_beatty_sequence;
+///
+/// let phi = QuadraticIrrational::golden_ratio();
+/// let upper_wythoff: Vec<_> = complementary_beatty_sequence(phi).unwrap().take(8).collect();
+/// assert_eq!(upper_wythoff, vec![2, 5, 7, 10, 13, 15, 18, 20]);
+///
+/// let half_sqrt_2 = QuadraticIrrational::new(0, 1, 2, 2).unwrap();
+/// assert!(complementary_beatty_sequence(half_sqrt_2).is_none());
+/// ```
+pub fn complementary_beatty_sequence(r: QuadraticIrrational) -> Option<BeattySequence> {
+    if r.floor()? < 1 {
+        return None;
+    }
+    Some(beatty_sequence(r.complement()?))
+}
+
+/// The iterator returned by `beatty_sequence` and
+/// `complementary_beatty_sequence`.
+#[derive(Clone, Debug)]
+pub struct BeattySequence {
+    r: QuadraticIrrational,
+    n: i128,
+}
+
+impl BeattySequence {
+    /// Returns the number whose multiples this sequence takes the floors of.
+    pub fn ratio(&self) -> QuadraticIrrational {
+        self.r
+    }
+}
+
+impl Iterator for BeattySequence {
+    type Item = i128;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let term = self.r.floor_of_multiple(self.n)?;
+        self.n += 1;
+        Some(term)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.n = self.n.checked_add(n as i128)?;
+        self.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_complementary(r: QuadraticIrrational, limit: i128) {
+        let mut seen = vec![0u8; limit as usize + 1];
+        for term in beatty_sequence(r).take_while(|&term| term <= limit) {
+            seen[term as usize] += 1;
+        }
+        for term in complementary_beatty_sequence(r)
+            .unwrap()
+            .take_while(|&term| term <= limit)
+        {
+            seen[term as usize] += 1;
+        }
+        assert_eq!(seen[0], 0);
+        assert!(seen[1..].iter().all(|&count| count == 1));
+    }
+
+    #[test]
+    fn complementary_sequences_partition_the_positive_integers() {
+        assert_complementary(QuadraticIrrational::golden_ratio(), 100_000);
+        assert_complementary(QuadraticIrrational::sqrt(2).unwrap(), 100_000);
+        assert_complementary(QuadraticIrrational::sqrt(3).unwrap(), 100_000);
+        assert_complementary(QuadraticIrrational::new(7, -2, 3, 1).unwrap(), 100_000);
+    }
+
+    #[test]
+    fn nth_matches_next() {
+        let r = QuadraticIrrational::sqrt(7).unwrap();
+        let terms: Vec<_> = beatty_sequence(r).take(1000).collect();
+        for (n, &term) in terms.iter().enumerate() {
+            assert_eq!(beatty_sequence(r).nth(n), Some(term));
+        }
+    }
+
+    #[test]
+    fn iteration_stops_before_overflow() {
+        let r = QuadraticIrrational::sqrt(2).unwrap();
+        let mut sequence = beatty_sequence(r);
+        assert!(sequence.nth(1 << 62).is_some());
+        assert_eq!(sequence.nth(usize::MAX), None);
+    }
+}
@@ -0,0 +1,291 @@
+/// Returns the n<sup>th</sup> term of Stern's diatomic sequence, also called
+/// fusc, where fusc(0) = 0, fusc(1) = 1, fusc(2n) = fusc(n), and
+/// fusc(2n + 1) = fusc(n) + fusc(n + 1).
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::calkin_wilf::fusc;
+///
+/// let terms: Vec<_> = (0..12).map(fusc).collect();
+/// assert_eq!(terms, vec![0, 1, 1, 2, 1, 3, 2, 3, 1, 4, 3, 5]);
+/// ```
+pub fn fusc(n: u64) -> u64 {
+    // fusc(n) = a·fusc(m) + b·fusc(m + 1) for the part m of n not yet read,
+    // starting from m = n, a = 1, and b = 0.
+    let (mut a, mut b) = (1, 0);
+    let mut n = n;
+    while n != 0 {
+        if n & 1 == 1 {
+            b += a;
+        } else {
+            a += b;
+        }
+        n >>= 1;
+    }
+    b
+}
+
+/// Returns an `Iterator<Item = u64>` implementation that goes through Stern's
+/// diatomic sequence fusc(0), fusc(1), fusc(2), … for every index that fits
+/// in a `u64`.
+///
+/// Consecutive terms fusc(n) / fusc(n + 1) run through the Calkin–Wilf
+/// sequence, starting from n = 1.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::calkin_wilf::stern_diatomic;
+///
+/// let terms: Vec<_> = stern_diatomic().take(12).collect();
+/// assert_eq!(terms, vec![0, 1, 1, 2, 1, 3, 2, 3, 1, 4, 3, 5]);
+/// assert_eq!(stern_diatomic().nth(1 << 40), Some(1));
+/// ```
+pub fn stern_diatomic() -> SternDiatomic {
+    SternDiatomic {
+        n: Some(0),
+        terms: (0, 1),
+    }
+}
+
+/// The iterator returned by `stern_diatomic`. Each step takes constant time,
+/// and its `nth` method takes time logarithmic in the index.
+#[derive(Clone, Debug)]
+pub struct SternDiatomic {
+    n: Option<u64>,
+    // fusc(n) and fusc(n + 1).
+    terms: (u64, u64),
+}
+
+impl Iterator for SternDiatomic {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.n?;
+        let (a, b) = self.terms;
+        self.n = n.checked_add(1);
+        // fusc(n + 2) = fusc(n) + fusc(n + 1) − 2 (fusc(n) mod fusc(n + 1)),
+        // and fusc(n + 1) is only zero when n + 1 is.
+        self.terms = (b, a + b - 2 * (a % b));
+        Some(a)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.n = self.n.and_then(|m| m.checked_add(n as u64));
+        if let Some(m) = self.n {
+            self.terms = (fusc(m), m.checked_add(1).map_or(1, fusc));
+        }
+        self.next()
+    }
+}
+
+impl core::iter::FusedIterator for SternDiatomic {}
+
+/// Returns the n<sup>th</sup> term of the Calkin–Wilf sequence, counting
+/// from zero, as a `(numerator, denominator)` fraction in lowest terms. This
+/// is fusc(n + 1) / fusc(n + 2).
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::calkin_wilf::nth_rational;
+///
+/// assert_eq!(nth_rational(0), (1, 1));
+/// assert_eq!(nth_rational(4), (3, 2));
+/// assert_eq!(nth_rational(u64::MAX), (1, 65));
+/// ```
+pub fn nth_rational(n: u64) -> (u64, u64) {
+    // The binary digits of n + 1 after the leading one spell the path from the
+    // root of the Calkin–Wilf tree, where a/b has the children a/(a + b) and
+    // (a + b)/b.
+    let m = n as u128 + 1;
+    let (mut a, mut b) = (1, 1);
+    for bit in (0..127 - m.leading_zeros()).rev() {
+        if m >> bit & 1 == 0 {
+            b += a;
+        } else {
+            a += b;
+        }
+    }
+    (a, b)
+}
+
+/// Returns an `Option` containing either the index, counting from zero, of
+/// p/q in the Calkin–Wilf sequence or `None` if p or q is zero, p/q is not in
+/// lowest terms, or the index would cause overflow.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::calkin_wilf::index_of;
+///
+/// assert_eq!(index_of(3, 2), Some(4));
+/// assert_eq!(index_of(1, 65), Some(u64::MAX));
+/// assert_eq!(index_of(1, 66), None);
+/// assert_eq!(index_of(2, 4), None);
+/// ```
+pub fn index_of(p: u64, q: u64) -> Option<u64> {
+    if p == 0 || q == 0 {
+        return None;
+    }
+
+    // Climb towards the root, taking each run of steps in the same direction
+    // at once as in the Euclidean algorithm. The path read from the leaf gives
+    // the digits of the one-based index from the lowest up.
+    let (mut p, mut q) = (p, q);
+    let (mut len, mut low) = (0u32, 0u128);
+    while p != q {
+        let (larger, smaller, bit) = if p < q { (q, p, 0) } else { (p, q, 1) };
+        let steps = if larger % smaller == 0 {
+            larger / smaller - 1
+        } else {
+            larger / smaller
+        };
+        if steps > 64 || len + steps as u32 > 64 {
+            return None;
+        }
+        if bit == 1 {
+            low |= ((1 << steps) - 1) << len;
+        }
+        len += steps as u32;
+        if p < q {
+            q -= steps * p;
+        } else {
+            p -= steps * q;
+        }
+    }
+    if p != 1 {
+        return None;
+    }
+    core::convert::TryFrom::try_from((1 << len | low) - 1).ok()
+}
+
+/// Returns an `Iterator<Item = (u64, u64)>` implementation that goes through
+/// the Calkin–Wilf sequence, which contains every positive rational number
+/// exactly once, as `(numerator, denominator)` fractions in lowest terms.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::calkin_wilf::calkin_wilf;
+///
+/// let terms: Vec<_> = calkin_wilf().take(7).collect();
+/// assert_eq!(terms, vec![(1, 1), (1, 2), (2, 1), (1, 3), (3, 2), (2, 3), (3, 1)]);
+/// ```
+pub fn calkin_wilf() -> CalkinWilf {
+    CalkinWilf {
+        n: Some(0),
+        term: (1, 1),
+    }
+}
+
+/// The iterator returned by `calkin_wilf`. Each step takes constant time, and
+/// its `nth` method takes time logarithmic in the index.
+#[derive(Clone, Debug)]
+pub struct CalkinWilf {
+    n: Option<u64>,
+    term: (u64, u64),
+}
+
+impl Iterator for CalkinWilf {
+    type Item = (u64, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.n?;
+        let (a, b) = self.term;
+        self.n = n.checked_add(1);
+        // Newman's formula: the term after x is 1 / (2⌊x⌋ − x + 1).
+        if self.n.is_some() {
+            self.term = (b, (2 * (a / b) + 1) * b - a);
+        }
+        Some((a, b))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.n = self.n.and_then(|m| m.checked_add(n as u64));
+        if let Some(m) = self.n {
+            self.term = nth_rational(m);
+        }
+        self.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::number_theory::Gcd;
+
+    #[test]
+    fn fusc_recurrence() {
+        for n in 1..1u64 << 12 {
+            assert_eq!(fusc(2 * n), fusc(n));
+            assert_eq!(fusc(2 * n + 1), fusc(n) + fusc(n + 1));
+        }
+        // fusc(2ᵏ − 1) = k.
+        assert_eq!(fusc(u64::MAX), 64);
+    }
+
+    #[test]
+    fn stern_diatomic_matches_fusc() {
+        for (n, term) in stern_diatomic().take(1 << 14).enumerate() {
+            assert_eq!(term, fusc(n as u64));
+        }
+        for n in [1000, 1 << 20, (1 << 32) + 5] {
+            let mut iter = stern_diatomic();
+            assert_eq!(iter.nth(n), Some(fusc(n as u64)));
+            assert_eq!(iter.next(), Some(fusc(n as u64 + 1)));
+            assert_eq!(iter.next(), Some(fusc(n as u64 + 2)));
+        }
+
+        let mut iter = stern_diatomic();
+        assert_eq!(iter.nth(usize::MAX), Some(fusc(usize::MAX as u64)));
+        if usize::MAX as u64 == u64::MAX {
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.next(), None);
+        } else {
+            assert_eq!(iter.next(), Some(fusc(usize::MAX as u64 + 1)));
+        }
+    }
+
+    #[test]
+    fn iterator_matches_fusc() {
+        for (n, term) in calkin_wilf().take(1 << 14).enumerate() {
+            let n = n as u64;
+            assert_eq!(term, (fusc(n + 1), fusc(n + 2)));
+            assert_eq!(term, nth_rational(n));
+            assert_eq!(index_of(term.0, term.1), Some(n));
+        }
+    }
+
+    #[test]
+    fn every_rational_appears() {
+        for p in 1..60 {
+            for q in 1..60 {
+                match index_of(p, q) {
+                    Some(n) => assert_eq!(nth_rational(n), (p, q)),
+                    None => assert_ne!(u64::gcd(&p, &q), 1, "{}/{}", p, q),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn indices_near_the_limit() {
+        for n in (u64::MAX - 1000..=u64::MAX).chain([1 << 63, (1 << 63) - 1]) {
+            let (p, q) = nth_rational(n);
+            assert_eq!(u64::gcd(&p, &q), 1);
+            assert_eq!(index_of(p, q), Some(n));
+        }
+        assert_eq!(index_of(65, 1), None);
+        assert_eq!(index_of(64, 1), Some(u64::MAX - 1));
+        assert_eq!(index_of(u64::MAX, u64::MAX - 1), None);
+
+        let mut iter = calkin_wilf();
+        assert_eq!(iter.nth(usize::MAX), Some(nth_rational(usize::MAX as u64)));
+        if usize::MAX as u64 == u64::MAX {
+            assert_eq!(iter.next(), None);
+        }
+        assert_eq!(calkin_wilf().nth(1000), Some(nth_rational(1000)));
+    }
+}
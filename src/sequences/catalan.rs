@@ -0,0 +1,331 @@
+use crate::number_theory::primes::{Modular, Small};
+
+pub trait Catalan: Sized {
+    /// Returns an `Option` containing either the n<sup>th</sup> Catalan
+    /// number, C(2n, n) / (n + 1), or `None` if that would cause overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Catalan;
+    ///
+    /// let terms: Vec<_> = (0..8).map(|n| u32::nth_catalan(&n).unwrap()).collect();
+    /// assert_eq!(terms, vec![1, 1, 2, 5, 14, 42, 132, 429]);
+    /// assert_eq!(u8::nth_catalan(&6), Some(132));
+    /// assert_eq!(u8::nth_catalan(&7), None);
+    /// ```
+    fn nth_catalan(n: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the entry of Catalan's triangle
+    /// in row n and column k, (n − k + 1) / (n + 1) · C(n + k, k), or `None`
+    /// if that would cause overflow. Entries with k greater than n are zero.
+    ///
+    /// This is also the ballot number counting the arrangements of n votes
+    /// for one candidate and k for another in which the second candidate is
+    /// never ahead, and the diagonal k = n is the Catalan sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Catalan;
+    ///
+    /// assert_eq!(u32::catalan_triangle(&5, &3), Some(28));
+    /// assert_eq!(u32::catalan_triangle(&5, &5), Some(42));
+    /// assert_eq!(u32::catalan_triangle(&3, &5), Some(0));
+    /// assert_eq!(u64::catalan_triangle(&u64::MAX, &1), Some(u64::MAX));
+    /// ```
+    fn catalan_triangle(n: &Self, k: &Self) -> Option<Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through row
+    /// n of Catalan's triangle from column zero to column n, stopping early
+    /// just before overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Catalan;
+    ///
+    /// let row: Vec<_> = u32::catalan_triangle_row(&5).collect();
+    /// assert_eq!(row, vec![1, 5, 14, 28, 42, 42]);
+    ///
+    /// let row: Vec<_> = u8::catalan_triangle_row(&7).collect();
+    /// assert_eq!(row, vec![1, 7, 27, 75, 165]);
+    /// ```
+    fn catalan_triangle_row(n: &Self) -> CatalanTriangleRow<Self>;
+}
+
+/// The iterator returned by `Catalan::catalan_triangle_row`.
+pub struct CatalanTriangleRow<T> {
+    n: T,
+    k: T,
+    entry: Option<T>,
+}
+
+macro_rules! catalan_trait_for_unsigned {
+    ($type:ty) => {
+        impl $crate::sequences::catalan::Catalan for $type {
+            fn nth_catalan(n: &Self) -> ::core::option::Option<Self> {
+                Self::catalan_triangle(n, n)
+            }
+
+            fn catalan_triangle(n: &Self, k: &Self) -> ::core::option::Option<Self> {
+                let (n, k) = (*n, *k);
+                if k > n {
+                    return ::core::option::Option::Some(0);
+                }
+                let mut entry = 1;
+                for j in 1..=k {
+                    entry = Self::next_in_row(n, j, entry)?;
+                }
+                ::core::option::Option::Some(entry)
+            }
+
+            fn catalan_triangle_row(
+                n: &Self,
+            ) -> $crate::sequences::catalan::CatalanTriangleRow<Self> {
+                $crate::sequences::catalan::CatalanTriangleRow {
+                    n: *n,
+                    k: 0,
+                    entry: ::core::option::Option::Some(1),
+                }
+            }
+        }
+
+        impl $crate::sequences::catalan::CatalanHelpers for $type {
+            fn next_in_row(n: Self, k: Self, previous: Self) -> ::core::option::Option<Self> {
+                if k == 1 {
+                    return ::core::option::Option::Some(n);
+                }
+
+                // The ratio of consecutive entries is
+                // (n + k)(n − k + 1) / (k (n − k + 2)). After cancelling the
+                // denominator against the numerator factors, what is left of
+                // it divides the previous entry, so only the result can
+                // overflow. Past k = 1, n + k only overflows when the result
+                // does.
+                let mut numerators = [n.checked_add(k)?, n - k + 1];
+                let mut denominators = [k, n - k + 2];
+                for b in denominators.iter_mut() {
+                    for a in numerators.iter_mut() {
+                        let g = <$type as $crate::number_theory::Gcd>::gcd(a, b);
+                        *a /= g;
+                        *b /= g;
+                    }
+                }
+                (previous / denominators[0] / denominators[1])
+                    .checked_mul(numerators[0])?
+                    .checked_mul(numerators[1])
+            }
+        }
+
+        impl ::core::iter::Iterator for $crate::sequences::catalan::CatalanTriangleRow<$type> {
+            type Item = $type;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                let entry = self.entry?;
+                self.entry = if self.k < self.n {
+                    self.k += 1;
+                    <$type as $crate::sequences::catalan::CatalanHelpers>::next_in_row(
+                        self.n, self.k, entry,
+                    )
+                } else {
+                    ::core::option::Option::None
+                };
+                ::core::option::Option::Some(entry)
+            }
+        }
+    };
+}
+
+trait CatalanHelpers: Sized {
+    /// Returns the entry in row n and column k of Catalan's triangle given
+    /// the entry to its left, for k from 1 to n.
+    fn next_in_row(n: Self, k: Self, previous: Self) -> Option<Self>;
+}
+
+catalan_trait_for_unsigned!(::core::primitive::u8);
+catalan_trait_for_unsigned!(::core::primitive::u16);
+catalan_trait_for_unsigned!(::core::primitive::u32);
+catalan_trait_for_unsigned!(::core::primitive::u64);
+catalan_trait_for_unsigned!(::core::primitive::u128);
+catalan_trait_for_unsigned!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Catalan for rug::Integer {
+    fn nth_catalan(n: &Self) -> Option<Self> {
+        Self::catalan_triangle(n, n)
+    }
+
+    fn catalan_triangle(n: &Self, k: &Self) -> Option<Self> {
+        if *n < 0 || *k < 0 {
+            return None;
+        }
+        if k > n {
+            return Some(Self::new());
+        }
+        let (n, k) = (n.to_u32()?, k.to_u32()?);
+        let total = n.checked_add(k)?;
+        let mut entry = Self::from(Self::binomial_u(total, k));
+        if k > 0 {
+            entry -= Self::from(Self::binomial_u(total, k - 1));
+        }
+        Some(entry)
+    }
+
+    fn catalan_triangle_row(n: &Self) -> CatalanTriangleRow<Self> {
+        CatalanTriangleRow {
+            n: n.clone(),
+            k: Self::new(),
+            entry: if *n < 0 { None } else { Some(Self::from(1)) },
+        }
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for CatalanTriangleRow<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.entry.take()?;
+        if self.k < self.n {
+            self.k += 1;
+            // The next entry is entry · (n + k)(n − k + 1) / (k (n − k + 2)).
+            let difference = rug::Integer::from(&self.n - &self.k);
+            let numerator = rug::Integer::from(&self.n + &self.k) * (difference.clone() + 1u8);
+            let denominator = (difference + 2u8) * &self.k;
+            self.entry = Some(rug::Integer::from(&entry * &numerator) / denominator);
+        }
+        Some(entry)
+    }
+}
+
+/// Returns the entry in row n and column k of Catalan's triangle modulo m.
+///
+/// The entry is found as the difference C(n + k, k) − C(n + k, k − 1) of
+/// binomial coefficients taken from Pascal's triangle reduced modulo m, so m
+/// need not be prime. This takes O((n + k) k) time and O(k) space.
+///
+/// # Panics
+///
+/// Panics if m is zero.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::catalan::catalan_triangle_mod;
+///
+/// assert_eq!(catalan_triangle_mod(5, 3, 10), 8);
+/// assert_eq!(catalan_triangle_mod(100, 100, 1_000_000_007), 558_488_487);
+/// ```
+pub fn catalan_triangle_mod(n: u64, k: u64, m: u64) -> u64 {
+    assert!(m != 0, "modulus must be nonzero");
+    if k > n {
+        return 0;
+    }
+
+    // Only the first k + 1 entries of each row of Pascal's triangle are
+    // needed.
+    let modular = Small { n: m };
+    let k = k as usize;
+    let mut row = vec![0u64; k + 1];
+    row[0] = 1 % m;
+    for r in 1..=n + k as u64 {
+        let width = core::cmp::min(r, k as u64) as usize;
+        for j in (1..=width).rev() {
+            row[j] = modular.add(row[j], row[j - 1]);
+        }
+    }
+    if k == 0 {
+        row[0]
+    } else {
+        modular.sub(row[k], row[k - 1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                for n in 0..=150u32 {
+                    let expected_row: Vec<_> = rug::Integer::catalan_triangle_row(&n.into())
+                        .take_while(|entry| *entry <= <$type>::MAX)
+                        .map(|entry| entry.to_u128().unwrap() as $type)
+                        .collect();
+                    let row: Vec<_> = <$type>::catalan_triangle_row(&(n as $type)).collect();
+                    assert_eq!(row, expected_row, "n = {}", n);
+
+                    for k in 0..=n + 1 {
+                        let big = rug::Integer::catalan_triangle(&n.into(), &k.into()).unwrap();
+                        let expected = if big <= <$type>::MAX {
+                            ::core::option::Option::Some(big.to_u128().unwrap() as $type)
+                        } else {
+                            ::core::option::Option::None
+                        };
+                        assert_eq!(
+                            <$type>::catalan_triangle(&(n as $type), &(k as $type)),
+                            expected,
+                            "n = {}, k = {}",
+                            n,
+                            k
+                        );
+                    }
+                }
+
+                let max = <$type>::MAX;
+                assert_eq!(<$type>::catalan_triangle(&max, &0), Some(1));
+                assert_eq!(<$type>::catalan_triangle(&max, &1), Some(max));
+                assert_eq!(<$type>::catalan_triangle(&max, &2), None);
+                assert_eq!(
+                    <$type>::catalan_triangle_row(&max).collect::<Vec<_>>(),
+                    vec![1, max]
+                );
+            }
+        };
+    }
+
+    test_against_rug!(::core::primitive::u8, test_u8_against_rug);
+    test_against_rug!(::core::primitive::u16, test_u16_against_rug);
+    test_against_rug!(::core::primitive::u32, test_u32_against_rug);
+    test_against_rug!(::core::primitive::u64, test_u64_against_rug);
+    test_against_rug!(::core::primitive::u128, test_u128_against_rug);
+    test_against_rug!(::core::primitive::usize, test_usize_against_rug);
+
+    #[test]
+    fn rug_row_matches_pointwise() {
+        for n in 0..60u32 {
+            let row: Vec<_> = rug::Integer::catalan_triangle_row(&n.into()).collect();
+            assert_eq!(row.len(), n as usize + 1);
+            for (k, entry) in row.iter().enumerate() {
+                assert_eq!(
+                    *entry,
+                    rug::Integer::catalan_triangle(&n.into(), &(k as u32).into()).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn modular_matches_exact() {
+        for m in [1u64, 2, 12, 1_000_000_007, u64::MAX] {
+            for n in 0..40u64 {
+                for k in 0..=n + 1 {
+                    let exact = rug::Integer::catalan_triangle(&n.into(), &k.into()).unwrap();
+                    assert_eq!(
+                        catalan_triangle_mod(n, k, m),
+                        (exact % m).to_u64().unwrap(),
+                        "n = {}, k = {}, m = {}",
+                        n,
+                        k,
+                        m
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,328 @@
+pub trait Collatz: Sized {
+    /// The exact type of the `Iterator<Item = Self>` returned by
+    /// `collatz_iter`.
+    type Iter: Iterator<Item = Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through the
+    /// Collatz trajectory of n, repeatedly halving even terms and sending odd
+    /// terms m to 3m + 1, from n itself until it reaches one or until just
+    /// before overflow. The trajectory is empty if n is not positive.
+    ///
+    /// Whether the trajectory was cut short by overflow can be checked with
+    /// `CollatzIter::overflowed`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Collatz;
+    ///
+    /// let terms: Vec<_> = u8::collatz_iter(&6).collect();
+    /// assert_eq!(terms, vec![6, 3, 10, 5, 16, 8, 4, 2, 1]);
+    ///
+    /// // 27 goes on to 142, which does not fit in an i8.
+    /// let mut iter = i8::collatz_iter(&27);
+    /// let terms: Vec<_> = iter.by_ref().collect();
+    /// assert_eq!(terms, vec![27, 82, 41, 124, 62, 31, 94, 47]);
+    /// assert!(iter.overflowed());
+    ///
+    /// assert_eq!(i8::collatz_iter(&0).next(), None);
+    /// ```
+    fn collatz_iter(n: &Self) -> Self::Iter;
+
+    /// Returns an `Option` containing either the total stopping time of n,
+    /// the number of steps its Collatz trajectory takes to reach one, or
+    /// `None` if the trajectory would cause overflow or n is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Collatz;
+    ///
+    /// assert_eq!(u8::stopping_time(&1), Some(0));
+    /// assert_eq!(u8::stopping_time(&6), Some(8));
+    /// assert_eq!(u16::stopping_time(&27), Some(111));
+    /// assert_eq!(u8::stopping_time(&27), None);
+    /// assert_eq!(i8::stopping_time(&-1), None);
+    /// ```
+    fn stopping_time(n: &Self) -> Option<u64>;
+
+    /// Returns an `Option` containing either the largest term of the Collatz
+    /// trajectory of n or `None` if the trajectory would cause overflow or n
+    /// is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Collatz;
+    ///
+    /// assert_eq!(u8::max_excursion(&6), Some(16));
+    /// assert_eq!(u16::max_excursion(&27), Some(9232));
+    /// assert_eq!(u8::max_excursion(&27), None);
+    /// assert_eq!(u8::max_excursion(&0), None);
+    /// ```
+    fn max_excursion(n: &Self) -> Option<Self>;
+}
+
+/// The iterator returned by `Collatz::collatz_iter`.
+#[derive(Clone, Debug)]
+pub struct CollatzIter<T> {
+    term: Option<T>,
+    overflowed: bool,
+}
+
+impl<T> CollatzIter<T> {
+    /// Returns whether the trajectory ended because its next term would have
+    /// caused overflow rather than because it reached one.
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+}
+
+macro_rules! collatz_trait_for_primitive {
+    ($type:ty) => {
+        impl $crate::sequences::collatz::Collatz for $type {
+            type Iter = $crate::sequences::collatz::CollatzIter<$type>;
+
+            fn collatz_iter(n: &Self) -> Self::Iter {
+                $crate::sequences::collatz::CollatzIter {
+                    term: if *n > 0 {
+                        ::core::option::Option::Some(*n)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    overflowed: false,
+                }
+            }
+
+            fn stopping_time(n: &Self) -> ::core::option::Option<u64> {
+                let mut iter = Self::collatz_iter(n);
+                let terms = iter.by_ref().count() as u64;
+                if terms == 0 || iter.overflowed {
+                    ::core::option::Option::None
+                } else {
+                    ::core::option::Option::Some(terms - 1)
+                }
+            }
+
+            fn max_excursion(n: &Self) -> ::core::option::Option<Self> {
+                let mut iter = Self::collatz_iter(n);
+                let max = iter.by_ref().max();
+                if iter.overflowed {
+                    ::core::option::Option::None
+                } else {
+                    max
+                }
+            }
+        }
+
+        impl ::core::iter::Iterator for $crate::sequences::collatz::CollatzIter<$type> {
+            type Item = $type;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                let term = self.term?;
+                self.term = if term == 1 {
+                    ::core::option::Option::None
+                } else if term & 1 == 0 {
+                    ::core::option::Option::Some(term / 2)
+                } else {
+                    let next = term.checked_mul(3).and_then(|triple| triple.checked_add(1));
+                    self.overflowed = next.is_none();
+                    next
+                };
+                ::core::option::Option::Some(term)
+            }
+        }
+
+        impl ::core::iter::FusedIterator for $crate::sequences::collatz::CollatzIter<$type> {}
+    };
+}
+
+collatz_trait_for_primitive!(::core::primitive::i8);
+collatz_trait_for_primitive!(::core::primitive::u8);
+collatz_trait_for_primitive!(::core::primitive::i16);
+collatz_trait_for_primitive!(::core::primitive::u16);
+collatz_trait_for_primitive!(::core::primitive::i32);
+collatz_trait_for_primitive!(::core::primitive::u32);
+collatz_trait_for_primitive!(::core::primitive::i64);
+collatz_trait_for_primitive!(::core::primitive::u64);
+collatz_trait_for_primitive!(::core::primitive::i128);
+collatz_trait_for_primitive!(::core::primitive::u128);
+collatz_trait_for_primitive!(::core::primitive::isize);
+collatz_trait_for_primitive!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Collatz for rug::Integer {
+    type Iter = CollatzIter<rug::Integer>;
+
+    fn collatz_iter(n: &Self) -> Self::Iter {
+        CollatzIter {
+            term: if *n > 0 { Some(n.clone()) } else { None },
+            overflowed: false,
+        }
+    }
+
+    fn stopping_time(n: &Self) -> Option<u64> {
+        if *n <= 0 {
+            return None;
+        }
+        // Each odd step is followed by a halving, and runs of halvings are
+        // taken at once.
+        let mut n = n.clone();
+        let mut steps = 0;
+        loop {
+            let zeros = n.find_one(0).unwrap();
+            n >>= zeros;
+            steps += zeros as u64;
+            if n == 1 {
+                return Some(steps);
+            }
+            n *= 3u8;
+            n += 1u8;
+            steps += 1;
+        }
+    }
+
+    fn max_excursion(n: &Self) -> Option<Self> {
+        if *n <= 0 {
+            return None;
+        }
+        // The largest term is either n or reached by an odd step.
+        let mut max = n.clone();
+        let mut n = n.clone();
+        loop {
+            let zeros = n.find_one(0).unwrap();
+            n >>= zeros;
+            if n == 1 {
+                return Some(max);
+            }
+            n *= 3u8;
+            n += 1u8;
+            if n > max {
+                max.clone_from(&n);
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for CollatzIter<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let term = self.term.take()?;
+        if term != 1 {
+            self.term = Some(if term.is_even() {
+                rug::Integer::from(&term >> 1)
+            } else {
+                rug::Integer::from(&term * 3u8) + 1u8
+            });
+        }
+        Some(term)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let max = rug::Integer::from(<$type>::MAX);
+                for n in 1..=2000u16 {
+                    let n = match <$type as ::core::convert::TryFrom<u16>>::try_from(n) {
+                        ::core::result::Result::Ok(n) => n,
+                        ::core::result::Result::Err(_) => break,
+                    };
+                    let big = rug::Integer::from(n);
+                    let expected: ::std::vec::Vec<rug::Integer> = rug::Integer::collatz_iter(&big)
+                        .take_while(|term| *term <= max)
+                        .collect();
+                    let fits = rug::Integer::max_excursion(&big).unwrap() <= max;
+
+                    let mut iter = <$type>::collatz_iter(&n);
+                    let terms: ::std::vec::Vec<rug::Integer> =
+                        iter.by_ref().map(rug::Integer::from).collect();
+                    ::core::assert_eq!(terms, expected);
+                    ::core::assert_eq!(iter.overflowed(), !fits);
+                    ::core::assert_eq!(iter.next(), ::core::option::Option::None);
+
+                    ::core::assert_eq!(
+                        <$type>::stopping_time(&n),
+                        rug::Integer::stopping_time(&big).filter(|_| fits)
+                    );
+                    ::core::assert_eq!(
+                        <$type>::max_excursion(&n).map(rug::Integer::from),
+                        rug::Integer::max_excursion(&big).filter(|_| fits)
+                    );
+                }
+
+                // The largest odd values overflow at once, and the largest
+                // power of two halves down to one.
+                let odd = if <$type>::MAX % 2 == 1 {
+                    <$type>::MAX
+                } else {
+                    <$type>::MAX - 1
+                };
+                ::core::assert_eq!(<$type>::stopping_time(&odd), ::core::option::Option::None);
+                let power = (<$type>::MAX >> 1) + 1;
+                ::core::assert_eq!(
+                    <$type>::stopping_time(&power),
+                    ::core::option::Option::Some(power.trailing_zeros() as u64)
+                );
+                ::core::assert_eq!(
+                    <$type>::max_excursion(&power),
+                    ::core::option::Option::Some(power)
+                );
+                ::core::assert_eq!(
+                    <$type>::collatz_iter(&0).next(),
+                    ::core::option::Option::None
+                );
+                ::core::assert_eq!(<$type>::stopping_time(&0), ::core::option::Option::None);
+            }
+        };
+    }
+
+    test_against_rug!(::core::primitive::i8, i8_collatz);
+    test_against_rug!(::core::primitive::u8, u8_collatz);
+    test_against_rug!(::core::primitive::i16, i16_collatz);
+    test_against_rug!(::core::primitive::u16, u16_collatz);
+    test_against_rug!(::core::primitive::i32, i32_collatz);
+    test_against_rug!(::core::primitive::u32, u32_collatz);
+    test_against_rug!(::core::primitive::i64, i64_collatz);
+    test_against_rug!(::core::primitive::u64, u64_collatz);
+    test_against_rug!(::core::primitive::i128, i128_collatz);
+    test_against_rug!(::core::primitive::u128, u128_collatz);
+    test_against_rug!(::core::primitive::isize, isize_collatz);
+    test_against_rug!(::core::primitive::usize, usize_collatz);
+
+    #[test]
+    fn rug_matches_iterator() {
+        for n in 1..3000u32 {
+            let n = rug::Integer::from(n);
+            let terms: Vec<_> = rug::Integer::collatz_iter(&n).collect();
+            assert_eq!(terms.last(), Some(&rug::Integer::from(1)));
+            assert_eq!(
+                rug::Integer::stopping_time(&n),
+                Some(terms.len() as u64 - 1)
+            );
+            assert_eq!(rug::Integer::max_excursion(&n), terms.into_iter().max());
+        }
+    }
+
+    #[test]
+    fn rug_large_trajectories() {
+        // 2¹⁰⁰ − 1 climbs through 3¹⁰⁰ − 1 before it can come down.
+        let n = rug::Integer::from(rug::Integer::u_pow_u(2, 100)) - 1u8;
+        let max = rug::Integer::max_excursion(&n).unwrap();
+        assert!(max >= rug::Integer::from(rug::Integer::u_pow_u(3, 100)) - 1u8);
+        assert_eq!(
+            rug::Integer::stopping_time(&n),
+            Some(rug::Integer::collatz_iter(&n).count() as u64 - 1)
+        );
+        assert_eq!(rug::Integer::stopping_time(&(-5).into()), None);
+    }
+}
@@ -0,0 +1,818 @@
+/// Returns whether n is a happy number in the given base, so that repeatedly
+/// replacing it with the sum of the squares of its base-b digits reaches
+/// one.
+///
+/// Every trajectory ends in a cycle, as after one step the value is at most
+/// 64 (b − 1)², and the cycle is found by Floyd's algorithm in constant space.
+/// One is a fixed point, so n is happy exactly when its cycle contains one.
+/// Trajectories in base b commonly run for a sizable fraction of b steps
+/// before they repeat, so this is slow for very large bases.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::digit_dynamics::is_happy;
+///
+/// // 7 → 49 → 97 → 130 → 10 → 1.
+/// assert!(is_happy(7, 10));
+/// // 4 → 16 → 37 → 58 → 89 → 145 → 42 → 20 → 4.
+/// assert!(!is_happy(4, 10));
+/// // Every positive number is happy in binary.
+/// assert!(is_happy(u64::MAX, 2));
+/// assert!(!is_happy(0, 10));
+/// ```
+pub fn is_happy(n: u64, base: u32) -> bool {
+    assert!(base >= 2, "base must be at least two");
+    let step = |x| square_digit_sum(x, base);
+    let (mut slow, mut fast) = (step(n as u128), step(step(n as u128)));
+    while slow != fast {
+        slow = step(slow);
+        fast = step(step(fast));
+    }
+    slow == 1
+}
+
+/// Returns an `Iterator<Item = u64>` implementation that goes through the
+/// happy numbers in the given base in increasing order, through all those
+/// that fit in a `u64`.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::digit_dynamics::happy_numbers;
+///
+/// let terms: Vec<_> = happy_numbers(10).take(12).collect();
+/// assert_eq!(terms, vec![1, 7, 10, 13, 19, 23, 28, 31, 32, 44, 49, 68]);
+///
+/// let terms: Vec<_> = happy_numbers(3).take(6).collect();
+/// assert_eq!(terms, vec![1, 3, 9, 13, 17, 23]);
+/// ```
+pub fn happy_numbers(base: u32) -> HappyNumbers {
+    assert!(base >= 2, "base must be at least two");
+    HappyNumbers { base, n: Some(1) }
+}
+
+/// The iterator returned by `happy_numbers`.
+#[derive(Clone, Debug)]
+pub struct HappyNumbers {
+    base: u32,
+    // The next number to try, or `None` once every u64 has been tried.
+    n: Option<u64>,
+}
+
+impl Iterator for HappyNumbers {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let n = self.n?;
+            self.n = n.checked_add(1);
+            if is_happy(n, self.base) {
+                return Some(n);
+            }
+        }
+    }
+}
+
+impl core::iter::FusedIterator for HappyNumbers {}
+
+/// Returns the sum of the squares of the base-b digits of x, which cannot
+/// overflow as there are at most 128 digits, each with a square below
+/// 2<sup>64</sup>.
+fn square_digit_sum(mut x: u128, base: u32) -> u128 {
+    let base = base as u128;
+    let mut sum = 0;
+    while x != 0 {
+        let digit = x % base;
+        sum += digit * digit;
+        x /= base;
+    }
+    sum
+}
+
+/// Returns one step of Kaprekar's routine on n written with the given number
+/// of base-b digits, counting leading zeros: the digits arranged in
+/// descending order minus the digits arranged in ascending order.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two, `width` is zero, base<sup>width</sup>
+/// does not fit in a `u64`, or n has more than `width` digits.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::digit_dynamics::kaprekar_step;
+///
+/// assert_eq!(kaprekar_step(3524, 4, 10), 5432 - 2345);
+/// // 0111 becomes 1110 − 0111.
+/// assert_eq!(kaprekar_step(111, 4, 10), 999);
+/// assert_eq!(kaprekar_step(6174, 4, 10), 6174);
+/// ```
+pub fn kaprekar_step(n: u64, width: u32, base: u32) -> u64 {
+    let limit = width_limit(width, base);
+    assert!(n < limit, "n must have at most width digits");
+    let mut counts = vec![0u32; base as usize];
+    let mut x = n;
+    for _ in 0..width {
+        counts[(x % base as u64) as usize] += 1;
+        x /= base as u64;
+    }
+    arrangement_difference(&counts, base)
+}
+
+/// Returns an `Iterator<Item = u64>` implementation that goes through n and
+/// the results of repeatedly applying `kaprekar_step` to it, stopping just
+/// before the first value that has already appeared.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two, `width` is zero, base<sup>width</sup>
+/// does not fit in a `u64`, or n has more than `width` digits.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::digit_dynamics::kaprekar_routine;
+///
+/// let terms: Vec<_> = kaprekar_routine(3524, 4, 10).collect();
+/// assert_eq!(terms, vec![3524, 3087, 8352, 6174]);
+///
+/// // Repdigits go straight to zero.
+/// let terms: Vec<_> = kaprekar_routine(777, 3, 10).collect();
+/// assert_eq!(terms, vec![777, 0]);
+/// ```
+pub fn kaprekar_routine(n: u64, width: u32, base: u32) -> KaprekarRoutine {
+    let limit = width_limit(width, base);
+    assert!(n < limit, "n must have at most width digits");
+    KaprekarRoutine {
+        width,
+        base,
+        next: Some(n),
+        seen: Vec::new(),
+    }
+}
+
+/// Returns the cycle that Kaprekar's routine on n eventually repeats, starting
+/// from its smallest value. A fixed point such as 6174 for four decimal
+/// digits is a cycle of length one.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two, `width` is zero, base<sup>width</sup>
+/// does not fit in a `u64`, or n has more than `width` digits.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::digit_dynamics::kaprekar_cycle;
+///
+/// assert_eq!(kaprekar_cycle(3524, 4, 10), vec![6174]);
+/// assert_eq!(kaprekar_cycle(123, 3, 10), vec![495]);
+/// // Two-digit numbers fall into a cycle of five instead.
+/// assert_eq!(kaprekar_cycle(21, 2, 10), vec![9, 81, 63, 27, 45]);
+/// ```
+pub fn kaprekar_cycle(n: u64, width: u32, base: u32) -> Vec<u64> {
+    let mut routine = kaprekar_routine(n, width, base);
+    routine.by_ref().for_each(drop);
+    // The step after the last value is the first repeat, where the cycle
+    // starts.
+    let last = *routine.seen.last().unwrap();
+    let start = kaprekar_step(last, width, base);
+    let position = routine.seen.iter().position(|&x| x == start).unwrap();
+    let mut cycle = routine.seen.split_off(position);
+    let smallest = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+    cycle.rotate_left(smallest);
+    cycle
+}
+
+/// Returns the fixed points of Kaprekar's routine for numbers with the given
+/// number of base-b digits in increasing order, such as 0 and 495 for three
+/// decimal digits or 0 and 6174 for four. Zero is always one, as it is where
+/// the repdigits go.
+///
+/// As the step depends only on which digits appear, this tries each multiset
+/// of `width` digits once, of which there are C(width + b − 1, width).
+///
+/// # Panics
+///
+/// Panics if `base` is less than two, `width` is zero, or base<sup>width</sup>
+/// does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::digit_dynamics::kaprekar_fixed_points;
+///
+/// assert_eq!(kaprekar_fixed_points(3, 10), vec![0, 495]);
+/// assert_eq!(kaprekar_fixed_points(4, 10), vec![0, 6174]);
+/// assert_eq!(kaprekar_fixed_points(2, 10), vec![0]);
+/// assert_eq!(kaprekar_fixed_points(6, 10), vec![0, 549_945, 631_764]);
+/// ```
+pub fn kaprekar_fixed_points(width: u32, base: u32) -> Vec<u64> {
+    fn search(counts: &mut Vec<u32>, digit: usize, left: u32, base: u32, found: &mut Vec<u64>) {
+        if digit + 1 == counts.len() {
+            counts[digit] = left;
+            let difference = arrangement_difference(counts, base);
+            let mut digits = vec![0u32; counts.len()];
+            let mut x = difference;
+            for _ in 0..counts.iter().sum::<u32>() {
+                digits[(x % base as u64) as usize] += 1;
+                x /= base as u64;
+            }
+            if digits == *counts {
+                found.push(difference);
+            }
+            return;
+        }
+        for count in 0..=left {
+            counts[digit] = count;
+            search(counts, digit + 1, left - count, base, found);
+        }
+    }
+
+    width_limit(width, base);
+    let mut found = Vec::new();
+    search(&mut vec![0; base as usize], 0, width, base, &mut found);
+    found.sort_unstable();
+    found.dedup();
+    found
+}
+
+/// The iterator returned by `kaprekar_routine`.
+#[derive(Clone, Debug)]
+pub struct KaprekarRoutine {
+    width: u32,
+    base: u32,
+    next: Option<u64>,
+    // The values produced so far. Trajectories are short, so a linear search
+    // for repeats is quick.
+    seen: Vec<u64>,
+}
+
+impl Iterator for KaprekarRoutine {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.next?;
+        self.seen.push(n);
+        let next = kaprekar_step(n, self.width, self.base);
+        self.next = if self.seen.contains(&next) {
+            None
+        } else {
+            Some(next)
+        };
+        Some(n)
+    }
+}
+
+impl core::iter::FusedIterator for KaprekarRoutine {}
+
+/// Returns whether n is a Kaprekar number in the given base, so that the
+/// base-b digits of n² can be split into a left part and a positive right
+/// part that add up to n, as 45² = 2025 and 20 + 25 = 45. One is taken to be
+/// a Kaprekar number, as 1² splits into zero and one.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::digit_dynamics::is_kaprekar_number;
+///
+/// let terms: Vec<_> = (1..3000).filter(|&n| is_kaprekar_number(n, 10)).collect();
+/// assert_eq!(terms, vec![1, 9, 45, 55, 99, 297, 703, 999, 2223, 2728]);
+///
+/// // 10 is not, as the right part of 100 would be zero.
+/// assert!(!is_kaprekar_number(10, 10));
+/// assert!(is_kaprekar_number(u64::MAX, 2));
+/// ```
+pub fn is_kaprekar_number(n: u64, base: u32) -> bool {
+    assert!(base >= 2, "base must be at least two");
+    let square = n as u128 * n as u128;
+    let base = base as u128;
+    let mut place = base;
+    while place <= square {
+        let (left, right) = (square / place, square % place);
+        if right != 0 && left + right == n as u128 {
+            return true;
+        }
+        place = match place.checked_mul(base) {
+            Some(place) => place,
+            None => break,
+        };
+    }
+    // The split with nothing on the left works only for one.
+    n == 1
+}
+
+/// Returns whether n is a narcissistic number in the given base, that is,
+/// whether n is positive and is the sum of its k base-b digits each raised to
+/// the k<sup>th</sup> power, as 153 = 1³ + 5³ + 3³.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::digit_dynamics::is_narcissistic;
+///
+/// assert!(is_narcissistic(153, 10));
+/// assert!(is_narcissistic(4_679_307_774, 10));
+/// assert!(!is_narcissistic(154, 10));
+/// // 5 is 12 in base 3, and 1² + 2² = 5.
+/// assert!(is_narcissistic(5, 3));
+/// assert!(!is_narcissistic(0, 10));
+/// ```
+pub fn is_narcissistic(n: u64, base: u32) -> bool {
+    assert!(base >= 2, "base must be at least two");
+    let base = base as u64;
+    let mut width = 0;
+    let mut x = n;
+    while x != 0 {
+        width += 1;
+        x /= base;
+    }
+    let mut sum = 0u128;
+    let mut x = n;
+    while x != 0 {
+        sum += ((x % base) as u128).saturating_pow(width);
+        if sum > n as u128 {
+            return false;
+        }
+        x /= base;
+    }
+    n != 0 && sum == n as u128
+}
+
+/// Returns an `Iterator<Item = u64>` implementation that goes through every
+/// narcissistic number in the given base that fits in a `u64`, in increasing
+/// order, and then ends.
+///
+/// The numbers with each number of digits are found together, lazily, by
+/// trying each multiset of digits once rather than each number, as the sum of
+/// powers depends only on which digits appear. A width of w digits has
+/// C(w + b − 1, w) multisets, so going through the whole sequence takes a few
+/// tens of millions of steps in base 10.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::digit_dynamics::narcissistic_numbers;
+///
+/// let terms: Vec<_> = narcissistic_numbers(10).take(14).collect();
+/// assert_eq!(terms, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 153, 370, 371, 407, 1634]);
+///
+/// let terms: Vec<_> = narcissistic_numbers(3).collect();
+/// assert_eq!(terms, vec![1, 2, 5, 8, 17]);
+/// ```
+pub fn narcissistic_numbers(base: u32) -> NarcissisticNumbers {
+    assert!(base >= 2, "base must be at least two");
+    // Numbers of w digits are at least b^(w − 1).
+    let mut max_width = 1;
+    while (base as u64).checked_pow(max_width).is_some() {
+        max_width += 1;
+    }
+    NarcissisticNumbers {
+        base,
+        width: 1,
+        max_width,
+        current: Vec::new().into_iter(),
+    }
+}
+
+/// Returns an `Iterator<Item = u64>` implementation that goes through the
+/// narcissistic numbers in the given base with exactly `width` digits that
+/// fit in a `u64`, in increasing order.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::digit_dynamics::narcissistic_numbers_with_width;
+///
+/// let terms: Vec<_> = narcissistic_numbers_with_width(3, 10).collect();
+/// assert_eq!(terms, vec![153, 370, 371, 407]);
+///
+/// // No decimal narcissistic number has two digits.
+/// assert_eq!(narcissistic_numbers_with_width(2, 10).next(), None);
+/// assert_eq!(narcissistic_numbers_with_width(0, 10).next(), None);
+/// ```
+pub fn narcissistic_numbers_with_width(width: u32, base: u32) -> NarcissisticNumbers {
+    assert!(base >= 2, "base must be at least two");
+    NarcissisticNumbers {
+        base,
+        width: 1,
+        max_width: 0,
+        current: narcissistic_of_width(width, base).into_iter(),
+    }
+}
+
+/// The iterator returned by `narcissistic_numbers` and
+/// `narcissistic_numbers_with_width`.
+#[derive(Clone, Debug)]
+pub struct NarcissisticNumbers {
+    base: u32,
+    // The next width to search, which is past the last once it exceeds
+    // `max_width`.
+    width: u32,
+    max_width: u32,
+    current: std::vec::IntoIter<u64>,
+}
+
+impl Iterator for NarcissisticNumbers {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(n) = self.current.next() {
+                return Some(n);
+            }
+            if self.width > self.max_width {
+                return None;
+            }
+            self.current = narcissistic_of_width(self.width, self.base).into_iter();
+            self.width += 1;
+        }
+    }
+}
+
+impl core::iter::FusedIterator for NarcissisticNumbers {}
+
+/// Returns the narcissistic numbers with the given number of base-b digits
+/// that fit in a `u64`, in increasing order.
+fn narcissistic_of_width(width: u32, base: u32) -> Vec<u64> {
+    struct Search {
+        width: u32,
+        base: u64,
+        // d^width for each digit d, saturating.
+        powers: Vec<u128>,
+        counts: Vec<u32>,
+        found: Vec<u64>,
+    }
+
+    impl Search {
+        fn go(&mut self, digit: usize, left: u32, sum: u128) {
+            if sum > u64::MAX as u128 {
+                return;
+            }
+            if digit == 0 {
+                self.counts[0] = left;
+                self.check(sum);
+                return;
+            }
+            let mut sum = sum;
+            for count in 0..=left {
+                self.counts[digit] = count;
+                self.go(digit - 1, left - count, sum);
+                sum = sum.saturating_add(self.powers[digit]);
+                if sum > u64::MAX as u128 {
+                    break;
+                }
+            }
+            self.counts[digit] = 0;
+        }
+
+        fn check(&mut self, sum: u128) {
+            let n = sum as u64;
+            let mut counts = vec![0; self.counts.len()];
+            let mut x = n;
+            let mut digits = 0;
+            while x != 0 {
+                counts[(x % self.base) as usize] += 1;
+                x /= self.base;
+                digits += 1;
+            }
+            counts[0] += self.width.saturating_sub(digits);
+            if n != 0 && digits == self.width && counts == self.counts {
+                self.found.push(n);
+            }
+        }
+    }
+
+    if width == 0 {
+        return Vec::new();
+    }
+    let mut search = Search {
+        width,
+        base: base as u64,
+        powers: (0..base as u128).map(|d| d.saturating_pow(width)).collect(),
+        counts: vec![0; base as usize],
+        found: Vec::new(),
+    };
+    search.go(base as usize - 1, width, 0);
+    search.found.sort_unstable();
+    search.found
+}
+
+/// Returns base<sup>width</sup>, checking the arguments to the Kaprekar
+/// routine.
+fn width_limit(width: u32, base: u32) -> u64 {
+    assert!(base >= 2, "base must be at least two");
+    assert!(width != 0, "width must be positive");
+    (base as u64)
+        .checked_pow(width)
+        .expect("numbers of that width must fit in a u64")
+}
+
+/// Returns the number with the given digit counts arranged in descending
+/// order minus the number with them in ascending order.
+fn arrangement_difference(counts: &[u32], base: u32) -> u64 {
+    let base = base as u64;
+    let (mut descending, mut ascending) = (0, 0);
+    for (digit, &count) in counts.iter().enumerate().rev() {
+        for _ in 0..count {
+            descending = descending * base + digit as u64;
+        }
+    }
+    for (digit, &count) in counts.iter().enumerate() {
+        for _ in 0..count {
+            ascending = ascending * base + digit as u64;
+        }
+    }
+    descending - ascending
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn naive_is_happy(n: u64, base: u32) -> bool {
+        let mut seen = HashSet::new();
+        let mut x = n as u128;
+        while seen.insert(x) {
+            x = square_digit_sum(x, base);
+        }
+        x == 1
+    }
+
+    #[test]
+    fn matches_naive() {
+        for base in 2..=16 {
+            for n in 0..3000 {
+                assert_eq!(
+                    is_happy(n, base),
+                    naive_is_happy(n, base),
+                    "{} in base {}",
+                    n,
+                    base
+                );
+            }
+            for n in u64::MAX - 100..=u64::MAX {
+                assert_eq!(
+                    is_happy(n, base),
+                    naive_is_happy(n, base),
+                    "{} in base {}",
+                    n,
+                    base
+                );
+            }
+        }
+        for base in [1000, 65_536] {
+            for n in (0..100).chain(u64::MAX - 10..=u64::MAX) {
+                assert_eq!(
+                    is_happy(n, base),
+                    naive_is_happy(n, base),
+                    "{} in base {}",
+                    n,
+                    base
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn happy_bases() {
+        // Two and four are the only bases below 5 × 10⁸ in which every
+        // positive number is happy.
+        for base in [2, 4] {
+            assert!(happy_numbers(base).take(1000).eq(1..=1000));
+        }
+        assert!(!happy_numbers(3).take(1000).eq(1..=1000));
+    }
+
+    #[test]
+    fn iterator_matches_is_happy() {
+        for base in [3, 7, 10] {
+            let terms: Vec<_> = happy_numbers(base).take_while(|&n| n < 10_000).collect();
+            let expected: Vec<_> = (1..10_000).filter(|&n| is_happy(n, base)).collect();
+            assert_eq!(terms, expected);
+        }
+
+        let mut iter = HappyNumbers {
+            base: 2,
+            n: Some(u64::MAX - 1),
+        };
+        assert_eq!(iter.next(), Some(u64::MAX - 1));
+        assert_eq!(iter.next(), Some(u64::MAX));
+        assert_eq!(iter.next(), None);
+    }
+
+    /// Returns the base-b digits of n, most significant first, padded with
+    /// zeros to the width.
+    fn digits(mut n: u64, width: u32, base: u32) -> Vec<u64> {
+        let mut digits = Vec::new();
+        for _ in 0..width {
+            digits.push(n % base as u64);
+            n /= base as u64;
+        }
+        digits.reverse();
+        digits
+    }
+
+    fn from_digits(digits: &[u64], base: u32) -> u64 {
+        digits.iter().fold(0, |n, &digit| n * base as u64 + digit)
+    }
+
+    #[test]
+    fn kaprekar_routine_by_brute_force() {
+        for (width, base) in [
+            (1, 10),
+            (2, 10),
+            (3, 10),
+            (4, 10),
+            (3, 2),
+            (4, 3),
+            (3, 16),
+            (5, 7),
+        ] {
+            let limit = (base as u64).pow(width);
+            let mut fixed_points = Vec::new();
+            for n in 0..limit {
+                let mut ascending = digits(n, width, base);
+                ascending.sort_unstable();
+                let descending: Vec<_> = ascending.iter().rev().copied().collect();
+                let step = from_digits(&descending, base) - from_digits(&ascending, base);
+                assert_eq!(kaprekar_step(n, width, base), step);
+                if step == n {
+                    fixed_points.push(n);
+                }
+
+                let terms: Vec<_> = kaprekar_routine(n, width, base).collect();
+                let unique: HashSet<_> = terms.iter().collect();
+                assert_eq!(unique.len(), terms.len());
+                assert!(terms.contains(&kaprekar_step(*terms.last().unwrap(), width, base)));
+
+                let cycle = kaprekar_cycle(n, width, base);
+                assert_eq!(cycle[0], *cycle.iter().min().unwrap());
+                for (i, &x) in cycle.iter().enumerate() {
+                    assert_eq!(kaprekar_step(x, width, base), cycle[(i + 1) % cycle.len()]);
+                }
+            }
+            assert_eq!(kaprekar_fixed_points(width, base), fixed_points);
+        }
+    }
+
+    #[test]
+    fn kaprekar_routine_limits() {
+        // The widest numbers that fit, whose steps depend only on their
+        // digits.
+        let n = (u64::MAX / 3) >> 1;
+        assert_eq!(
+            kaprekar_step(n, 63, 2),
+            kaprekar_step(n.reverse_bits() >> 1, 63, 2)
+        );
+        let n = 1_234_567_890_123_456_789;
+        assert_eq!(
+            kaprekar_step(n, 19, 10),
+            kaprekar_step(9_876_543_210_987_654_321, 19, 10)
+        );
+        for x in kaprekar_cycle(n, 19, 10) {
+            assert!(kaprekar_step(x, 19, 10) < 10_000_000_000_000_000_000);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "n must have at most width digits")]
+    fn kaprekar_routine_too_wide() {
+        kaprekar_step(10_000, 4, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "numbers of that width must fit in a u64")]
+    fn kaprekar_routine_overflow() {
+        kaprekar_fixed_points(20, 10);
+    }
+
+    #[test]
+    fn kaprekar_numbers() {
+        // The decimal Kaprekar numbers below 10⁶.
+        let expected = [
+            1, 9, 45, 55, 99, 297, 703, 999, 2223, 2728, 4879, 4950, 5050, 5292, 7272, 7777, 9999,
+            17344, 22222, 38962, 77778, 82656, 95121, 99999, 142857, 148149, 181819, 187110,
+            208495, 318682, 329967, 351352, 356643, 390313, 461539, 466830, 499500, 500500, 533170,
+            538461, 609687, 627615, 643357, 648648, 670033, 681318, 791505, 812890, 818181, 851851,
+            857143, 961038, 994708, 999999,
+        ];
+        let terms: Vec<_> = (1..1_000_000)
+            .filter(|&n| is_kaprekar_number(n, 10))
+            .collect();
+        assert_eq!(terms, expected);
+
+        for base in [2, 3, 7, 16] {
+            for n in 1..2000 {
+                let square = digits(n * n, 30, base);
+                let expected = (1..30).any(|split| {
+                    let right = from_digits(&square[split..], base);
+                    right != 0 && from_digits(&square[..split], base) + right == n
+                }) || n == 1;
+                assert_eq!(
+                    is_kaprekar_number(n, base),
+                    expected,
+                    "{} in base {}",
+                    n,
+                    base
+                );
+            }
+        }
+        // bᵏ − 1 is always one.
+        assert!(is_kaprekar_number(u64::MAX, 2));
+        assert!(is_kaprekar_number(9_999_999_999_999_999_999, 10));
+        assert!(!is_kaprekar_number(0, 10));
+    }
+
+    #[test]
+    fn narcissistic_by_brute_force() {
+        for base in 2..=16 {
+            let expected: Vec<_> = (0..100_000)
+                .filter(|&n| {
+                    let digits = digits(n, 20, base);
+                    let digits: Vec<_> = digits.iter().skip_while(|&&d| d == 0).collect();
+                    let sum: u64 = digits.iter().map(|&&d| d.pow(digits.len() as u32)).sum();
+                    n != 0 && sum == n
+                })
+                .collect();
+            for n in 0..100_000 {
+                assert_eq!(is_narcissistic(n, base), expected.contains(&n));
+            }
+            let terms: Vec<_> = narcissistic_numbers(base)
+                .take_while(|&n| n < 100_000)
+                .collect();
+            assert_eq!(terms, expected, "base {}", base);
+        }
+    }
+
+    #[test]
+    fn narcissistic_sequences_end() {
+        // The complete sequences for small bases.
+        assert_eq!(narcissistic_numbers(2).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(
+            narcissistic_numbers(3).collect::<Vec<_>>(),
+            vec![1, 2, 5, 8, 17]
+        );
+        assert_eq!(
+            narcissistic_numbers(4).collect::<Vec<_>>(),
+            vec![1, 2, 3, 28, 29, 35, 43, 55, 62, 83, 243]
+        );
+        for n in narcissistic_numbers(7) {
+            assert!(is_narcissistic(n, 7));
+        }
+
+        let decimal: Vec<_> = (10..=12)
+            .flat_map(|width| narcissistic_numbers_with_width(width, 10))
+            .collect();
+        assert_eq!(
+            decimal,
+            vec![
+                4_679_307_774,
+                32_164_049_650,
+                32_164_049_651,
+                40_028_394_225,
+                42_678_290_603,
+                44_708_635_679,
+                49_388_550_606,
+                82_693_916_578,
+                94_204_591_914,
+            ]
+        );
+        assert!(is_narcissistic(4_498_128_791_164_624_869, 10));
+        assert!(is_narcissistic(4_929_273_885_928_088_826, 10));
+        assert!(!is_narcissistic(u64::MAX, 10));
+    }
+
+    #[test]
+    #[should_panic(expected = "base must be at least two")]
+    fn base_one() {
+        is_happy(1, 1);
+    }
+}
@@ -0,0 +1,214 @@
+pub trait Fermat: Sized {
+    /// The exact type of the `Iterator<Item = Self>` returned by
+    /// `fermat_numbers`.
+    type Iter: Iterator<Item = Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through
+    /// the Fermat numbers F<sub>k</sub> = 2<sup>2<sup>k</sup></sup> + 1 for
+    /// k = 0, 1, 2, … until just before overflow.
+    ///
+    /// Only F<sub>0</sub> through F<sub>4</sub> are known to be prime, and
+    /// `pepin_test` tells whether any other is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Fermat;
+    ///
+    /// let terms: Vec<_> = u64::fermat_numbers().collect();
+    /// assert_eq!(terms, vec![3, 5, 17, 257, 65_537, 4_294_967_297]);
+    /// assert_eq!(i16::fermat_numbers().collect::<Vec<_>>(), vec![3, 5, 17, 257]);
+    /// assert_eq!(
+    ///     u128::fermat_numbers().last(),
+    ///     Some(18_446_744_073_709_551_617)
+    /// );
+    /// ```
+    fn fermat_numbers() -> Self::Iter;
+}
+
+/// The iterator returned by `Fermat::fermat_numbers`.
+#[derive(Clone, Debug)]
+pub struct FermatIter<T> {
+    // The next index, or `None` once the terms have overflowed.
+    k: Option<u32>,
+    phantom: core::marker::PhantomData<T>,
+}
+
+macro_rules! fermat_trait_for_primitive {
+    ($type:ty) => {
+        impl $crate::sequences::fermat::Fermat for $type {
+            type Iter = $crate::sequences::fermat::FermatIter<$type>;
+
+            fn fermat_numbers() -> Self::Iter {
+                $crate::sequences::fermat::FermatIter {
+                    k: ::core::option::Option::Some(0),
+                    phantom: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        impl ::core::iter::Iterator for $crate::sequences::fermat::FermatIter<$type> {
+            type Item = $type;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                let k = self.k?;
+                // 2^(2^k) must be no more than MAX − 1, so 2^k must be below
+                // the number of value bits.
+                let exponent = 1u32 << k;
+                if exponent >= <$type>::MAX.count_ones() {
+                    self.k = ::core::option::Option::None;
+                    return ::core::option::Option::None;
+                }
+                self.k = ::core::option::Option::Some(k + 1);
+                ::core::option::Option::Some((1 << exponent) + 1)
+            }
+
+            fn size_hint(&self) -> (usize, ::core::option::Option<usize>) {
+                let len = match self.k {
+                    ::core::option::Option::Some(k) => {
+                        // The terms with 2^k below the number of value bits.
+                        let bits = <$type>::MAX.count_ones();
+                        ((bits - 1).ilog2() + 1).saturating_sub(k) as usize
+                    }
+                    ::core::option::Option::None => 0,
+                };
+                (len, ::core::option::Option::Some(len))
+            }
+        }
+
+        impl ::core::iter::ExactSizeIterator for $crate::sequences::fermat::FermatIter<$type> {}
+
+        impl ::core::iter::FusedIterator for $crate::sequences::fermat::FermatIter<$type> {}
+    };
+}
+
+fermat_trait_for_primitive!(::core::primitive::i8);
+fermat_trait_for_primitive!(::core::primitive::u8);
+fermat_trait_for_primitive!(::core::primitive::i16);
+fermat_trait_for_primitive!(::core::primitive::u16);
+fermat_trait_for_primitive!(::core::primitive::i32);
+fermat_trait_for_primitive!(::core::primitive::u32);
+fermat_trait_for_primitive!(::core::primitive::i64);
+fermat_trait_for_primitive!(::core::primitive::u64);
+fermat_trait_for_primitive!(::core::primitive::i128);
+fermat_trait_for_primitive!(::core::primitive::u128);
+fermat_trait_for_primitive!(::core::primitive::isize);
+fermat_trait_for_primitive!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Fermat for rug::Integer {
+    type Iter = FermatIter<rug::Integer>;
+
+    fn fermat_numbers() -> Self::Iter {
+        FermatIter {
+            k: Some(0),
+            phantom: core::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for FermatIter<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let k = self.k?;
+        // Past this the exponent no longer fits in a u32.
+        self.k = if k < 31 { Some(k + 1) } else { None };
+        Some((rug::Integer::from(1) << (1u32 << k)) + 1u8)
+    }
+}
+
+/// Returns whether the Fermat number F<sub>k</sub> =
+/// 2<sup>2<sup>k</sup></sup> + 1 is prime, using Pépin's test.
+///
+/// For k ≥ 1, F<sub>k</sub> is prime exactly when
+/// 3<sup>(F<sub>k</sub> − 1)/2</sup> ≡ −1 (mod F<sub>k</sub>). That takes
+/// 2<sup>k</sup> − 1 modular squarings of 2<sup>k</sup>-bit integers, so k
+/// much past 20 takes a very long time.
+///
+/// # Panics
+///
+/// Panics if k is larger than 31, as then 2<sup>k</sup> does not fit in a
+/// `u32`.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::fermat::pepin_test;
+///
+/// let primes: Vec<_> = (0..12).filter(|&k| pepin_test(k)).collect();
+/// assert_eq!(primes, vec![0, 1, 2, 3, 4]);
+/// ```
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+pub fn pepin_test(k: u32) -> bool {
+    assert!(k <= 31, "k must be at most 31");
+    if k == 0 {
+        return true;
+    }
+    let exponent = 1u32 << k;
+    let fermat = (rug::Integer::from(1) << exponent) + 1u8;
+    // (F_k − 1)/2 = 2^(2^k − 1), so the power is 2^k − 1 squarings of 3.
+    let mut power = rug::Integer::from(3);
+    for _ in 0..exponent - 1 {
+        power.square_mut();
+        power %= &fermat;
+    }
+    power + 1u8 == fermat
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let max = rug::Integer::from(<$type>::MAX);
+                let expected: ::std::vec::Vec<rug::Integer> = rug::Integer::fermat_numbers()
+                    .take_while(|term| *term <= max)
+                    .collect();
+                let mut iter = <$type>::fermat_numbers();
+                ::core::assert_eq!(iter.len(), expected.len());
+                let terms: ::std::vec::Vec<rug::Integer> =
+                    iter.by_ref().map(rug::Integer::from).collect();
+                ::core::assert_eq!(terms, expected);
+                ::core::assert_eq!(iter.len(), 0);
+                ::core::assert_eq!(iter.next(), ::core::option::Option::None);
+            }
+        };
+    }
+
+    test_against_rug!(::core::primitive::i8, i8_fermat);
+    test_against_rug!(::core::primitive::u8, u8_fermat);
+    test_against_rug!(::core::primitive::i16, i16_fermat);
+    test_against_rug!(::core::primitive::u16, u16_fermat);
+    test_against_rug!(::core::primitive::i32, i32_fermat);
+    test_against_rug!(::core::primitive::u32, u32_fermat);
+    test_against_rug!(::core::primitive::i64, i64_fermat);
+    test_against_rug!(::core::primitive::u64, u64_fermat);
+    test_against_rug!(::core::primitive::i128, i128_fermat);
+    test_against_rug!(::core::primitive::u128, u128_fermat);
+    test_against_rug!(::core::primitive::isize, isize_fermat);
+    test_against_rug!(::core::primitive::usize, usize_fermat);
+
+    #[test]
+    fn pepin_matches_primality() {
+        for (k, term) in rug::Integer::fermat_numbers().take(12).enumerate() {
+            let expected = term.is_probably_prime(30) != rug::integer::IsPrime::No;
+            assert_eq!(pepin_test(k as u32), expected, "k = {}", k);
+        }
+        // Euler's factor of F_5.
+        assert!(u64::fermat_numbers().last().unwrap() % 641 == 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "k must be at most 31")]
+    fn pepin_too_large() {
+        pepin_test(32);
+    }
+}
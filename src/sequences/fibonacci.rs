@@ -1,10 +1,15 @@
+use crate::number_theory::primes::{Modular, Montgomery, Small};
+use crate::number_theory::{Factor, Gcd};
+
 pub trait Fibonacci: Sized {
     /// The exact type of the `Iterator<Item = Self>` returned by
-    /// `fibonacci_iter`.
+    /// `fibonacci_iter`. For primitive integer types this is `PrimitiveIter`,
+    /// which is also a `DoubleEndedIterator` and an `ExactSizeIterator`.
     type Iter: Iterator<Item = Self>;
 
     /// Returns an `Iterator<Item = Self>` implementation that goes through all
-    /// Fibonacci numbers from zero until just before overflow.
+    /// Fibonacci numbers from zero until just before overflow. The `NonZero`
+    /// integer types cannot hold zero, so for them it starts from F(1).
     ///
     /// # Examples
     ///
@@ -20,9 +25,81 @@ pub trait Fibonacci: Sized {
     /// assert_eq!(iter.next(), Some(5));
     /// assert_eq!(iter.nth(5), Some(89));
     /// assert_eq!(iter.next(), None);
+    ///
+    /// let mut iter = u64::fibonacci_iter();
+    /// assert_eq!(iter.len(), 94);
+    /// assert_eq!(iter.next_back(), Some(12_200_160_415_121_876_738));
+    ///
+    /// let mut iter = core::num::NonZeroU8::fibonacci_iter().map(|n| n.get());
+    /// assert_eq!(iter.next(), Some(1));
+    /// assert_eq!(iter.next(), Some(1));
+    /// assert_eq!(iter.next(), Some(2));
     /// ```
     fn fibonacci_iter() -> Self::Iter;
 
+    /// Returns an `Iterator<Item = Self>` implementation that goes through the
+    /// Fibonacci numbers F(n), F(n + 1), … until just before overflow, without
+    /// walking through the ones before F(n).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Fibonacci;
+    ///
+    /// let terms: Vec<_> = u16::fibonacci_iter_from(20).collect();
+    /// assert_eq!(terms, vec![6765, 10946, 17711, 28657, 46368]);
+    /// assert_eq!(u16::fibonacci_iter_from(100).next(), None);
+    /// ```
+    fn fibonacci_iter_from(n: usize) -> Self::Iter;
+
+    /// The exact type of the `Iterator<Item = (Self, Self)>` returned by
+    /// `fibonacci_pairs`. For primitive integer types this is
+    /// `PrimitivePairs`, which is also a `DoubleEndedIterator` and an
+    /// `ExactSizeIterator`.
+    type Pairs: Iterator<Item = (Self, Self)>;
+
+    /// Returns an `Iterator<Item = (Self, Self)>` implementation that goes
+    /// through the pairs of consecutive Fibonacci numbers (F(n), F(n + 1))
+    /// from n = 0 until F(n + 1) would overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Fibonacci;
+    ///
+    /// let mut pairs = u8::fibonacci_pairs();
+    /// assert_eq!(pairs.next(), Some((0, 1)));
+    /// assert_eq!(pairs.next(), Some((1, 1)));
+    /// assert_eq!(pairs.next(), Some((1, 2)));
+    /// assert_eq!(pairs.last(), Some((144, 233)));
+    ///
+    /// // Ratios of consecutive terms converge to the golden ratio.
+    /// let (a, b) = u64::fibonacci_pairs().last().unwrap();
+    /// assert_eq!(b as f64 / a as f64, (1.0 + 5f64.sqrt()) / 2.0);
+    /// ```
+    fn fibonacci_pairs() -> Self::Pairs;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through the
+    /// Fibonacci numbers from zero up to and including limit in the same order
+    /// as `fibonacci_iter`, so one appears twice. It is empty when limit is
+    /// negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Fibonacci;
+    ///
+    /// let terms: Vec<_> = u32::fibonacci_up_to(&21).collect();
+    /// assert_eq!(terms, vec![0, 1, 1, 2, 3, 5, 8, 13, 21]);
+    /// assert_eq!(i8::fibonacci_up_to(&-1).next(), None);
+    ///
+    /// let even_sum: u32 = u32::fibonacci_up_to(&4_000_000)
+    ///     .filter(|n| n % 2 == 0)
+    ///     .sum();
+    /// assert_eq!(even_sum, 4_613_732);
+    /// ```
+    fn fibonacci_up_to(limit: &Self) -> FibonacciUpTo<Self>;
+
     /// Returns an `Option` containing either the n<sup>th</sup> Fibonacci
     /// number or `None` if that would cause overflow.
     ///
@@ -35,841 +112,1242 @@ pub trait Fibonacci: Sized {
     /// assert_eq!(i32::nth_fibonacci(&50), None);
     /// ```
     fn nth_fibonacci(n: &Self) -> Option<Self>;
+
+    /// Returns whether n is a Fibonacci number F(k) with k ≥ 0, which it is
+    /// exactly when 5n² + 4 or 5n² − 4 is a perfect square. Negative numbers
+    /// are never Fibonacci numbers in this sense.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Fibonacci;
+    ///
+    /// assert!(u32::is_fibonacci(&144));
+    /// assert!(!u32::is_fibonacci(&145));
+    /// assert!(u128::is_fibonacci(&332_825_110_087_067_562_321_196_029_789_634_457_848));
+    /// assert!(!i8::is_fibonacci(&-1));
+    /// ```
+    fn is_fibonacci(n: &Self) -> bool;
+
+    /// Returns an `Option` containing either the index k ≥ 0 for which
+    /// F(k) = value or `None` if value is not a Fibonacci number. Since
+    /// F(1) = F(2) = 1, the index returned for one is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Fibonacci;
+    ///
+    /// assert_eq!(u32::fibonacci_index(&144), Some(12));
+    /// assert_eq!(u32::fibonacci_index(&145), None);
+    /// assert_eq!(i8::fibonacci_index(&1), Some(1));
+    /// assert_eq!(i8::fibonacci_index(&0), Some(0));
+    /// assert_eq!(i8::fibonacci_index(&-1), None);
+    /// ```
+    fn fibonacci_index(value: &Self) -> Option<usize>;
 }
 
-macro_rules! fibonacci_trait_from_signed_array {
-    ($type:ty, $array:expr) => {
-        impl self::Fibonacci for $type {
-            type Iter = ::core::iter::Copied<::core::slice::Iter<'static, $type>>;
+pub trait FibonacciMod: Sized {
+    /// Returns the n<sup>th</sup> Fibonacci number modulo m, in the range from
+    /// zero up to but not including m.
+    ///
+    /// This uses fast doubling, F(2k) = F(k) (2F(k + 1) − F(k)) and
+    /// F(2k + 1) = F(k)² + F(k + 1)², reducing modulo m at every step, so it
+    /// takes O(log n) multiplications and works for every index, including
+    /// negative ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if m is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::FibonacciMod;
+    ///
+    /// assert_eq!(u32::nth_fibonacci_mod(&100, &1000), 75);
+    /// assert_eq!(u64::nth_fibonacci_mod(&1_000_000_000_000_000_000, &1_000_000_007), 209_783_453);
+    /// assert_eq!(i32::nth_fibonacci_mod(&-6, &10), 2);
+    /// ```
+    fn nth_fibonacci_mod(n: &Self, m: &Self) -> Self;
+}
+
+pub trait FibonacciApprox: Sized {
+    /// Returns an `Option` containing either the n<sup>th</sup> Fibonacci
+    /// number rounded to the nearest value of this floating-point type, or
+    /// `None` if that would be infinite. Negative indices follow
+    /// F(−n) = (−1)^(n + 1) F(n).
+    ///
+    /// Terms that fit in a `u128` are rounded from their exact values. Past
+    /// that, Binet's formula F(n) = (φⁿ − (−φ)⁻ⁿ) / √5 leaves only φⁿ / √5,
+    /// which is evaluated with about 106 bits of precision before rounding,
+    /// so the results are correctly rounded all the way up to overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::FibonacciApprox;
+    ///
+    /// assert_eq!(f64::nth_fibonacci_approx(10), Some(55.0));
+    /// assert_eq!(f64::nth_fibonacci_approx(-10), Some(-55.0));
+    /// assert_eq!(f64::nth_fibonacci_approx(1000), Some(4.3466557686937455e208));
+    /// assert_eq!(f64::nth_fibonacci_approx(1476), Some(1.3069892237633993e308));
+    /// assert_eq!(f64::nth_fibonacci_approx(1477), None);
+    /// assert_eq!(f32::nth_fibonacci_approx(100), Some(3.5422486e20));
+    /// assert_eq!(f32::nth_fibonacci_approx(187), None);
+    /// ```
+    fn nth_fibonacci_approx(n: i32) -> Option<Self>;
+}
+
+pub trait Negafibonacci: Fibonacci {
+    /// The exact type of the `Iterator<Item = Self>` returned by
+    /// `negafibonacci_iter`.
+    type NegaIter: Iterator<Item = Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through the
+    /// Fibonacci numbers F(0), F(−1), F(−2), … until just before overflow, so
+    /// that its n<sup>th</sup> item is F(−n) = (−1)^(n + 1) F(n).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Negafibonacci;
+    ///
+    /// let mut iter = i8::negafibonacci_iter();
+    /// assert_eq!(iter.next(), Some(0));
+    /// assert_eq!(iter.next(), Some(1));
+    /// assert_eq!(iter.next(), Some(-1));
+    /// assert_eq!(iter.next(), Some(2));
+    /// assert_eq!(iter.next(), Some(-3));
+    /// assert_eq!(iter.next(), Some(5));
+    /// assert_eq!(iter.nth(5), Some(89));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    fn negafibonacci_iter() -> Self::NegaIter;
+}
+
+/// Expands to a `&'static [$type; N]` holding every Fibonacci number that
+/// fits in `$type`, computed at compile time.
+macro_rules! fibonacci_table {
+    ($type:ty) => {{
+        const ARRAY_SIZE: usize = {
+            // Zero and one always fit, and every checked sum that does not
+            // overflow adds one more.
+            let mut result = 2usize;
+            let mut a: $type = 0;
+            let mut b: $type = 1;
+            while let ::core::option::Option::Some(next) = a.checked_add(b) {
+                a = b;
+                b = next;
+                result += 1;
+            }
+            result
+        };
+
+        const ARRAY: [$type; ARRAY_SIZE] = {
+            let mut result = [0; ARRAY_SIZE];
+            result[1] = 1;
+            let mut i = 2;
+            while i < ARRAY_SIZE {
+                result[i] = result[i - 1] + result[i - 2];
+                i += 1;
+            }
+            result
+        };
+
+        &ARRAY
+    }};
+}
+
+macro_rules! fibonacci_trait_for_signed {
+    ($type:ty) => {
+        impl $crate::sequences::fibonacci::Fibonacci for $type {
+            type Iter = $crate::sequences::fibonacci::PrimitiveIter<$type>;
 
             fn fibonacci_iter() -> Self::Iter {
-                ($array).iter().copied()
+                $crate::sequences::fibonacci::PrimitiveIter {
+                    terms: fibonacci_table!($type).iter().copied(),
+                }
             }
 
-            fn nth_fibonacci(n: &Self) -> Option<Self> {
-                let array = ($array);
+            fn fibonacci_iter_from(n: usize) -> Self::Iter {
+                let table = fibonacci_table!($type);
+                $crate::sequences::fibonacci::PrimitiveIter {
+                    terms: table.get(n..).unwrap_or(&[]).iter().copied(),
+                }
+            }
 
-                if *n < 0 {
-                    let element = array.get(-*n as usize).copied();
-                    if *n & 1 == 0 {
-                        element.map(<$type as ::core::ops::Neg>::neg)
-                    } else {
-                        element
-                    }
+            type Pairs = $crate::sequences::fibonacci::PrimitivePairs<$type>;
+
+            fn fibonacci_pairs() -> Self::Pairs {
+                $crate::sequences::fibonacci::PrimitivePairs {
+                    pairs: fibonacci_table!($type).windows(2),
+                }
+            }
+
+            fn fibonacci_up_to(limit: &Self) -> $crate::sequences::fibonacci::FibonacciUpTo<Self> {
+                $crate::sequences::fibonacci::FibonacciUpTo {
+                    terms: Self::fibonacci_iter(),
+                    limit: ::core::option::Option::Some(*limit),
+                }
+            }
+
+            fn nth_fibonacci(n: &Self) -> ::core::option::Option<Self> {
+                // F(−n) = (−1)^(n + 1) F(n).
+                let element = <usize as ::core::convert::TryFrom<_>>::try_from(n.unsigned_abs())
+                    .ok()
+                    .and_then(|n| fibonacci_table!($type).get(n).copied());
+                if *n < 0 && *n & 1 == 0 {
+                    element.map(<$type as ::core::ops::Neg>::neg)
                 } else {
-                    array.get(*n as usize).copied()
+                    element
+                }
+            }
+
+            fn is_fibonacci(n: &Self) -> bool {
+                *n >= 0 && $crate::sequences::fibonacci::is_fibonacci(*n as u128)
+            }
+
+            fn fibonacci_index(value: &Self) -> ::core::option::Option<usize> {
+                match *value {
+                    1 => ::core::option::Option::Some(1),
+                    value => fibonacci_table!($type).binary_search(&value).ok(),
                 }
             }
         }
     };
 }
 
-macro_rules! fibonacci_trait_from_unsigned_array {
-    ($type:ty, $array:expr) => {
-        impl $crate::sequences::fibonacci::Fibonacci for $type {
-            type Iter = ::core::iter::Copied<::core::slice::Iter<'static, $type>>;
+macro_rules! fibonacci_trait_for_unsigned {
+    ($type:ty) => {
+        impl $crate::sequences::fibonacci::Fibonacci for $type {
+            type Iter = $crate::sequences::fibonacci::PrimitiveIter<$type>;
+
+            fn fibonacci_iter() -> Self::Iter {
+                $crate::sequences::fibonacci::PrimitiveIter {
+                    terms: fibonacci_table!($type).iter().copied(),
+                }
+            }
+
+            fn fibonacci_iter_from(n: usize) -> Self::Iter {
+                let table = fibonacci_table!($type);
+                $crate::sequences::fibonacci::PrimitiveIter {
+                    terms: table.get(n..).unwrap_or(&[]).iter().copied(),
+                }
+            }
+
+            type Pairs = $crate::sequences::fibonacci::PrimitivePairs<$type>;
+
+            fn fibonacci_pairs() -> Self::Pairs {
+                $crate::sequences::fibonacci::PrimitivePairs {
+                    pairs: fibonacci_table!($type).windows(2),
+                }
+            }
+
+            fn fibonacci_up_to(limit: &Self) -> $crate::sequences::fibonacci::FibonacciUpTo<Self> {
+                $crate::sequences::fibonacci::FibonacciUpTo {
+                    terms: Self::fibonacci_iter(),
+                    limit: ::core::option::Option::Some(*limit),
+                }
+            }
+
+            fn nth_fibonacci(n: &Self) -> ::core::option::Option<Self> {
+                <usize as ::core::convert::TryFrom<_>>::try_from(*n)
+                    .ok()
+                    .and_then(|n| fibonacci_table!($type).get(n).copied())
+            }
+
+            fn is_fibonacci(n: &Self) -> bool {
+                $crate::sequences::fibonacci::is_fibonacci(*n as u128)
+            }
+
+            fn fibonacci_index(value: &Self) -> ::core::option::Option<usize> {
+                match *value {
+                    1 => ::core::option::Option::Some(1),
+                    value => fibonacci_table!($type).binary_search(&value).ok(),
+                }
+            }
+        }
+    };
+}
+
+/// Expands to a `&'static [$type; N]` holding every Fibonacci number except
+/// zero that fits in the `NonZero` integer type `$type`, whose primitive
+/// integer type is `$primitive`, computed at compile time.
+macro_rules! nonzero_fibonacci_table {
+    ($type:ty, $primitive:ty) => {{
+        const TABLE: &[$primitive] = fibonacci_table!($primitive);
+
+        const ARRAY: [$type; TABLE.len() - 1] = {
+            let mut result = [<$type>::MIN; TABLE.len() - 1];
+            let mut i = 0;
+            while i < result.len() {
+                result[i] = match <$type>::new(TABLE[i + 1]) {
+                    ::core::option::Option::Some(term) => term,
+                    ::core::option::Option::None => ::core::panic!("only F(0) is zero"),
+                };
+                i += 1;
+            }
+            result
+        };
+
+        &ARRAY
+    }};
+}
+
+macro_rules! fibonacci_trait_for_nonzero {
+    ($type:ty, $primitive:ty) => {
+        impl $crate::sequences::fibonacci::Fibonacci for $type {
+            type Iter = $crate::sequences::fibonacci::PrimitiveIter<$type>;
+
+            fn fibonacci_iter() -> Self::Iter {
+                $crate::sequences::fibonacci::PrimitiveIter {
+                    terms: nonzero_fibonacci_table!($type, $primitive).iter().copied(),
+                }
+            }
+
+            fn fibonacci_iter_from(n: usize) -> Self::Iter {
+                // The table starts from F(1), and F(0) is skipped.
+                let table = nonzero_fibonacci_table!($type, $primitive);
+                $crate::sequences::fibonacci::PrimitiveIter {
+                    terms: table
+                        .get(n.saturating_sub(1)..)
+                        .unwrap_or(&[])
+                        .iter()
+                        .copied(),
+                }
+            }
+
+            type Pairs = $crate::sequences::fibonacci::PrimitivePairs<$type>;
+
+            fn fibonacci_pairs() -> Self::Pairs {
+                $crate::sequences::fibonacci::PrimitivePairs {
+                    pairs: nonzero_fibonacci_table!($type, $primitive).windows(2),
+                }
+            }
+
+            fn fibonacci_up_to(limit: &Self) -> $crate::sequences::fibonacci::FibonacciUpTo<Self> {
+                $crate::sequences::fibonacci::FibonacciUpTo {
+                    terms: Self::fibonacci_iter(),
+                    limit: ::core::option::Option::Some(*limit),
+                }
+            }
+
+            fn nth_fibonacci(n: &Self) -> ::core::option::Option<Self> {
+                // Only F(0) is zero, and n is never zero.
+                <$primitive as $crate::sequences::fibonacci::Fibonacci>::nth_fibonacci(&n.get())
+                    .and_then(<$type>::new)
+            }
+
+            fn is_fibonacci(n: &Self) -> bool {
+                <$primitive as $crate::sequences::fibonacci::Fibonacci>::is_fibonacci(&n.get())
+            }
+
+            fn fibonacci_index(value: &Self) -> ::core::option::Option<usize> {
+                <$primitive as $crate::sequences::fibonacci::Fibonacci>::fibonacci_index(
+                    &value.get(),
+                )
+            }
+        }
+    };
+}
+
+fibonacci_trait_for_nonzero!(::core::num::NonZeroI8, ::core::primitive::i8);
+fibonacci_trait_for_nonzero!(::core::num::NonZeroU8, ::core::primitive::u8);
+fibonacci_trait_for_nonzero!(::core::num::NonZeroI16, ::core::primitive::i16);
+fibonacci_trait_for_nonzero!(::core::num::NonZeroU16, ::core::primitive::u16);
+fibonacci_trait_for_nonzero!(::core::num::NonZeroI32, ::core::primitive::i32);
+fibonacci_trait_for_nonzero!(::core::num::NonZeroU32, ::core::primitive::u32);
+fibonacci_trait_for_nonzero!(::core::num::NonZeroI64, ::core::primitive::i64);
+fibonacci_trait_for_nonzero!(::core::num::NonZeroU64, ::core::primitive::u64);
+fibonacci_trait_for_nonzero!(::core::num::NonZeroI128, ::core::primitive::i128);
+fibonacci_trait_for_nonzero!(::core::num::NonZeroU128, ::core::primitive::u128);
+fibonacci_trait_for_nonzero!(::core::num::NonZeroIsize, ::core::primitive::isize);
+fibonacci_trait_for_nonzero!(::core::num::NonZeroUsize, ::core::primitive::usize);
+
+/// Returns every Fibonacci number that fits in a `usize` in increasing order.
+pub(crate) fn usize_table() -> &'static [usize] {
+    fibonacci_table!(usize)
+}
+
+/// Returns whether n is a Fibonacci number.
+fn is_fibonacci(n: u128) -> bool {
+    let is_square = |m: u128| {
+        let root = m.isqrt();
+        root * root == m
+    };
+    match n.checked_mul(n).and_then(|square| square.checked_mul(5)) {
+        Some(five_squares) if five_squares < u128::MAX - 4 => {
+            is_square(five_squares + 4) || (five_squares >= 4 && is_square(five_squares - 4))
+        }
+        // Past about 2^63, 5n² ± 4 no longer fits, so look n up instead.
+        _ => fibonacci_table!(u128).binary_search(&n).is_ok(),
+    }
+}
+
+fibonacci_trait_for_signed!(::core::primitive::i8);
+fibonacci_trait_for_unsigned!(::core::primitive::u8);
+fibonacci_trait_for_signed!(::core::primitive::i16);
+fibonacci_trait_for_unsigned!(::core::primitive::u16);
+fibonacci_trait_for_signed!(::core::primitive::i32);
+fibonacci_trait_for_unsigned!(::core::primitive::u32);
+fibonacci_trait_for_signed!(::core::primitive::i64);
+fibonacci_trait_for_unsigned!(::core::primitive::u64);
+fibonacci_trait_for_signed!(::core::primitive::i128);
+fibonacci_trait_for_unsigned!(::core::primitive::u128);
+fibonacci_trait_for_signed!(::core::primitive::isize);
+fibonacci_trait_for_unsigned!(::core::primitive::usize);
+
+macro_rules! fibonacci_mod_trait_for_signed {
+    ($type:ty) => {
+        impl $crate::sequences::fibonacci::FibonacciMod for $type {
+            fn nth_fibonacci_mod(n: &Self, m: &Self) -> Self {
+                ::core::assert!(*m > 0, "modulus must be positive");
+                let m = *m as u128;
+                let result =
+                    $crate::sequences::fibonacci::fibonacci_mod(n.unsigned_abs() as u128, m);
+                // F(−n) = (−1)^(n + 1) F(n).
+                if *n < 0 && *n & 1 == 0 && result != 0 {
+                    (m - result) as $type
+                } else {
+                    result as $type
+                }
+            }
+        }
+    };
+}
+
+macro_rules! fibonacci_mod_trait_for_unsigned {
+    ($type:ty) => {
+        impl $crate::sequences::fibonacci::FibonacciMod for $type {
+            fn nth_fibonacci_mod(n: &Self, m: &Self) -> Self {
+                ::core::assert!(*m > 0, "modulus must be positive");
+                $crate::sequences::fibonacci::fibonacci_mod(*n as u128, *m as u128) as $type
+            }
+        }
+    };
+}
+
+fibonacci_mod_trait_for_signed!(::core::primitive::i8);
+fibonacci_mod_trait_for_unsigned!(::core::primitive::u8);
+fibonacci_mod_trait_for_signed!(::core::primitive::i16);
+fibonacci_mod_trait_for_unsigned!(::core::primitive::u16);
+fibonacci_mod_trait_for_signed!(::core::primitive::i32);
+fibonacci_mod_trait_for_unsigned!(::core::primitive::u32);
+fibonacci_mod_trait_for_signed!(::core::primitive::i64);
+fibonacci_mod_trait_for_unsigned!(::core::primitive::u64);
+fibonacci_mod_trait_for_signed!(::core::primitive::i128);
+fibonacci_mod_trait_for_unsigned!(::core::primitive::u128);
+fibonacci_mod_trait_for_signed!(::core::primitive::isize);
+fibonacci_mod_trait_for_unsigned!(::core::primitive::usize);
+
+macro_rules! negafibonacci_trait_for_signed {
+    ($type:ty) => {
+        impl $crate::sequences::fibonacci::Negafibonacci for $type {
+            type NegaIter = ::core::iter::Map<
+                ::core::iter::Enumerate<::core::iter::Copied<::core::slice::Iter<'static, $type>>>,
+                fn((usize, $type)) -> $type,
+            >;
+
+            fn negafibonacci_iter() -> Self::NegaIter {
+                // No power of two above eight is a Fibonacci number, so −F(n)
+                // fits exactly when F(n) does.
+                fibonacci_table!($type)
+                    .iter()
+                    .copied()
+                    .enumerate()
+                    .map(|(n, term)| if n & 1 == 0 { -term } else { term })
+            }
+        }
+    };
+}
+
+negafibonacci_trait_for_signed!(::core::primitive::i8);
+negafibonacci_trait_for_signed!(::core::primitive::i16);
+negafibonacci_trait_for_signed!(::core::primitive::i32);
+negafibonacci_trait_for_signed!(::core::primitive::i64);
+negafibonacci_trait_for_signed!(::core::primitive::i128);
+negafibonacci_trait_for_signed!(::core::primitive::isize);
+
+impl FibonacciApprox for f32 {
+    fn nth_fibonacci_approx(n: i32) -> Option<Self> {
+        // F(186) is the last Fibonacci number below `f32::MAX`.
+        let term = *fibonacci_table!(u128).get(n.unsigned_abs() as usize)? as f32;
+        // F(−n) = (−1)^(n + 1) F(n).
+        if n < 0 && n & 1 == 0 {
+            Some(-term)
+        } else {
+            Some(term)
+        }
+    }
+}
+
+impl FibonacciApprox for f64 {
+    fn nth_fibonacci_approx(n: i32) -> Option<Self> {
+        let index = n.unsigned_abs();
+        let term = match fibonacci_table!(u128).get(index as usize) {
+            Some(&term) => term as f64,
+            None => {
+                // φⁿ / √5 = φⁿ⁻² · φ² / √5, which keeps the power of φ finite
+                // for every finite result.
+                const PHI: (f64, f64) = (1.618033988749895, -5.432115203682506e-17);
+                const PHI_SQUARED_OVER_SQRT_5: (f64, f64) =
+                    (1.1708203932499368, 1.0063407173292375e-16);
+                let mut power = (1.0, 0.0);
+                for bit in (0..32 - (index - 2).leading_zeros()).rev() {
+                    power = double_double_mul(power, power);
+                    if (index - 2) >> bit & 1 == 1 {
+                        power = double_double_mul(power, PHI);
+                    }
+                }
+                let term = double_double_mul(power, PHI_SQUARED_OVER_SQRT_5).0;
+                if !term.is_finite() {
+                    return None;
+                }
+                term
+            }
+        };
+        // F(−n) = (−1)^(n + 1) F(n).
+        if n < 0 && n & 1 == 0 {
+            Some(-term)
+        } else {
+            Some(term)
+        }
+    }
+}
+
+/// Returns the product of two double-double numbers, each an unevaluated sum
+/// hi + lo with |lo| at most half an ulp of hi.
+fn double_double_mul(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    let product = a.0 * b.0;
+    // The fused multiply-add gives the rounding error of the product exactly.
+    let error = a.0.mul_add(b.0, -product) + (a.0 * b.1 + a.1 * b.0);
+    let hi = product + error;
+    (hi, error - (hi - product))
+}
+
+/// Returns the Zeckendorf representation of n, the unique indices k of
+/// nonconsecutive Fibonacci numbers F(k) with k ≥ 2 that sum to n, in
+/// decreasing order. The representation of zero is empty.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::fibonacci::zeckendorf;
+///
+/// // 100 = 89 + 8 + 3 = F(11) + F(6) + F(4).
+/// assert_eq!(zeckendorf(100), vec![11, 6, 4]);
+/// assert_eq!(zeckendorf(0), Vec::<u32>::new());
+/// ```
+pub fn zeckendorf(n: u128) -> Vec<u32> {
+    let table = fibonacci_table!(u128);
+    let mut n = n;
+    let mut indices = Vec::new();
+    // Greedily taking the largest Fibonacci number that fits never leaves
+    // enough for the one just below it.
+    let mut k = table.len() - 1;
+    while n != 0 {
+        if table[k] <= n {
+            n -= table[k];
+            indices.push(k as u32);
+            k -= 1;
+        }
+        k -= 1;
+    }
+    indices
+}
+
+/// Returns an `Option` containing either the sum of the Fibonacci numbers
+/// F(k) for the given indices k or `None` if that would cause overflow.
+///
+/// This reverses `zeckendorf`, and it accepts any indices, whether or not they
+/// are nonconsecutive.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::fibonacci::{from_zeckendorf, zeckendorf};
+///
+/// assert_eq!(from_zeckendorf(&[11, 6, 4]), Some(100));
+/// assert_eq!(from_zeckendorf(&zeckendorf(u128::MAX)), Some(u128::MAX));
+/// assert_eq!(from_zeckendorf(&[200]), None);
+/// ```
+pub fn from_zeckendorf(indices: &[u32]) -> Option<u128> {
+    let table = fibonacci_table!(u128);
+    indices
+        .iter()
+        .try_fold(0u128, |sum, &k| sum.checked_add(*table.get(k as usize)?))
+}
+
+/// Returns the Fibonacci code of n, a self-delimiting universal code in
+/// which bit i is set when F(i + 2) is in the Zeckendorf representation of n,
+/// followed by one more set bit.
+///
+/// Since a Zeckendorf representation never has two consecutive Fibonacci
+/// numbers, the only two consecutive set bits are the last two, so codes can
+/// be concatenated and still be told apart.
+///
+/// # Panics
+///
+/// Panics if n is zero.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::fibonacci::fibonacci_encode;
+///
+/// assert_eq!(fibonacci_encode(1), vec![true, true]);
+/// // 11 = 8 + 3 = F(6) + F(4).
+/// assert_eq!(fibonacci_encode(11), vec![false, false, true, false, true, true]);
+/// assert_eq!(fibonacci_encode(u128::MAX).len(), 186);
+/// ```
+pub fn fibonacci_encode(n: u128) -> Vec<bool> {
+    assert!(n != 0, "zero has no Fibonacci code");
+    let indices = zeckendorf(n);
+    let mut bits = vec![false; indices[0] as usize - 1];
+    for k in indices {
+        bits[k as usize - 2] = true;
+    }
+    bits.push(true);
+    bits
+}
+
+/// Returns an `Option` containing either the number whose Fibonacci code
+/// begins bits along with the length of that code, or `None` if bits has no
+/// complete code or its number would cause overflow.
+///
+/// This reverses `fibonacci_encode`, and the length tells where the next code
+/// of a stream begins.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::fibonacci::{fibonacci_decode, fibonacci_encode};
+///
+/// let stream: Vec<_> = [3, 1, 4, 1, 5]
+///     .iter()
+///     .flat_map(|&n| fibonacci_encode(n))
+///     .collect();
+/// let mut rest = &stream[..];
+/// let mut decoded = Vec::new();
+/// while let Some((n, length)) = fibonacci_decode(rest) {
+///     decoded.push(n);
+///     rest = &rest[length..];
+/// }
+/// assert_eq!(decoded, vec![3, 1, 4, 1, 5]);
+/// assert!(rest.is_empty());
+///
+/// assert_eq!(fibonacci_decode(&[false, true]), None);
+/// ```
+pub fn fibonacci_decode(bits: &[bool]) -> Option<(u128, usize)> {
+    let table = fibonacci_table!(u128);
+    let mut n = 0u128;
+    let mut previous = false;
+    for (i, &bit) in bits.iter().enumerate() {
+        if bit && previous {
+            return Some((n, i + 1));
+        }
+        if bit {
+            n = n.checked_add(*table.get(i + 2)?)?;
+        }
+        previous = bit;
+    }
+    None
+}
+
+/// Returns the Pisano period π(m), the period of the Fibonacci numbers
+/// modulo m.
+///
+/// This factors m and combines the periods of its prime power factors with
+/// π(p<sup>e</sup>) = p<sup>e − 1</sup> π(p), which has no known
+/// counterexample, and lcm(π(a), π(b)) = π(ab) for coprime a and b. The
+/// period π(p) of each prime is found among the divisors of p − 1 when
+/// p ≡ ±1 (mod 5) and of 2(p + 1) when p ≡ ±2 (mod 5), so it takes about
+/// as long as factoring m and those candidates.
+///
+/// # Panics
+///
+/// Panics if m is zero.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::fibonacci::pisano_period;
+///
+/// assert_eq!(pisano_period(10), 60);
+/// assert_eq!(pisano_period(1000), 1500);
+/// assert_eq!(pisano_period(1_000_000_007), 2_000_000_016);
+/// assert_eq!(pisano_period(1), 1);
+/// ```
+pub fn pisano_period(m: u64) -> u128 {
+    assert!(m != 0, "modulus must be nonzero");
+    let mut period = 1u128;
+    for &(p, e) in u64::factorize(&m).unwrap().factors() {
+        let p_period = (p as u128).pow(e - 1) * prime_pisano_period(p);
+        period = u128::lcm(&period, &p_period);
+    }
+    period
+}
+
+/// Returns π(p) for a prime p.
+fn prime_pisano_period(p: u64) -> u128 {
+    let p = p as u128;
+    let mut period = match p {
+        2 => return 3,
+        5 => return 20,
+        _ if p % 5 == 1 || p % 5 == 4 => p - 1,
+        _ => 2 * (p + 1),
+    };
+    // The periods modulo p are the multiples of π(p), so take out each prime
+    // factor of the candidate for as long as what is left is still a period.
+    // The candidate 2(p + 1) can be past u64::MAX, so it is factored whole.
+    let is_period = |n| fibonacci_mod(n, p) == 0 && fibonacci_mod(n + 1, p) == 1;
+    for &(q, _) in u128::factorize(&period).unwrap().factors() {
+        while period % q == 0 && is_period(period / q) {
+            period /= q;
+        }
+    }
+    period
+}
+
+/// Returns F(n) mod m for positive m.
+fn fibonacci_mod(n: u128, m: u128) -> u128 {
+    if let Ok(m) = <u64 as core::convert::TryFrom<u128>>::try_from(m) {
+        return fibonacci_in(&Small { n: m }, n) as u128;
+    }
+    if m % 2 == 1 {
+        let modular = Montgomery::new(m);
+        return modular.value(fibonacci_in(&modular, n));
+    }
+    // Montgomery arithmetic needs an odd modulus, so m = 2^s o is taken
+    // apart and F(n) is put back together from its residues modulo o and
+    // 2^s by the Chinese remainder theorem.
+    let twos = m.trailing_zeros();
+    let odd = m >> twos;
+    let low = PowerOfTwo {
+        mask: (1 << twos) - 1,
+    };
+    let x = fibonacci_mod(n, odd);
+    let y = fibonacci_in(&low, n);
+    // Each step of Newton's iteration doubles the number of correct low bits
+    // of the inverse of o modulo 2^s, and o is its own inverse modulo eight.
+    let mut inverse = odd;
+    for _ in 0..6 {
+        inverse = inverse.wrapping_mul(2u128.wrapping_sub(odd.wrapping_mul(inverse)));
+    }
+    x + odd * low.mul(low.sub(y, low.elem(x)), low.elem(inverse))
+}
+
+/// Returns F(n) in the given modular arithmetic by fast doubling.
+fn fibonacci_in<M: Modular>(modular: &M, n: u128) -> M::Elem {
+    // (a, b) = (F(k), F(k + 1)) for the prefix k of the bits of n.
+    let (mut a, mut b) = (modular.elem(0), modular.elem(1 % modular.modulus()));
+    for bit in (0..128 - n.leading_zeros()).rev() {
+        let twice_b_minus_a = modular.sub(modular.add(b, b), a);
+        let (even, odd) = (
+            modular.mul(a, twice_b_minus_a),
+            modular.add(modular.mul(a, a), modular.mul(b, b)),
+        );
+        if n >> bit & 1 == 0 {
+            a = even;
+            b = odd;
+        } else {
+            a = odd;
+            b = modular.add(even, odd);
+        }
+    }
+    a
+}
+
+/// Arithmetic modulo a power of two below 2<sup>128</sup>, which only has to
+/// wrap and keep the low bits.
+struct PowerOfTwo {
+    mask: u128,
+}
+
+impl Modular for PowerOfTwo {
+    type Elem = u128;
+
+    fn modulus(&self) -> u128 {
+        self.mask + 1
+    }
+
+    fn elem(&self, x: u128) -> u128 {
+        x & self.mask
+    }
+
+    fn value(&self, a: u128) -> u128 {
+        a
+    }
+
+    fn add(&self, a: u128, b: u128) -> u128 {
+        a.wrapping_add(b) & self.mask
+    }
+
+    fn sub(&self, a: u128, b: u128) -> u128 {
+        a.wrapping_sub(b) & self.mask
+    }
+
+    fn mul(&self, a: u128, b: u128) -> u128 {
+        a.wrapping_mul(b) & self.mask
+    }
+
+    fn half(&self, _: u128) -> u128 {
+        unreachable!("two has no inverse modulo a power of two")
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Fibonacci for rug::Integer {
+    type Iter = RugIter;
+
+    fn fibonacci_iter() -> Self::Iter {
+        RugIter::new()
+    }
+
+    fn fibonacci_iter_from(n: usize) -> Self::Iter {
+        RugIter::starting_at(n)
+    }
+
+    type Pairs = RugPairs;
+
+    fn fibonacci_pairs() -> Self::Pairs {
+        RugPairs {
+            terms: RugIter::new(),
+        }
+    }
+
+    fn fibonacci_up_to(limit: &Self) -> FibonacciUpTo<Self> {
+        FibonacciUpTo {
+            terms: Self::fibonacci_iter(),
+            limit: Some(limit.clone()),
+        }
+    }
+
+    fn nth_fibonacci(n: &Self) -> Option<Self> {
+        let index = n.clone().abs().to_usize()?;
+        let result = match core::convert::TryFrom::try_from(index) {
+            Ok(index) => rug::Integer::from(rug::Integer::fibonacci(index)),
+            Err(_) => rug_fibonacci_pair(index).0,
+        };
+        // F(−n) = (−1)^(n + 1) F(n).
+        if *n < 0 && index & 1 == 0 {
+            Some(-result)
+        } else {
+            Some(result)
+        }
+    }
+
+    fn is_fibonacci(n: &Self) -> bool {
+        if *n < 0 {
+            return false;
+        }
+        let five_squares = rug::Integer::from(n.square_ref()) * 5u8;
+        rug::Integer::from(&five_squares + 4u8).is_perfect_square()
+            || (five_squares - 4u8).is_perfect_square()
+    }
+
+    fn fibonacci_index(value: &Self) -> Option<usize> {
+        if *value <= 1 {
+            return value.to_usize();
+        }
+        // F(k) is the nearest integer to φᵏ / √5, so the bit length of value
+        // pins k down to within two candidates, each checked exactly.
+        let log2_phi = ((1.0 + 5f64.sqrt()) / 2.0).log2();
+        let log2_sqrt_5 = 5f64.sqrt().log2();
+        let bits = value.significant_bits() as f64;
+        let low = ((bits - 1.0 + log2_sqrt_5) / log2_phi) as u32;
+        (low.saturating_sub(1)..=low.saturating_add(2))
+            .find(|&k| rug::Integer::from(rug::Integer::fibonacci(k)) == *value)
+            .and_then(|k| core::convert::TryFrom::try_from(k).ok())
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl FibonacciMod for rug::Integer {
+    fn nth_fibonacci_mod(n: &Self, m: &Self) -> Self {
+        use rug::ops::RemRounding;
+
+        assert!(*m > 0, "modulus must be positive");
+        let index = n.clone().abs();
+        let (mut a, mut b) = (rug::Integer::new(), rug::Integer::from(1) % m);
+        for bit in (0..index.significant_bits()).rev() {
+            let twice_b_minus_a = rug::Integer::from(&b * 2u8) - &a;
+            let even = (twice_b_minus_a * &a).rem_euc(m);
+            let odd = (rug::Integer::from(a.square_ref()) + b.square_ref()) % m;
+            if index.get_bit(bit) {
+                b = rug::Integer::from(&even + &odd) % m;
+                a = odd;
+            } else {
+                a = even;
+                b = odd;
+            }
+        }
+        // F(−n) = (−1)^(n + 1) F(n).
+        if *n < 0 && n.is_even() {
+            a = (-a).rem_euc(m);
+        }
+        a
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Negafibonacci for rug::Integer {
+    type NegaIter = RugNegaIter;
+
+    fn negafibonacci_iter() -> Self::NegaIter {
+        RugNegaIter::new()
+    }
+}
+
+/// The iterator returned by `fibonacci_iter` for primitive integer types.
+///
+/// It walks a table of every Fibonacci number that fits in the type, so it
+/// knows its exact length, runs from either end, and its `nth` method takes
+/// constant time.
+#[derive(Clone, Debug)]
+pub struct PrimitiveIter<T: 'static> {
+    terms: core::iter::Copied<core::slice::Iter<'static, T>>,
+}
+
+impl<T: Copy> Iterator for PrimitiveIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.terms.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.terms.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.terms.nth(n)
+    }
+
+    fn count(self) -> usize {
+        self.terms.len()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.terms.next_back()
+    }
+}
+
+impl<T: Copy> DoubleEndedIterator for PrimitiveIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.terms.next_back()
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.terms.nth_back(n)
+    }
+}
+
+impl<T: Copy> ExactSizeIterator for PrimitiveIter<T> {}
+
+impl<T: Copy> core::iter::FusedIterator for PrimitiveIter<T> {}
+
+/// The iterator returned by `fibonacci_pairs` for primitive integer types.
+///
+/// Like `PrimitiveIter`, it walks a table, so it knows its exact length, runs
+/// from either end, and its `nth` method takes constant time.
+#[derive(Clone, Debug)]
+pub struct PrimitivePairs<T: 'static> {
+    pairs: core::slice::Windows<'static, T>,
+}
+
+impl<T: Copy> Iterator for PrimitivePairs<T> {
+    type Item = (T, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pairs.next().map(|pair| (pair[0], pair[1]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.pairs.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.pairs.nth(n).map(|pair| (pair[0], pair[1]))
+    }
+
+    fn count(self) -> usize {
+        self.pairs.len()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<T: Copy> DoubleEndedIterator for PrimitivePairs<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.pairs.next_back().map(|pair| (pair[0], pair[1]))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.pairs.nth_back(n).map(|pair| (pair[0], pair[1]))
+    }
+}
+
+impl<T: Copy> ExactSizeIterator for PrimitivePairs<T> {}
 
-            fn fibonacci_iter() -> Self::Iter {
-                ($array).iter().copied()
-            }
+impl<T: Copy> core::iter::FusedIterator for PrimitivePairs<T> {}
 
-            fn nth_fibonacci(n: &Self) -> Option<Self> {
-                ($array).get(*n as usize).copied()
-            }
-        }
-    };
+/// The iterator returned by `fibonacci_up_to`.
+///
+/// It goes through the terms of `fibonacci_iter` and stops at the first one
+/// past the limit, so no term beyond that is ever computed.
+#[derive(Clone, Debug)]
+pub struct FibonacciUpTo<T: Fibonacci> {
+    terms: T::Iter,
+    // This is None once a term has passed the limit.
+    limit: Option<T>,
 }
 
-fibonacci_trait_from_signed_array!(
-    ::core::primitive::i8,
-    [0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89]
-);
-
-fibonacci_trait_from_unsigned_array!(
-    ::core::primitive::u8,
-    [0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89, 144, 233]
-);
-
-fibonacci_trait_from_signed_array!(
-    ::core::primitive::i16,
-    [
-        0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89, 144, 233, 377, 610, 987, 1597, 2584, 4181, 6765,
-        10946, 17711, 28657
-    ]
-);
-
-fibonacci_trait_from_unsigned_array!(
-    ::core::primitive::u16,
-    [
-        0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89, 144, 233, 377, 610, 987, 1597, 2584, 4181, 6765,
-        10946, 17711, 28657, 46368
-    ]
-);
-
-fibonacci_trait_from_signed_array!(
-    ::core::primitive::i32,
-    [
-        0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89, 144, 233, 377, 610, 987, 1597, 2584, 4181, 6765,
-        10946, 17711, 28657, 46368, 75025, 121393, 196418, 317811, 514229, 832040, 1346269,
-        2178309, 3524578, 5702887, 9227465, 14930352, 24157817, 39088169, 63245986, 102334155,
-        165580141, 267914296, 433494437, 701408733, 1134903170, 1836311903
-    ]
-);
-
-fibonacci_trait_from_unsigned_array!(
-    ::core::primitive::u32,
-    [
-        0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89, 144, 233, 377, 610, 987, 1597, 2584, 4181, 6765,
-        10946, 17711, 28657, 46368, 75025, 121393, 196418, 317811, 514229, 832040, 1346269,
-        2178309, 3524578, 5702887, 9227465, 14930352, 24157817, 39088169, 63245986, 102334155,
-        165580141, 267914296, 433494437, 701408733, 1134903170, 1836311903, 2971215073
-    ]
-);
-
-fibonacci_trait_from_signed_array!(
-    ::core::primitive::i64,
-    [
-        0,
-        1,
-        1,
-        2,
-        3,
-        5,
-        8,
-        13,
-        21,
-        34,
-        55,
-        89,
-        144,
-        233,
-        377,
-        610,
-        987,
-        1597,
-        2584,
-        4181,
-        6765,
-        10946,
-        17711,
-        28657,
-        46368,
-        75025,
-        121393,
-        196418,
-        317811,
-        514229,
-        832040,
-        1346269,
-        2178309,
-        3524578,
-        5702887,
-        9227465,
-        14930352,
-        24157817,
-        39088169,
-        63245986,
-        102334155,
-        165580141,
-        267914296,
-        433494437,
-        701408733,
-        1134903170,
-        1836311903,
-        2971215073,
-        4807526976,
-        7778742049,
-        12586269025,
-        20365011074,
-        32951280099,
-        53316291173,
-        86267571272,
-        139583862445,
-        225851433717,
-        365435296162,
-        591286729879,
-        956722026041,
-        1548008755920,
-        2504730781961,
-        4052739537881,
-        6557470319842,
-        10610209857723,
-        17167680177565,
-        27777890035288,
-        44945570212853,
-        72723460248141,
-        117669030460994,
-        190392490709135,
-        308061521170129,
-        498454011879264,
-        806515533049393,
-        1304969544928657,
-        2111485077978050,
-        3416454622906707,
-        5527939700884757,
-        8944394323791464,
-        14472334024676221,
-        23416728348467685,
-        37889062373143906,
-        61305790721611591,
-        99194853094755497,
-        160500643816367088,
-        259695496911122585,
-        420196140727489673,
-        679891637638612258,
-        1100087778366101931,
-        1779979416004714189,
-        2880067194370816120,
-        4660046610375530309,
-        7540113804746346429
-    ]
-);
-
-fibonacci_trait_from_unsigned_array!(
-    ::core::primitive::u64,
-    [
-        0,
-        1,
-        1,
-        2,
-        3,
-        5,
-        8,
-        13,
-        21,
-        34,
-        55,
-        89,
-        144,
-        233,
-        377,
-        610,
-        987,
-        1597,
-        2584,
-        4181,
-        6765,
-        10946,
-        17711,
-        28657,
-        46368,
-        75025,
-        121393,
-        196418,
-        317811,
-        514229,
-        832040,
-        1346269,
-        2178309,
-        3524578,
-        5702887,
-        9227465,
-        14930352,
-        24157817,
-        39088169,
-        63245986,
-        102334155,
-        165580141,
-        267914296,
-        433494437,
-        701408733,
-        1134903170,
-        1836311903,
-        2971215073,
-        4807526976,
-        7778742049,
-        12586269025,
-        20365011074,
-        32951280099,
-        53316291173,
-        86267571272,
-        139583862445,
-        225851433717,
-        365435296162,
-        591286729879,
-        956722026041,
-        1548008755920,
-        2504730781961,
-        4052739537881,
-        6557470319842,
-        10610209857723,
-        17167680177565,
-        27777890035288,
-        44945570212853,
-        72723460248141,
-        117669030460994,
-        190392490709135,
-        308061521170129,
-        498454011879264,
-        806515533049393,
-        1304969544928657,
-        2111485077978050,
-        3416454622906707,
-        5527939700884757,
-        8944394323791464,
-        14472334024676221,
-        23416728348467685,
-        37889062373143906,
-        61305790721611591,
-        99194853094755497,
-        160500643816367088,
-        259695496911122585,
-        420196140727489673,
-        679891637638612258,
-        1100087778366101931,
-        1779979416004714189,
-        2880067194370816120,
-        4660046610375530309,
-        7540113804746346429,
-        12200160415121876738
-    ]
-);
-
-fibonacci_trait_from_signed_array!(
-    ::core::primitive::i128,
-    [
-        0,
-        1,
-        1,
-        2,
-        3,
-        5,
-        8,
-        13,
-        21,
-        34,
-        55,
-        89,
-        144,
-        233,
-        377,
-        610,
-        987,
-        1597,
-        2584,
-        4181,
-        6765,
-        10946,
-        17711,
-        28657,
-        46368,
-        75025,
-        121393,
-        196418,
-        317811,
-        514229,
-        832040,
-        1346269,
-        2178309,
-        3524578,
-        5702887,
-        9227465,
-        14930352,
-        24157817,
-        39088169,
-        63245986,
-        102334155,
-        165580141,
-        267914296,
-        433494437,
-        701408733,
-        1134903170,
-        1836311903,
-        2971215073,
-        4807526976,
-        7778742049,
-        12586269025,
-        20365011074,
-        32951280099,
-        53316291173,
-        86267571272,
-        139583862445,
-        225851433717,
-        365435296162,
-        591286729879,
-        956722026041,
-        1548008755920,
-        2504730781961,
-        4052739537881,
-        6557470319842,
-        10610209857723,
-        17167680177565,
-        27777890035288,
-        44945570212853,
-        72723460248141,
-        117669030460994,
-        190392490709135,
-        308061521170129,
-        498454011879264,
-        806515533049393,
-        1304969544928657,
-        2111485077978050,
-        3416454622906707,
-        5527939700884757,
-        8944394323791464,
-        14472334024676221,
-        23416728348467685,
-        37889062373143906,
-        61305790721611591,
-        99194853094755497,
-        160500643816367088,
-        259695496911122585,
-        420196140727489673,
-        679891637638612258,
-        1100087778366101931,
-        1779979416004714189,
-        2880067194370816120,
-        4660046610375530309,
-        7540113804746346429,
-        12200160415121876738,
-        19740274219868223167,
-        31940434634990099905,
-        51680708854858323072,
-        83621143489848422977,
-        135301852344706746049,
-        218922995834555169026,
-        354224848179261915075,
-        573147844013817084101,
-        927372692193078999176,
-        1500520536206896083277,
-        2427893228399975082453,
-        3928413764606871165730,
-        6356306993006846248183,
-        10284720757613717413913,
-        16641027750620563662096,
-        26925748508234281076009,
-        43566776258854844738105,
-        70492524767089125814114,
-        114059301025943970552219,
-        184551825793033096366333,
-        298611126818977066918552,
-        483162952612010163284885,
-        781774079430987230203437,
-        1264937032042997393488322,
-        2046711111473984623691759,
-        3311648143516982017180081,
-        5358359254990966640871840,
-        8670007398507948658051921,
-        14028366653498915298923761,
-        22698374052006863956975682,
-        36726740705505779255899443,
-        59425114757512643212875125,
-        96151855463018422468774568,
-        155576970220531065681649693,
-        251728825683549488150424261,
-        407305795904080553832073954,
-        659034621587630041982498215,
-        1066340417491710595814572169,
-        1725375039079340637797070384,
-        2791715456571051233611642553,
-        4517090495650391871408712937,
-        7308805952221443105020355490,
-        11825896447871834976429068427,
-        19134702400093278081449423917,
-        30960598847965113057878492344,
-        50095301248058391139327916261,
-        81055900096023504197206408605,
-        131151201344081895336534324866,
-        212207101440105399533740733471,
-        343358302784187294870275058337,
-        555565404224292694404015791808,
-        898923707008479989274290850145,
-        1454489111232772683678306641953,
-        2353412818241252672952597492098,
-        3807901929474025356630904134051,
-        6161314747715278029583501626149,
-        9969216677189303386214405760200,
-        16130531424904581415797907386349,
-        26099748102093884802012313146549,
-        42230279526998466217810220532898,
-        68330027629092351019822533679447,
-        110560307156090817237632754212345,
-        178890334785183168257455287891792,
-        289450641941273985495088042104137,
-        468340976726457153752543329995929,
-        757791618667731139247631372100066,
-        1226132595394188293000174702095995,
-        1983924214061919432247806074196061,
-        3210056809456107725247980776292056,
-        5193981023518027157495786850488117,
-        8404037832974134882743767626780173,
-        13598018856492162040239554477268290,
-        22002056689466296922983322104048463,
-        35600075545958458963222876581316753,
-        57602132235424755886206198685365216,
-        93202207781383214849429075266681969,
-        150804340016807970735635273952047185,
-        244006547798191185585064349218729154,
-        394810887814999156320699623170776339,
-        638817435613190341905763972389505493,
-        1033628323428189498226463595560281832,
-        1672445759041379840132227567949787325,
-        2706074082469569338358691163510069157,
-        4378519841510949178490918731459856482,
-        7084593923980518516849609894969925639,
-        11463113765491467695340528626429782121,
-        18547707689471986212190138521399707760,
-        30010821454963453907530667147829489881,
-        48558529144435440119720805669229197641,
-        78569350599398894027251472817058687522,
-        127127879743834334146972278486287885163,
-    ]
-);
-
-fibonacci_trait_from_unsigned_array!(
-    ::core::primitive::u128,
-    [
-        0,
-        1,
-        1,
-        2,
-        3,
-        5,
-        8,
-        13,
-        21,
-        34,
-        55,
-        89,
-        144,
-        233,
-        377,
-        610,
-        987,
-        1597,
-        2584,
-        4181,
-        6765,
-        10946,
-        17711,
-        28657,
-        46368,
-        75025,
-        121393,
-        196418,
-        317811,
-        514229,
-        832040,
-        1346269,
-        2178309,
-        3524578,
-        5702887,
-        9227465,
-        14930352,
-        24157817,
-        39088169,
-        63245986,
-        102334155,
-        165580141,
-        267914296,
-        433494437,
-        701408733,
-        1134903170,
-        1836311903,
-        2971215073,
-        4807526976,
-        7778742049,
-        12586269025,
-        20365011074,
-        32951280099,
-        53316291173,
-        86267571272,
-        139583862445,
-        225851433717,
-        365435296162,
-        591286729879,
-        956722026041,
-        1548008755920,
-        2504730781961,
-        4052739537881,
-        6557470319842,
-        10610209857723,
-        17167680177565,
-        27777890035288,
-        44945570212853,
-        72723460248141,
-        117669030460994,
-        190392490709135,
-        308061521170129,
-        498454011879264,
-        806515533049393,
-        1304969544928657,
-        2111485077978050,
-        3416454622906707,
-        5527939700884757,
-        8944394323791464,
-        14472334024676221,
-        23416728348467685,
-        37889062373143906,
-        61305790721611591,
-        99194853094755497,
-        160500643816367088,
-        259695496911122585,
-        420196140727489673,
-        679891637638612258,
-        1100087778366101931,
-        1779979416004714189,
-        2880067194370816120,
-        4660046610375530309,
-        7540113804746346429,
-        12200160415121876738,
-        19740274219868223167,
-        31940434634990099905,
-        51680708854858323072,
-        83621143489848422977,
-        135301852344706746049,
-        218922995834555169026,
-        354224848179261915075,
-        573147844013817084101,
-        927372692193078999176,
-        1500520536206896083277,
-        2427893228399975082453,
-        3928413764606871165730,
-        6356306993006846248183,
-        10284720757613717413913,
-        16641027750620563662096,
-        26925748508234281076009,
-        43566776258854844738105,
-        70492524767089125814114,
-        114059301025943970552219,
-        184551825793033096366333,
-        298611126818977066918552,
-        483162952612010163284885,
-        781774079430987230203437,
-        1264937032042997393488322,
-        2046711111473984623691759,
-        3311648143516982017180081,
-        5358359254990966640871840,
-        8670007398507948658051921,
-        14028366653498915298923761,
-        22698374052006863956975682,
-        36726740705505779255899443,
-        59425114757512643212875125,
-        96151855463018422468774568,
-        155576970220531065681649693,
-        251728825683549488150424261,
-        407305795904080553832073954,
-        659034621587630041982498215,
-        1066340417491710595814572169,
-        1725375039079340637797070384,
-        2791715456571051233611642553,
-        4517090495650391871408712937,
-        7308805952221443105020355490,
-        11825896447871834976429068427,
-        19134702400093278081449423917,
-        30960598847965113057878492344,
-        50095301248058391139327916261,
-        81055900096023504197206408605,
-        131151201344081895336534324866,
-        212207101440105399533740733471,
-        343358302784187294870275058337,
-        555565404224292694404015791808,
-        898923707008479989274290850145,
-        1454489111232772683678306641953,
-        2353412818241252672952597492098,
-        3807901929474025356630904134051,
-        6161314747715278029583501626149,
-        9969216677189303386214405760200,
-        16130531424904581415797907386349,
-        26099748102093884802012313146549,
-        42230279526998466217810220532898,
-        68330027629092351019822533679447,
-        110560307156090817237632754212345,
-        178890334785183168257455287891792,
-        289450641941273985495088042104137,
-        468340976726457153752543329995929,
-        757791618667731139247631372100066,
-        1226132595394188293000174702095995,
-        1983924214061919432247806074196061,
-        3210056809456107725247980776292056,
-        5193981023518027157495786850488117,
-        8404037832974134882743767626780173,
-        13598018856492162040239554477268290,
-        22002056689466296922983322104048463,
-        35600075545958458963222876581316753,
-        57602132235424755886206198685365216,
-        93202207781383214849429075266681969,
-        150804340016807970735635273952047185,
-        244006547798191185585064349218729154,
-        394810887814999156320699623170776339,
-        638817435613190341905763972389505493,
-        1033628323428189498226463595560281832,
-        1672445759041379840132227567949787325,
-        2706074082469569338358691163510069157,
-        4378519841510949178490918731459856482,
-        7084593923980518516849609894969925639,
-        11463113765491467695340528626429782121,
-        18547707689471986212190138521399707760,
-        30010821454963453907530667147829489881,
-        48558529144435440119720805669229197641,
-        78569350599398894027251472817058687522,
-        127127879743834334146972278486287885163,
-        205697230343233228174223751303346572685,
-        332825110087067562321196029789634457848
-    ]
-);
-
-fibonacci_trait_from_signed_array!(::core::primitive::isize, {
-    const ARRAY_SIZE: usize = {
-        let mut result = 0usize;
-        let mut a = Some(0isize);
-        let mut b = Some(1isize);
-
-        while a.is_some() {
-            let temp = b;
-            b = match (a, b) {
-                (Some(a), Some(b)) => a.checked_add(b),
-                _ => None,
-            };
-            a = temp;
+impl<T: Fibonacci + PartialOrd> Iterator for FibonacciUpTo<T> {
+    type Item = T;
 
-            result += 1;
+    fn next(&mut self) -> Option<Self::Item> {
+        let limit = self.limit.as_ref()?;
+        let term = self.terms.next().filter(|term| term <= limit);
+        if term.is_none() {
+            self.limit = None;
         }
+        term
+    }
 
-        result
-    };
-
-    const ARRAY: [isize; ARRAY_SIZE] = {
-        let mut result = [0isize; ARRAY_SIZE];
-        let mut a = Some(0isize);
-        let mut b = Some(1isize);
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.limit {
+            Some(_) => (0, self.terms.size_hint().1),
+            None => (0, Some(0)),
+        }
+    }
+}
 
-        let mut i = 0;
-        while a.is_some() {
-            result[i] = match a {
-                Some(a) => a,
-                _ => loop {},
-            };
+impl<T: Fibonacci + PartialOrd> core::iter::FusedIterator for FibonacciUpTo<T> {}
 
-            let temp = b;
-            b = match (a, b) {
-                (Some(a), Some(b)) => a.checked_add(b),
-                _ => None,
-            };
-            a = temp;
+/// Returns (F(n), F(n + 1)), using GMP's own Fibonacci routine when n + 1
+/// fits in a `u32` and fast doubling, F(2k) = F(k) (2F(k + 1) − F(k)) and
+/// F(2k + 1) = F(k)² + F(k + 1)², otherwise.
+#[cfg(any(feature = "rug", doc, test))]
+fn rug_fibonacci_pair(n: usize) -> (rug::Integer, rug::Integer) {
+    if let Some(next) = n
+        .checked_add(1)
+        .and_then(|next| core::convert::TryFrom::try_from(next).ok())
+    {
+        let (b, a) = <(rug::Integer, rug::Integer)>::from(rug::Integer::fibonacci_2(next));
+        return (a, b);
+    }
 
-            i += 1;
+    let (mut a, mut b) = (rug::Integer::new(), rug::Integer::from(1));
+    for bit in (0..usize::BITS - n.leading_zeros()).rev() {
+        let even = (rug::Integer::from(&b * 2u8) - &a) * &a;
+        let odd = rug::Integer::from(a.square_ref()) + b.square_ref();
+        if n >> bit & 1 == 1 {
+            b = even + &odd;
+            a = odd;
+        } else {
+            a = even;
+            b = odd;
         }
+    }
+    (a, b)
+}
 
-        result
-    };
-
-    ARRAY
-});
+/// The iterator returned by `fibonacci_iter` and `fibonacci_iter_from` for
+/// `rug::Integer`.
+///
+/// It also implements `StreamingSequence`, whose `next_ref` method lends out
+/// each term instead of cloning it, and updates its terms in place.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::{Fibonacci, StreamingSequence};
+///
+/// let mut terms = rug::Integer::fibonacci_iter();
+/// let mut bits = 0;
+/// for _ in 0..=1000 {
+///     bits = terms.next_ref().unwrap().significant_bits();
+/// }
+/// assert_eq!(bits, 694);
+/// ```
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+pub struct RugIter {
+    index: usize,
+    current: rug::Integer,
+    next: rug::Integer,
+    lent: bool,
+}
 
-fibonacci_trait_from_unsigned_array!(::core::primitive::usize, {
-    const ARRAY_SIZE: usize = {
-        let mut result = 0usize;
-        let mut a = Some(0usize);
-        let mut b = Some(1usize);
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl RugIter {
+    fn new() -> Self {
+        Self::starting_at(0)
+    }
 
-        while a.is_some() {
-            let temp = b;
-            b = match (a, b) {
-                (Some(a), Some(b)) => a.checked_add(b),
-                _ => None,
-            };
-            a = temp;
+    /// Starts at F(n), seeded with the pair (F(n), F(n + 1)).
+    fn starting_at(index: usize) -> Self {
+        let (current, next) = rug_fibonacci_pair(index);
+        Self {
+            index,
+            current,
+            next,
+            lent: false,
+        }
+    }
 
-            result += 1;
+    /// Moves past the term last lent out by `next_ref`, if any.
+    fn settle(&mut self) {
+        if self.lent {
+            self.lent = false;
+            self.step();
         }
+    }
 
-        result
-    };
+    fn step(&mut self) {
+        self.current += &self.next;
+        core::mem::swap(&mut self.current, &mut self.next);
+        self.index += 1;
+    }
+}
 
-    const ARRAY: [usize; ARRAY_SIZE] = {
-        let mut result = [0usize; ARRAY_SIZE];
-        let mut a = Some(0usize);
-        let mut b = Some(1usize);
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl crate::sequences::StreamingSequence for RugIter {
+    type Item = rug::Integer;
 
-        let mut i = 0;
-        while a.is_some() {
-            result[i] = match a {
-                Some(a) => a,
-                _ => loop {},
-            };
+    fn next_ref(&mut self) -> Option<&Self::Item> {
+        self.settle();
+        self.lent = true;
+        Some(&self.current)
+    }
+}
 
-            let temp = b;
-            b = match (a, b) {
-                (Some(a), Some(b)) => a.checked_add(b),
-                _ => None,
-            };
-            a = temp;
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for RugIter {
+    type Item = rug::Integer;
 
-            i += 1;
-        }
+    fn next(&mut self) -> Option<Self::Item> {
+        self.settle();
+        let result = self.current.clone();
+        self.step();
+        Some(result)
+    }
 
-        result
-    };
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.settle();
+        // A few additions are cheaper than recomputing the pair from scratch.
+        if n < 64 {
+            for _ in 0..n {
+                self.step();
+            }
+        } else {
+            *self = Self::starting_at(self.index.saturating_add(n));
+        }
+        self.next()
+    }
+}
 
-    ARRAY
-});
+/// The iterator returned by `fibonacci_pairs` for `rug::Integer`.
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+pub struct RugPairs {
+    terms: RugIter,
+}
 
 #[cfg(any(feature = "rug", doc, test))]
 #[doc(cfg(feature = "rug"))]
-impl Fibonacci for rug::Integer {
-    type Iter = RugIter;
+impl Iterator for RugPairs {
+    type Item = (rug::Integer, rug::Integer);
 
-    fn fibonacci_iter() -> Self::Iter {
-        RugIter::new()
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = (self.terms.current.clone(), self.terms.next.clone());
+        self.terms.step();
+        Some(result)
     }
 
-    fn nth_fibonacci(n: &Self) -> Option<Self> {
-        if *n < rug::Integer::new() {
-            (-n.clone()).to_usize().and_then(|n| {
-                Self::fibonacci_iter().nth(n).map(
-                    |result| {
-                        if n & 1 == 1 {
-                            -result
-                        } else {
-                            result
-                        }
-                    },
-                )
-            })
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        // A few additions are cheaper than recomputing the pair from scratch.
+        if n < 64 {
+            for _ in 0..n {
+                self.terms.step();
+            }
         } else {
-            n.to_usize().and_then(|n| Self::fibonacci_iter().nth(n))
+            self.terms = RugIter::starting_at(self.terms.index.saturating_add(n));
         }
+        self.next()
     }
 }
 
+/// The iterator returned by `negafibonacci_iter` for `rug::Integer`.
+///
+/// It also implements `StreamingSequence`, whose `next_ref` method lends out
+/// each term instead of cloning it, and updates its terms in place.
 #[cfg(any(feature = "rug", doc, test))]
 #[doc(cfg(feature = "rug"))]
-pub struct RugIter {
-    a: rug::Integer,
-    b: rug::Integer,
-    a_next: bool,
+pub struct RugNegaIter {
+    current: rug::Integer,
+    next: rug::Integer,
+    lent: bool,
 }
 
 #[cfg(any(feature = "rug", doc, test))]
 #[doc(cfg(feature = "rug"))]
-impl RugIter {
+impl RugNegaIter {
     fn new() -> Self {
         Self {
-            a: rug::Integer::new(),
-            b: rug::Integer::new() + 1u8,
-            a_next: true,
+            current: rug::Integer::new(),
+            next: rug::Integer::from(1),
+            lent: false,
+        }
+    }
+
+    /// Moves past the term last lent out by `next_ref`, if any.
+    fn settle(&mut self) {
+        if self.lent {
+            self.lent = false;
+            self.step();
         }
     }
+
+    fn step(&mut self) {
+        // F(−n − 2) = F(−n) − F(−n − 1).
+        self.current -= &self.next;
+        core::mem::swap(&mut self.current, &mut self.next);
+    }
 }
 
 #[cfg(any(feature = "rug", doc, test))]
 #[doc(cfg(feature = "rug"))]
-impl Iterator for RugIter {
+impl crate::sequences::StreamingSequence for RugNegaIter {
+    type Item = rug::Integer;
+
+    fn next_ref(&mut self) -> Option<&Self::Item> {
+        self.settle();
+        self.lent = true;
+        Some(&self.current)
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for RugNegaIter {
     type Item = rug::Integer;
 
     fn next(&mut self) -> Option<Self::Item> {
-        Some(if self.a_next {
-            self.a_next = false;
-            let result = self.a.clone();
-            self.a += &self.b;
-            result
-        } else {
-            self.a_next = true;
-            let result = self.b.clone();
-            self.b += &self.a;
-            result
-        })
+        self.settle();
+        let result = self.current.clone();
+        self.step();
+        Some(result)
     }
 }
 
@@ -989,6 +1467,722 @@ mod tests {
         };
     }
 
+    #[test]
+    fn table_lengths() {
+        assert_eq!(u8::fibonacci_iter().len(), 14);
+        assert_eq!(i64::fibonacci_iter().len(), 93);
+        assert_eq!(u64::fibonacci_iter().len(), 94);
+        assert_eq!(i128::fibonacci_iter().len(), 185);
+        assert_eq!(u128::fibonacci_iter().len(), 187);
+        assert_eq!(
+            u128::fibonacci_iter().last(),
+            Some(332_825_110_087_067_562_321_196_029_789_634_457_848)
+        );
+        assert_eq!(i8::nth_fibonacci(&i8::MIN), None);
+        assert_eq!(i128::nth_fibonacci(&i128::MIN), None);
+        assert_eq!(u128::nth_fibonacci(&(1 << 64)), None);
+    }
+
+    macro_rules! test_mod_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let max = <$type>::MAX;
+                let moduli = [1, 2, 10, 97, max / 3, max - 1, max];
+                let indices = [0, 1, 2, 3, 50, 99, max / 7, max - 1, max];
+                for &m in moduli.iter() {
+                    for &n in indices.iter() {
+                        let expected = rug::Integer::nth_fibonacci_mod(&n.into(), &m.into());
+                        assert_eq!(
+                            <$type>::nth_fibonacci_mod(&n, &m),
+                            expected,
+                            "n = {}, m = {}",
+                            n,
+                            m
+                        );
+                    }
+                }
+            }
+        };
+    }
+
+    test_mod_against_rug!(::core::primitive::i8, i8_mod);
+    test_mod_against_rug!(::core::primitive::u8, u8_mod);
+    test_mod_against_rug!(::core::primitive::i16, i16_mod);
+    test_mod_against_rug!(::core::primitive::u16, u16_mod);
+    test_mod_against_rug!(::core::primitive::i32, i32_mod);
+    test_mod_against_rug!(::core::primitive::u32, u32_mod);
+    test_mod_against_rug!(::core::primitive::i64, i64_mod);
+    test_mod_against_rug!(::core::primitive::u64, u64_mod);
+    test_mod_against_rug!(::core::primitive::i128, i128_mod);
+    test_mod_against_rug!(::core::primitive::u128, u128_mod);
+    test_mod_against_rug!(::core::primitive::isize, isize_mod);
+    test_mod_against_rug!(::core::primitive::usize, usize_mod);
+
+    #[test]
+    fn mod_matches_exact_values() {
+        for n in -300i32..300 {
+            let exact = rug::Integer::nth_fibonacci(&n.into()).unwrap();
+            for m in [1u32, 2, 3, 1000, 1_000_000_007] {
+                let m = rug::Integer::from(m);
+                let expected = rug::ops::RemRounding::rem_euc(exact.clone(), &m);
+                assert_eq!(
+                    rug::Integer::nth_fibonacci_mod(&n.into(), &m),
+                    expected,
+                    "n = {}, m = {}",
+                    n,
+                    m
+                );
+                if let Some(m) = m.to_i32() {
+                    assert_eq!(i32::nth_fibonacci_mod(&n, &m), expected);
+                }
+            }
+        }
+        assert_eq!(
+            u128::nth_fibonacci_mod(&u128::MAX, &(u128::MAX - 158)),
+            340_282_077_470_296_522_189_389_112_343_726_107_160
+        );
+        assert_eq!(
+            u64::nth_fibonacci_mod(&u64::MAX, &(u64::MAX - 58)),
+            18_446_743_708_274_255_395
+        );
+    }
+
+    #[test]
+    fn mod_splits_large_even_moduli() {
+        for &m in &[1u128 << 64, 1 << 127, 3 << 100, (u128::MAX / 3) << 1] {
+            for &n in &[0u128, 1, 2, 1000, u128::MAX / 7, u128::MAX] {
+                let expected = rug::Integer::nth_fibonacci_mod(&n.into(), &m.into());
+                assert_eq!(
+                    u128::nth_fibonacci_mod(&n, &m),
+                    expected,
+                    "n = {}, m = {}",
+                    n,
+                    m
+                );
+            }
+        }
+    }
+
+    macro_rules! test_is_fibonacci {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let terms: ::std::vec::Vec<_> = <$type>::fibonacci_iter().collect();
+                let max = <$type>::MAX;
+                for &n in terms.iter() {
+                    assert!(<$type>::is_fibonacci(&n), "n = {}", n);
+                    if n < max && !terms.contains(&(n + 1)) {
+                        assert!(!<$type>::is_fibonacci(&(n + 1)), "n = {}", n + 1);
+                    }
+                    if n > 4 {
+                        assert!(!<$type>::is_fibonacci(&(n - 1)), "n = {}", n - 1);
+                    }
+                }
+                for n in (0..=max).take(3000) {
+                    assert_eq!(<$type>::is_fibonacci(&n), terms.contains(&n), "n = {}", n);
+                }
+                assert!(!<$type>::is_fibonacci(&max));
+            }
+        };
+    }
+
+    test_is_fibonacci!(::core::primitive::i8, i8_is_fibonacci);
+    test_is_fibonacci!(::core::primitive::u8, u8_is_fibonacci);
+    test_is_fibonacci!(::core::primitive::i16, i16_is_fibonacci);
+    test_is_fibonacci!(::core::primitive::u16, u16_is_fibonacci);
+    test_is_fibonacci!(::core::primitive::i32, i32_is_fibonacci);
+    test_is_fibonacci!(::core::primitive::u32, u32_is_fibonacci);
+    test_is_fibonacci!(::core::primitive::i64, i64_is_fibonacci);
+    test_is_fibonacci!(::core::primitive::u64, u64_is_fibonacci);
+    test_is_fibonacci!(::core::primitive::i128, i128_is_fibonacci);
+    test_is_fibonacci!(::core::primitive::u128, u128_is_fibonacci);
+    test_is_fibonacci!(::core::primitive::isize, isize_is_fibonacci);
+    test_is_fibonacci!(::core::primitive::usize, usize_is_fibonacci);
+
+    #[test]
+    fn rug_is_fibonacci() {
+        let terms: Vec<_> = rug::Integer::fibonacci_iter().take(1000).collect();
+        for pair in terms.windows(2).skip(4) {
+            assert!(rug::Integer::is_fibonacci(&pair[1]));
+            assert!(!rug::Integer::is_fibonacci(&rug::Integer::from(
+                &pair[1] - 1u8
+            )));
+            let between = rug::Integer::from(&pair[0] / 2u8) + &pair[1];
+            assert!(!rug::Integer::is_fibonacci(&between));
+        }
+        for n in 0..3000u32 {
+            assert_eq!(
+                rug::Integer::is_fibonacci(&n.into()),
+                u32::is_fibonacci(&n),
+                "n = {}",
+                n
+            );
+        }
+        assert!(!rug::Integer::is_fibonacci(&(-1).into()));
+    }
+
+    macro_rules! test_fibonacci_index {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                for (k, term) in <$type>::fibonacci_iter().enumerate() {
+                    let expected = if k == 2 { 1 } else { k };
+                    assert_eq!(<$type>::fibonacci_index(&term), Some(expected));
+                }
+                for n in (0..=<$type>::MAX).take(3000) {
+                    assert_eq!(
+                        <$type>::fibonacci_index(&n).is_some(),
+                        <$type>::is_fibonacci(&n),
+                        "n = {}",
+                        n
+                    );
+                }
+                assert_eq!(<$type>::fibonacci_index(&<$type>::MAX), None);
+            }
+        };
+    }
+
+    test_fibonacci_index!(::core::primitive::i8, i8_fibonacci_index);
+    test_fibonacci_index!(::core::primitive::u8, u8_fibonacci_index);
+    test_fibonacci_index!(::core::primitive::i16, i16_fibonacci_index);
+    test_fibonacci_index!(::core::primitive::u16, u16_fibonacci_index);
+    test_fibonacci_index!(::core::primitive::i32, i32_fibonacci_index);
+    test_fibonacci_index!(::core::primitive::u32, u32_fibonacci_index);
+    test_fibonacci_index!(::core::primitive::i64, i64_fibonacci_index);
+    test_fibonacci_index!(::core::primitive::u64, u64_fibonacci_index);
+    test_fibonacci_index!(::core::primitive::i128, i128_fibonacci_index);
+    test_fibonacci_index!(::core::primitive::u128, u128_fibonacci_index);
+    test_fibonacci_index!(::core::primitive::isize, isize_fibonacci_index);
+    test_fibonacci_index!(::core::primitive::usize, usize_fibonacci_index);
+
+    #[test]
+    fn rug_fibonacci_index() {
+        for (k, term) in rug::Integer::fibonacci_iter().take(3000).enumerate() {
+            let expected = if k == 2 { 1 } else { k };
+            assert_eq!(rug::Integer::fibonacci_index(&term), Some(expected));
+            if k > 4 {
+                let off = rug::Integer::from(&term + 1u8);
+                assert_eq!(rug::Integer::fibonacci_index(&off), None, "k = {}", k);
+            }
+        }
+        for n in 0..3000u32 {
+            assert_eq!(
+                rug::Integer::fibonacci_index(&n.into()),
+                u32::fibonacci_index(&n),
+                "n = {}",
+                n
+            );
+        }
+        assert_eq!(rug::Integer::fibonacci_index(&(-1).into()), None);
+        let huge = rug::Integer::from(rug::Integer::fibonacci(100_000));
+        assert_eq!(rug::Integer::fibonacci_index(&huge), Some(100_000));
+    }
+
+    macro_rules! test_iter_from_both_ends {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let terms: ::std::vec::Vec<_> = <$type>::fibonacci_iter().collect();
+                let mut iter = <$type>::fibonacci_iter();
+                assert_eq!(iter.len(), terms.len());
+                assert_eq!(iter.next_back(), terms.last().copied());
+                assert_eq!(iter.nth(3), Some(2));
+                assert_eq!(iter.len(), terms.len() - 5);
+                let rest: ::std::vec::Vec<_> = iter.rev().collect();
+                let expected: ::std::vec::Vec<_> =
+                    terms[4..terms.len() - 1].iter().rev().copied().collect();
+                assert_eq!(rest, expected);
+                assert_eq!(<$type>::fibonacci_iter().last(), terms.last().copied());
+                assert_eq!(<$type>::fibonacci_iter().nth(terms.len()), None);
+            }
+        };
+    }
+
+    test_iter_from_both_ends!(::core::primitive::i8, i8_iter_from_both_ends);
+    test_iter_from_both_ends!(::core::primitive::u8, u8_iter_from_both_ends);
+    test_iter_from_both_ends!(::core::primitive::i16, i16_iter_from_both_ends);
+    test_iter_from_both_ends!(::core::primitive::u16, u16_iter_from_both_ends);
+    test_iter_from_both_ends!(::core::primitive::i32, i32_iter_from_both_ends);
+    test_iter_from_both_ends!(::core::primitive::u32, u32_iter_from_both_ends);
+    test_iter_from_both_ends!(::core::primitive::i64, i64_iter_from_both_ends);
+    test_iter_from_both_ends!(::core::primitive::u64, u64_iter_from_both_ends);
+    test_iter_from_both_ends!(::core::primitive::i128, i128_iter_from_both_ends);
+    test_iter_from_both_ends!(::core::primitive::u128, u128_iter_from_both_ends);
+    test_iter_from_both_ends!(::core::primitive::isize, isize_iter_from_both_ends);
+    test_iter_from_both_ends!(::core::primitive::usize, usize_iter_from_both_ends);
+
+    macro_rules! test_iter_from {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let len = <$type>::fibonacci_iter().len();
+                for n in 0..len + 3 {
+                    assert!(<$type>::fibonacci_iter_from(n).eq(<$type>::fibonacci_iter().skip(n)));
+                }
+                assert_eq!(<$type>::fibonacci_iter_from(usize::MAX).next(), None);
+            }
+        };
+    }
+
+    test_iter_from!(::core::primitive::i8, i8_iter_from);
+    test_iter_from!(::core::primitive::u8, u8_iter_from);
+    test_iter_from!(::core::primitive::i16, i16_iter_from);
+    test_iter_from!(::core::primitive::u16, u16_iter_from);
+    test_iter_from!(::core::primitive::i32, i32_iter_from);
+    test_iter_from!(::core::primitive::u32, u32_iter_from);
+    test_iter_from!(::core::primitive::i64, i64_iter_from);
+    test_iter_from!(::core::primitive::u64, u64_iter_from);
+    test_iter_from!(::core::primitive::i128, i128_iter_from);
+    test_iter_from!(::core::primitive::u128, u128_iter_from);
+    test_iter_from!(::core::primitive::isize, isize_iter_from);
+    test_iter_from!(::core::primitive::usize, usize_iter_from);
+
+    #[test]
+    fn rug_iter_nth() {
+        let terms: Vec<_> = rug::Integer::fibonacci_iter().take(2000).collect();
+        for step in [0, 1, 5, 63, 64, 65, 500] {
+            let mut iter = rug::Integer::fibonacci_iter();
+            let mut index = 0;
+            while index + step < 1900 {
+                assert_eq!(iter.nth(step).as_ref(), Some(&terms[index + step]));
+                assert_eq!(iter.next().as_ref(), Some(&terms[index + step + 1]));
+                index += step + 2;
+            }
+        }
+    }
+
+    #[test]
+    fn rug_streaming_matches_iterator() {
+        use crate::sequences::StreamingSequence;
+
+        let expected: Vec<_> = rug::Integer::fibonacci_iter().take(400).collect();
+        let mut streaming = rug::Integer::fibonacci_iter();
+        for term in expected.iter() {
+            assert_eq!(streaming.next_ref(), Some(term));
+        }
+
+        // Mixing the two kinds of advance keeps to the same sequence.
+        let mut mixed = rug::Integer::fibonacci_iter_from(10);
+        assert_eq!(mixed.next_ref(), Some(&expected[10]));
+        assert_eq!(mixed.next().as_ref(), Some(&expected[11]));
+        assert_eq!(mixed.next_ref(), Some(&expected[12]));
+        assert_eq!(mixed.nth(100).as_ref(), Some(&expected[113]));
+        assert_eq!(mixed.next_ref(), Some(&expected[114]));
+        assert_eq!(mixed.next_ref(), Some(&expected[115]));
+        assert_eq!(mixed.next().as_ref(), Some(&expected[116]));
+
+        let expected: Vec<_> = rug::Integer::negafibonacci_iter().take(400).collect();
+        let mut streaming = rug::Integer::negafibonacci_iter();
+        // Every third term is taken by value, and the rest by reference.
+        let mut index = 0;
+        while index < expected.len() {
+            if index % 3 == 2 {
+                assert_eq!(streaming.next().as_ref(), Some(&expected[index]));
+            } else {
+                assert_eq!(streaming.next_ref(), Some(&expected[index]));
+            }
+            index += 1;
+        }
+        assert_eq!(
+            streaming.next_ref(),
+            rug::Integer::negafibonacci_iter().nth(400).as_ref()
+        );
+    }
+
+    macro_rules! test_pairs {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let terms: ::std::vec::Vec<_> = <$type>::fibonacci_iter().collect();
+                let pairs: ::std::vec::Vec<_> = <$type>::fibonacci_pairs().collect();
+                assert_eq!(pairs.len(), terms.len() - 1);
+                assert_eq!(<$type>::fibonacci_pairs().len(), pairs.len());
+                for (n, &(a, b)) in pairs.iter().enumerate() {
+                    assert_eq!((a, b), (terms[n], terms[n + 1]));
+                    assert_eq!(<$type>::fibonacci_pairs().nth(n), Some((a, b)));
+                }
+                let reversed: ::std::vec::Vec<_> = <$type>::fibonacci_pairs().rev().collect();
+                assert!(reversed.iter().eq(pairs.iter().rev()));
+            }
+        };
+    }
+
+    test_pairs!(::core::primitive::i8, i8_pairs);
+    test_pairs!(::core::primitive::u8, u8_pairs);
+    test_pairs!(::core::primitive::i16, i16_pairs);
+    test_pairs!(::core::primitive::u16, u16_pairs);
+    test_pairs!(::core::primitive::i32, i32_pairs);
+    test_pairs!(::core::primitive::u32, u32_pairs);
+    test_pairs!(::core::primitive::i64, i64_pairs);
+    test_pairs!(::core::primitive::u64, u64_pairs);
+    test_pairs!(::core::primitive::i128, i128_pairs);
+    test_pairs!(::core::primitive::u128, u128_pairs);
+    test_pairs!(::core::primitive::isize, isize_pairs);
+    test_pairs!(::core::primitive::usize, usize_pairs);
+
+    macro_rules! test_nonzero {
+        ($type:ty, $primitive:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let terms: ::std::vec::Vec<_> = <$primitive>::fibonacci_iter().skip(1).collect();
+                let nonzero: ::std::vec::Vec<_> =
+                    <$type>::fibonacci_iter().map(|term| term.get()).collect();
+                assert_eq!(nonzero, terms);
+                for n in 0..terms.len() + 2 {
+                    assert!(<$type>::fibonacci_iter_from(n)
+                        .map(|term| term.get())
+                        .eq(<$primitive>::fibonacci_iter_from(n.max(1))));
+                }
+                assert!(<$type>::fibonacci_pairs()
+                    .map(|(a, b)| (a.get(), b.get()))
+                    .eq(<$primitive>::fibonacci_pairs().skip(1)));
+
+                for n in (1..=<$primitive>::MAX).take(300) {
+                    let nonzero = <$type>::new(n).unwrap();
+                    assert_eq!(
+                        <$type>::nth_fibonacci(&nonzero).map(|term| term.get()),
+                        <$primitive>::nth_fibonacci(&n)
+                    );
+                    assert_eq!(
+                        <$type>::is_fibonacci(&nonzero),
+                        <$primitive>::is_fibonacci(&n)
+                    );
+                    assert_eq!(
+                        <$type>::fibonacci_index(&nonzero),
+                        <$primitive>::fibonacci_index(&n)
+                    );
+                    assert!(<$type>::fibonacci_up_to(&nonzero)
+                        .map(|term| term.get())
+                        .eq(<$primitive>::fibonacci_up_to(&n).skip(1)));
+                }
+            }
+        };
+    }
+
+    test_nonzero!(::core::num::NonZeroI8, ::core::primitive::i8, nonzero_i8);
+    test_nonzero!(::core::num::NonZeroU8, ::core::primitive::u8, nonzero_u8);
+    test_nonzero!(::core::num::NonZeroI16, ::core::primitive::i16, nonzero_i16);
+    test_nonzero!(::core::num::NonZeroU16, ::core::primitive::u16, nonzero_u16);
+    test_nonzero!(::core::num::NonZeroI32, ::core::primitive::i32, nonzero_i32);
+    test_nonzero!(::core::num::NonZeroU32, ::core::primitive::u32, nonzero_u32);
+    test_nonzero!(::core::num::NonZeroI64, ::core::primitive::i64, nonzero_i64);
+    test_nonzero!(::core::num::NonZeroU64, ::core::primitive::u64, nonzero_u64);
+    test_nonzero!(
+        ::core::num::NonZeroI128,
+        ::core::primitive::i128,
+        nonzero_i128
+    );
+    test_nonzero!(
+        ::core::num::NonZeroU128,
+        ::core::primitive::u128,
+        nonzero_u128
+    );
+    test_nonzero!(
+        ::core::num::NonZeroIsize,
+        ::core::primitive::isize,
+        nonzero_isize
+    );
+    test_nonzero!(
+        ::core::num::NonZeroUsize,
+        ::core::primitive::usize,
+        nonzero_usize
+    );
+
+    #[test]
+    fn nonzero_negative_indices() {
+        let n = core::num::NonZeroI32::new(-10).unwrap();
+        assert_eq!(
+            core::num::NonZeroI32::nth_fibonacci(&n).map(|term| term.get()),
+            Some(-55)
+        );
+    }
+
+    #[test]
+    fn rug_pairs() {
+        let terms: Vec<_> = rug::Integer::fibonacci_iter().take(1000).collect();
+        for (n, (a, b)) in rug::Integer::fibonacci_pairs().take(999).enumerate() {
+            assert_eq!((&a, &b), (&terms[n], &terms[n + 1]));
+        }
+        for step in [0, 1, 5, 63, 64, 65, 500] {
+            let mut pairs = rug::Integer::fibonacci_pairs();
+            let mut n = step;
+            while n + 1 < terms.len() {
+                let (a, b) = pairs.nth(step).unwrap();
+                assert_eq!((&a, &b), (&terms[n], &terms[n + 1]), "step = {}", step);
+                n += step + 1;
+            }
+        }
+        let pairs = rug::Integer::fibonacci_pairs().zip(u128::fibonacci_pairs());
+        for ((a, b), (c, d)) in pairs {
+            assert_eq!(a, c);
+            assert_eq!(b, d);
+        }
+    }
+
+    #[test]
+    fn rug_iter_from() {
+        let terms: Vec<_> = rug::Integer::fibonacci_iter().take(1100).collect();
+        for n in 0..1000 {
+            assert!(rug::Integer::fibonacci_iter_from(n)
+                .take(100)
+                .eq(terms[n..n + 100].iter().cloned()));
+        }
+        let mut iter = rug::Integer::fibonacci_iter_from(100_000);
+        assert_eq!(
+            iter.next(),
+            Some(rug::Integer::from(rug::Integer::fibonacci(100_000)))
+        );
+        assert_eq!(
+            iter.next(),
+            Some(rug::Integer::from(rug::Integer::fibonacci(100_001)))
+        );
+    }
+
+    macro_rules! test_up_to {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let terms: ::std::vec::Vec<_> = <$type>::fibonacci_iter().collect();
+                for limit in (0..=<$type>::MAX).take(1000).chain(terms.iter().copied()) {
+                    let expected: ::std::vec::Vec<_> = terms
+                        .iter()
+                        .copied()
+                        .take_while(|&term| term <= limit)
+                        .collect();
+                    assert_eq!(
+                        <$type>::fibonacci_up_to(&limit).collect::<::std::vec::Vec<_>>(),
+                        expected,
+                        "limit = {}",
+                        limit
+                    );
+                }
+                assert!(<$type>::fibonacci_up_to(&<$type>::MAX).eq(terms));
+                assert_eq!(<$type>::fibonacci_up_to(&<$type>::MIN).count(), {
+                    if <$type>::MIN == 0 {
+                        1
+                    } else {
+                        0
+                    }
+                });
+            }
+        };
+    }
+
+    test_up_to!(::core::primitive::i8, i8_up_to);
+    test_up_to!(::core::primitive::u8, u8_up_to);
+    test_up_to!(::core::primitive::i16, i16_up_to);
+    test_up_to!(::core::primitive::u16, u16_up_to);
+    test_up_to!(::core::primitive::i32, i32_up_to);
+    test_up_to!(::core::primitive::u32, u32_up_to);
+    test_up_to!(::core::primitive::i64, i64_up_to);
+    test_up_to!(::core::primitive::u64, u64_up_to);
+    test_up_to!(::core::primitive::i128, i128_up_to);
+    test_up_to!(::core::primitive::u128, u128_up_to);
+    test_up_to!(::core::primitive::isize, isize_up_to);
+    test_up_to!(::core::primitive::usize, usize_up_to);
+
+    #[test]
+    fn rug_up_to() {
+        for limit in 0..1000u32 {
+            let expected = u32::fibonacci_up_to(&limit).map(rug::Integer::from);
+            assert!(rug::Integer::fibonacci_up_to(&limit.into()).eq(expected));
+        }
+        assert_eq!(rug::Integer::fibonacci_up_to(&(-1).into()).next(), None);
+        let limit = rug::Integer::from(u128::MAX);
+        let expected = u128::fibonacci_iter().map(rug::Integer::from);
+        assert!(rug::Integer::fibonacci_up_to(&limit).eq(expected));
+
+        // The iterator stops at the first term past the limit rather than
+        // going on through the unbounded rug sequence.
+        let mut iter = rug::Integer::fibonacci_up_to(&1000.into());
+        assert_eq!(iter.by_ref().last(), Some(rug::Integer::from(987)));
+        assert_eq!(iter.next(), None);
+    }
+
+    macro_rules! test_negafibonacci {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let terms: ::std::vec::Vec<_> = <$type>::negafibonacci_iter().collect();
+                assert_eq!(terms.len(), <$type>::fibonacci_iter().count());
+                for (n, term) in terms.iter().enumerate() {
+                    assert_eq!(<$type>::nth_fibonacci(&-(n as $type)), Some(*term));
+                }
+                assert_eq!(
+                    <$type>::nth_fibonacci(&-(terms.len() as $type)),
+                    ::core::option::Option::None
+                );
+            }
+        };
+    }
+
+    test_negafibonacci!(::core::primitive::i8, i8_negafibonacci);
+    test_negafibonacci!(::core::primitive::i16, i16_negafibonacci);
+    test_negafibonacci!(::core::primitive::i32, i32_negafibonacci);
+    test_negafibonacci!(::core::primitive::i64, i64_negafibonacci);
+    test_negafibonacci!(::core::primitive::i128, i128_negafibonacci);
+    test_negafibonacci!(::core::primitive::isize, isize_negafibonacci);
+
+    #[test]
+    fn rug_negafibonacci() {
+        let terms = rug::Integer::negafibonacci_iter().zip(rug::Integer::fibonacci_iter());
+        for (n, (negative, positive)) in terms.take(500).enumerate() {
+            if n & 1 == 0 {
+                assert_eq!(negative, -positive);
+            } else {
+                assert_eq!(negative, positive);
+            }
+        }
+        let terms = rug::Integer::negafibonacci_iter().zip(i128::negafibonacci_iter());
+        for (big, small) in terms {
+            assert_eq!(big, small);
+        }
+    }
+
+    #[test]
+    fn zeckendorf_round_trips() {
+        let check = |n: u128| {
+            let indices = zeckendorf(n);
+            assert!(indices.iter().all(|&k| k >= 2), "n = {}", n);
+            assert!(
+                indices.windows(2).all(|pair| pair[0] >= pair[1] + 2),
+                "n = {}",
+                n
+            );
+            assert_eq!(from_zeckendorf(&indices), Some(n));
+        };
+        (0..20_000).for_each(check);
+        (u128::MAX - 20_000..=u128::MAX).for_each(check);
+        u128::fibonacci_iter().skip(1).for_each(|f| {
+            check(f);
+            check(f - 1);
+        });
+        assert_eq!(zeckendorf(u128::MAX).len(), 61);
+        assert_eq!(from_zeckendorf(&[186, 185]), None);
+    }
+
+    #[test]
+    fn approximations_are_correctly_rounded() {
+        for n in -1600..=1600 {
+            let exact = rug::Integer::nth_fibonacci(&n.into()).unwrap();
+            let expected = rug::Float::with_val(53, &exact).to_f64();
+            let expected = if expected.is_finite() {
+                Some(expected)
+            } else {
+                None
+            };
+            assert_eq!(f64::nth_fibonacci_approx(n), expected, "n = {}", n);
+
+            let expected = rug::Float::with_val(24, &exact).to_f32();
+            let expected = if expected.is_finite() {
+                Some(expected)
+            } else {
+                None
+            };
+            assert_eq!(f32::nth_fibonacci_approx(n), expected, "n = {}", n);
+        }
+        assert_eq!(f64::nth_fibonacci_approx(i32::MIN), None);
+        assert_eq!(f32::nth_fibonacci_approx(i32::MAX), None);
+    }
+
+    #[test]
+    fn fibonacci_codes_round_trip() {
+        let check = |n: u128| {
+            let bits = fibonacci_encode(n);
+            assert!(bits.ends_with(&[true, true]), "n = {}", n);
+            assert!(
+                bits[..bits.len() - 1]
+                    .windows(2)
+                    .all(|pair| !(pair[0] && pair[1])),
+                "n = {}",
+                n
+            );
+            assert_eq!(fibonacci_decode(&bits), Some((n, bits.len())));
+            let mut extended = bits.clone();
+            extended.extend_from_slice(&[true, false, true]);
+            assert_eq!(fibonacci_decode(&extended), Some((n, bits.len())));
+            assert_eq!(fibonacci_decode(&bits[..bits.len() - 1]), None);
+        };
+        (1..20_000).for_each(check);
+        (u128::MAX - 20_000..=u128::MAX).for_each(check);
+        u128::fibonacci_iter().skip(3).for_each(|f| {
+            check(f);
+            check(f - 1);
+        });
+
+        // F(187) is past u128::MAX.
+        let mut too_big = vec![false; 185];
+        too_big.extend_from_slice(&[true, true]);
+        assert_eq!(fibonacci_decode(&too_big), None);
+        assert_eq!(fibonacci_decode(&[]), None);
+    }
+
+    #[test]
+    fn pisano_periods_match_brute_force() {
+        for m in 1..3000u64 {
+            let (mut a, mut b, mut period) = (0, 1 % m, 0u128);
+            loop {
+                let next = (a + b) % m;
+                a = b;
+                b = next;
+                period += 1;
+                if a == 0 && b == 1 % m {
+                    break;
+                }
+            }
+            assert_eq!(pisano_period(m), period, "m = {}", m);
+        }
+    }
+
+    #[test]
+    fn pisano_periods_of_large_primes() {
+        assert_eq!(
+            pisano_period(9_223_372_036_854_776_243),
+            6_148_914_691_236_517_496
+        );
+    }
+
+    #[test]
+    fn pisano_periods_are_periods() {
+        for m in [
+            1_000_000_007u64,
+            999_999_999_989,
+            1 << 40,
+            3u64.pow(30),
+            4_294_967_291 * 3,
+            u32::MAX as u64 * 10,
+            // Primes above 2^63 that are ±2 (mod 5), whose candidate
+            // 2(p + 1) is past u64::MAX.
+            9_223_372_036_854_776_243,
+            9_223_372_036_854_776_393,
+            9_223_372_036_854_776_657,
+        ] {
+            let period = pisano_period(m);
+            assert_eq!(
+                u128::nth_fibonacci_mod(&period, &(m as u128)),
+                0,
+                "m = {}",
+                m
+            );
+            assert_eq!(
+                u128::nth_fibonacci_mod(&(period + 1), &(m as u128)),
+                1,
+                "m = {}",
+                m
+            );
+            for &(p, _) in u128::factorize(&period).unwrap().factors() {
+                let smaller = period / p;
+                assert!(
+                    u128::nth_fibonacci_mod(&smaller, &(m as u128)) != 0
+                        || u128::nth_fibonacci_mod(&(smaller + 1), &(m as u128)) != 1,
+                    "m = {}",
+                    m
+                );
+            }
+        }
+    }
+
     test_signed_bounded_nth!(::core::primitive::i8, i8_nth);
     test_unsigned_bounded_nth!(::core::primitive::u8, u8_nth);
     test_signed_bounded_nth!(::core::primitive::i16, i16_nth);
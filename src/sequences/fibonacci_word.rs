@@ -0,0 +1,129 @@
+use crate::number_theory::QuadraticIrrational;
+
+/// Returns the n<sup>th</sup> symbol, counting from zero, of the infinite
+/// Fibonacci word 0100101001001….
+///
+/// The infinite Fibonacci word is the limit of the words S(0) = 0,
+/// S(1) = 01, and S(n) = S(n − 1) S(n − 2). Its n<sup>th</sup> symbol is
+/// 2 + ⌊(n + 1)φ⌋ − ⌊(n + 2)φ⌋, which is one exactly when the Zeckendorf
+/// representation of n includes F(2) = 1, so it is found in O(log n) time
+/// without any overflow.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::fibonacci_word::nth_symbol;
+///
+/// let symbols: Vec<_> = (0..13).map(nth_symbol).collect();
+/// assert_eq!(symbols, vec![0, 1, 0, 0, 1, 0, 1, 0, 0, 1, 0, 0, 1]);
+/// assert_eq!(nth_symbol(1_000_000_000_000), 0);
+/// assert_eq!(nth_symbol(u64::MAX), 0);
+/// ```
+pub fn nth_symbol(n: u64) -> u8 {
+    let indices = crate::sequences::fibonacci::zeckendorf(n.into());
+    (indices.last() == Some(&2)) as u8
+}
+
+/// Returns an `Iterator<Item = u8>` implementation that goes through the
+/// symbols of the infinite Fibonacci word until just before overflow.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::fibonacci_word::fibonacci_word;
+///
+/// let prefix: Vec<_> = fibonacci_word().take(8).collect();
+/// assert_eq!(prefix, vec![0, 1, 0, 0, 1, 0, 1, 0]);
+///
+/// // The first F(n) symbols have F(n − 2) ones.
+/// let ones = fibonacci_word().take(6765).filter(|&symbol| symbol == 1).count();
+/// assert_eq!(ones, 2584);
+/// ```
+pub fn fibonacci_word() -> FibonacciWord {
+    FibonacciWord {
+        n: 0,
+        floor: Some(1),
+    }
+}
+
+/// The iterator returned by `fibonacci_word`. Its `nth` method takes constant
+/// time.
+#[derive(Clone, Debug)]
+pub struct FibonacciWord {
+    n: i128,
+    // ⌊(n + 1)φ⌋, or `None` once that overflows.
+    floor: Option<i128>,
+}
+
+impl Iterator for FibonacciWord {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let floor = self.floor?;
+        let next_floor = QuadraticIrrational::golden_ratio().floor_of_multiple(self.n + 2);
+        self.floor = next_floor;
+        self.n += 1;
+        Some((2 + floor - next_floor?) as u8)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.floor?;
+        self.n = self.n.checked_add(n as i128)?;
+        self.floor = QuadraticIrrational::golden_ratio().floor_of_multiple(self.n + 1);
+        self.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_concatenation() {
+        let (mut shorter, mut longer) = (vec![0u8], vec![0u8, 1]);
+        while longer.len() < 100_000 {
+            let next = [&longer[..], &shorter[..]].concat();
+            shorter = core::mem::replace(&mut longer, next);
+        }
+        let symbols: Vec<_> = fibonacci_word().take(longer.len()).collect();
+        assert_eq!(symbols, longer);
+        for (n, &symbol) in longer.iter().enumerate() {
+            assert_eq!(nth_symbol(n as u64), symbol);
+        }
+    }
+
+    #[test]
+    fn nth_matches_next() {
+        let symbols: Vec<_> = fibonacci_word().take(1000).collect();
+        for (n, &symbol) in symbols.iter().enumerate() {
+            assert_eq!(fibonacci_word().nth(n), Some(symbol));
+        }
+        let mut word = fibonacci_word();
+        assert_eq!(word.nth(500), Some(symbols[500]));
+        assert_eq!(word.next(), Some(symbols[501]));
+    }
+
+    #[test]
+    fn iteration_stops_before_overflow() {
+        let mut word = fibonacci_word();
+        assert_eq!(word.nth(1 << 62), Some(nth_symbol(1 << 62)));
+        assert!(word.next().is_some());
+        assert_eq!(word.nth(usize::MAX), None);
+        assert_eq!(word.next(), None);
+    }
+
+    #[test]
+    fn large_indices() {
+        // ⌊kφ⌋ = ⌊(k + ⌊√(5k²)⌋) / 2⌋, as √(5k²) is irrational for k > 0.
+        let floor = |k: rug::Integer| {
+            let root = (rug::Integer::from(k.square_ref()) * 5u8).sqrt();
+            (root + k) >> 1u32
+        };
+        let powers = (0..64).map(|i| 1 << i);
+        for n in (u64::MAX - 1000..=u64::MAX).chain(powers) {
+            let n_plus_one = rug::Integer::from(n) + 1u8;
+            let expected = floor(n_plus_one.clone()) + 2u8 - floor(n_plus_one + 1u8);
+            assert_eq!(nth_symbol(n), expected.to_u8().unwrap(), "n = {}", n);
+        }
+    }
+}
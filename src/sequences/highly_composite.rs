@@ -0,0 +1,242 @@
+/// Returns an `Iterator<Item = (u64, u64)>` implementation that goes through
+/// the highly composite numbers that fit in a `u64`, the positive integers
+/// with more divisors than every smaller positive integer, each paired with
+/// its number of divisors.
+///
+/// Every highly composite number is 2<sup>a</sup> 3<sup>b</sup>
+/// 5<sup>c</sup> ⋯ with a ≥ b ≥ c ≥ ⋯, as otherwise swapping exponents would
+/// give a smaller number with as many divisors, so only the 47,616 numbers of
+/// that form are searched rather than every integer.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::highly_composite::highly_composite_numbers;
+///
+/// let terms: Vec<_> = highly_composite_numbers().take(10).collect();
+/// assert_eq!(
+///     terms,
+///     vec![(1, 1), (2, 2), (4, 3), (6, 4), (12, 6), (24, 8), (36, 9), (48, 10), (60, 12), (120, 16)]
+/// );
+/// assert_eq!(highly_composite_numbers().count(), 170);
+/// assert_eq!(
+///     highly_composite_numbers().last(),
+///     Some((18_401_055_938_125_660_800, 184_320))
+/// );
+/// ```
+pub fn highly_composite_numbers() -> HighlyComposite {
+    HighlyComposite {
+        candidates: candidates().into_iter(),
+        record: 0,
+    }
+}
+
+/// Returns an `Iterator<Item = (u64, u128)>` implementation that goes through
+/// the superabundant numbers that fit in a `u64`, the positive integers n for
+/// which σ(n)/n is larger than for every smaller positive integer, each
+/// paired with σ(n).
+///
+/// Like the highly composite numbers, every superabundant number has
+/// nonincreasing exponents on consecutive primes from two, so only the
+/// numbers of that form are searched. The abundancies are compared exactly.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::highly_composite::superabundant_numbers;
+///
+/// let terms: Vec<_> = superabundant_numbers().take(10).collect();
+/// assert_eq!(
+///     terms,
+///     vec![(1, 1), (2, 3), (4, 7), (6, 12), (12, 28), (24, 60), (36, 91), (48, 124), (60, 168), (120, 360)]
+/// );
+/// assert_eq!(superabundant_numbers().count(), 117);
+/// ```
+pub fn superabundant_numbers() -> Superabundant {
+    Superabundant {
+        candidates: candidates().into_iter(),
+        record: (1, 0),
+    }
+}
+
+/// The iterator returned by `highly_composite_numbers`.
+#[derive(Clone, Debug)]
+pub struct HighlyComposite {
+    candidates: std::vec::IntoIter<Candidate>,
+    // The largest number of divisors so far.
+    record: u64,
+}
+
+/// The iterator returned by `superabundant_numbers`.
+#[derive(Clone, Debug)]
+pub struct Superabundant {
+    candidates: std::vec::IntoIter<Candidate>,
+    // The number with the largest abundancy so far and its σ.
+    record: (u64, u128),
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Candidate {
+    n: u64,
+    divisors: u64,
+    sigma: u128,
+}
+
+impl Iterator for HighlyComposite {
+    type Item = (u64, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for candidate in self.candidates.by_ref() {
+            if candidate.divisors > self.record {
+                self.record = candidate.divisors;
+                return Some((candidate.n, candidate.divisors));
+            }
+        }
+        None
+    }
+}
+
+impl core::iter::FusedIterator for HighlyComposite {}
+
+impl Iterator for Superabundant {
+    type Item = (u64, u128);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for candidate in self.candidates.by_ref() {
+            // σ(n)/n > σ(m)/m exactly when σ(n) m > σ(m) n.
+            let (m, sigma_m) = self.record;
+            if wide_mul(candidate.sigma, m) > wide_mul(sigma_m, candidate.n) {
+                self.record = (candidate.n, candidate.sigma);
+                return Some((candidate.n, candidate.sigma));
+            }
+        }
+        None
+    }
+}
+
+impl core::iter::FusedIterator for Superabundant {}
+
+/// Returns the numbers 2<sup>a</sup> 3<sup>b</sup> 5<sup>c</sup> ⋯ with
+/// a ≥ b ≥ c ≥ ⋯ that fit in a `u64` in increasing order, along with their
+/// numbers and sums of divisors.
+fn candidates() -> Vec<Candidate> {
+    // The product of the primes up to 53 does not fit.
+    const PRIMES: [u64; 15] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+
+    fn search(index: usize, max_exponent: u32, candidate: Candidate, found: &mut Vec<Candidate>) {
+        found.push(candidate);
+        let Some(&p) = PRIMES.get(index) else {
+            return;
+        };
+        let mut n = candidate.n;
+        // The sum 1 + p + ⋯ + p^e of the divisors of p^e.
+        let (mut power, mut sum) = (1u128, 1u128);
+        for exponent in 1..=max_exponent {
+            n = match n.checked_mul(p) {
+                Some(n) => n,
+                None => break,
+            };
+            power *= p as u128;
+            sum += power;
+            let next = Candidate {
+                n,
+                divisors: candidate.divisors * (exponent as u64 + 1),
+                sigma: candidate.sigma * sum,
+            };
+            search(index + 1, exponent, next, found);
+        }
+    }
+
+    let mut found = Vec::new();
+    let one = Candidate {
+        n: 1,
+        divisors: 1,
+        sigma: 1,
+    };
+    search(0, 64, one, &mut found);
+    found.sort_unstable_by_key(|candidate| candidate.n);
+    found
+}
+
+/// Returns the 256-bit product a b as its high and low halves.
+fn wide_mul(a: u128, b: u64) -> (u128, u128) {
+    let low = (a as u64 as u128) * b as u128;
+    let high = (a >> 64) * b as u128;
+    let (sum, carry) = low.overflowing_add(high << 64);
+    ((high >> 64) + carry as u128, sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the numbers and sums of divisors of 0, 1, …, limit − 1.
+    fn divisor_tables(limit: usize) -> (Vec<u64>, Vec<u128>) {
+        let mut counts = vec![0; limit];
+        let mut sums = vec![0; limit];
+        for d in 1..limit {
+            for multiple in (d..limit).step_by(d) {
+                counts[multiple] += 1;
+                sums[multiple] += d as u128;
+            }
+        }
+        (counts, sums)
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let limit = 1_000_000;
+        let (counts, sums) = divisor_tables(limit);
+
+        let mut record = 0;
+        let mut expected = Vec::new();
+        for (n, &count) in counts.iter().enumerate().skip(1) {
+            if count > record {
+                record = count;
+                expected.push((n as u64, count));
+            }
+        }
+        let terms: Vec<_> = highly_composite_numbers()
+            .take_while(|&(n, _)| n < limit as u64)
+            .collect();
+        assert_eq!(terms, expected);
+
+        let mut record = (1, 0);
+        let mut expected = Vec::new();
+        for (n, &sum) in sums.iter().enumerate().skip(1) {
+            if sum * record.0 as u128 > record.1 * n as u128 {
+                record = (n, sum);
+                expected.push((n as u64, sum));
+            }
+        }
+        let terms: Vec<_> = superabundant_numbers()
+            .take_while(|&(n, _)| n < limit as u64)
+            .collect();
+        assert_eq!(terms, expected);
+    }
+
+    #[test]
+    fn large_terms() {
+        use crate::number_theory::DivisorSigma;
+
+        for (n, sigma) in superabundant_numbers() {
+            if let Some(expected) = u64::divisor_sigma(&n) {
+                assert_eq!(sigma, expected as u128);
+            }
+            assert_eq!(u128::divisor_sigma(&(n as u128)), Some(sigma));
+        }
+        // Every superabundant number after 6 is divisible by four.
+        assert!(superabundant_numbers().skip(4).all(|(n, _)| n % 4 == 0));
+    }
+
+    #[test]
+    fn wide_products() {
+        let big = u128::MAX;
+        assert_eq!(
+            wide_mul(big, u64::MAX),
+            (u64::MAX as u128 - 1, big - u64::MAX as u128 + 1)
+        );
+        assert_eq!(wide_mul(1 << 100, 1 << 30), (1 << 2, 0));
+        assert_eq!(wide_mul(12345, 678), (0, 12345 * 678));
+    }
+}
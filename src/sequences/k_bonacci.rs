@@ -0,0 +1,358 @@
+use std::collections::VecDeque;
+
+pub trait KBonacci: Sized {
+    /// The exact type of the `Iterator<Item = Self>` returned by
+    /// `k_bonacci_iter`.
+    type Iter: Iterator<Item = Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through the
+    /// k-bonacci numbers until just before overflow. These start with k − 1
+    /// zeros and a one, and each later term is the sum of the k before it, so
+    /// k = 2 gives the Fibonacci numbers, k = 3 the tribonacci numbers, and
+    /// so on.
+    ///
+    /// # Panics
+    ///
+    /// Panics if k is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::KBonacci;
+    ///
+    /// let tribonacci: Vec<_> = u32::k_bonacci_iter(3).take(10).collect();
+    /// assert_eq!(tribonacci, vec![0, 0, 1, 1, 2, 4, 7, 13, 24, 44]);
+    ///
+    /// let tetranacci: Vec<_> = u8::k_bonacci_iter(4).collect();
+    /// assert_eq!(tetranacci, vec![0, 0, 0, 1, 1, 2, 4, 8, 15, 29, 56, 108, 208]);
+    /// ```
+    fn k_bonacci_iter(k: usize) -> Self::Iter;
+
+    /// Returns an `Option` containing either the n<sup>th</sup> k-bonacci
+    /// number or `None` if that would cause overflow.
+    ///
+    /// # Panics
+    ///
+    /// Panics if k is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::KBonacci;
+    ///
+    /// assert_eq!(u64::nth_k_bonacci(3, 37), Some(1_132_436_852));
+    /// assert_eq!(i8::nth_k_bonacci(5, 1_000_000), None);
+    /// assert_eq!(u8::nth_k_bonacci(1_000_000, 999_998), Some(0));
+    /// assert_eq!(u8::nth_k_bonacci(1, usize::MAX), Some(1));
+    /// ```
+    fn nth_k_bonacci(k: usize, n: usize) -> Option<Self>;
+}
+
+/// The iterator returned by `KBonacci::k_bonacci_iter`. Its `nth` method
+/// skips over the leading zeros, and the ones when k is one, in constant time.
+///
+/// For `rug::Integer`, it also implements `StreamingSequence`, whose
+/// `next_ref` method lends out each term instead of cloning it.
+#[derive(Clone, Debug)]
+pub struct KBonacciIter<T> {
+    k: usize,
+    index: usize,
+    // Those of the last k terms from index k − 1 onward, as the ones before
+    // are zero, and their sum, or `None` if that sum would cause overflow.
+    window: VecDeque<T>,
+    sum: Option<T>,
+}
+
+impl<T> KBonacciIter<T> {
+    fn new(k: usize, zero: T) -> Self {
+        assert!(k != 0, "order must be positive");
+        Self {
+            k,
+            index: 0,
+            window: VecDeque::new(),
+            sum: Some(zero),
+        }
+    }
+
+    /// Skips up to n of the terms that are known without being computed and
+    /// returns how many of the n are left to skip.
+    fn skip_known(&mut self, n: usize) -> usize {
+        let zeros = (self.k - 1).saturating_sub(self.index).min(n);
+        self.index += zeros;
+        if self.k == 1 && self.index != 0 {
+            // Every term after the first one is also one.
+            self.index = self.index.saturating_add(n - zeros);
+            0
+        } else {
+            n - zeros
+        }
+    }
+}
+
+macro_rules! k_bonacci_trait_for_primitive {
+    ($type:ty) => {
+        impl $crate::sequences::k_bonacci::KBonacci for $type {
+            type Iter = $crate::sequences::k_bonacci::KBonacciIter<$type>;
+
+            fn k_bonacci_iter(k: usize) -> Self::Iter {
+                $crate::sequences::k_bonacci::KBonacciIter::new(k, 0)
+            }
+
+            fn nth_k_bonacci(k: usize, n: usize) -> ::core::option::Option<Self> {
+                ::core::iter::Iterator::nth(&mut Self::k_bonacci_iter(k), n)
+            }
+        }
+
+        impl ::core::iter::Iterator for $crate::sequences::k_bonacci::KBonacciIter<$type> {
+            type Item = $type;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                let term = match self.index.cmp(&(self.k - 1)) {
+                    ::core::cmp::Ordering::Less => {
+                        self.index += 1;
+                        return ::core::option::Option::Some(0);
+                    }
+                    ::core::cmp::Ordering::Equal => 1,
+                    ::core::cmp::Ordering::Greater => self.sum?,
+                };
+                self.window.push_back(term);
+                let dropped = if self.window.len() > self.k {
+                    self.window.pop_front().unwrap_or(0)
+                } else {
+                    0
+                };
+                // The term dropped from the sum is never more than the one
+                // added, so only the sum itself can overflow.
+                self.sum = self.sum.and_then(|sum| sum.checked_add(term - dropped));
+                self.index = self.index.saturating_add(1);
+                ::core::option::Option::Some(term)
+            }
+
+            fn nth(&mut self, n: usize) -> ::core::option::Option<Self::Item> {
+                let mut n = self.skip_known(n);
+                while n != 0 {
+                    self.next()?;
+                    n = self.skip_known(n - 1);
+                }
+                self.next()
+            }
+        }
+    };
+}
+
+k_bonacci_trait_for_primitive!(::core::primitive::i8);
+k_bonacci_trait_for_primitive!(::core::primitive::u8);
+k_bonacci_trait_for_primitive!(::core::primitive::i16);
+k_bonacci_trait_for_primitive!(::core::primitive::u16);
+k_bonacci_trait_for_primitive!(::core::primitive::i32);
+k_bonacci_trait_for_primitive!(::core::primitive::u32);
+k_bonacci_trait_for_primitive!(::core::primitive::i64);
+k_bonacci_trait_for_primitive!(::core::primitive::u64);
+k_bonacci_trait_for_primitive!(::core::primitive::i128);
+k_bonacci_trait_for_primitive!(::core::primitive::u128);
+k_bonacci_trait_for_primitive!(::core::primitive::isize);
+k_bonacci_trait_for_primitive!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl KBonacci for rug::Integer {
+    type Iter = KBonacciIter<rug::Integer>;
+
+    fn k_bonacci_iter(k: usize) -> Self::Iter {
+        KBonacciIter::new(k, rug::Integer::new())
+    }
+
+    fn nth_k_bonacci(k: usize, n: usize) -> Option<Self> {
+        Self::k_bonacci_iter(k).nth(n)
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl crate::sequences::StreamingSequence for KBonacciIter<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next_ref(&mut self) -> Option<&Self::Item> {
+        match self.index.cmp(&(self.k - 1)) {
+            core::cmp::Ordering::Less => {
+                // The sum stays zero until the first one is reached, so it
+                // can stand in for the leading zeros.
+                self.index += 1;
+                return self.sum.as_ref();
+            }
+            core::cmp::Ordering::Equal => {
+                self.window.push_back(rug::Integer::from(1));
+                *self.sum.as_mut()? += 1u8;
+            }
+            core::cmp::Ordering::Greater => {
+                let sum = self.sum.as_mut()?;
+                let term = if self.window.len() == self.k {
+                    // Reuse the dropped term d for the new one, which is the
+                    // old sum S, while the sum becomes 2S − d.
+                    let mut term = self.window.pop_front()?;
+                    rug::ops::SubFrom::sub_from(&mut term, &*sum);
+                    core::mem::swap(sum, &mut term);
+                    *sum += &term;
+                    term
+                } else {
+                    let term = sum.clone();
+                    *sum += &term;
+                    term
+                };
+                self.window.push_back(term);
+            }
+        }
+        self.index = self.index.saturating_add(1);
+        self.window.back()
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for KBonacciIter<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        crate::sequences::StreamingSequence::next_ref(self).cloned()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let mut n = self.skip_known(n);
+        while n != 0 {
+            crate::sequences::StreamingSequence::next_ref(self)?;
+            n = self.skip_known(n - 1);
+        }
+        self.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the first terms of the k-bonacci numbers straight from their
+    /// definition.
+    fn naive(k: usize, count: usize) -> Vec<rug::Integer> {
+        let mut terms = vec![rug::Integer::new(); k - 1];
+        terms.push(rug::Integer::from(1));
+        while terms.len() < count {
+            let sum = terms[terms.len() - k..].iter().sum();
+            terms.push(sum);
+        }
+        terms.truncate(count);
+        terms
+    }
+
+    macro_rules! test_k_bonacci {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let max = rug::Integer::from(<$type>::MAX);
+                for k in 1..12 {
+                    let expected: ::std::vec::Vec<_> = naive(k, k + 200)
+                        .into_iter()
+                        .take_while(|term| *term <= max)
+                        .collect();
+                    let terms: ::std::vec::Vec<_> = <$type>::k_bonacci_iter(k)
+                        .take(k + 200)
+                        .map(rug::Integer::from)
+                        .collect();
+                    if k == 1 {
+                        // The ones never overflow, so there is no end to check.
+                        ::core::assert_eq!(terms, expected);
+                        continue;
+                    }
+                    ::core::assert_eq!(terms, expected, "k = {}", k);
+                    for n in 0..expected.len() + 2 {
+                        ::core::assert_eq!(
+                            <$type>::nth_k_bonacci(k, n).map(rug::Integer::from),
+                            expected.get(n).cloned(),
+                            "k = {}, n = {}",
+                            k,
+                            n
+                        );
+                    }
+                }
+            }
+        };
+    }
+
+    test_k_bonacci!(::core::primitive::i8, i8_k_bonacci);
+    test_k_bonacci!(::core::primitive::u8, u8_k_bonacci);
+    test_k_bonacci!(::core::primitive::i16, i16_k_bonacci);
+    test_k_bonacci!(::core::primitive::u16, u16_k_bonacci);
+    test_k_bonacci!(::core::primitive::i32, i32_k_bonacci);
+    test_k_bonacci!(::core::primitive::u32, u32_k_bonacci);
+    test_k_bonacci!(::core::primitive::i64, i64_k_bonacci);
+    test_k_bonacci!(::core::primitive::u64, u64_k_bonacci);
+    test_k_bonacci!(::core::primitive::i128, i128_k_bonacci);
+    test_k_bonacci!(::core::primitive::u128, u128_k_bonacci);
+    test_k_bonacci!(::core::primitive::isize, isize_k_bonacci);
+    test_k_bonacci!(::core::primitive::usize, usize_k_bonacci);
+
+    #[test]
+    fn fibonacci_is_2_bonacci() {
+        use crate::sequences::Fibonacci;
+
+        assert!(u128::k_bonacci_iter(2).eq(u128::fibonacci_iter()));
+        assert!(rug::Integer::k_bonacci_iter(2)
+            .take(1000)
+            .eq(rug::Integer::fibonacci_iter().take(1000)));
+    }
+
+    #[test]
+    fn rug_k_bonacci() {
+        for k in 1..12 {
+            let expected = naive(k, 500);
+            let terms: Vec<_> = rug::Integer::k_bonacci_iter(k).take(500).collect();
+            assert_eq!(terms, expected, "k = {}", k);
+            for step in [0, 1, 5, 64] {
+                let mut iter = rug::Integer::k_bonacci_iter(k);
+                for n in (step..500).step_by(step + 1) {
+                    assert_eq!(iter.nth(step).as_ref(), Some(&expected[n]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rug_streaming_matches_iterator() {
+        use crate::sequences::StreamingSequence;
+
+        for k in 1..12 {
+            let expected = naive(k, k + 200);
+            let mut streaming = rug::Integer::k_bonacci_iter(k);
+            for term in expected.iter() {
+                assert_eq!(streaming.next_ref(), Some(term), "k = {}", k);
+            }
+
+            // Mixing the two kinds of advance keeps to the same sequence.
+            let mut mixed = rug::Integer::k_bonacci_iter(k);
+            assert_eq!(mixed.nth(k + 10).as_ref(), Some(&expected[k + 10]));
+            assert_eq!(mixed.next_ref(), Some(&expected[k + 11]));
+            assert_eq!(mixed.next().as_ref(), Some(&expected[k + 12]));
+            assert_eq!(mixed.next_ref(), Some(&expected[k + 13]));
+        }
+    }
+
+    #[test]
+    fn long_orders_and_indices() {
+        let k = 1 << 40;
+        let mut iter = u64::k_bonacci_iter(k);
+        assert_eq!(iter.nth(k - 2), Some(0));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.nth(62), Some(1 << 63));
+        assert_eq!(iter.next(), None);
+
+        let mut ones = u8::k_bonacci_iter(1);
+        assert_eq!(ones.nth(usize::MAX - 1), Some(1));
+        assert_eq!(rug::Integer::nth_k_bonacci(1, usize::MAX), Some(1.into()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn order_zero_panics() {
+        u32::k_bonacci_iter(0);
+    }
+}
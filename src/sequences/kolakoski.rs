@@ -0,0 +1,215 @@
+/// Returns an `Iterator<Item = u64>` implementation that goes through the
+/// Kolakoski sequence 1, 2, 2, 1, 1, 2, 1, 2, 2, 1, …, the sequence of ones
+/// and twos that is its own sequence of run lengths.
+///
+/// This is `generalized_kolakoski(1, 2)`.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::kolakoski::kolakoski;
+///
+/// let terms: Vec<_> = kolakoski().take(20).collect();
+/// assert_eq!(terms, vec![1, 2, 2, 1, 1, 2, 1, 2, 2, 1, 2, 2, 1, 1, 2, 1, 1, 2, 2, 1]);
+/// ```
+pub fn kolakoski() -> Kolakoski {
+    generalized_kolakoski(1, 2)
+}
+
+/// Returns an `Iterator<Item = u64>` implementation that goes through the
+/// Kolakoski sequence over the alphabet {a, b}, which starts with a, has runs
+/// alternating between a and b, and is its own sequence of run lengths.
+///
+/// The iterator never ends, and after n terms it holds O(log n) state rather
+/// than the terms themselves: the run lengths come from a second copy of the
+/// sequence lagging behind the first, whose run lengths come from a third
+/// copy, and so on, with only the first couple of runs of each copy known
+/// outright.
+///
+/// # Panics
+///
+/// Panics if a or b is zero or if a equals b.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::kolakoski::generalized_kolakoski;
+///
+/// let terms: Vec<_> = generalized_kolakoski(2, 1).take(12).collect();
+/// assert_eq!(terms, vec![2, 2, 1, 1, 2, 1, 2, 2, 1, 2, 2, 1]);
+///
+/// let terms: Vec<_> = generalized_kolakoski(1, 3).take(12).collect();
+/// assert_eq!(terms, vec![1, 3, 3, 3, 1, 1, 1, 3, 3, 3, 1, 3]);
+///
+/// let terms: Vec<_> = generalized_kolakoski(2, 3).take(12).collect();
+/// assert_eq!(terms, vec![2, 2, 3, 3, 2, 2, 2, 3, 3, 3, 2, 2]);
+/// ```
+pub fn generalized_kolakoski(a: u64, b: u64) -> Kolakoski {
+    assert!(a != 0 && b != 0, "symbols must be nonzero");
+    assert!(a != b, "symbols must be distinct");
+    Kolakoski {
+        a,
+        b,
+        levels: Vec::new(),
+    }
+}
+
+/// The iterator returned by `kolakoski` and `generalized_kolakoski`.
+#[derive(Clone, Debug)]
+pub struct Kolakoski {
+    a: u64,
+    b: u64,
+    // The copies of the sequence, each supplying the run lengths of the one
+    // before it.
+    levels: Vec<Level>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Level {
+    // Whether the current run is of b rather than a.
+    in_b_run: bool,
+    // The number of terms left in the current run.
+    remaining: u64,
+    // The number of runs started, up to three.
+    runs: u8,
+}
+
+impl Kolakoski {
+    /// Returns the next term of the copy of the sequence at the given level.
+    fn next_at(&mut self, level: usize) -> u64 {
+        if level == self.levels.len() {
+            self.levels.push(Level {
+                in_b_run: true,
+                remaining: 0,
+                runs: 0,
+            });
+        }
+        let mut state = self.levels[level];
+        if state.remaining == 0 {
+            // Run i has length equal to term i. Term 0 is a, and term 1 is
+            // either the second a of the first run or, when a is one, the
+            // first b of the second. Every later term lies in a run that has
+            // already started, so the next level supplies it, skipping the
+            // first two terms it would repeat.
+            state.remaining = match state.runs {
+                0 => self.a,
+                1 if self.a == 1 => self.b,
+                1 => self.a,
+                _ => {
+                    if state.runs == 2 {
+                        self.next_at(level + 1);
+                        self.next_at(level + 1);
+                    }
+                    self.next_at(level + 1)
+                }
+            };
+            state.runs = (state.runs + 1).min(3);
+            state.in_b_run = !state.in_b_run;
+        }
+        state.remaining -= 1;
+        self.levels[level] = state;
+        if state.in_b_run {
+            self.b
+        } else {
+            self.a
+        }
+    }
+}
+
+impl Iterator for Kolakoski {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_at(0))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl core::iter::FusedIterator for Kolakoski {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the first n terms by reading the run lengths back out of the
+    /// terms already written.
+    fn naive(a: u64, b: u64, n: usize) -> Vec<u64> {
+        let mut terms = Vec::new();
+        let mut run = 0;
+        while terms.len() < n {
+            let symbol = if run % 2 == 0 { a } else { b };
+            // The length of the first run, or of the second when a is one,
+            // is the symbol about to be written.
+            if terms.len() == run {
+                terms.push(symbol);
+            }
+            let length = terms[run];
+            let written = terms.len() - terms.iter().rev().take_while(|&&t| t == symbol).count();
+            while terms.len() - written < length as usize {
+                terms.push(symbol);
+            }
+            run += 1;
+        }
+        terms.truncate(n);
+        terms
+    }
+
+    #[test]
+    fn matches_naive() {
+        for (a, b) in [
+            (1, 2),
+            (2, 1),
+            (1, 3),
+            (3, 1),
+            (2, 3),
+            (3, 2),
+            (1, 5),
+            (4, 7),
+        ] {
+            let terms: Vec<_> = generalized_kolakoski(a, b).take(100_000).collect();
+            assert_eq!(terms, naive(a, b, 100_000), "a = {}, b = {}", a, b);
+        }
+    }
+
+    #[test]
+    fn is_its_own_run_length_sequence() {
+        for (a, b) in [(1, 2), (2, 1), (1, 3), (3, 2)] {
+            let terms: Vec<_> = generalized_kolakoski(a, b).take(100_000).collect();
+            let mut lengths = Vec::new();
+            for run in terms.chunk_by(|x, y| x == y) {
+                lengths.push(run.len() as u64);
+            }
+            // The last run may be cut short.
+            lengths.pop();
+            assert_eq!(lengths[..], terms[..lengths.len()]);
+        }
+    }
+
+    #[test]
+    fn memory_is_logarithmic() {
+        let mut sequence = kolakoski();
+        let ones = sequence
+            .by_ref()
+            .take(10_000_000)
+            .filter(|&term| term == 1)
+            .count();
+        // The density of ones is believed to be one half.
+        assert!((ones as i64 - 5_000_000).abs() < 1000);
+        assert!(sequence.levels.len() < 50);
+    }
+
+    #[test]
+    #[should_panic(expected = "symbols must be distinct")]
+    fn equal_symbols() {
+        generalized_kolakoski(2, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "symbols must be nonzero")]
+    fn zero_symbol() {
+        generalized_kolakoski(0, 1);
+    }
+}
@@ -0,0 +1,297 @@
+/// Returns an `Iterator<Item = u64>` implementation that goes through the
+/// lucky numbers 1, 3, 7, 9, 13, 15, 21, … in increasing order.
+///
+/// The lucky numbers are what survives of the positive integers when every
+/// second one is removed, then every third of those left, as 3 is now the
+/// second, then every seventh, as 7 is now the third, and so on. Removing a
+/// term never changes which terms come before it, so the iterator sieves a
+/// prefix of the integers and sieves a prefix twice as long whenever it runs
+/// out.
+///
+/// It only sieves the numbers below 2<sup>32</sup>, which already takes
+/// several gigabytes, and returns `None` after the last lucky number below
+/// that.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::lucky::lucky_numbers;
+///
+/// let terms: Vec<_> = lucky_numbers().take(15).collect();
+/// assert_eq!(terms, vec![1, 3, 7, 9, 13, 15, 21, 25, 31, 33, 37, 43, 49, 51, 63]);
+/// assert_eq!(lucky_numbers().nth(9999), Some(115_591));
+/// ```
+pub fn lucky_numbers() -> LuckyNumbers {
+    LuckyNumbers {
+        terms: Vec::new(),
+        limit: 0,
+        index: 0,
+    }
+}
+
+/// Returns whether n is a lucky number.
+///
+/// The odd number n is the last of the odd numbers up to it, so its index
+/// among those still left is just how many are left. Each round of the sieve
+/// removes it if that index is a multiple of the step, and once the step
+/// passes the index it is never removed, so only the rounds up to there are
+/// run. Their steps are at most the index, which keeps the sieve to the odd
+/// numbers up to about n / 2.
+///
+/// # Panics
+///
+/// Panics if n is 2<sup>32</sup> or more.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::lucky::is_lucky;
+///
+/// assert!(is_lucky(1));
+/// assert!(is_lucky(49));
+/// assert!(!is_lucky(19));
+/// assert!(!is_lucky(0));
+/// ```
+pub fn is_lucky(n: u64) -> bool {
+    assert!(n < LIMIT, "n must be below 2^32");
+    if n.is_multiple_of(2) {
+        return false;
+    }
+    // The index of n among the odd numbers left, counting from one.
+    let mut index = (n / 2 + 1) as usize;
+    // A step of at most index is at most index / 2 in this sieve's positions,
+    // so the sieve covers every step that could still remove n.
+    let len = index / 2 + 1;
+    let mut tree = Fenwick::full(len);
+    let mut remaining = len;
+
+    let mut round = 1;
+    // If the sieve has at most round numbers left, the next step lies past
+    // it, so it is past index too.
+    while round < index.min(remaining) {
+        let step = 2 * tree.select(round + 1) + 1;
+        if step > index {
+            break;
+        }
+        if index.is_multiple_of(step) {
+            return false;
+        }
+        index -= index / step;
+        let mut rank = remaining - remaining % step;
+        while rank != 0 {
+            let position = tree.select(rank);
+            tree.remove(position);
+            rank -= step;
+        }
+        remaining -= remaining / step;
+        round += 1;
+    }
+    true
+}
+
+/// The lucky numbers are only sieved below this, which keeps the counts in
+/// the Fenwick tree within a `u32`.
+const LIMIT: u64 = 1 << 32;
+
+/// The iterator returned by `lucky_numbers`.
+#[derive(Clone, Debug)]
+pub struct LuckyNumbers {
+    // The lucky numbers up to the limit.
+    terms: Vec<u64>,
+    limit: u64,
+    index: usize,
+}
+
+impl Iterator for LuckyNumbers {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index == self.terms.len() {
+            if self.limit == LIMIT - 1 {
+                return None;
+            }
+            self.limit = (self.limit * 2).clamp(64, LIMIT - 1);
+            self.terms = lucky_up_to(self.limit);
+        }
+        self.index += 1;
+        Some(self.terms[self.index - 1])
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let index = self.index.checked_add(n)?;
+        while index >= self.terms.len() {
+            if self.limit == LIMIT - 1 {
+                self.index = self.terms.len();
+                return None;
+            }
+            // The nth lucky number is about n ln n, like the nth prime.
+            let estimate = (index as f64 + 2.0) * (index as f64 + 2.0).ln() * 1.25;
+            self.limit = (self.limit * 2)
+                .max(estimate as u64)
+                .clamp(64, LIMIT - 1);
+            self.terms = lucky_up_to(self.limit);
+        }
+        self.index = index;
+        self.next()
+    }
+}
+
+impl core::iter::FusedIterator for LuckyNumbers {}
+
+/// Returns the lucky numbers no larger than the limit, which must be below
+/// `LIMIT`, in increasing order.
+fn lucky_up_to(limit: u64) -> Vec<u64> {
+    // The odd numbers up to the limit, of which a Fenwick tree counts those
+    // still alive so that the element of any rank is found in logarithmic
+    // time.
+    let len = (limit as usize).div_ceil(2);
+    let mut tree = Fenwick::full(len);
+    let mut alive = vec![true; len];
+    let mut remaining = len;
+
+    let mut round = 1;
+    while round < remaining {
+        let step = 2 * tree.select(round + 1) + 1;
+        if step > remaining {
+            break;
+        }
+        // Removing from the back keeps the ranks of the earlier ones.
+        let mut rank = remaining - remaining % step;
+        while rank != 0 {
+            let position = tree.select(rank);
+            tree.remove(position);
+            alive[position] = false;
+            rank -= step;
+        }
+        remaining -= remaining / step;
+        round += 1;
+    }
+
+    alive
+        .iter()
+        .enumerate()
+        .filter(|&(_, &alive)| alive)
+        .map(|(position, _)| 2 * position as u64 + 1)
+        .collect()
+}
+
+/// A Fenwick tree over fewer than 2<sup>32</sup> positions that are each
+/// alive or not.
+struct Fenwick {
+    counts: Vec<u32>,
+}
+
+impl Fenwick {
+    /// Returns a tree with the given number of positions, all alive.
+    fn full(len: usize) -> Self {
+        let mut counts = vec![1; len + 1];
+        counts[0] = 0;
+        for i in 1..=len {
+            let parent = i + (i & i.wrapping_neg());
+            if parent <= len {
+                counts[parent] += counts[i];
+            }
+        }
+        Self { counts }
+    }
+
+    fn remove(&mut self, position: usize) {
+        let mut i = position + 1;
+        while i < self.counts.len() {
+            self.counts[i] -= 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Returns the position of the alive position with the given rank,
+    /// counting from one.
+    fn select(&self, mut rank: usize) -> usize {
+        let mut i = 0;
+        let len = self.counts.len() - 1;
+        let mut bit = if len == 0 {
+            0
+        } else {
+            1 << (usize::BITS - 1 - len.leading_zeros())
+        };
+        while bit != 0 {
+            if i + bit < self.counts.len() && (self.counts[i + bit] as usize) < rank {
+                i += bit;
+                rank -= self.counts[i] as usize;
+            }
+            bit >>= 1;
+        }
+        i
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_lucky_up_to(limit: u64) -> Vec<u64> {
+        let mut terms: Vec<_> = (1..=limit).step_by(2).collect();
+        let mut round = 1;
+        while round < terms.len() {
+            let step = terms[round] as usize;
+            let mut index = 0;
+            terms.retain(|_| {
+                index += 1;
+                index % step != 0
+            });
+            round += 1;
+        }
+        terms
+    }
+
+    #[test]
+    fn matches_naive() {
+        for limit in (0..200).chain([1000, 4095, 4096, 4097, 20_000]) {
+            assert_eq!(
+                lucky_up_to(limit),
+                naive_lucky_up_to(limit),
+                "limit = {}",
+                limit
+            );
+        }
+    }
+
+    #[test]
+    fn iterator_and_membership() {
+        let expected = naive_lucky_up_to(50_000);
+        let terms: Vec<_> = lucky_numbers().take(expected.len()).collect();
+        assert_eq!(terms, expected);
+        for (i, &term) in expected.iter().enumerate().step_by(97) {
+            assert_eq!(lucky_numbers().nth(i), Some(term));
+        }
+        let mut iter = lucky_numbers();
+        assert_eq!(iter.nth(1000), Some(expected[1000]));
+        assert_eq!(iter.next(), Some(expected[1001]));
+        assert_eq!(iter.nth(10), Some(expected[1012]));
+
+        for n in 0..3000 {
+            assert_eq!(is_lucky(n), expected.contains(&n), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn large_membership() {
+        let terms = lucky_up_to(1_000_000);
+        for n in 999_801..=1_000_000 {
+            assert_eq!(is_lucky(n), terms.binary_search(&n).is_ok(), "n = {}", n);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "n must be below 2^32")]
+    fn membership_too_large() {
+        is_lucky(u64::MAX);
+    }
+
+    #[test]
+    fn lucky_counts() {
+        // There are 153 lucky numbers below 1000 and 1118 below 10⁴.
+        assert_eq!(lucky_up_to(1000).len(), 153);
+        assert_eq!(lucky_up_to(10_000).len(), 1118);
+        assert_eq!(lucky_up_to(1_000_000).len(), 71_918);
+    }
+}
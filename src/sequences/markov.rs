@@ -0,0 +1,245 @@
+pub trait Markov: Sized {
+    /// The exact type of the `Iterator<Item = (Self, Self, Self)>` returned
+    /// by `markov_triples`.
+    type Triples: Iterator<Item = (Self, Self, Self)>;
+
+    /// Returns an `Iterator<Item = (Self, Self, Self)>` implementation that
+    /// goes through every Markov triple, a solution of x² + y² + z² = 3xyz,
+    /// whose largest element is at most `bound`.
+    ///
+    /// Each triple is yielded in increasing order. Triples are found by
+    /// walking the tree of Vieta involutions (x, y, z) → (x, z, 3xz − y)
+    /// and (y, z, 3yz − x) depth first from (1, 1, 1).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Markov;
+    ///
+    /// let mut triples: Vec<_> = u32::markov_triples(&30).collect();
+    /// triples.sort();
+    /// assert_eq!(
+    ///     triples,
+    ///     vec![(1, 1, 1), (1, 1, 2), (1, 2, 5), (1, 5, 13), (2, 5, 29)]
+    /// );
+    /// ```
+    fn markov_triples(bound: &Self) -> Self::Triples;
+
+    /// Returns whether n is a Markov number, that is, whether n is an
+    /// element of some Markov triple.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Markov;
+    ///
+    /// assert!(u32::is_markov_number(&194));
+    /// assert!(!u32::is_markov_number(&195));
+    /// ```
+    fn is_markov_number(n: &Self) -> bool;
+}
+
+/// The iterator returned by `Markov::markov_triples`.
+pub struct MarkovTriples<T> {
+    stack: Vec<(T, T, T)>,
+    bound: T,
+}
+
+macro_rules! markov_trait_for_unsigned {
+    ($type:ty) => {
+        impl $crate::sequences::markov::Markov for $type {
+            type Triples = $crate::sequences::markov::MarkovTriples<$type>;
+
+            fn markov_triples(bound: &Self) -> Self::Triples {
+                $crate::sequences::markov::MarkovTriples {
+                    stack: if *bound >= 1 {
+                        ::std::vec![(1, 1, 1)]
+                    } else {
+                        ::std::vec::Vec::new()
+                    },
+                    bound: *bound,
+                }
+            }
+
+            fn is_markov_number(n: &Self) -> bool {
+                // Every Markov number is the largest element of the triple
+                // in which it first appears.
+                Self::markov_triples(n).any(|(_, _, z)| z == *n)
+            }
+        }
+
+        impl ::core::iter::Iterator for $crate::sequences::markov::MarkovTriples<$type> {
+            type Item = ($type, $type, $type);
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                let (x, y, z) = self.stack.pop()?;
+
+                let bound = self.bound;
+                let child = |a: $type, b: $type| {
+                    // 3az − b is computed as 2az + (az − b) so that the
+                    // result is found whenever it fits, as az ≥ b.
+                    let product = a.checked_mul(z)?;
+                    product
+                        .checked_mul(2)?
+                        .checked_add(product - b)
+                        .filter(|&c| c <= bound)
+                };
+                if let ::core::option::Option::Some(c) = child(y, x) {
+                    self.stack.push((y, z, c));
+                }
+                if x != y {
+                    if let ::core::option::Option::Some(c) = child(x, y) {
+                        self.stack.push((x, z, c));
+                    }
+                }
+
+                ::core::option::Option::Some((x, y, z))
+            }
+        }
+    };
+}
+
+markov_trait_for_unsigned!(::core::primitive::u8);
+markov_trait_for_unsigned!(::core::primitive::u16);
+markov_trait_for_unsigned!(::core::primitive::u32);
+markov_trait_for_unsigned!(::core::primitive::u64);
+markov_trait_for_unsigned!(::core::primitive::u128);
+markov_trait_for_unsigned!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Markov for rug::Integer {
+    type Triples = MarkovTriples<rug::Integer>;
+
+    fn markov_triples(bound: &Self) -> Self::Triples {
+        let one = rug::Integer::from(1);
+        MarkovTriples {
+            stack: if *bound >= one {
+                vec![(one.clone(), one.clone(), one)]
+            } else {
+                Vec::new()
+            },
+            bound: bound.clone(),
+        }
+    }
+
+    fn is_markov_number(n: &Self) -> bool {
+        Self::markov_triples(n).any(|(_, _, z)| z == *n)
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for MarkovTriples<rug::Integer> {
+    type Item = (rug::Integer, rug::Integer, rug::Integer);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (x, y, z) = self.stack.pop()?;
+
+        let bound = &self.bound;
+        let child = |a: &rug::Integer, b: &rug::Integer| {
+            let c = rug::Integer::from(a * &z) * 3u8 - b;
+            if c <= *bound {
+                Some(c)
+            } else {
+                None
+            }
+        };
+        let first = child(&y, &x).map(|c| (y.clone(), z.clone(), c));
+        let second = if x != y {
+            child(&x, &y).map(|c| (x.clone(), z.clone(), c))
+        } else {
+            None
+        };
+        self.stack.extend(first);
+        self.stack.extend(second);
+
+        Some((x, y, z))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MARKOV_NUMBERS: [u64; 35] = [
+        1, 2, 5, 13, 29, 34, 89, 169, 194, 233, 433, 610, 985, 1325, 1597, 2897, 4181, 5741, 6466,
+        7561, 9077, 10946, 14701, 28657, 33461, 37666, 43261, 51641, 62210, 75025, 96557, 135137,
+        195025, 196418, 294685,
+    ];
+
+    macro_rules! test_markov_numbers {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let bound = <$type as ::core::convert::TryFrom<u64>>::try_from(300_000)
+                    .unwrap_or(<$type>::MAX);
+                let mut numbers: ::std::vec::Vec<u64> = <$type>::markov_triples(&bound)
+                    .map(|(x, y, z)| {
+                        let (x, y, z) = (x as u128, y as u128, z as u128);
+                        ::core::assert!(x <= y && y <= z);
+                        ::core::assert_eq!(x * x + y * y + z * z, 3 * x * y * z);
+                        z as u64
+                    })
+                    .collect();
+                numbers.sort_unstable();
+                numbers.dedup();
+
+                let expected: ::std::vec::Vec<u64> = MARKOV_NUMBERS
+                    .iter()
+                    .copied()
+                    .filter(|&n| n as u128 <= bound as u128)
+                    .collect();
+                ::core::assert_eq!(numbers, expected);
+            }
+        };
+    }
+
+    test_markov_numbers!(::core::primitive::u8, u8_markov);
+    test_markov_numbers!(::core::primitive::u16, u16_markov);
+    test_markov_numbers!(::core::primitive::u32, u32_markov);
+    test_markov_numbers!(::core::primitive::u64, u64_markov);
+    test_markov_numbers!(::core::primitive::u128, u128_markov);
+    test_markov_numbers!(::core::primitive::usize, usize_markov);
+
+    #[test]
+    fn markov_membership() {
+        for n in 0..2000u32 {
+            assert_eq!(
+                u32::is_markov_number(&n),
+                MARKOV_NUMBERS.contains(&(n as u64))
+            );
+        }
+        assert!(u64::is_markov_number(&433494437));
+        assert!(u128::markov_triples(&u128::MAX).all(|(_, _, z)| z > 0));
+    }
+
+    #[test]
+    fn rug_deep_levels() {
+        use crate::sequences::Fibonacci;
+
+        let bound = rug::Integer::from(rug::Integer::u_pow_u(10, 60));
+        let mut small_triples = Vec::new();
+        let mut count = 0;
+        for (x, y, z) in rug::Integer::markov_triples(&bound) {
+            let lhs = x.clone().square() + y.clone().square() + z.clone().square();
+            assert_eq!(lhs, rug::Integer::from(&x * &y) * &z * 3u8);
+            assert!(z <= bound);
+            if let (Some(x), Some(y), Some(z)) = (x.to_u64(), y.to_u64(), z.to_u64()) {
+                small_triples.push((x, y, z));
+            }
+            count += 1;
+        }
+
+        let mut expected: Vec<_> = u64::markov_triples(&u64::MAX).collect();
+        expected.sort_unstable();
+        small_triples.sort_unstable();
+        assert_eq!(small_triples, expected);
+        assert!(count > expected.len());
+
+        // (1, F(2n - 1), F(2n + 1)) is a Markov triple for every n.
+        let fibonacci_201 = rug::Integer::fibonacci_iter().nth(201).unwrap();
+        assert!(rug::Integer::is_markov_number(&fibonacci_201));
+        assert!(!rug::Integer::is_markov_number(&(fibonacci_201 + 1u8)));
+    }
+}
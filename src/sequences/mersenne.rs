@@ -0,0 +1,254 @@
+#[cfg(any(feature = "rug", doc, test))]
+use rug::Assign;
+
+pub trait Mersenne: Sized {
+    /// The exact type of the `Iterator<Item = Self>` returned by
+    /// `mersenne_numbers`.
+    type Iter: Iterator<Item = Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through
+    /// the Mersenne numbers 2<sup>p</sup> − 1 for each prime p in increasing
+    /// order until just before overflow.
+    ///
+    /// Only a prime exponent can give a prime, but not every prime exponent
+    /// does, as with 2<sup>11</sup> − 1 = 23 × 89. `lucas_lehmer` tells
+    /// which do.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Mersenne;
+    ///
+    /// let terms: Vec<_> = u32::mersenne_numbers().collect();
+    /// assert_eq!(
+    ///     terms,
+    ///     vec![3, 7, 31, 127, 2047, 8191, 131_071, 524_287, 8_388_607, 536_870_911, 2_147_483_647]
+    /// );
+    /// assert_eq!(i8::mersenne_numbers().collect::<Vec<_>>(), vec![3, 7, 31, 127]);
+    /// assert_eq!(u128::mersenne_numbers().count(), 31);
+    /// ```
+    fn mersenne_numbers() -> Self::Iter;
+}
+
+/// The iterator returned by `Mersenne::mersenne_numbers`.
+#[derive(Clone, Debug)]
+pub struct MersenneIter<T> {
+    // The next exponent to try, or `None` once the terms have overflowed.
+    exponent: Option<u32>,
+    phantom: core::marker::PhantomData<T>,
+}
+
+macro_rules! mersenne_trait_for_primitive {
+    ($type:ty) => {
+        impl $crate::sequences::mersenne::Mersenne for $type {
+            type Iter = $crate::sequences::mersenne::MersenneIter<$type>;
+
+            fn mersenne_numbers() -> Self::Iter {
+                $crate::sequences::mersenne::MersenneIter {
+                    exponent: ::core::option::Option::Some(2),
+                    phantom: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        impl ::core::iter::Iterator for $crate::sequences::mersenne::MersenneIter<$type> {
+            type Item = $type;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                loop {
+                    let p = self.exponent?;
+                    if p > <$type>::MAX.count_ones() {
+                        self.exponent = ::core::option::Option::None;
+                        return ::core::option::Option::None;
+                    }
+                    self.exponent = ::core::option::Option::Some(p + 1);
+                    if $crate::sequences::mersenne::is_prime_exponent(p) {
+                        return ::core::option::Option::Some(
+                            <$type>::MAX >> (<$type>::MAX.count_ones() - p),
+                        );
+                    }
+                }
+            }
+        }
+
+        impl ::core::iter::FusedIterator for $crate::sequences::mersenne::MersenneIter<$type> {}
+    };
+}
+
+mersenne_trait_for_primitive!(::core::primitive::i8);
+mersenne_trait_for_primitive!(::core::primitive::u8);
+mersenne_trait_for_primitive!(::core::primitive::i16);
+mersenne_trait_for_primitive!(::core::primitive::u16);
+mersenne_trait_for_primitive!(::core::primitive::i32);
+mersenne_trait_for_primitive!(::core::primitive::u32);
+mersenne_trait_for_primitive!(::core::primitive::i64);
+mersenne_trait_for_primitive!(::core::primitive::u64);
+mersenne_trait_for_primitive!(::core::primitive::i128);
+mersenne_trait_for_primitive!(::core::primitive::u128);
+mersenne_trait_for_primitive!(::core::primitive::isize);
+mersenne_trait_for_primitive!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Mersenne for rug::Integer {
+    type Iter = MersenneIter<rug::Integer>;
+
+    fn mersenne_numbers() -> Self::Iter {
+        MersenneIter {
+            exponent: Some(2),
+            phantom: core::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for MersenneIter<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let p = self.exponent?;
+            self.exponent = p.checked_add(1);
+            if is_prime_exponent(p) {
+                return Some((rug::Integer::from(1) << p) - 1u8);
+            }
+        }
+    }
+}
+
+/// Returns whether the Mersenne number 2<sup>p</sup> − 1 is prime, using the
+/// Lucas–Lehmer test.
+///
+/// For an odd prime p, 2<sup>p</sup> − 1 is prime exactly when it divides
+/// s<sub>p − 2</sub>, where s<sub>0</sub> = 4 and s<sub>k + 1</sub> =
+/// s<sub>k</sub><sup>2</sup> − 2. Exponents below 64 are tested with
+/// primitive arithmetic, and larger ones with p − 2 squarings of p-bit
+/// integers, each reduced modulo 2<sup>p</sup> − 1 with shifts and additions
+/// rather than division.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::mersenne::lucas_lehmer;
+///
+/// let exponents: Vec<_> = (0..700).filter(|&p| lucas_lehmer(p)).collect();
+/// assert_eq!(
+///     exponents,
+///     vec![2, 3, 5, 7, 13, 17, 19, 31, 61, 89, 107, 127, 521, 607]
+/// );
+/// ```
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+pub fn lucas_lehmer(p: u32) -> bool {
+    if p < 64 {
+        return small_lucas_lehmer(p);
+    }
+    if !is_prime_exponent(p) {
+        return false;
+    }
+    let mersenne = (rug::Integer::from(1) << p) - 1u8;
+    let mut s = rug::Integer::from(4);
+    let mut high = rug::Integer::new();
+    for _ in 0..p - 2 {
+        s.square_mut();
+        // Since 2^p ≡ 1, the bits above the lowest p fold back onto them.
+        while s.significant_bits() > p {
+            high.assign(&s >> p);
+            s.keep_bits_mut(p);
+            s += &high;
+        }
+        if s == mersenne {
+            s.assign(0);
+        }
+        s -= 2u8;
+        if s < 0 {
+            s += &mersenne;
+        }
+    }
+    s == 0
+}
+
+/// Returns whether 2<sup>p</sup> − 1 is prime, for p up to 64.
+pub(crate) fn small_lucas_lehmer(p: u32) -> bool {
+    debug_assert!(p <= 64);
+    if p == 2 {
+        return true;
+    }
+    if !is_prime_exponent(p) {
+        return false;
+    }
+    let mersenne = (1u128 << p) - 1;
+    let mut s = 4u128;
+    for _ in 0..p - 2 {
+        s = (s * s + mersenne - 2) % mersenne;
+    }
+    s == 0
+}
+
+/// Returns whether p is prime.
+fn is_prime_exponent(p: u32) -> bool {
+    p >= 2
+        && (2..p)
+            .take_while(|d| d * d <= p)
+            .all(|d| !p.is_multiple_of(d))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let max = rug::Integer::from(<$type>::MAX);
+                let expected: ::std::vec::Vec<rug::Integer> = rug::Integer::mersenne_numbers()
+                    .take_while(|term| *term <= max)
+                    .collect();
+                let mut iter = <$type>::mersenne_numbers();
+                let terms: ::std::vec::Vec<rug::Integer> =
+                    iter.by_ref().map(rug::Integer::from).collect();
+                ::core::assert_eq!(terms, expected);
+                ::core::assert_eq!(iter.next(), ::core::option::Option::None);
+            }
+        };
+    }
+
+    test_against_rug!(::core::primitive::i8, i8_mersenne);
+    test_against_rug!(::core::primitive::u8, u8_mersenne);
+    test_against_rug!(::core::primitive::i16, i16_mersenne);
+    test_against_rug!(::core::primitive::u16, u16_mersenne);
+    test_against_rug!(::core::primitive::i32, i32_mersenne);
+    test_against_rug!(::core::primitive::u32, u32_mersenne);
+    test_against_rug!(::core::primitive::i64, i64_mersenne);
+    test_against_rug!(::core::primitive::u64, u64_mersenne);
+    test_against_rug!(::core::primitive::i128, i128_mersenne);
+    test_against_rug!(::core::primitive::u128, u128_mersenne);
+    test_against_rug!(::core::primitive::isize, isize_mersenne);
+    test_against_rug!(::core::primitive::usize, usize_mersenne);
+
+    #[test]
+    fn lucas_lehmer_matches_primality() {
+        for (p, term) in (2..)
+            .filter(|&p| is_prime_exponent(p))
+            .zip(rug::Integer::mersenne_numbers().take(60))
+        {
+            let expected = term.is_probably_prime(30) != rug::integer::IsPrime::No;
+            assert_eq!(lucas_lehmer(p), expected, "p = {}", p);
+        }
+        for p in [4, 9, 15, 64, 65, 91, 1000] {
+            assert!(!lucas_lehmer(p), "p = {}", p);
+        }
+    }
+
+    #[test]
+    fn large_mersenne_primes() {
+        for p in [1279, 2203, 2281, 3217, 4253, 4423] {
+            assert!(lucas_lehmer(p), "p = {}", p);
+        }
+        for p in [1277, 2207, 4289] {
+            assert!(!lucas_lehmer(p), "p = {}", p);
+        }
+    }
+}
@@ -0,0 +1,240 @@
+pub trait Perfect: Sized {
+    /// The exact type of the `Iterator<Item = Self>` returned by
+    /// `perfect_numbers`.
+    type Iter: Iterator<Item = Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through
+    /// the even perfect numbers 2<sup>p − 1</sup> (2<sup>p</sup> − 1), for
+    /// each Mersenne prime 2<sup>p</sup> − 1, in increasing order until just
+    /// before overflow.
+    ///
+    /// By the Euclid–Euler theorem these are all of the even perfect numbers.
+    /// No odd perfect number is known, and any would be far too large for a
+    /// primitive integer. The Mersenne numbers are tested by the Lucas–Lehmer
+    /// test.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Perfect;
+    ///
+    /// let terms: Vec<_> = u64::perfect_numbers().collect();
+    /// assert_eq!(
+    ///     terms,
+    ///     vec![6, 28, 496, 8128, 33_550_336, 8_589_869_056, 137_438_691_328, 2_305_843_008_139_952_128]
+    /// );
+    /// assert_eq!(u8::perfect_numbers().collect::<Vec<_>>(), vec![6, 28]);
+    /// assert_eq!(
+    ///     u128::perfect_numbers().last(),
+    ///     Some(2_658_455_991_569_831_744_654_692_615_953_842_176)
+    /// );
+    /// ```
+    fn perfect_numbers() -> Self::Iter;
+
+    /// Returns whether n is perfect, that is, whether n is positive and the
+    /// sum of its divisors is 2n.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Perfect;
+    ///
+    /// assert!(u32::is_perfect(&8128));
+    /// assert!(!u32::is_perfect(&8129));
+    /// assert!(!u32::is_perfect(&0));
+    /// assert!(!i32::is_perfect(&-6));
+    /// ```
+    fn is_perfect(n: &Self) -> bool;
+}
+
+/// The iterator returned by `Perfect::perfect_numbers`.
+#[derive(Clone, Debug)]
+pub struct PerfectIter<T> {
+    // The next exponent to try, or `None` once the terms have overflowed.
+    exponent: Option<u32>,
+    phantom: core::marker::PhantomData<T>,
+}
+
+macro_rules! perfect_trait_for_primitive {
+    ($type:ty, $unsigned:ty) => {
+        impl $crate::sequences::perfect::Perfect for $type {
+            type Iter = $crate::sequences::perfect::PerfectIter<$type>;
+
+            fn perfect_numbers() -> Self::Iter {
+                $crate::sequences::perfect::PerfectIter {
+                    exponent: ::core::option::Option::Some(2),
+                    phantom: ::core::marker::PhantomData,
+                }
+            }
+
+            fn is_perfect(n: &Self) -> bool {
+                match <$unsigned as ::core::convert::TryFrom<$type>>::try_from(*n) {
+                    ::core::result::Result::Ok(n) => {
+                        <$unsigned as $crate::number_theory::DivisorSigma>::is_multiperfect(&n, &2)
+                    }
+                    ::core::result::Result::Err(_) => false,
+                }
+            }
+        }
+
+        impl ::core::iter::Iterator for $crate::sequences::perfect::PerfectIter<$type> {
+            type Item = $type;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                loop {
+                    let p = self.exponent?;
+                    // The terms grow with p, so the first that does not fit
+                    // ends the sequence.
+                    let term = $crate::sequences::perfect::euclid_number(p).and_then(|term| {
+                        <$type as ::core::convert::TryFrom<u128>>::try_from(term).ok()
+                    });
+                    if term.is_none() {
+                        self.exponent = ::core::option::Option::None;
+                        return ::core::option::Option::None;
+                    }
+                    self.exponent = ::core::option::Option::Some(p + 1);
+                    if $crate::sequences::mersenne::small_lucas_lehmer(p) {
+                        return term;
+                    }
+                }
+            }
+        }
+
+        impl ::core::iter::FusedIterator for $crate::sequences::perfect::PerfectIter<$type> {}
+    };
+}
+
+perfect_trait_for_primitive!(::core::primitive::i8, ::core::primitive::u8);
+perfect_trait_for_primitive!(::core::primitive::u8, ::core::primitive::u8);
+perfect_trait_for_primitive!(::core::primitive::i16, ::core::primitive::u16);
+perfect_trait_for_primitive!(::core::primitive::u16, ::core::primitive::u16);
+perfect_trait_for_primitive!(::core::primitive::i32, ::core::primitive::u32);
+perfect_trait_for_primitive!(::core::primitive::u32, ::core::primitive::u32);
+perfect_trait_for_primitive!(::core::primitive::i64, ::core::primitive::u64);
+perfect_trait_for_primitive!(::core::primitive::u64, ::core::primitive::u64);
+perfect_trait_for_primitive!(::core::primitive::i128, ::core::primitive::u128);
+perfect_trait_for_primitive!(::core::primitive::u128, ::core::primitive::u128);
+perfect_trait_for_primitive!(::core::primitive::isize, ::core::primitive::usize);
+perfect_trait_for_primitive!(::core::primitive::usize, ::core::primitive::usize);
+
+/// Returns 2<sup>p − 1</sup> (2<sup>p</sup> − 1), or `None` if that does not
+/// fit in a `u128`.
+fn euclid_number(p: u32) -> Option<u128> {
+    let mersenne = 1u128.checked_shl(p)? - 1;
+    mersenne.checked_mul(1 << (p - 1))
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Perfect for rug::Integer {
+    type Iter = PerfectIter<rug::Integer>;
+
+    fn perfect_numbers() -> Self::Iter {
+        PerfectIter {
+            exponent: Some(2),
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    fn is_perfect(n: &Self) -> bool {
+        if *n <= 0 {
+            return false;
+        }
+        // Every even perfect number is 2^(p − 1) (2^p − 1), and there is no
+        // odd one below 10^1500.
+        let zeros = n.find_one(0).unwrap();
+        let odd = rug::Integer::from(n >> zeros);
+        zeros != 0
+            && odd == (rug::Integer::from(1) << (zeros + 1)) - 1u8
+            && crate::sequences::mersenne::lucas_lehmer(zeros + 1)
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for PerfectIter<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let p = self.exponent?;
+            self.exponent = p.checked_add(1);
+            if crate::sequences::mersenne::lucas_lehmer(p) {
+                let mersenne = (rug::Integer::from(1) << p) - 1u8;
+                return Some(mersenne << (p - 1));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MERSENNE_EXPONENTS: [u32; 18] = [
+        2, 3, 5, 7, 13, 17, 19, 31, 61, 89, 107, 127, 521, 607, 1279, 2203, 2281, 3217,
+    ];
+
+    macro_rules! test_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let max = rug::Integer::from(<$type>::MAX);
+                let expected: ::std::vec::Vec<rug::Integer> = rug::Integer::perfect_numbers()
+                    .take_while(|term| *term <= max)
+                    .collect();
+                let mut iter = <$type>::perfect_numbers();
+                let terms: ::std::vec::Vec<rug::Integer> =
+                    iter.by_ref().map(rug::Integer::from).collect();
+                ::core::assert_eq!(terms, expected);
+                ::core::assert_eq!(iter.next(), ::core::option::Option::None);
+
+                for term in <$type>::perfect_numbers().take(4) {
+                    ::core::assert!(<$type>::is_perfect(&term));
+                    ::core::assert!(!<$type>::is_perfect(&(term - 1)));
+                }
+                for n in 0..=100u8 {
+                    let n = n as $type;
+                    ::core::assert_eq!(<$type>::is_perfect(&n), n == 6 || n == 28);
+                }
+            }
+        };
+    }
+
+    test_against_rug!(::core::primitive::i8, i8_perfect);
+    test_against_rug!(::core::primitive::u8, u8_perfect);
+    test_against_rug!(::core::primitive::i16, i16_perfect);
+    test_against_rug!(::core::primitive::u16, u16_perfect);
+    test_against_rug!(::core::primitive::i32, i32_perfect);
+    test_against_rug!(::core::primitive::u32, u32_perfect);
+    test_against_rug!(::core::primitive::i64, i64_perfect);
+    test_against_rug!(::core::primitive::u64, u64_perfect);
+    test_against_rug!(::core::primitive::i128, i128_perfect);
+    test_against_rug!(::core::primitive::u128, u128_perfect);
+    test_against_rug!(::core::primitive::isize, isize_perfect);
+    test_against_rug!(::core::primitive::usize, usize_perfect);
+
+    #[test]
+    fn rug_perfect_numbers() {
+        let terms: Vec<_> = rug::Integer::perfect_numbers()
+            .take(MERSENNE_EXPONENTS.len())
+            .collect();
+        for (term, &p) in terms.iter().zip(&MERSENNE_EXPONENTS) {
+            let expected = ((rug::Integer::from(1) << p) - 1u8) << (p - 1);
+            assert_eq!(*term, expected);
+            assert!(rug::Integer::is_perfect(term));
+            assert!(!rug::Integer::is_perfect(&(term.clone() + 2u8)));
+        }
+        assert!(!rug::Integer::is_perfect(&rug::Integer::from(-6)));
+        // 2^10 (2^11 − 1) has the right form, but 2^11 − 1 = 23 · 89.
+        assert!(!rug::Integer::is_perfect(&(rug::Integer::from(2047) << 10)));
+    }
+
+    #[test]
+    fn lucas_lehmer_small_exponents() {
+        let exponents: Vec<_> = (0..64)
+            .filter(|&p| crate::sequences::mersenne::small_lucas_lehmer(p))
+            .collect();
+        assert_eq!(exponents, MERSENNE_EXPONENTS[..9]);
+    }
+}
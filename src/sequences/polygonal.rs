@@ -0,0 +1,742 @@
+use core::convert::TryFrom;
+
+/// Polygonal numbers P(s, n) = ((s − 2)n² − (s − 4)n) / 2, the numbers of
+/// dots in n nested regular s-gons sharing a corner, for every number of
+/// sides s ≥ 3.
+///
+/// Besides the general methods, which take the number of sides, there are
+/// shorthands for squares and for pentagonal, hexagonal, heptagonal, and
+/// octagonal numbers. Triangular numbers have their own trait,
+/// `Triangular`, though `nth_polygonal(&3, n)` gives them too.
+pub trait Polygonal: Sized + TryFrom<u8> {
+    /// Returns an `Option` containing either the n<sup>th</sup> s-gonal
+    /// number or `None` if that would cause overflow or n is negative.
+    ///
+    /// # Panics
+    ///
+    /// Panics if s is less than three.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert_eq!(u32::nth_polygonal(&3, &10), Some(55));
+    /// assert_eq!(u32::nth_polygonal(&12, &10), Some(460));
+    /// assert_eq!(u8::nth_polygonal(&12, &8), None);
+    /// assert_eq!(i8::nth_polygonal(&5, &-1), None);
+    /// ```
+    fn nth_polygonal(s: &Self, n: &Self) -> Option<Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through all
+    /// s-gonal numbers from zero until just before overflow.
+    ///
+    /// # Panics
+    ///
+    /// Panics if s is less than three.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// let terms: Vec<_> = u8::polygonal_iter(&10).collect();
+    /// assert_eq!(terms, vec![0, 1, 10, 27, 52, 85, 126, 175, 232]);
+    /// ```
+    fn polygonal_iter(s: &Self) -> PolygonalIter<Self>;
+
+    /// Returns whether x is an s-gonal number. Negative numbers never are.
+    ///
+    /// # Panics
+    ///
+    /// Panics if s is less than three.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert!(u32::is_polygonal(&12, &460));
+    /// assert!(!u32::is_polygonal(&12, &461));
+    /// assert!(!i8::is_polygonal(&3, &-1));
+    /// ```
+    fn is_polygonal(s: &Self, x: &Self) -> bool;
+
+    /// Returns an `Option` containing either the index k ≥ 0 for which
+    /// P(s, k) = x or `None` if x is not an s-gonal number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if s is less than three.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert_eq!(u32::polygonal_index(&12, &460), Some(10));
+    /// assert_eq!(u32::polygonal_index(&12, &461), None);
+    /// assert_eq!(u64::polygonal_index(&u64::MAX, &u64::MAX), Some(2));
+    /// ```
+    fn polygonal_index(s: &Self, x: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the n<sup>th</sup> square, n²,
+    /// or `None` if that would cause overflow or n is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert_eq!(u8::nth_square(&15), Some(225));
+    /// assert_eq!(u8::nth_square(&16), None);
+    /// ```
+    fn nth_square(n: &Self) -> Option<Self> {
+        Self::nth_polygonal(&sides(4), n)
+    }
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through all
+    /// squares from zero until just before overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert_eq!(i8::square_iter().last(), Some(121));
+    /// ```
+    fn square_iter() -> PolygonalIter<Self> {
+        Self::polygonal_iter(&sides(4))
+    }
+
+    /// Returns whether x is a square. Negative numbers never are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert!(u32::is_square(&144));
+    /// assert!(!u32::is_square(&145));
+    /// ```
+    fn is_square(x: &Self) -> bool {
+        Self::is_polygonal(&sides(4), x)
+    }
+
+    /// Returns an `Option` containing either the nonnegative square root of
+    /// x or `None` if x is not a square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert_eq!(u32::square_index(&144), Some(12));
+    /// assert_eq!(u32::square_index(&145), None);
+    /// ```
+    fn square_index(x: &Self) -> Option<Self> {
+        Self::polygonal_index(&sides(4), x)
+    }
+
+    /// Returns an `Option` containing either the n<sup>th</sup> pentagonal
+    /// number, n(3n − 1) / 2, or `None` if that would cause overflow or n is
+    /// negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert_eq!(u32::nth_pentagonal(&10), Some(145));
+    /// ```
+    fn nth_pentagonal(n: &Self) -> Option<Self> {
+        Self::nth_polygonal(&sides(5), n)
+    }
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through all
+    /// pentagonal numbers from zero until just before overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// let terms: Vec<_> = u8::pentagonal_iter().collect();
+    /// assert_eq!(terms, vec![0, 1, 5, 12, 22, 35, 51, 70, 92, 117, 145, 176, 210, 247]);
+    /// ```
+    fn pentagonal_iter() -> PolygonalIter<Self> {
+        Self::polygonal_iter(&sides(5))
+    }
+
+    /// Returns whether x is a pentagonal number. Negative numbers never are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert!(u32::is_pentagonal(&1_560_090));
+    /// assert!(!u32::is_pentagonal(&1_560_091));
+    /// ```
+    fn is_pentagonal(x: &Self) -> bool {
+        Self::is_polygonal(&sides(5), x)
+    }
+
+    /// Returns an `Option` containing either the index k ≥ 0 of the
+    /// pentagonal number x or `None` if x is not a pentagonal number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert_eq!(u32::pentagonal_index(&1_560_090), Some(1020));
+    /// ```
+    fn pentagonal_index(x: &Self) -> Option<Self> {
+        Self::polygonal_index(&sides(5), x)
+    }
+
+    /// Returns an `Option` containing either the n<sup>th</sup> hexagonal
+    /// number, n(2n − 1), or `None` if that would cause overflow or n is
+    /// negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert_eq!(u32::nth_hexagonal(&10), Some(190));
+    /// ```
+    fn nth_hexagonal(n: &Self) -> Option<Self> {
+        Self::nth_polygonal(&sides(6), n)
+    }
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through all
+    /// hexagonal numbers from zero until just before overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// let terms: Vec<_> = u8::hexagonal_iter().collect();
+    /// assert_eq!(terms, vec![0, 1, 6, 15, 28, 45, 66, 91, 120, 153, 190, 231]);
+    /// ```
+    fn hexagonal_iter() -> PolygonalIter<Self> {
+        Self::polygonal_iter(&sides(6))
+    }
+
+    /// Returns whether x is a hexagonal number. Negative numbers never are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert!(u32::is_hexagonal(&40_755));
+    /// assert!(!u32::is_hexagonal(&40_756));
+    /// ```
+    fn is_hexagonal(x: &Self) -> bool {
+        Self::is_polygonal(&sides(6), x)
+    }
+
+    /// Returns an `Option` containing either the index k ≥ 0 of the hexagonal
+    /// number x or `None` if x is not a hexagonal number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert_eq!(u32::hexagonal_index(&40_755), Some(143));
+    /// ```
+    fn hexagonal_index(x: &Self) -> Option<Self> {
+        Self::polygonal_index(&sides(6), x)
+    }
+
+    /// Returns an `Option` containing either the n<sup>th</sup> heptagonal
+    /// number, n(5n − 3) / 2, or `None` if that would cause overflow or n is
+    /// negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert_eq!(u32::nth_heptagonal(&10), Some(235));
+    /// ```
+    fn nth_heptagonal(n: &Self) -> Option<Self> {
+        Self::nth_polygonal(&sides(7), n)
+    }
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through all
+    /// heptagonal numbers from zero until just before overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// let terms: Vec<_> = u8::heptagonal_iter().collect();
+    /// assert_eq!(terms, vec![0, 1, 7, 18, 34, 55, 81, 112, 148, 189, 235]);
+    /// ```
+    fn heptagonal_iter() -> PolygonalIter<Self> {
+        Self::polygonal_iter(&sides(7))
+    }
+
+    /// Returns whether x is a heptagonal number. Negative numbers never are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert!(u32::is_heptagonal(&235));
+    /// assert!(!u32::is_heptagonal(&236));
+    /// ```
+    fn is_heptagonal(x: &Self) -> bool {
+        Self::is_polygonal(&sides(7), x)
+    }
+
+    /// Returns an `Option` containing either the index k ≥ 0 of the
+    /// heptagonal number x or `None` if x is not a heptagonal number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert_eq!(u32::heptagonal_index(&235), Some(10));
+    /// ```
+    fn heptagonal_index(x: &Self) -> Option<Self> {
+        Self::polygonal_index(&sides(7), x)
+    }
+
+    /// Returns an `Option` containing either the n<sup>th</sup> octagonal
+    /// number, n(3n − 2), or `None` if that would cause overflow or n is
+    /// negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert_eq!(u32::nth_octagonal(&10), Some(280));
+    /// ```
+    fn nth_octagonal(n: &Self) -> Option<Self> {
+        Self::nth_polygonal(&sides(8), n)
+    }
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through all
+    /// octagonal numbers from zero until just before overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// let terms: Vec<_> = u8::octagonal_iter().collect();
+    /// assert_eq!(terms, vec![0, 1, 8, 21, 40, 65, 96, 133, 176, 225]);
+    /// ```
+    fn octagonal_iter() -> PolygonalIter<Self> {
+        Self::polygonal_iter(&sides(8))
+    }
+
+    /// Returns whether x is an octagonal number. Negative numbers never are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert!(u32::is_octagonal(&280));
+    /// assert!(!u32::is_octagonal(&281));
+    /// ```
+    fn is_octagonal(x: &Self) -> bool {
+        Self::is_polygonal(&sides(8), x)
+    }
+
+    /// Returns an `Option` containing either the index k ≥ 0 of the
+    /// octagonal number x or `None` if x is not an octagonal number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Polygonal;
+    ///
+    /// assert_eq!(u32::octagonal_index(&280), Some(10));
+    /// ```
+    fn octagonal_index(x: &Self) -> Option<Self> {
+        Self::polygonal_index(&sides(8), x)
+    }
+}
+
+/// The iterator returned by `Polygonal::polygonal_iter` and the per-shape
+/// iterator methods.
+///
+/// For `rug::Integer`, it also implements `StreamingSequence`, whose
+/// `next_ref` method lends out each term instead of cloning it.
+#[derive(Clone, Debug)]
+pub struct PolygonalIter<T> {
+    step: T,
+    term: Option<T>,
+    difference: Option<T>,
+}
+
+/// Returns the number of sides s as a `T`, which every integer type can hold
+/// for the shapes with shorthands.
+fn sides<T: TryFrom<u8>>(s: u8) -> T {
+    match T::try_from(s) {
+        Ok(s) => s,
+        Err(_) => unreachable!("every integer type holds the numbers up to eight"),
+    }
+}
+
+macro_rules! polygonal_trait_for_primitive {
+    ($type:ty) => {
+        impl $crate::sequences::polygonal::Polygonal for $type {
+            fn nth_polygonal(s: &Self, n: &Self) -> ::core::option::Option<Self> {
+                let s = $crate::sequences::polygonal::checked_sides(*s);
+                let n = <u128 as ::core::convert::TryFrom<$type>>::try_from(*n).ok()?;
+                let term = $crate::sequences::polygonal::polygonal(s, n)?;
+                <$type as ::core::convert::TryFrom<u128>>::try_from(term).ok()
+            }
+
+            fn polygonal_iter(s: &Self) -> $crate::sequences::polygonal::PolygonalIter<Self> {
+                $crate::sequences::polygonal::checked_sides(*s);
+                $crate::sequences::polygonal::PolygonalIter {
+                    step: *s - 2,
+                    term: ::core::option::Option::Some(0),
+                    difference: ::core::option::Option::Some(1),
+                }
+            }
+
+            fn is_polygonal(s: &Self, x: &Self) -> bool {
+                Self::polygonal_index(s, x).is_some()
+            }
+
+            fn polygonal_index(s: &Self, x: &Self) -> ::core::option::Option<Self> {
+                let s = $crate::sequences::polygonal::checked_sides(*s);
+                let x = <u128 as ::core::convert::TryFrom<$type>>::try_from(*x).ok()?;
+                let index = $crate::sequences::polygonal::polygonal_root(s, x);
+                if $crate::sequences::polygonal::polygonal(s, index)
+                    == ::core::option::Option::Some(x)
+                {
+                    <$type as ::core::convert::TryFrom<u128>>::try_from(index).ok()
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+        }
+
+        impl ::core::iter::Iterator for $crate::sequences::polygonal::PolygonalIter<$type> {
+            type Item = $type;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                let term = self.term?;
+                // The differences grow, so once one overflows, so does every
+                // later term.
+                self.term = self
+                    .difference
+                    .and_then(|difference| term.checked_add(difference));
+                self.difference = self
+                    .difference
+                    .and_then(|difference| difference.checked_add(self.step));
+                ::core::option::Option::Some(term)
+            }
+        }
+
+        impl ::core::iter::FusedIterator for $crate::sequences::polygonal::PolygonalIter<$type> {}
+    };
+}
+
+polygonal_trait_for_primitive!(::core::primitive::i8);
+polygonal_trait_for_primitive!(::core::primitive::u8);
+polygonal_trait_for_primitive!(::core::primitive::i16);
+polygonal_trait_for_primitive!(::core::primitive::u16);
+polygonal_trait_for_primitive!(::core::primitive::i32);
+polygonal_trait_for_primitive!(::core::primitive::u32);
+polygonal_trait_for_primitive!(::core::primitive::i64);
+polygonal_trait_for_primitive!(::core::primitive::u64);
+polygonal_trait_for_primitive!(::core::primitive::i128);
+polygonal_trait_for_primitive!(::core::primitive::u128);
+polygonal_trait_for_primitive!(::core::primitive::isize);
+polygonal_trait_for_primitive!(::core::primitive::usize);
+
+/// Returns s as a `u128` after checking that it is at least three.
+fn checked_sides<T>(s: T) -> u128
+where
+    u128: TryFrom<T>,
+{
+    match u128::try_from(s) {
+        Ok(s) if s >= 3 => s,
+        _ => panic!("number of sides must be at least three"),
+    }
+}
+
+/// Returns P(s, n), or `None` if that does not fit in a `u128`.
+fn polygonal(s: u128, n: u128) -> Option<u128> {
+    // P(s, n) = n + (s − 2) T(n − 1), and each part is at most the result.
+    if n == 0 {
+        return Some(0);
+    }
+    let triangular = crate::sequences::triangular::triangular(n - 1)?;
+    (s - 2).checked_mul(triangular)?.checked_add(n)
+}
+
+/// Returns the largest k with P(s, k) ≤ x.
+fn polygonal_root(s: u128, x: u128) -> u128 {
+    // Since P(s, k) ≥ T(k) > k² / 2, k is less than √(2x) + 1.
+    let (mut low, mut high) = (0, core::cmp::min(x, 2 * x.isqrt() + 1));
+    while low < high {
+        let middle = low + (high - low).div_ceil(2);
+        if polygonal(s, middle).is_some_and(|term| term <= x) {
+            low = middle;
+        } else {
+            high = middle - 1;
+        }
+    }
+    low
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Polygonal for rug::Integer {
+    fn nth_polygonal(s: &Self, n: &Self) -> Option<Self> {
+        assert!(*s >= 3, "number of sides must be at least three");
+        if *n < 0 {
+            return None;
+        }
+        let triangular = n * rug::Integer::from(n - 1u8) / 2u8;
+        Some(rug::Integer::from(s - 2u8) * triangular + n)
+    }
+
+    fn polygonal_iter(s: &Self) -> PolygonalIter<Self> {
+        assert!(*s >= 3, "number of sides must be at least three");
+        // This holds the last term handed out, so it starts one before the
+        // first, with P(s, −1) = s − 3 and P(s, 0) − P(s, −1) = 3 − s.
+        PolygonalIter {
+            step: rug::Integer::from(s - 2u8),
+            term: Some(rug::Integer::from(s - 3u8)),
+            difference: Some(rug::Integer::from(3u8 - s)),
+        }
+    }
+
+    fn is_polygonal(s: &Self, x: &Self) -> bool {
+        Self::polygonal_index(s, x).is_some()
+    }
+
+    fn polygonal_index(s: &Self, x: &Self) -> Option<Self> {
+        assert!(*s >= 3, "number of sides must be at least three");
+        if *x <= 0 {
+            return if *x == 0 {
+                Some(rug::Integer::new())
+            } else {
+                None
+            };
+        }
+        // For positive x, P(s, k) = x exactly when k = ((s − 4) + √((s − 4)² + 8(s −
+        // 2)x)) / (2(s − 2)) is an integer.
+        let four_less = rug::Integer::from(s - 4u8);
+        let two_less = rug::Integer::from(s - 2u8);
+        let discriminant = rug::Integer::from(four_less.square_ref()) + two_less.clone() * x * 8u8;
+        if !discriminant.is_perfect_square() {
+            return None;
+        }
+        let numerator = discriminant.sqrt() + four_less;
+        let denominator = two_less * 2u8;
+        if numerator.is_divisible(&denominator) {
+            Some(numerator / denominator)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl crate::sequences::StreamingSequence for PolygonalIter<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next_ref(&mut self) -> Option<&Self::Item> {
+        let term = self.term.as_mut()?;
+        let difference = self.difference.as_mut()?;
+        *term += &*difference;
+        *difference += &self.step;
+        Some(term)
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for PolygonalIter<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        crate::sequences::StreamingSequence::next_ref(self).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let max = rug::Integer::from(<$type>::MAX);
+                for s in 3..=20u8 {
+                    let big_s = rug::Integer::from(s);
+                    let s = s as $type;
+
+                    let expected: ::std::vec::Vec<rug::Integer> =
+                        rug::Integer::polygonal_iter(&big_s)
+                            .take(500)
+                            .take_while(|term| *term <= max)
+                            .collect();
+                    let terms: ::std::vec::Vec<rug::Integer> = <$type>::polygonal_iter(&s)
+                        .take(500)
+                        .map(rug::Integer::from)
+                        .collect();
+                    ::core::assert_eq!(terms, expected);
+
+                    for (n, term) in expected.iter().enumerate() {
+                        let n = n as $type;
+                        let term = term.to_i128().unwrap() as $type;
+                        ::core::assert_eq!(
+                            <$type>::nth_polygonal(&s, &n),
+                            ::core::option::Option::Some(term)
+                        );
+                        ::core::assert_eq!(
+                            <$type>::polygonal_index(&s, &term),
+                            ::core::option::Option::Some(n)
+                        );
+                    }
+
+                    for x in 0..=2000u16 {
+                        let expected = rug::Integer::polygonal_index(&big_s, &x.into());
+                        let x = match <$type as ::core::convert::TryFrom<u16>>::try_from(x) {
+                            ::core::result::Result::Ok(x) => x,
+                            ::core::result::Result::Err(_) => break,
+                        };
+                        ::core::assert_eq!(
+                            <$type>::polygonal_index(&s, &x).map(rug::Integer::from),
+                            expected
+                        );
+                        ::core::assert_eq!(<$type>::is_polygonal(&s, &x), expected.is_some());
+                    }
+
+                    // The last term before overflow, and the values around it.
+                    let index = polygonal_root(s as u128, <$type>::MAX as u128);
+                    let last = <$type>::nth_polygonal(&s, &(index as $type)).unwrap();
+                    ::core::assert_eq!(
+                        <$type>::polygonal_index(&s, &last),
+                        ::core::option::Option::Some(index as $type)
+                    );
+                    ::core::assert_eq!(
+                        <$type>::nth_polygonal(&s, &(index as $type + 1)),
+                        ::core::option::Option::None
+                    );
+                    for x in [last - 1, <$type>::MAX] {
+                        ::core::assert_eq!(
+                            <$type>::polygonal_index(&s, &x).map(rug::Integer::from),
+                            rug::Integer::polygonal_index(&big_s, &x.into())
+                        );
+                    }
+                }
+
+                let max = <$type>::MAX;
+                ::core::assert_eq!(
+                    <$type>::nth_polygonal(&max, &2),
+                    ::core::option::Option::Some(max)
+                );
+                ::core::assert_eq!(
+                    <$type>::nth_polygonal(&max, &3),
+                    ::core::option::Option::None
+                );
+                ::core::assert_eq!(<$type>::polygonal_iter(&max).count(), 3);
+                ::core::assert_eq!(
+                    <$type>::polygonal_index(&max, &max),
+                    ::core::option::Option::Some(2)
+                );
+            }
+        };
+    }
+
+    test_against_rug!(::core::primitive::i8, i8_polygonal);
+    test_against_rug!(::core::primitive::u8, u8_polygonal);
+    test_against_rug!(::core::primitive::i16, i16_polygonal);
+    test_against_rug!(::core::primitive::u16, u16_polygonal);
+    test_against_rug!(::core::primitive::i32, i32_polygonal);
+    test_against_rug!(::core::primitive::u32, u32_polygonal);
+    test_against_rug!(::core::primitive::i64, i64_polygonal);
+    test_against_rug!(::core::primitive::u64, u64_polygonal);
+    test_against_rug!(::core::primitive::i128, i128_polygonal);
+    test_against_rug!(::core::primitive::u128, u128_polygonal);
+    test_against_rug!(::core::primitive::isize, isize_polygonal);
+    test_against_rug!(::core::primitive::usize, usize_polygonal);
+
+    #[test]
+    fn shapes() {
+        use crate::sequences::Triangular;
+
+        for n in 0..100u64 {
+            assert_eq!(u64::nth_polygonal(&3, &n), u64::nth_triangular(&n));
+            assert_eq!(u64::nth_square(&n), Some(n * n));
+            assert_eq!(
+                u64::nth_pentagonal(&n),
+                Some(n * (3 * n).saturating_sub(1) / 2)
+            );
+            assert_eq!(u64::nth_hexagonal(&n), Some(n * (2 * n).saturating_sub(1)));
+            assert_eq!(
+                u64::nth_heptagonal(&n),
+                Some(n * (5 * n).saturating_sub(3) / 2)
+            );
+            assert_eq!(u64::nth_octagonal(&n), Some(n * (3 * n).saturating_sub(2)));
+        }
+        assert!(u128::square_iter().take(1000).eq((0..1000).map(|n| n * n)));
+        assert_eq!(
+            u128::square_index(&(u64::MAX as u128).pow(2)),
+            Some(u64::MAX as u128)
+        );
+        assert!(!u128::is_square(&u128::MAX));
+
+        // Every hexagonal number is triangular, and 40755 is the first one
+        // past one that is also pentagonal.
+        let both: Vec<_> = u64::hexagonal_iter()
+            .skip(2)
+            .filter(u64::is_pentagonal)
+            .take(2)
+            .collect();
+        assert_eq!(both, vec![40_755, 1_533_776_805]);
+        assert!(both.iter().all(u64::is_triangular));
+    }
+
+    #[test]
+    #[should_panic(expected = "number of sides must be at least three")]
+    fn too_few_sides() {
+        u32::nth_polygonal(&2, &5);
+    }
+
+    #[test]
+    fn rug_large_values() {
+        let s = rug::Integer::from(rug::Integer::u_pow_u(10, 30));
+        let n = rug::Integer::from(rug::Integer::u_pow_u(10, 40));
+        let term = rug::Integer::nth_polygonal(&s, &n).unwrap();
+        assert_eq!(rug::Integer::polygonal_index(&s, &term), Some(n));
+        assert!(!rug::Integer::is_polygonal(&s, &(term + 1u8)));
+        assert_eq!(rug::Integer::nth_polygonal(&s, &(-1).into()), None);
+        assert!(!rug::Integer::is_square(&(-4).into()));
+
+        use crate::sequences::StreamingSequence;
+        for s in 3..=20u8 {
+            let s = rug::Integer::from(s);
+            let mut streaming = rug::Integer::polygonal_iter(&s);
+            for n in 0..300u16 {
+                let expected = rug::Integer::nth_polygonal(&s, &n.into());
+                assert_eq!(streaming.next_ref(), expected.as_ref());
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+/// Returns whether the Proth number k 2<sup>n</sup> + 1 is prime, using
+/// Proth's theorem.
+///
+/// For odd k < 2<sup>n</sup>, N = k 2<sup>n</sup> + 1 is prime exactly when
+/// a<sup>(N − 1)/2</sup> ≡ −1 (mod N) for any a with Jacobi symbol
+/// (a/N) = −1. Such an a is looked for among 2, 3, 4, …, where one turns up
+/// quickly unless N is a square, and a Jacobi symbol of zero on the way
+/// shows that N is composite. Numbers below 2<sup>128</sup>
+/// are tested with primitive arithmetic, and larger ones with one modular
+/// exponentiation of (n + 64)-bit integers.
+///
+/// # Panics
+///
+/// Panics if k is even or not less than 2<sup>n</sup>.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::proth::proth_test;
+///
+/// // 3 × 2^n + 1 is prime for these n.
+/// let exponents: Vec<_> = (2..250).filter(|&n| proth_test(3, n)).collect();
+/// assert_eq!(exponents, vec![2, 5, 6, 8, 12, 18, 30, 36, 41, 66, 189, 201, 209]);
+///
+/// // The Fermat numbers are the Proth numbers with k = 1.
+/// assert!(proth_test(1, 16));
+/// assert!(!proth_test(1, 32));
+/// ```
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+pub fn proth_test(k: u64, n: u32) -> bool {
+    assert!(
+        k & 1 == 1 && (n >= 64 || k < 1 << n),
+        "k must be odd and less than 2^n"
+    );
+    if n < 64 {
+        return small_proth_test(k, n);
+    }
+    let proth = (rug::Integer::from(k) << n) + 1u8;
+    if proth.is_perfect_square() {
+        return false;
+    }
+    let half = rug::Integer::from(k) << (n - 1);
+    for a in 2u64.. {
+        match rug::Integer::from(a).jacobi(&proth) {
+            -1 => {
+                let power = rug::Integer::from(a).pow_mod(&half, &proth).unwrap();
+                return power + 1u8 == proth;
+            }
+            0 => return false,
+            _ => {}
+        }
+    }
+    unreachable!()
+}
+
+/// Returns whether k 2<sup>n</sup> + 1 is prime, for n below 64.
+#[cfg(any(feature = "rug", doc, test))]
+fn small_proth_test(k: u64, n: u32) -> bool {
+    use crate::number_theory::primes::{jacobi, power, Modular, Montgomery};
+
+    let proth = ((k as u128) << n) + 1;
+    let root = proth.isqrt();
+    if root * root == proth {
+        return false;
+    }
+    let modular = Montgomery::new(proth);
+    for a in 2u128.. {
+        match jacobi(a, proth) {
+            -1 => {
+                let power = power(&modular, modular.elem(a), proth >> 1);
+                return power == modular.elem(proth - 1);
+            }
+            0 => return false,
+            _ => {}
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_sieve() {
+        let sieve = crate::number_theory::Sieve::new(1 << 20);
+        for n in 1..10 {
+            for k in (1..1 << n).step_by(2) {
+                let proth = (k << n) + 1;
+                assert_eq!(
+                    proth_test(k, n),
+                    sieve.is_prime(proth),
+                    "{} × 2^{} + 1",
+                    k,
+                    n
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn matches_rug() {
+        for &k in &[1, 3, 5, 7, 9, 13, 27, 1_000_001, u64::MAX] {
+            for n in (1u32..400).filter(|&n| n >= 64 || k < 1 << n) {
+                let proth = (rug::Integer::from(k) << n) + 1u8;
+                let expected = proth.is_probably_prime(30) != rug::integer::IsPrime::No;
+                assert_eq!(proth_test(k, n), expected, "{} × 2^{} + 1", k, n);
+            }
+        }
+    }
+
+    #[test]
+    fn known_primes() {
+        // The largest n below 2000 for k = 5 and k = 7.
+        assert!(proth_test(5, 1947));
+        assert!(proth_test(7, 1804));
+        assert!(!proth_test(5, 1948));
+    }
+
+    #[test]
+    #[should_panic(expected = "k must be odd and less than 2^n")]
+    fn even_k() {
+        proth_test(4, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "k must be odd and less than 2^n")]
+    fn large_k() {
+        proth_test(17, 4);
+    }
+}
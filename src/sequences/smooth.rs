@@ -0,0 +1,318 @@
+pub trait Smooth: Sized {
+    /// The exact type of the `Iterator<Item = Self>` returned by
+    /// `smooth_numbers`.
+    type Iter: Iterator<Item = Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through the
+    /// numbers whose prime factors all lie in `primes`, in increasing order
+    /// from one until just before overflow. With the primes 2, 3, and 5,
+    /// these are the regular or Hamming numbers.
+    ///
+    /// This merges the multiples of the terms so far by each prime, as in
+    /// Dijkstra's solution to Hamming's problem, so each term takes time
+    /// linear in the number of primes. Only the terms that some prime has yet
+    /// to multiply are kept.
+    ///
+    /// The factors need not be prime, in which case this goes through the
+    /// products of their powers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of `primes` is less than two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Smooth;
+    ///
+    /// let terms: Vec<_> = u32::smooth_numbers(&[2, 3, 5]).take(15).collect();
+    /// assert_eq!(terms, vec![1, 2, 3, 4, 5, 6, 8, 9, 10, 12, 15, 16, 18, 20, 24]);
+    ///
+    /// let terms: Vec<_> = u8::smooth_numbers(&[3, 7]).collect();
+    /// assert_eq!(terms, vec![1, 3, 7, 9, 21, 27, 49, 63, 81, 147, 189, 243]);
+    ///
+    /// assert_eq!(u64::smooth_numbers(&[2]).count(), 64);
+    /// assert_eq!(u64::smooth_numbers(&[]).collect::<Vec<_>>(), vec![1]);
+    /// ```
+    fn smooth_numbers(primes: &[Self]) -> Self::Iter;
+
+    /// Returns whether n is positive and has every prime factor in `primes`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of `primes` is less than two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Smooth;
+    ///
+    /// assert!(u32::is_smooth(&4_860, &[2, 3, 5]));
+    /// assert!(!u32::is_smooth(&4_862, &[2, 3, 5]));
+    /// assert!(u8::is_smooth(&1, &[]));
+    /// assert!(!i8::is_smooth(&-8, &[2]));
+    /// ```
+    fn is_smooth(n: &Self, primes: &[Self]) -> bool;
+}
+
+/// The iterator returned by `Smooth::smooth_numbers`.
+#[derive(Clone, Debug)]
+pub struct SmoothIter<T> {
+    primes: Vec<T>,
+    // The terms from the one at `offset` on, which are those that some prime
+    // has yet to multiply.
+    terms: std::collections::VecDeque<T>,
+    offset: usize,
+    // For each prime, the index of the next term for it to multiply and the
+    // product, or `None` once that overflows.
+    pointers: Vec<usize>,
+    candidates: Vec<Option<T>>,
+    next: Option<T>,
+}
+
+macro_rules! smooth_trait_for_primitive {
+    ($type:ty) => {
+        impl $crate::sequences::smooth::Smooth for $type {
+            type Iter = $crate::sequences::smooth::SmoothIter<$type>;
+
+            fn smooth_numbers(primes: &[Self]) -> Self::Iter {
+                ::core::assert!(
+                    primes.iter().all(|&p| p >= 2),
+                    "primes must be at least two"
+                );
+                $crate::sequences::smooth::SmoothIter {
+                    primes: primes.to_vec(),
+                    terms: ::core::iter::once(1).collect(),
+                    offset: 0,
+                    pointers: ::std::vec![0; primes.len()],
+                    candidates: primes.iter().map(|&p| ::core::option::Option::Some(p)).collect(),
+                    next: ::core::option::Option::Some(1),
+                }
+            }
+
+            fn is_smooth(n: &Self, primes: &[Self]) -> bool {
+                ::core::assert!(
+                    primes.iter().all(|&p| p >= 2),
+                    "primes must be at least two"
+                );
+                if *n <= 0 {
+                    return false;
+                }
+                let mut n = *n;
+                for &p in primes {
+                    while n % p == 0 {
+                        n /= p;
+                    }
+                }
+                n == 1
+            }
+        }
+
+        impl ::core::iter::Iterator for $crate::sequences::smooth::SmoothIter<$type> {
+            type Item = $type;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                let term = self.next?;
+                self.next = self.candidates.iter().flatten().min().copied();
+                if let ::core::option::Option::Some(next) = self.next {
+                    self.terms.push_back(next);
+                    for i in 0..self.primes.len() {
+                        if self.candidates[i] == ::core::option::Option::Some(next) {
+                            self.pointers[i] += 1;
+                            let factor = self.terms[self.pointers[i] - self.offset];
+                            self.candidates[i] = factor.checked_mul(self.primes[i]);
+                        }
+                    }
+                    // The products that overflowed stay overflowed, so their
+                    // pointers no longer hold back the trimming.
+                    let first = (0..self.primes.len())
+                        .filter(|&i| self.candidates[i].is_some())
+                        .map(|i| self.pointers[i])
+                        .min()
+                        .unwrap_or(self.offset + self.terms.len());
+                    while self.offset < first && self.terms.len() > 1 {
+                        self.terms.pop_front();
+                        self.offset += 1;
+                    }
+                }
+                ::core::option::Option::Some(term)
+            }
+        }
+
+        impl ::core::iter::FusedIterator for $crate::sequences::smooth::SmoothIter<$type> {}
+    };
+}
+
+smooth_trait_for_primitive!(::core::primitive::i8);
+smooth_trait_for_primitive!(::core::primitive::u8);
+smooth_trait_for_primitive!(::core::primitive::i16);
+smooth_trait_for_primitive!(::core::primitive::u16);
+smooth_trait_for_primitive!(::core::primitive::i32);
+smooth_trait_for_primitive!(::core::primitive::u32);
+smooth_trait_for_primitive!(::core::primitive::i64);
+smooth_trait_for_primitive!(::core::primitive::u64);
+smooth_trait_for_primitive!(::core::primitive::i128);
+smooth_trait_for_primitive!(::core::primitive::u128);
+smooth_trait_for_primitive!(::core::primitive::isize);
+smooth_trait_for_primitive!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Smooth for rug::Integer {
+    type Iter = SmoothIter<rug::Integer>;
+
+    fn smooth_numbers(primes: &[Self]) -> Self::Iter {
+        assert!(
+            primes.iter().all(|p| *p >= 2),
+            "primes must be at least two"
+        );
+        SmoothIter {
+            primes: primes.to_vec(),
+            terms: core::iter::once(rug::Integer::from(1)).collect(),
+            offset: 0,
+            pointers: vec![0; primes.len()],
+            candidates: primes.iter().cloned().map(Some).collect(),
+            next: Some(rug::Integer::from(1)),
+        }
+    }
+
+    fn is_smooth(n: &Self, primes: &[Self]) -> bool {
+        assert!(
+            primes.iter().all(|p| *p >= 2),
+            "primes must be at least two"
+        );
+        if *n <= 0 {
+            return false;
+        }
+        let mut n = n.clone();
+        for p in primes {
+            while n.is_divisible(p) {
+                n /= p;
+            }
+        }
+        n == 1
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for SmoothIter<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let term = self.next.take()?;
+        let next = self.candidates.iter().flatten().min().cloned();
+        if let Some(next) = &next {
+            self.terms.push_back(next.clone());
+            for i in 0..self.primes.len() {
+                if self.candidates[i].as_ref() == Some(next) {
+                    self.pointers[i] += 1;
+                    let factor = &self.terms[self.pointers[i] - self.offset];
+                    self.candidates[i] = Some(rug::Integer::from(factor * &self.primes[i]));
+                }
+            }
+            let first = self
+                .pointers
+                .iter()
+                .copied()
+                .min()
+                .unwrap_or(self.offset + 1);
+            while self.offset < first && self.terms.len() > 1 {
+                self.terms.pop_front();
+                self.offset += 1;
+            }
+        }
+        self.next = next;
+        Some(term)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let max = rug::Integer::from(<$type>::MAX);
+                for primes in [
+                    &[2u8, 3, 5][..],
+                    &[2],
+                    &[3, 7],
+                    &[2, 3, 5, 7, 11, 13],
+                    &[5, 4, 6],
+                ] {
+                    let big: ::std::vec::Vec<rug::Integer> =
+                        primes.iter().map(|&p| rug::Integer::from(p)).collect();
+                    let small: ::std::vec::Vec<$type> =
+                        primes.iter().map(|&p| p as $type).collect();
+                    let expected: ::std::vec::Vec<rug::Integer> =
+                        rug::Integer::smooth_numbers(&big)
+                            .take(5000)
+                            .take_while(|term| *term <= max)
+                            .collect();
+                    let terms: ::std::vec::Vec<rug::Integer> = <$type>::smooth_numbers(&small)
+                        .take(5000)
+                        .map(rug::Integer::from)
+                        .collect();
+                    ::core::assert_eq!(terms, expected);
+                }
+
+                // The powers of two end just before overflow.
+                let mut iter = <$type>::smooth_numbers(&[2]);
+                let count = iter.by_ref().count();
+                ::core::assert_eq!(count as u32, <$type>::MAX.count_ones());
+                ::core::assert_eq!(iter.next(), ::core::option::Option::None);
+            }
+        };
+    }
+
+    test_against_rug!(::core::primitive::i8, i8_smooth);
+    test_against_rug!(::core::primitive::u8, u8_smooth);
+    test_against_rug!(::core::primitive::i16, i16_smooth);
+    test_against_rug!(::core::primitive::u16, u16_smooth);
+    test_against_rug!(::core::primitive::i32, i32_smooth);
+    test_against_rug!(::core::primitive::u32, u32_smooth);
+    test_against_rug!(::core::primitive::i64, i64_smooth);
+    test_against_rug!(::core::primitive::u64, u64_smooth);
+    test_against_rug!(::core::primitive::i128, i128_smooth);
+    test_against_rug!(::core::primitive::u128, u128_smooth);
+    test_against_rug!(::core::primitive::isize, isize_smooth);
+    test_against_rug!(::core::primitive::usize, usize_smooth);
+
+    #[test]
+    fn matches_is_smooth() {
+        for primes in [&[2u32, 3, 5][..], &[2, 7], &[3, 5, 7, 11], &[2, 2, 3]] {
+            let terms: Vec<_> = u32::smooth_numbers(primes)
+                .take_while(|&n| n < 100_000)
+                .collect();
+            let expected: Vec<_> = (1..100_000).filter(|n| u32::is_smooth(n, primes)).collect();
+            assert_eq!(terms, expected);
+        }
+    }
+
+    #[test]
+    fn hamming_numbers() {
+        // There are 1105 regular numbers up to 10⁸ and 100,526 that fit in a
+        // u128, and the millionth is 2⁵⁵ 3⁴⁷ 5⁶⁴.
+        let count = u64::smooth_numbers(&[2, 3, 5])
+            .take_while(|&n| n <= 100_000_000)
+            .count();
+        assert_eq!(count, 1105);
+        let millionth = rug::Integer::smooth_numbers(&[2.into(), 3.into(), 5.into()])
+            .nth(999_999)
+            .unwrap();
+        let expected = rug::Integer::from(rug::Integer::u_pow_u(2, 55))
+            * rug::Integer::from(rug::Integer::u_pow_u(3, 47))
+            * rug::Integer::from(rug::Integer::u_pow_u(5, 64));
+        assert_eq!(millionth, expected);
+        assert_eq!(u128::smooth_numbers(&[2, 3, 5]).count(), 100_526);
+    }
+
+    #[test]
+    #[should_panic(expected = "primes must be at least two")]
+    fn factor_one() {
+        u8::smooth_numbers(&[1]);
+    }
+}
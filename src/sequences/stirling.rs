@@ -0,0 +1,1090 @@
+use core::convert::TryFrom;
+
+use crate::number_theory::primes::{power, Modular, Small};
+use crate::number_theory::Gcd;
+
+pub trait Stirling: Sized {
+    /// Returns an `Option` containing either the unsigned Stirling number of
+    /// the first kind [n, k], the number of permutations of n elements with
+    /// exactly k cycles, or `None` if that would cause overflow or n or k is
+    /// negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Stirling;
+    ///
+    /// assert_eq!(u32::unsigned_stirling1(&5, &2), Some(50));
+    /// assert_eq!(u32::unsigned_stirling1(&5, &6), Some(0));
+    /// assert_eq!(u8::unsigned_stirling1(&6, &2), None);
+    /// assert_eq!(
+    ///     u128::unsigned_stirling1(&1_000_000, &999_998),
+    ///     Some(124_999_583_333_708_333_250_000)
+    /// );
+    /// ```
+    fn unsigned_stirling1(n: &Self, k: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the signed Stirling number of
+    /// the first kind s(n, k) = (−1)<sup>n − k</sup> [n, k], the coefficient
+    /// of x<sup>k</sup> in the falling factorial x(x − 1)⋯(x − n + 1), or
+    /// `None` if that would cause overflow or n or k is negative. For
+    /// unsigned types, every negative value causes overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Stirling;
+    ///
+    /// assert_eq!(i32::stirling1(&5, &2), Some(-50));
+    /// assert_eq!(i32::stirling1(&5, &3), Some(35));
+    /// assert_eq!(u32::stirling1(&5, &2), None);
+    /// ```
+    fn stirling1(n: &Self, k: &Self) -> Option<Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through row
+    /// n of the unsigned Stirling numbers of the first kind from [n, 0] to
+    /// [n, n], stopping early just before overflow. The row is empty if n is
+    /// negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Stirling;
+    ///
+    /// let row: Vec<_> = u32::unsigned_stirling1_row(&5).collect();
+    /// assert_eq!(row, vec![0, 24, 50, 35, 10, 1]);
+    ///
+    /// let row: Vec<_> = u8::unsigned_stirling1_row(&6).collect();
+    /// assert_eq!(row, vec![0, 120]);
+    /// ```
+    fn unsigned_stirling1_row(n: &Self) -> StirlingRow<Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through row
+    /// n of the signed Stirling numbers of the first kind from s(n, 0) to
+    /// s(n, n), stopping early just before overflow. The row is empty if n is
+    /// negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Stirling;
+    ///
+    /// let row: Vec<_> = i32::stirling1_row(&5).collect();
+    /// assert_eq!(row, vec![0, 24, -50, 35, -10, 1]);
+    /// ```
+    fn stirling1_row(n: &Self) -> StirlingRow<Self>;
+
+    /// Returns an `Option` containing either the Stirling number of the second
+    /// kind {n, k}, the number of ways to partition n elements into exactly k
+    /// nonempty blocks, or `None` if that would cause overflow or n or k is
+    /// negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Stirling;
+    ///
+    /// assert_eq!(u32::stirling2(&5, &2), Some(15));
+    /// assert_eq!(u32::stirling2(&5, &6), Some(0));
+    /// assert_eq!(u8::stirling2(&10, &2), None);
+    /// assert_eq!(u64::stirling2(&u64::MAX, &(u64::MAX - 1)), None);
+    /// assert_eq!(u128::stirling2(&1_000_000, &999_999), Some(499_999_500_000));
+    /// ```
+    fn stirling2(n: &Self, k: &Self) -> Option<Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through row
+    /// n of the Stirling numbers of the second kind from {n, 0} to {n, n},
+    /// stopping early just before overflow. The row is empty if n is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Stirling;
+    ///
+    /// let row: Vec<_> = u32::stirling2_row(&5).collect();
+    /// assert_eq!(row, vec![0, 1, 15, 25, 10, 1]);
+    ///
+    /// let row: Vec<_> = i8::stirling2_row(&8).collect();
+    /// assert_eq!(row, vec![0, 1, 127]);
+    /// ```
+    fn stirling2_row(n: &Self) -> StirlingRow<Self>;
+
+    /// Returns an `Option` containing either the Lah number L(n, k), the
+    /// number of ways to partition n elements into exactly k nonempty linearly
+    /// ordered blocks, or `None` if that would cause overflow or n or k is
+    /// negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Stirling;
+    ///
+    /// assert_eq!(u32::lah(&4, &2), Some(36));
+    /// assert_eq!(u32::lah(&4, &5), Some(0));
+    /// assert_eq!(u8::lah(&6, &2), None);
+    /// assert_eq!(u128::lah(&1_000_000, &999_999), Some(999_999_000_000));
+    /// ```
+    fn lah(n: &Self, k: &Self) -> Option<Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through row
+    /// n of the Lah numbers from L(n, 0) to L(n, n), stopping early just
+    /// before overflow. The row is empty if n is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Stirling;
+    ///
+    /// let row: Vec<_> = u32::lah_row(&4).collect();
+    /// assert_eq!(row, vec![0, 24, 36, 12, 1]);
+    ///
+    /// let row: Vec<_> = u8::lah_row(&6).collect();
+    /// assert_eq!(row, vec![0]);
+    /// ```
+    fn lah_row(n: &Self) -> StirlingRow<Self>;
+}
+
+/// The iterator returned by the row methods of `Stirling`.
+#[derive(Clone, Debug)]
+pub struct StirlingRow<T> {
+    entries: std::vec::IntoIter<T>,
+}
+
+impl<T> StirlingRow<T> {
+    fn new(entries: Vec<T>) -> Self {
+        Self {
+            entries: entries.into_iter(),
+        }
+    }
+}
+
+impl<T> Iterator for StirlingRow<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for StirlingRow<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for StirlingRow<T> {}
+
+macro_rules! stirling_trait_for_primitive {
+    ($type:ty) => {
+        impl $crate::sequences::stirling::Stirling for $type {
+            fn unsigned_stirling1(n: &Self, k: &Self) -> ::core::option::Option<Self> {
+                let n = <u128 as ::core::convert::TryFrom<_>>::try_from(*n).ok()?;
+                let k = <u128 as ::core::convert::TryFrom<_>>::try_from(*k).ok()?;
+                let magnitude = $crate::sequences::stirling::unsigned_stirling1(n, k)?;
+                <$type as ::core::convert::TryFrom<u128>>::try_from(magnitude).ok()
+            }
+
+            fn stirling1(n: &Self, k: &Self) -> ::core::option::Option<Self> {
+                let n = <u128 as ::core::convert::TryFrom<_>>::try_from(*n).ok()?;
+                let k = <u128 as ::core::convert::TryFrom<_>>::try_from(*k).ok()?;
+                let magnitude = $crate::sequences::stirling::unsigned_stirling1(n, k)?;
+                $crate::sequences::stirling::with_sign(magnitude, n.wrapping_sub(k) & 1 == 1)
+            }
+
+            fn unsigned_stirling1_row(n: &Self) -> $crate::sequences::stirling::StirlingRow<Self> {
+                $crate::sequences::stirling::primitive_row(*n, |n, k| {
+                    let magnitude = $crate::sequences::stirling::unsigned_stirling1(n, k)?;
+                    <$type as ::core::convert::TryFrom<u128>>::try_from(magnitude).ok()
+                })
+            }
+
+            fn stirling1_row(n: &Self) -> $crate::sequences::stirling::StirlingRow<Self> {
+                $crate::sequences::stirling::primitive_row(*n, |n, k| {
+                    let magnitude = $crate::sequences::stirling::unsigned_stirling1(n, k)?;
+                    $crate::sequences::stirling::with_sign(magnitude, (n - k) & 1 == 1)
+                })
+            }
+
+            fn stirling2(n: &Self, k: &Self) -> ::core::option::Option<Self> {
+                let n = <u128 as ::core::convert::TryFrom<_>>::try_from(*n).ok()?;
+                let k = <u128 as ::core::convert::TryFrom<_>>::try_from(*k).ok()?;
+                let entry = $crate::sequences::stirling::stirling2(n, k)?;
+                <$type as ::core::convert::TryFrom<u128>>::try_from(entry).ok()
+            }
+
+            fn stirling2_row(n: &Self) -> $crate::sequences::stirling::StirlingRow<Self> {
+                $crate::sequences::stirling::primitive_row(*n, |n, k| {
+                    let entry = $crate::sequences::stirling::stirling2(n, k)?;
+                    <$type as ::core::convert::TryFrom<u128>>::try_from(entry).ok()
+                })
+            }
+
+            fn lah(n: &Self, k: &Self) -> ::core::option::Option<Self> {
+                let n = <u128 as ::core::convert::TryFrom<_>>::try_from(*n).ok()?;
+                let k = <u128 as ::core::convert::TryFrom<_>>::try_from(*k).ok()?;
+                let entry = $crate::sequences::stirling::lah(n, k)?;
+                <$type as ::core::convert::TryFrom<u128>>::try_from(entry).ok()
+            }
+
+            fn lah_row(n: &Self) -> $crate::sequences::stirling::StirlingRow<Self> {
+                $crate::sequences::stirling::primitive_row(*n, |n, k| {
+                    let entry = $crate::sequences::stirling::lah(n, k)?;
+                    <$type as ::core::convert::TryFrom<u128>>::try_from(entry).ok()
+                })
+            }
+        }
+    };
+}
+
+stirling_trait_for_primitive!(::core::primitive::i8);
+stirling_trait_for_primitive!(::core::primitive::u8);
+stirling_trait_for_primitive!(::core::primitive::i16);
+stirling_trait_for_primitive!(::core::primitive::u16);
+stirling_trait_for_primitive!(::core::primitive::i32);
+stirling_trait_for_primitive!(::core::primitive::u32);
+stirling_trait_for_primitive!(::core::primitive::i64);
+stirling_trait_for_primitive!(::core::primitive::u64);
+stirling_trait_for_primitive!(::core::primitive::i128);
+stirling_trait_for_primitive!(::core::primitive::u128);
+stirling_trait_for_primitive!(::core::primitive::isize);
+stirling_trait_for_primitive!(::core::primitive::usize);
+
+/// Returns the entries `entry(n, k)` of row n for k from zero to n, stopping
+/// at the first `None`, or an empty row if n is negative.
+fn primitive_row<T, U>(n: T, entry: impl Fn(u128, u128) -> Option<U>) -> StirlingRow<U>
+where
+    u128: TryFrom<T>,
+{
+    let entries = match u128::try_from(n) {
+        Ok(n) => (0..=n)
+            .map(|k| entry(n, k))
+            .take_while(Option::is_some)
+            .flatten()
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    StirlingRow::new(entries)
+}
+
+/// Returns magnitude with the given sign, or `None` if that does not fit in
+/// `T`.
+fn with_sign<T: TryFrom<u128> + TryFrom<i128>>(magnitude: u128, negative: bool) -> Option<T> {
+    if negative && magnitude != 0 {
+        T::try_from(0i128.checked_sub_unsigned(magnitude)?).ok()
+    } else {
+        T::try_from(magnitude).ok()
+    }
+}
+
+/// Returns [n, k], or `None` if that does not fit in a `u128`.
+fn unsigned_stirling1(n: u128, k: u128) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    let m = n - k;
+    if m == 0 {
+        return Some(1);
+    }
+    if k == 0 {
+        return Some(0);
+    }
+    // Of the permutations with n − m cycles, C(n, m + 1) m! have one cycle of
+    // length m + 1 and fix everything else, and 35! already overflows.
+    if m >= 35 {
+        return None;
+    }
+
+    // [n, n − m] = Σ ⟨⟨m, j⟩⟩ C(n + j, 2m) over the second-order Eulerian
+    // numbers ⟨⟨m, j⟩⟩, each of which is positive, so every term is at most
+    // the sum.
+    let mut sum: u128 = 0;
+    for (j, eulerian) in second_order_eulerian_row(m as usize)
+        .into_iter()
+        .enumerate()
+    {
+        let binomial = binomial(n.checked_add(j as u128)?, 2 * m)?;
+        if binomial != 0 {
+            sum = sum.checked_add(eulerian?.checked_mul(binomial)?)?;
+        }
+    }
+    Some(sum)
+}
+
+/// Returns {n, k}, or `None` if that does not fit in a `u128`.
+fn stirling2(n: u128, k: u128) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    let m = n - k;
+    if m == 0 {
+        return Some(1);
+    }
+    if k <= 1 {
+        return Some(k);
+    }
+    // Putting the first k elements in separate blocks and the other n − k in
+    // any of them gives k^(n − k) distinct partitions, so 2^128 overflows.
+    if m >= 128 {
+        return None;
+    }
+
+    if k <= m {
+        // {i, j} = j {i − 1, j} + {i − 1, j − 1}, keeping only columns up to
+        // k. Every entry that reaches {n, k} is at most {n, k}.
+        let k = k as usize;
+        let mut row = vec![Some(0u128); k + 1];
+        row[0] = Some(1);
+        for i in 1..=n as usize {
+            for j in (1..=core::cmp::min(i, k)).rev() {
+                row[j] = row[j]
+                    .and_then(|entry| entry.checked_mul(j as u128))
+                    .and_then(|entry| entry.checked_add(row[j - 1]?));
+            }
+            row[0] = Some(0);
+        }
+        return row[k];
+    }
+
+    // {n, n − m} = Σ ⟨⟨m, j⟩⟩ C(n + m − 1 − j, 2m), again with every term at
+    // most the sum.
+    let mut sum: u128 = 0;
+    for (j, eulerian) in second_order_eulerian_row(m as usize)
+        .into_iter()
+        .enumerate()
+    {
+        let binomial = binomial(n.checked_add(m - 1 - j as u128)?, 2 * m)?;
+        if binomial != 0 {
+            sum = sum.checked_add(eulerian?.checked_mul(binomial)?)?;
+        }
+    }
+    Some(sum)
+}
+
+/// Returns L(n, k), or `None` if that does not fit in a `u128`.
+fn lah(n: u128, k: u128) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    if k == n {
+        return Some(1);
+    }
+    if k == 0 {
+        return Some(0);
+    }
+    // L(n, k) = C(n − 1, k − 1) n! / k!, and n! / k! ≥ (n − k)!, which
+    // overflows from 35! on.
+    if n - k >= 35 {
+        return None;
+    }
+    let mut result = binomial(n - 1, k - 1)?;
+    for factor in k + 1..=n {
+        result = result.checked_mul(factor)?;
+    }
+    Some(result)
+}
+
+/// Returns the second-order Eulerian numbers ⟨⟨m, j⟩⟩ for j from zero to
+/// m − 1, each of which is `None` if it does not fit in a `u128`.
+fn second_order_eulerian_row(m: usize) -> Vec<Option<u128>> {
+    // ⟨⟨i, j⟩⟩ = (j + 1) ⟨⟨i − 1, j⟩⟩ + (2i − 1 − j) ⟨⟨i − 1, j − 1⟩⟩.
+    let mut row = vec![Some(1u128)];
+    for i in 1..m {
+        let mut next = Vec::with_capacity(i + 1);
+        for j in 0..=i {
+            let kept = match row.get(j) {
+                Some(&entry) => entry.and_then(|entry| entry.checked_mul(j as u128 + 1)),
+                None => Some(0),
+            };
+            let carried = match j.checked_sub(1) {
+                Some(j_minus_1) => {
+                    row[j_minus_1].and_then(|entry| entry.checked_mul((2 * i + 1 - j) as u128))
+                }
+                None => Some(0),
+            };
+            next.push(kept.and_then(|kept| kept.checked_add(carried?)));
+        }
+        row = next;
+    }
+    row
+}
+
+/// Returns C(n, k), or `None` if that does not fit in a `u128`.
+fn binomial(n: u128, k: u128) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    let k = core::cmp::min(k, n - k);
+    let mut result: u128 = 1;
+    for i in 1..=k {
+        // result · (n − k + i) / i is computed after cancelling the common
+        // factor of result and i, so it only overflows when C(n − k + i, i)
+        // does.
+        let g = u128::gcd(&result, &i);
+        result = (result / g).checked_mul((n - k + i) / (i / g))?;
+    }
+    Some(result)
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Stirling for rug::Integer {
+    fn unsigned_stirling1(n: &Self, k: &Self) -> Option<Self> {
+        if *n < 0 || *k < 0 {
+            return None;
+        }
+        if k > n {
+            return Some(Self::new());
+        }
+        let (n, k) = (n.to_u32()?, k.to_u32()?);
+        let m = n - k;
+        if m == 0 {
+            return Some(Self::from(1));
+        }
+        if k == 0 {
+            return Some(Self::new());
+        }
+
+        // When k is large next to n − k, the sum over the second-order
+        // Eulerian numbers is quicker than working down the triangle.
+        if m <= k {
+            let sum = rug_second_order_eulerian_row(m)
+                .iter()
+                .zip(0u32..)
+                // n + j can pass u32::MAX, but 2m ≤ n cannot.
+                .map(|(entry, j)| Self::from(u64::from(n) + u64::from(j)).binomial(2 * m) * entry)
+                .sum();
+            return Some(sum);
+        }
+
+        // [i + 1, j] = i [i, j] + [i, j − 1], keeping only columns up to k.
+        let mut row = vec![Self::new(); k as usize + 1];
+        row[0] = Self::from(1);
+        for i in 0..n {
+            for j in (1..=core::cmp::min(i + 1, k) as usize).rev() {
+                row[j] *= i;
+                let previous = row[j - 1].clone();
+                row[j] += previous;
+            }
+            row[0] = Self::new();
+        }
+        row.pop()
+    }
+
+    fn stirling1(n: &Self, k: &Self) -> Option<Self> {
+        let magnitude = Self::unsigned_stirling1(n, k)?;
+        if rug::Integer::from(n - k).is_odd() {
+            Some(-magnitude)
+        } else {
+            Some(magnitude)
+        }
+    }
+
+    fn unsigned_stirling1_row(n: &Self) -> StirlingRow<Self> {
+        StirlingRow::new(rug_stirling1_row(n, false))
+    }
+
+    fn stirling1_row(n: &Self) -> StirlingRow<Self> {
+        StirlingRow::new(rug_stirling1_row(n, true))
+    }
+
+    fn stirling2(n: &Self, k: &Self) -> Option<Self> {
+        if *n < 0 || *k < 0 {
+            return None;
+        }
+        if k > n {
+            return Some(Self::new());
+        }
+        let (n, k) = (n.to_u32()?, k.to_u32()?);
+        let m = n - k;
+        if m == 0 {
+            return Some(Self::from(1));
+        }
+        if k == 0 {
+            return Some(Self::new());
+        }
+
+        if m <= k {
+            let sum = rug_second_order_eulerian_row(m)
+                .iter()
+                .zip(0u32..)
+                .map(|(entry, j)| {
+                    let top = u64::from(n) + u64::from(m) - 1 - u64::from(j);
+                    Self::from(top).binomial(2 * m) * entry
+                })
+                .sum();
+            return Some(sum);
+        }
+
+        // {n, k} = Σ (−1)^(k − j) C(k, j) j^n / k!, which needs only k powers
+        // when n is large.
+        let mut sum = Self::new();
+        let mut binomial = Self::from(1);
+        for j in (1..=k).rev() {
+            let term = Self::from(Self::u_pow_u(j, n)) * &binomial;
+            if (k - j) % 2 == 0 {
+                sum += term;
+            } else {
+                sum -= term;
+            }
+            binomial *= j;
+            binomial /= k - j + 1;
+        }
+        Some(sum / Self::from(Self::factorial(k)))
+    }
+
+    fn stirling2_row(n: &Self) -> StirlingRow<Self> {
+        let n = match n.to_u32() {
+            Some(n) => n,
+            None => return StirlingRow::new(Vec::new()),
+        };
+        // {i + 1, j} = j {i, j} + {i, j − 1}.
+        let mut row = vec![Self::from(1)];
+        for _ in 0..n {
+            row.push(Self::new());
+            for j in (1..row.len()).rev() {
+                row[j] *= j as u32;
+                let previous = row[j - 1].clone();
+                row[j] += previous;
+            }
+            row[0] = Self::new();
+        }
+        StirlingRow::new(row)
+    }
+
+    fn lah(n: &Self, k: &Self) -> Option<Self> {
+        if *n < 0 || *k < 0 {
+            return None;
+        }
+        if k > n {
+            return Some(Self::new());
+        }
+        if *k == 0 {
+            return Some(Self::from(*n == 0));
+        }
+        let (n, k) = (n.to_u32()?, k.to_u32()?);
+        let falling = (k + 1..=n).fold(Self::from(1), |product, factor| product * factor);
+        Some(Self::from(Self::binomial_u(n - 1, k - 1)) * falling)
+    }
+
+    fn lah_row(n: &Self) -> StirlingRow<Self> {
+        let n = match n.to_u32() {
+            Some(n) => n,
+            None => return StirlingRow::new(Vec::new()),
+        };
+        if n == 0 {
+            return StirlingRow::new(vec![Self::from(1)]);
+        }
+        // L(n, 1) = n! and L(n, k + 1) = L(n, k) (n − k) / (k (k + 1)).
+        let mut row = vec![Self::new(), Self::from(Self::factorial(n))];
+        for k in 1..n {
+            let entry = Self::from(&row[k as usize] * (n - k)) / k / (k + 1);
+            row.push(entry);
+        }
+        StirlingRow::new(row)
+    }
+}
+
+/// Returns the second-order Eulerian numbers ⟨⟨m, j⟩⟩ for j from zero to
+/// m − 1.
+#[cfg(any(feature = "rug", doc, test))]
+fn rug_second_order_eulerian_row(m: u32) -> Vec<rug::Integer> {
+    let mut row = vec![rug::Integer::from(1)];
+    for i in 1..m {
+        let mut next = vec![rug::Integer::new(); i as usize + 1];
+        for j in 0..=i {
+            if let Some(entry) = row.get(j as usize) {
+                next[j as usize] += rug::Integer::from(entry * (j + 1));
+            }
+            if j > 0 {
+                next[j as usize] += rug::Integer::from(&row[j as usize - 1] * (2 * i + 1 - j));
+            }
+        }
+        row = next;
+    }
+    row
+}
+
+/// Returns row n of the Stirling numbers of the first kind, which is empty if
+/// n is negative or does not fit in a `u32`.
+#[cfg(any(feature = "rug", doc, test))]
+fn rug_stirling1_row(n: &rug::Integer, signed: bool) -> Vec<rug::Integer> {
+    let n = match n.to_u32() {
+        Some(n) => n,
+        None => return Vec::new(),
+    };
+    // s(i + 1, j) = s(i, j − 1) − i s(i, j), and the unsigned numbers drop
+    // the minus sign.
+    let mut row = vec![rug::Integer::from(1)];
+    for i in 0..n {
+        row.push(rug::Integer::new());
+        for j in (1..row.len()).rev() {
+            row[j] *= i;
+            if signed {
+                row[j] = rug::Integer::from(&row[j - 1] - &row[j]);
+            } else {
+                let previous = row[j - 1].clone();
+                row[j] += previous;
+            }
+        }
+        row[0] = rug::Integer::new();
+    }
+    row
+}
+
+/// Returns the Stirling number of the second kind {n, k} modulo the prime p.
+///
+/// Since (1 − x)(1 − 2x)⋯(1 − (p − 1)x) ≡ 1 − x<sup>p − 1</sup>, writing
+/// k = qp + r and n − k = a(p − 1) + b gives
+/// {n, k} ≡ C(q + a, q) {b + r, r} when r is positive, and the remaining
+/// Stirling number is found from {b + r, r} = Σ (−1)<sup>r − j</sup> C(r, j)
+/// j<sup>b + r</sup> / r!. This takes O(min(k, p) log p) time and O(1) space
+/// regardless of how large n is.
+///
+/// # Panics
+///
+/// Panics if p is not prime.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::stirling::stirling2_mod;
+///
+/// assert_eq!(stirling2_mod(5, 2, 7), 1);
+/// assert_eq!(stirling2_mod(1_000_000_000_000, 3, 1_000_000_007), 948_681_649);
+/// ```
+pub fn stirling2_mod(n: u64, k: u64, p: u64) -> u64 {
+    assert!(
+        <u64 as crate::number_theory::Prime>::is_prime(&p),
+        "p must be prime"
+    );
+    if k > n {
+        return 0;
+    }
+    if k == 0 {
+        return u64::from(n == 0);
+    }
+
+    let (q, r) = (k / p, k % p);
+    let (a, b) = ((n - k) / (p - 1), (n - k) % (p - 1));
+    if r == 0 {
+        // Only the powers of 1 − x^(p − 1) are left.
+        return if b == 0 {
+            binomial_mod(q - 1 + a, q - 1, p)
+        } else {
+            0
+        };
+    }
+
+    let modular = Small { n: p };
+    let inverse = |x| power(&modular, x, (p - 2) as u128);
+    let mut sum = 0;
+    let mut binomial = 1;
+    let mut factorial = 1;
+    for j in 1..=r {
+        // This takes binomial from C(r, j − 1) to C(r, j).
+        binomial = modular.mul(modular.mul(binomial, r - j + 1), inverse(j));
+        factorial = modular.mul(factorial, j);
+        let power = power(&modular, j, b as u128 + r as u128);
+        let term = modular.mul(binomial, power);
+        sum = if (r - j) % 2 == 0 {
+            modular.add(sum, term)
+        } else {
+            modular.sub(sum, term)
+        };
+    }
+    let stirling = modular.mul(sum, inverse(factorial));
+    modular.mul(binomial_mod(q + a, q, p), stirling)
+}
+
+/// Returns C(n, k) modulo the prime p by Lucas's theorem.
+fn binomial_mod(mut n: u64, mut k: u64, p: u64) -> u64 {
+    let modular = Small { n: p };
+    let mut result = 1 % p;
+    while k != 0 {
+        let (n_digit, k_digit) = (n % p, k % p);
+        if k_digit > n_digit {
+            return 0;
+        }
+        let k_digit = core::cmp::min(k_digit, n_digit - k_digit);
+        let mut numerator = 1;
+        let mut denominator = 1;
+        for i in 0..k_digit {
+            numerator = modular.mul(numerator, n_digit - i);
+            denominator = modular.mul(denominator, i + 1);
+        }
+        result = modular.mul(
+            modular.mul(result, numerator),
+            power(&modular, denominator, (p - 2) as u128),
+        );
+        n /= p;
+        k /= p;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns rows 0 through n of the unsigned Stirling numbers of the first
+    /// kind from their recurrence.
+    fn triangle(n: usize) -> Vec<Vec<rug::Integer>> {
+        let mut rows = vec![vec![rug::Integer::from(1)]];
+        for i in 0..n {
+            let previous = &rows[i];
+            let row = (0..=i + 1)
+                .map(|j| {
+                    let mut entry = rug::Integer::new();
+                    if j <= i {
+                        entry += rug::Integer::from(&previous[j] * i as u32);
+                    }
+                    if j > 0 {
+                        entry += &previous[j - 1];
+                    }
+                    entry
+                })
+                .collect();
+            rows.push(row);
+        }
+        rows
+    }
+
+    macro_rules! test_against_triangle {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let rows = triangle(70);
+                for (n, row) in rows.iter().enumerate() {
+                    let mut expected_row = ::std::vec::Vec::new();
+                    let mut expected_signed_row = ::std::vec::Vec::new();
+                    let mut unsigned_ended = false;
+                    let mut signed_ended = false;
+                    for (k, entry) in row.iter().enumerate() {
+                        let signed = if (n - k) % 2 == 1 {
+                            rug::Integer::from(-entry)
+                        } else {
+                            entry.clone()
+                        };
+                        let (n, k) = (n as $type, k as $type);
+                        let expected = entry.to_u128().and_then(|entry| {
+                            <$type as ::core::convert::TryFrom<u128>>::try_from(entry).ok()
+                        });
+                        let expected_signed = if signed < 0 {
+                            signed.to_i128().and_then(|entry| {
+                                <$type as ::core::convert::TryFrom<i128>>::try_from(entry).ok()
+                            })
+                        } else {
+                            expected
+                        };
+                        assert_eq!(<$type>::unsigned_stirling1(&n, &k), expected);
+                        assert_eq!(<$type>::stirling1(&n, &k), expected_signed);
+                        unsigned_ended |= expected.is_none();
+                        signed_ended |= expected_signed.is_none();
+                        if !unsigned_ended {
+                            expected_row.push(expected.unwrap());
+                        }
+                        if !signed_ended {
+                            expected_signed_row.push(expected_signed.unwrap());
+                        }
+                    }
+                    let n = n as $type;
+                    assert_eq!(<$type>::unsigned_stirling1(&n, &(n + 1)), Some(0));
+                    assert!(<$type>::unsigned_stirling1_row(&n).eq(expected_row));
+                    assert!(<$type>::stirling1_row(&n).eq(expected_signed_row));
+                }
+            }
+        };
+    }
+
+    test_against_triangle!(::core::primitive::i8, i8_stirling1);
+    test_against_triangle!(::core::primitive::u8, u8_stirling1);
+    test_against_triangle!(::core::primitive::i16, i16_stirling1);
+    test_against_triangle!(::core::primitive::u16, u16_stirling1);
+    test_against_triangle!(::core::primitive::i32, i32_stirling1);
+    test_against_triangle!(::core::primitive::u32, u32_stirling1);
+    test_against_triangle!(::core::primitive::i64, i64_stirling1);
+    test_against_triangle!(::core::primitive::u64, u64_stirling1);
+    test_against_triangle!(::core::primitive::i128, i128_stirling1);
+    test_against_triangle!(::core::primitive::u128, u128_stirling1);
+    test_against_triangle!(::core::primitive::isize, isize_stirling1);
+    test_against_triangle!(::core::primitive::usize, usize_stirling1);
+
+    #[test]
+    fn rug_matches_triangle() {
+        let rows = triangle(120);
+        for (n, row) in rows.iter().enumerate() {
+            let n = rug::Integer::from(n);
+            assert!(rug::Integer::unsigned_stirling1_row(&n).eq(row.iter().cloned()));
+            for (k, entry) in row.iter().enumerate() {
+                let k = rug::Integer::from(k);
+                assert_eq!(
+                    rug::Integer::unsigned_stirling1(&n, &k).as_ref(),
+                    Some(entry)
+                );
+            }
+            let signed: Vec<_> = rug::Integer::stirling1_row(&n).collect();
+            for (k, entry) in signed.iter().enumerate() {
+                let k = rug::Integer::from(k);
+                assert_eq!(rug::Integer::stirling1(&n, &k).as_ref(), Some(entry));
+                assert_eq!(entry.clone().abs(), row[k.to_usize().unwrap()]);
+            }
+        }
+        assert_eq!(rug::Integer::stirling1(&(-1).into(), &0.into()), None);
+        assert_eq!(rug::Integer::unsigned_stirling1_row(&(-1).into()).len(), 0);
+    }
+
+    #[test]
+    fn long_rows() {
+        let n = 1u128 << 60;
+        assert_eq!(
+            u128::unsigned_stirling1(&n, &(n - 1)),
+            Some(n * (n - 1) / 2)
+        );
+        assert_eq!(u128::unsigned_stirling1(&n, &(n - 2)), None);
+        assert_eq!(u128::unsigned_stirling1(&u128::MAX, &u128::MAX), Some(1));
+        assert_eq!(i64::stirling1(&i64::MAX, &(i64::MAX - 1)), None);
+        assert_eq!(i8::stirling1(&-1, &0), None);
+        assert!(u64::unsigned_stirling1_row(&u64::MAX).eq([0]));
+        assert_eq!(i8::stirling1_row(&-1).len(), 0);
+
+        let n = rug::Integer::from(100_000);
+        let k = rug::Integer::from(99_990);
+        assert_eq!(
+            rug::Integer::unsigned_stirling1(&n, &k)
+                .unwrap()
+                .significant_bits(),
+            301
+        );
+
+        // [n, n − 2] = (3n − 1) C(n, 3) / 4.
+        let n = rug::Integer::from(u32::MAX);
+        let expected = (rug::Integer::from(3u8) * &n - 1u8) * n.clone().binomial(3) / 4u8;
+        assert_eq!(
+            rug::Integer::unsigned_stirling1(&n, &(n.clone() - 2u8)),
+            Some(expected)
+        );
+    }
+
+    /// Returns rows 0 through n of the Stirling numbers of the second kind
+    /// from their recurrence.
+    fn second_kind_triangle(n: usize) -> Vec<Vec<rug::Integer>> {
+        let mut rows = vec![vec![rug::Integer::from(1)]];
+        for i in 0..n {
+            let previous = &rows[i];
+            let row = (0..=i + 1)
+                .map(|j| {
+                    let mut entry = rug::Integer::new();
+                    if j <= i {
+                        entry += rug::Integer::from(&previous[j] * j as u32);
+                    }
+                    if j > 0 {
+                        entry += &previous[j - 1];
+                    }
+                    entry
+                })
+                .collect();
+            rows.push(row);
+        }
+        rows
+    }
+
+    macro_rules! test_second_kind_against_triangle {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let rows = second_kind_triangle(120);
+                for (n, row) in rows.iter().enumerate() {
+                    let expected: ::std::vec::Vec<_> = row
+                        .iter()
+                        .map(|entry| {
+                            entry.to_u128().and_then(|entry| {
+                                <$type as ::core::convert::TryFrom<u128>>::try_from(entry).ok()
+                            })
+                        })
+                        .collect();
+                    let n = n as $type;
+                    for (k, &entry) in expected.iter().enumerate() {
+                        assert_eq!(<$type>::stirling2(&n, &(k as $type)), entry);
+                    }
+                    assert_eq!(<$type>::stirling2(&n, &(n + 1)), Some(0));
+                    assert!(<$type>::stirling2_row(&n)
+                        .eq(expected.into_iter().take_while(Option::is_some).flatten()));
+                }
+
+                let max = <$type>::MAX;
+                assert_eq!(<$type>::stirling2(&max, &max), Some(1));
+                assert_eq!(<$type>::stirling2(&max, &1), Some(1));
+                assert_eq!(<$type>::stirling2(&max, &2), None);
+                assert!(<$type>::stirling2_row(&max).eq([0, 1]));
+            }
+        };
+    }
+
+    test_second_kind_against_triangle!(::core::primitive::i8, i8_stirling2);
+    test_second_kind_against_triangle!(::core::primitive::u8, u8_stirling2);
+    test_second_kind_against_triangle!(::core::primitive::i16, i16_stirling2);
+    test_second_kind_against_triangle!(::core::primitive::u16, u16_stirling2);
+    test_second_kind_against_triangle!(::core::primitive::i32, i32_stirling2);
+    test_second_kind_against_triangle!(::core::primitive::u32, u32_stirling2);
+    test_second_kind_against_triangle!(::core::primitive::i64, i64_stirling2);
+    test_second_kind_against_triangle!(::core::primitive::u64, u64_stirling2);
+    test_second_kind_against_triangle!(::core::primitive::i128, i128_stirling2);
+    test_second_kind_against_triangle!(::core::primitive::u128, u128_stirling2);
+    test_second_kind_against_triangle!(::core::primitive::isize, isize_stirling2);
+    test_second_kind_against_triangle!(::core::primitive::usize, usize_stirling2);
+
+    #[test]
+    fn rug_second_kind_matches_triangle() {
+        let rows = second_kind_triangle(120);
+        for (n, row) in rows.iter().enumerate() {
+            let n = rug::Integer::from(n);
+            assert!(rug::Integer::stirling2_row(&n).eq(row.iter().cloned()));
+            for (k, entry) in row.iter().enumerate() {
+                let k = rug::Integer::from(k);
+                assert_eq!(rug::Integer::stirling2(&n, &k).as_ref(), Some(entry));
+            }
+        }
+        assert_eq!(rug::Integer::stirling2(&(-1).into(), &0.into()), None);
+        assert_eq!(rug::Integer::stirling2_row(&(-1).into()).len(), 0);
+
+        for n in 120..=260u32 {
+            for k in 0..=n {
+                let expected =
+                    rug::Integer::stirling2(&n.into(), &k.into()).and_then(|entry| entry.to_u128());
+                assert_eq!(u128::stirling2(&n.into(), &k.into()), expected);
+            }
+        }
+
+        // {n, 2} = 2^(n − 1) − 1.
+        let n = rug::Integer::from(10_000);
+        let expected = (rug::Integer::from(1) << 9_999u32) - 1u8;
+        assert_eq!(rug::Integer::stirling2(&n, &2.into()), Some(expected));
+
+        // {n, n − 1} = C(n, 2), and {n, n − 2} = C(n, 3) + 3 C(n, 4).
+        let n = rug::Integer::from(u32::MAX);
+        assert_eq!(
+            rug::Integer::stirling2(&n, &(n.clone() - 1u8)),
+            Some(n.clone().binomial(2))
+        );
+        let expected = n.clone().binomial(3) + n.clone().binomial(4) * 3u8;
+        assert_eq!(
+            rug::Integer::stirling2(&n, &(n.clone() - 2u8)),
+            Some(expected)
+        );
+        assert_eq!(u128::stirling2(&u128::MAX, &(u128::MAX - 5)), None);
+    }
+
+    /// Returns rows 0 through n of the Lah numbers from their recurrence.
+    fn lah_triangle(n: usize) -> Vec<Vec<rug::Integer>> {
+        let mut rows = vec![vec![rug::Integer::from(1)]];
+        for i in 0..n {
+            let previous = &rows[i];
+            let row = (0..=i + 1)
+                .map(|j| {
+                    let mut entry = rug::Integer::new();
+                    if j <= i {
+                        entry += rug::Integer::from(&previous[j] * (i + j) as u32);
+                    }
+                    if j > 0 {
+                        entry += &previous[j - 1];
+                    }
+                    entry
+                })
+                .collect();
+            rows.push(row);
+        }
+        rows
+    }
+
+    macro_rules! test_lah_against_triangle {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let rows = lah_triangle(120);
+                for (n, row) in rows.iter().enumerate() {
+                    let expected: ::std::vec::Vec<_> = row
+                        .iter()
+                        .map(|entry| {
+                            entry.to_u128().and_then(|entry| {
+                                <$type as ::core::convert::TryFrom<u128>>::try_from(entry).ok()
+                            })
+                        })
+                        .collect();
+                    let n = n as $type;
+                    for (k, &entry) in expected.iter().enumerate() {
+                        assert_eq!(<$type>::lah(&n, &(k as $type)), entry);
+                    }
+                    assert_eq!(<$type>::lah(&n, &(n + 1)), Some(0));
+                    assert!(<$type>::lah_row(&n)
+                        .eq(expected.into_iter().take_while(Option::is_some).flatten()));
+                }
+
+                let max = <$type>::MAX;
+                assert_eq!(<$type>::lah(&max, &max), Some(1));
+                assert_eq!(<$type>::lah(&max, &(max - 1)), None);
+                assert_eq!(<$type>::lah(&1, &0), Some(0));
+                assert!(<$type>::lah_row(&max).eq([0]));
+            }
+        };
+    }
+
+    test_lah_against_triangle!(::core::primitive::i8, i8_lah);
+    test_lah_against_triangle!(::core::primitive::u8, u8_lah);
+    test_lah_against_triangle!(::core::primitive::i16, i16_lah);
+    test_lah_against_triangle!(::core::primitive::u16, u16_lah);
+    test_lah_against_triangle!(::core::primitive::i32, i32_lah);
+    test_lah_against_triangle!(::core::primitive::u32, u32_lah);
+    test_lah_against_triangle!(::core::primitive::i64, i64_lah);
+    test_lah_against_triangle!(::core::primitive::u64, u64_lah);
+    test_lah_against_triangle!(::core::primitive::i128, i128_lah);
+    test_lah_against_triangle!(::core::primitive::u128, u128_lah);
+    test_lah_against_triangle!(::core::primitive::isize, isize_lah);
+    test_lah_against_triangle!(::core::primitive::usize, usize_lah);
+
+    #[test]
+    fn rug_lah_matches_triangle() {
+        let rows = lah_triangle(120);
+        for (n, row) in rows.iter().enumerate() {
+            let n = rug::Integer::from(n);
+            assert!(rug::Integer::lah_row(&n).eq(row.iter().cloned()));
+            for (k, entry) in row.iter().enumerate() {
+                let k = rug::Integer::from(k);
+                assert_eq!(rug::Integer::lah(&n, &k).as_ref(), Some(entry));
+            }
+        }
+        assert_eq!(rug::Integer::lah(&(-1).into(), &0.into()), None);
+        assert_eq!(rug::Integer::lah_row(&(-1).into()).len(), 0);
+    }
+
+    #[test]
+    fn second_kind_mod_primes() {
+        let rows = second_kind_triangle(150);
+        for p in [2, 3, 5, 7, 11, 13, 131, 1_000_000_007] {
+            for (n, row) in rows.iter().enumerate() {
+                for (k, entry) in row.iter().enumerate() {
+                    let expected = rug::Integer::from(entry % p).to_u64().unwrap();
+                    assert_eq!(stirling2_mod(n as u64, k as u64, p), expected);
+                }
+                assert_eq!(stirling2_mod(n as u64, n as u64 + 1, p), 0);
+            }
+        }
+
+        let p = 18_446_744_073_709_551_557;
+        let modulus = rug::Integer::from(p);
+        for (n, k) in [(1_000, 30), (1_000, 990), (400, 200)] {
+            let big = rug::Integer::stirling2(&n.into(), &k.into()).unwrap();
+            let expected = (big % &modulus).to_u64().unwrap();
+            assert_eq!(stirling2_mod(n, k, p), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "p must be prime")]
+    fn second_kind_mod_composite() {
+        stirling2_mod(10, 3, 4);
+    }
+}
@@ -0,0 +1,12 @@
+pub trait StreamingSequence {
+    /// The type of the terms lent out by `next_ref`.
+    type Item: ?Sized;
+
+    /// Advances the sequence and returns an `Option` containing either a
+    /// reference to its next term or `None` if the sequence has ended.
+    ///
+    /// Unlike `Iterator::next`, this lends out a term that the sequence still
+    /// owns, so big terms can be inspected without being copied, and the
+    /// sequence can update them in place when it advances.
+    fn next_ref(&mut self) -> Option<&Self::Item>;
+}
@@ -0,0 +1,335 @@
+pub trait Triangular: Sized {
+    /// The exact type of the `Iterator<Item = Self>` returned by
+    /// `triangular_iter`.
+    type Iter: Iterator<Item = Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through all
+    /// triangular numbers T(n) = n(n + 1) / 2 from zero until just before
+    /// overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Triangular;
+    ///
+    /// let terms: Vec<_> = i8::triangular_iter().collect();
+    /// assert_eq!(terms, vec![0, 1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 66, 78, 91, 105, 120]);
+    /// assert_eq!(u8::triangular_iter().last(), Some(253));
+    /// ```
+    fn triangular_iter() -> Self::Iter;
+
+    /// Returns an `Option` containing either the n<sup>th</sup> triangular
+    /// number, n(n + 1) / 2, or `None` if that would cause overflow or n is
+    /// negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Triangular;
+    ///
+    /// assert_eq!(u32::nth_triangular(&100), Some(5050));
+    /// assert_eq!(u8::nth_triangular(&22), Some(253));
+    /// assert_eq!(u8::nth_triangular(&23), None);
+    /// assert_eq!(i8::nth_triangular(&-1), None);
+    /// assert_eq!(u128::nth_triangular(&(1 << 64)), Some((1 << 127) + (1 << 63)));
+    /// ```
+    fn nth_triangular(n: &Self) -> Option<Self>;
+
+    /// Returns whether n is a triangular number, which it is exactly when
+    /// 8n + 1 is a perfect square. Negative numbers are never triangular.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Triangular;
+    ///
+    /// assert!(u32::is_triangular(&5050));
+    /// assert!(!u32::is_triangular(&5051));
+    /// assert!(u8::is_triangular(&0));
+    /// assert!(!i8::is_triangular(&-1));
+    /// ```
+    fn is_triangular(n: &Self) -> bool;
+
+    /// Returns an `Option` containing either the index k ≥ 0 for which
+    /// T(k) = value or `None` if value is not a triangular number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Triangular;
+    ///
+    /// assert_eq!(u32::triangular_index(&5050), Some(100));
+    /// assert_eq!(u32::triangular_index(&5051), None);
+    /// assert_eq!(u128::triangular_index(&u128::nth_triangular(&u64::MAX.into()).unwrap()), Some(u64::MAX.into()));
+    /// assert_eq!(i8::triangular_index(&-1), None);
+    /// ```
+    fn triangular_index(value: &Self) -> Option<Self>;
+}
+
+/// The iterator returned by `Triangular::triangular_iter`.
+///
+/// For `rug::Integer`, it also implements `StreamingSequence`, whose
+/// `next_ref` method lends out each term instead of cloning it.
+#[derive(Clone, Debug)]
+pub struct TriangularIter<T> {
+    index: T,
+    term: Option<T>,
+}
+
+macro_rules! triangular_trait_for_primitive {
+    ($type:ty) => {
+        impl $crate::sequences::triangular::Triangular for $type {
+            type Iter = $crate::sequences::triangular::TriangularIter<$type>;
+
+            fn triangular_iter() -> Self::Iter {
+                $crate::sequences::triangular::TriangularIter {
+                    index: 0,
+                    term: ::core::option::Option::Some(0),
+                }
+            }
+
+            fn nth_triangular(n: &Self) -> ::core::option::Option<Self> {
+                let n = <u128 as ::core::convert::TryFrom<$type>>::try_from(*n).ok()?;
+                let term = $crate::sequences::triangular::triangular(n)?;
+                <$type as ::core::convert::TryFrom<u128>>::try_from(term).ok()
+            }
+
+            fn is_triangular(n: &Self) -> bool {
+                Self::triangular_index(n).is_some()
+            }
+
+            fn triangular_index(value: &Self) -> ::core::option::Option<Self> {
+                let value = <u128 as ::core::convert::TryFrom<$type>>::try_from(*value).ok()?;
+                let index = $crate::sequences::triangular::triangular_root(value);
+                if $crate::sequences::triangular::triangular(index)
+                    == ::core::option::Option::Some(value)
+                {
+                    <$type as ::core::convert::TryFrom<u128>>::try_from(index).ok()
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+        }
+
+        impl ::core::iter::Iterator for $crate::sequences::triangular::TriangularIter<$type> {
+            type Item = $type;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                let term = self.term?;
+                self.term = self.index.checked_add(1).and_then(|index| {
+                    self.index = index;
+                    term.checked_add(index)
+                });
+                ::core::option::Option::Some(term)
+            }
+        }
+
+        impl ::core::iter::FusedIterator for $crate::sequences::triangular::TriangularIter<$type> {}
+    };
+}
+
+triangular_trait_for_primitive!(::core::primitive::i8);
+triangular_trait_for_primitive!(::core::primitive::u8);
+triangular_trait_for_primitive!(::core::primitive::i16);
+triangular_trait_for_primitive!(::core::primitive::u16);
+triangular_trait_for_primitive!(::core::primitive::i32);
+triangular_trait_for_primitive!(::core::primitive::u32);
+triangular_trait_for_primitive!(::core::primitive::i64);
+triangular_trait_for_primitive!(::core::primitive::u64);
+triangular_trait_for_primitive!(::core::primitive::i128);
+triangular_trait_for_primitive!(::core::primitive::u128);
+triangular_trait_for_primitive!(::core::primitive::isize);
+triangular_trait_for_primitive!(::core::primitive::usize);
+
+/// Returns T(n), or `None` if that does not fit in a `u128`.
+pub(crate) fn triangular(n: u128) -> Option<u128> {
+    // Halving whichever of n and n + 1 is even first keeps the product from
+    // overflowing unless the result does.
+    let next = n.checked_add(1)?;
+    if n.is_multiple_of(2) {
+        (n / 2).checked_mul(next)
+    } else {
+        n.checked_mul(next / 2)
+    }
+}
+
+/// Returns the largest k with T(k) ≤ value.
+fn triangular_root(value: u128) -> u128 {
+    // The answer is ⌊(√(8 value + 1) − 1) / 2⌋, which lies within a few of
+    // 2 ⌊√(value / 2)⌋ ≈ √(2 value), and the latter cannot overflow.
+    let mut root = 2 * (value / 2).isqrt();
+    while triangular(root + 1).is_some_and(|term| term <= value) {
+        root += 1;
+    }
+    while triangular(root).is_none_or(|term| term > value) {
+        root -= 1;
+    }
+    root
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Triangular for rug::Integer {
+    type Iter = TriangularIter<rug::Integer>;
+
+    fn triangular_iter() -> Self::Iter {
+        // This holds the last term handed out, so it starts one before the
+        // first, with T(−1) = 0.
+        TriangularIter {
+            index: rug::Integer::from(-1),
+            term: Some(rug::Integer::new()),
+        }
+    }
+
+    fn nth_triangular(n: &Self) -> Option<Self> {
+        if *n < 0 {
+            return None;
+        }
+        Some(n * rug::Integer::from(n + 1u8) / 2u8)
+    }
+
+    fn is_triangular(n: &Self) -> bool {
+        *n >= 0 && (rug::Integer::from(n * 8u8) + 1u8).is_perfect_square()
+    }
+
+    fn triangular_index(value: &Self) -> Option<Self> {
+        if Self::is_triangular(value) {
+            let root = (rug::Integer::from(value * 8u8) + 1u8).sqrt();
+            Some((root - 1u8) / 2u8)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl crate::sequences::StreamingSequence for TriangularIter<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next_ref(&mut self) -> Option<&Self::Item> {
+        let term = self.term.as_mut()?;
+        self.index += 1u8;
+        *term += &self.index;
+        Some(term)
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for TriangularIter<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        crate::sequences::StreamingSequence::next_ref(self).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let max = rug::Integer::from(<$type>::MAX);
+                let expected: ::std::vec::Vec<rug::Integer> = rug::Integer::triangular_iter()
+                    .take(3000)
+                    .take_while(|term| *term <= max)
+                    .collect();
+                let terms: ::std::vec::Vec<rug::Integer> = <$type>::triangular_iter()
+                    .take(3000)
+                    .map(rug::Integer::from)
+                    .collect();
+                ::core::assert_eq!(terms, expected);
+
+                for n in 0..3000u16 {
+                    let big = rug::Integer::nth_triangular(&n.into()).unwrap();
+                    let expected = if big <= max {
+                        ::core::option::Option::Some(big.to_i128().unwrap() as $type)
+                    } else {
+                        ::core::option::Option::None
+                    };
+                    let n = match <$type as ::core::convert::TryFrom<u16>>::try_from(n) {
+                        ::core::result::Result::Ok(n) => n,
+                        ::core::result::Result::Err(_) => break,
+                    };
+                    ::core::assert_eq!(<$type>::nth_triangular(&n), expected);
+                    ::core::assert_eq!(
+                        <$type>::triangular_index(&n),
+                        rug::Integer::triangular_index(&n.into())
+                            .map(|index| index.to_i128().unwrap() as $type)
+                    );
+                    ::core::assert_eq!(
+                        <$type>::is_triangular(&n),
+                        rug::Integer::is_triangular(&n.into())
+                    );
+                }
+
+                // The last term before overflow, and the values around it.
+                let index = triangular_root(<$type>::MAX as u128) as $type;
+                let last = <$type>::nth_triangular(&index).unwrap();
+                ::core::assert_eq!(
+                    <$type>::triangular_index(&last),
+                    ::core::option::Option::Some(index)
+                );
+                ::core::assert_eq!(
+                    <$type>::nth_triangular(&index),
+                    ::core::option::Option::Some(last)
+                );
+                ::core::assert_eq!(
+                    <$type>::nth_triangular(&(index + 1)),
+                    ::core::option::Option::None
+                );
+                for value in [last - 1, last + 1, <$type>::MAX] {
+                    ::core::assert_eq!(
+                        <$type>::is_triangular(&value),
+                        rug::Integer::is_triangular(&value.into())
+                    );
+                }
+                ::core::assert_eq!(
+                    <$type>::nth_triangular(&<$type>::MAX),
+                    ::core::option::Option::None
+                );
+            }
+        };
+    }
+
+    test_against_rug!(::core::primitive::i8, i8_triangular);
+    test_against_rug!(::core::primitive::u8, u8_triangular);
+    test_against_rug!(::core::primitive::i16, i16_triangular);
+    test_against_rug!(::core::primitive::u16, u16_triangular);
+    test_against_rug!(::core::primitive::i32, i32_triangular);
+    test_against_rug!(::core::primitive::u32, u32_triangular);
+    test_against_rug!(::core::primitive::i64, i64_triangular);
+    test_against_rug!(::core::primitive::u64, u64_triangular);
+    test_against_rug!(::core::primitive::i128, i128_triangular);
+    test_against_rug!(::core::primitive::u128, u128_triangular);
+    test_against_rug!(::core::primitive::isize, isize_triangular);
+    test_against_rug!(::core::primitive::usize, usize_triangular);
+
+    #[test]
+    fn negative_values() {
+        assert_eq!(i32::nth_triangular(&-5), None);
+        assert!(!i32::is_triangular(&-3));
+        assert_eq!(i32::triangular_index(&-3), None);
+        assert_eq!(rug::Integer::nth_triangular(&(-5).into()), None);
+        assert!(!rug::Integer::is_triangular(&(-3).into()));
+    }
+
+    #[test]
+    fn rug_large_values() {
+        let n = rug::Integer::from(rug::Integer::u_pow_u(10, 40));
+        let term = rug::Integer::nth_triangular(&n).unwrap();
+        assert_eq!(rug::Integer::triangular_index(&term), Some(n));
+        assert!(!rug::Integer::is_triangular(&(term + 1u8)));
+
+        use crate::sequences::StreamingSequence;
+        let mut streaming = rug::Integer::triangular_iter();
+        for n in 0..300u16 {
+            let expected = rug::Integer::nth_triangular(&n.into());
+            assert_eq!(streaming.next_ref(), expected.as_ref());
+        }
+    }
+}
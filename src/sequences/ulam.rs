@@ -0,0 +1,225 @@
+/// Returns an `Iterator<Item = u64>` implementation that goes through the
+/// Ulam numbers 1, 2, 3, 4, 6, 8, 11, 13, 16, …, where each term after the
+/// first two is the smallest number larger than the last that is the sum of
+/// two distinct earlier terms in exactly one way.
+///
+/// This is `ulam_numbers_with_seed(1, 2)`.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::ulam::ulam_numbers;
+///
+/// let terms: Vec<_> = ulam_numbers().take(15).collect();
+/// assert_eq!(terms, vec![1, 2, 3, 4, 6, 8, 11, 13, 16, 18, 26, 28, 36, 38, 47]);
+/// ```
+pub fn ulam_numbers() -> UlamNumbers {
+    ulam_numbers_with_seed(1, 2)
+}
+
+/// Returns an `Iterator<Item = u64>` implementation that goes through the
+/// Ulam sequence starting with a and b, where each later term is the
+/// smallest number larger than the last that is the sum of two distinct
+/// earlier terms in exactly one way.
+///
+/// The sequence never ends, as the sum of the last two terms has only that
+/// one representation. The iterator keeps bitsets of the terms and of the
+/// numbers with one and with several representations up to twice the last
+/// term, and folds each new term u in by shifting the bitset of the earlier
+/// terms up by u, so each step takes time linear in the last term and is done
+/// 64 numbers at a time.
+///
+/// Those bitsets take about 3u / 4 bytes, so the iterator only goes through
+/// the terms below 2<sup>32</sup> and then returns `None`. Starting from
+/// small seeds, it would take far too long to get anywhere near that.
+///
+/// # Panics
+///
+/// Panics unless 0 < a < b < 2<sup>32</sup>.
+///
+/// # Examples
+///
+/// ```
+/// use mathematical::sequences::ulam::ulam_numbers_with_seed;
+///
+/// let terms: Vec<_> = ulam_numbers_with_seed(1, 3).take(12).collect();
+/// assert_eq!(terms, vec![1, 3, 4, 5, 6, 8, 10, 12, 17, 21, 23, 28]);
+///
+/// let terms: Vec<_> = ulam_numbers_with_seed(2, 5).take(12).collect();
+/// assert_eq!(terms, vec![2, 5, 7, 9, 11, 12, 13, 15, 19, 23, 27, 29]);
+/// ```
+pub fn ulam_numbers_with_seed(a: u64, b: u64) -> UlamNumbers {
+    assert!(0 < a && a < b, "seed must satisfy 0 < a < b");
+    assert!(b < LIMIT, "seed must be below 2^32");
+    UlamNumbers {
+        seed: (a, b),
+        count: 0,
+        done: false,
+        last: 0,
+        terms: Vec::new(),
+        once: Vec::new(),
+        many: Vec::new(),
+    }
+}
+
+/// Terms stop below this, which keeps the bitsets to about 3 GiB.
+const LIMIT: u64 = 1 << 32;
+
+/// The iterator returned by `ulam_numbers` and `ulam_numbers_with_seed`.
+#[derive(Clone, Debug)]
+pub struct UlamNumbers {
+    seed: (u64, u64),
+    // The number of terms produced, up to two.
+    count: u8,
+    // Whether the next term would be past the limit.
+    done: bool,
+    last: u64,
+    // Bitsets of the terms, of the sums of two distinct terms with exactly
+    // one representation, and of those with more than one.
+    terms: Vec<u64>,
+    once: Vec<u64>,
+    many: Vec<u64>,
+}
+
+impl UlamNumbers {
+    /// Records u as a term along with its sums with the earlier terms.
+    fn add(&mut self, u: u64) {
+        let u = <usize as core::convert::TryFrom<u64>>::try_from(u)
+            .expect("Ulam number too large for the bitsets");
+        let words = (2 * u) / 64 + 2;
+        self.terms.resize(words, 0);
+        self.once.resize(words, 0);
+        self.many.resize(words, 0);
+
+        // Add the earlier terms shifted up by u to the representation counts
+        // a word at a time.
+        let (offset, shift) = (u / 64, u % 64);
+        let used = u / 64 + 1;
+        for i in 0..used {
+            let word = self.terms[i];
+            if word == 0 {
+                continue;
+            }
+            let mut place = |index: usize, mask: u64| {
+                self.many[index] |= self.once[index] & mask;
+                self.once[index] ^= mask;
+                self.once[index] &= !self.many[index];
+            };
+            place(i + offset, word << shift);
+            if shift != 0 {
+                place(i + offset + 1, word >> (64 - shift));
+            }
+        }
+        self.terms[u / 64] |= 1 << (u % 64);
+        self.last = u as u64;
+    }
+
+    /// Returns the smallest number above the last term with exactly one
+    /// representation.
+    fn next_term(&self) -> u64 {
+        let start = self.last as usize + 1;
+        let mut index = start / 64;
+        let mut word = self.once[index] & (u64::MAX << (start % 64));
+        while word == 0 {
+            index += 1;
+            word = self.once[index];
+        }
+        (index * 64) as u64 + word.trailing_zeros() as u64
+    }
+}
+
+impl Iterator for UlamNumbers {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let term = match self.count {
+            0 => self.seed.0,
+            1 => self.seed.1,
+            _ => self.next_term(),
+        };
+        if term >= LIMIT {
+            self.done = true;
+            return None;
+        }
+        self.count = (self.count + 1).min(2);
+        self.add(term);
+        Some(term)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, None)
+        }
+    }
+}
+
+impl core::iter::FusedIterator for UlamNumbers {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn naive(a: u64, b: u64, n: usize) -> Vec<u64> {
+        let mut terms = vec![a, b];
+        let mut counts = HashMap::new();
+        *counts.entry(a + b).or_insert(0) += 1;
+        while terms.len() < n {
+            let last = *terms.last().unwrap();
+            let next = (last + 1..).find(|s| counts.get(s) == Some(&1)).unwrap();
+            for &t in &terms {
+                *counts.entry(t + next).or_insert(0) += 1;
+            }
+            terms.push(next);
+        }
+        terms.truncate(n);
+        terms
+    }
+
+    #[test]
+    fn matches_naive() {
+        for (a, b) in [
+            (1, 2),
+            (1, 3),
+            (2, 3),
+            (2, 5),
+            (1, 64),
+            (3, 70),
+            (63, 64),
+            (100, 129),
+        ] {
+            let terms: Vec<_> = ulam_numbers_with_seed(a, b).take(2000).collect();
+            assert_eq!(terms, naive(a, b, 2000), "a = {}, b = {}", a, b);
+        }
+    }
+
+    #[test]
+    fn ulam_counts() {
+        // The 10,000th Ulam number.
+        assert_eq!(ulam_numbers().nth(9999), Some(132_788));
+    }
+
+    #[test]
+    fn large_seed() {
+        let b = 1 << 24;
+        let terms: Vec<_> = ulam_numbers_with_seed(1, b).take(30).collect();
+        assert_eq!(terms, naive(1, b, 30));
+    }
+
+    #[test]
+    #[should_panic(expected = "seed must satisfy 0 < a < b")]
+    fn bad_seed() {
+        ulam_numbers_with_seed(2, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "seed must be below 2^32")]
+    fn seed_too_large() {
+        ulam_numbers_with_seed(u64::MAX - 1, u64::MAX);
+    }
+}
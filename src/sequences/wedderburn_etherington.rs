@@ -0,0 +1,216 @@
+pub trait WedderburnEtherington: Sized {
+    /// The exact type of the `Iterator<Item = Self>` returned by
+    /// `wedderburn_etherington_iter`.
+    type Iter: Iterator<Item = Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through all
+    /// Wedderburn–Etherington numbers, the numbers of unordered rooted binary
+    /// trees with n leaves, from zero until just before overflow.
+    ///
+    /// The iterator remembers every term it has produced, as each term is a
+    /// convolution of the earlier ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::WedderburnEtherington;
+    ///
+    /// let terms: Vec<_> = u8::wedderburn_etherington_iter().collect();
+    /// assert_eq!(terms, vec![0, 1, 1, 1, 2, 3, 6, 11, 23, 46, 98, 207]);
+    /// ```
+    fn wedderburn_etherington_iter() -> Self::Iter;
+
+    /// Returns an `Option` containing either the n<sup>th</sup>
+    /// Wedderburn–Etherington number or `None` if that would cause overflow
+    /// or n is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::WedderburnEtherington;
+    ///
+    /// assert_eq!(u32::nth_wedderburn_etherington(&14), Some(2179));
+    /// assert_eq!(u8::nth_wedderburn_etherington(&12), None);
+    /// ```
+    fn nth_wedderburn_etherington(n: &Self) -> Option<Self>;
+}
+
+/// The iterator returned by
+/// `WedderburnEtherington::wedderburn_etherington_iter`.
+pub struct WedderburnEtheringtonIter<T> {
+    terms: Vec<T>,
+    overflowed: bool,
+}
+
+macro_rules! wedderburn_etherington_trait_for_unsigned {
+    ($type:ty) => {
+        impl $crate::sequences::wedderburn_etherington::WedderburnEtherington for $type {
+            type Iter = $crate::sequences::wedderburn_etherington::WedderburnEtheringtonIter<$type>;
+
+            fn wedderburn_etherington_iter() -> Self::Iter {
+                $crate::sequences::wedderburn_etherington::WedderburnEtheringtonIter {
+                    terms: ::std::vec::Vec::new(),
+                    overflowed: false,
+                }
+            }
+
+            fn nth_wedderburn_etherington(n: &Self) -> Option<Self> {
+                let n = <usize as ::core::convert::TryFrom<$type>>::try_from(*n).ok()?;
+                Self::wedderburn_etherington_iter().nth(n)
+            }
+        }
+
+        impl ::core::iter::Iterator
+            for $crate::sequences::wedderburn_etherington::WedderburnEtheringtonIter<$type>
+        {
+            type Item = $type;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                if self.overflowed {
+                    return ::core::option::Option::None;
+                }
+
+                let terms = &self.terms;
+                let n = terms.len();
+                let next = (|| {
+                    if n < 2 {
+                        return ::core::option::Option::Some(n as $type);
+                    }
+
+                    let mut sum: $type = 0;
+                    for i in 1..n.div_ceil(2) {
+                        sum = sum.checked_add(terms[i].checked_mul(terms[n - i])?)?;
+                    }
+                    if n.is_multiple_of(2) {
+                        // a(m)(a(m) + 1) / 2 without overflowing before the
+                        // division.
+                        let a = terms[n / 2];
+                        let pairs = if a % 2 == 0 {
+                            (a / 2).checked_mul(a.checked_add(1)?)?
+                        } else {
+                            a.checked_mul(a / 2 + 1)?
+                        };
+                        sum = sum.checked_add(pairs)?;
+                    }
+                    ::core::option::Option::Some(sum)
+                })();
+
+                match next {
+                    ::core::option::Option::Some(term) => {
+                        self.terms.push(term);
+                    }
+                    ::core::option::Option::None => {
+                        self.overflowed = true;
+                    }
+                }
+                next
+            }
+        }
+    };
+}
+
+wedderburn_etherington_trait_for_unsigned!(::core::primitive::u8);
+wedderburn_etherington_trait_for_unsigned!(::core::primitive::u16);
+wedderburn_etherington_trait_for_unsigned!(::core::primitive::u32);
+wedderburn_etherington_trait_for_unsigned!(::core::primitive::u64);
+wedderburn_etherington_trait_for_unsigned!(::core::primitive::u128);
+wedderburn_etherington_trait_for_unsigned!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl WedderburnEtherington for rug::Integer {
+    type Iter = WedderburnEtheringtonIter<rug::Integer>;
+
+    fn wedderburn_etherington_iter() -> Self::Iter {
+        WedderburnEtheringtonIter {
+            terms: Vec::new(),
+            overflowed: false,
+        }
+    }
+
+    fn nth_wedderburn_etherington(n: &Self) -> Option<Self> {
+        n.to_usize()
+            .and_then(|n| Self::wedderburn_etherington_iter().nth(n))
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for WedderburnEtheringtonIter<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let terms = &self.terms;
+        let n = terms.len();
+        let next = if n < 2 {
+            rug::Integer::from(n)
+        } else {
+            let mut sum = rug::Integer::new();
+            for i in 1..n.div_ceil(2) {
+                sum += &terms[i] * &terms[n - i];
+            }
+            if n.is_multiple_of(2) {
+                let a = &terms[n / 2];
+                sum += (rug::Integer::from(a * a) + a) / 2u8;
+            }
+            sum
+        };
+
+        self.terms.push(next.clone());
+        Some(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_bounded_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let max = rug::Integer::from(<$type>::MAX);
+                let expected: ::std::vec::Vec<rug::Integer> =
+                    rug::Integer::wedderburn_etherington_iter()
+                        .take_while(|term| *term <= max)
+                        .collect();
+                let terms: ::std::vec::Vec<rug::Integer> = <$type>::wedderburn_etherington_iter()
+                    .map(rug::Integer::from)
+                    .collect();
+                ::core::assert_eq!(terms, expected);
+
+                for n in 0..=expected.len() as u8 {
+                    ::core::assert_eq!(
+                        <$type>::nth_wedderburn_etherington(&(n as $type)).map(rug::Integer::from),
+                        expected.get(n as usize).cloned()
+                    );
+                }
+            }
+        };
+    }
+
+    test_bounded_against_rug!(::core::primitive::u8, u8_wedderburn_etherington);
+    test_bounded_against_rug!(::core::primitive::u16, u16_wedderburn_etherington);
+    test_bounded_against_rug!(::core::primitive::u32, u32_wedderburn_etherington);
+    test_bounded_against_rug!(::core::primitive::u64, u64_wedderburn_etherington);
+    test_bounded_against_rug!(::core::primitive::u128, u128_wedderburn_etherington);
+    test_bounded_against_rug!(::core::primitive::usize, usize_wedderburn_etherington);
+
+    #[test]
+    fn rug_wedderburn_etherington() {
+        let terms: Vec<_> = rug::Integer::wedderburn_etherington_iter()
+            .take(24)
+            .collect();
+        assert_eq!(
+            terms,
+            [
+                0, 1, 1, 1, 2, 3, 6, 11, 23, 46, 98, 207, 451, 983, 2179, 4850, 10905, 24631,
+                56011, 127912, 293547, 676157, 1563372, 3626149
+            ]
+        );
+        assert_eq!(
+            rug::Integer::nth_wedderburn_etherington(&rug::Integer::from(-1)),
+            None
+        );
+    }
+}
@@ -0,0 +1,387 @@
+pub trait Wythoff: Sized {
+    /// Returns an `Option` containing either the n<sup>th</sup> term of the
+    /// lower Wythoff sequence, ⌊nφ⌋, or `None` if that would cause overflow.
+    ///
+    /// The term is computed exactly from the Zeckendorf representation of n
+    /// rather than with floating-point arithmetic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Wythoff;
+    ///
+    /// let terms: Vec<_> = (0..8).map(|n| u32::lower_wythoff(&n).unwrap()).collect();
+    /// assert_eq!(terms, vec![0, 1, 3, 4, 6, 8, 9, 11]);
+    /// assert_eq!(u8::lower_wythoff(&200), None);
+    /// ```
+    fn lower_wythoff(n: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the n<sup>th</sup> term of the
+    /// upper Wythoff sequence, ⌊nφ²⌋, or `None` if that would cause
+    /// overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Wythoff;
+    ///
+    /// let terms: Vec<_> = (0..8).map(|n| u32::upper_wythoff(&n).unwrap()).collect();
+    /// assert_eq!(terms, vec![0, 2, 5, 7, 10, 13, 15, 18]);
+    /// ```
+    fn upper_wythoff(n: &Self) -> Option<Self>;
+
+    /// Returns an `Option` containing either the element of the Wythoff
+    /// array at the given row and column, both numbered from zero, or `None`
+    /// if that would cause overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Wythoff;
+    ///
+    /// assert_eq!(u32::wythoff_array(&0, &4), Some(8));
+    /// assert_eq!(u32::wythoff_array(&3, &2), Some(24));
+    /// assert_eq!(u8::wythoff_array(&0, &13), None);
+    /// ```
+    fn wythoff_array(row: &Self, column: &Self) -> Option<Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through a
+    /// row of the Wythoff array, numbered from zero, until just before
+    /// overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Wythoff;
+    ///
+    /// let row: Vec<_> = u8::wythoff_array_row(&1).collect();
+    /// assert_eq!(row, vec![4, 7, 11, 18, 29, 47, 76, 123, 199]);
+    /// ```
+    fn wythoff_array_row(row: &Self) -> WythoffArrayRow<Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes down a
+    /// column of the Wythoff array, numbered from zero, until just before
+    /// overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Wythoff;
+    ///
+    /// let column: Vec<_> = u32::wythoff_array_column(&0).take(6).collect();
+    /// assert_eq!(column, vec![1, 4, 6, 9, 12, 14]);
+    /// ```
+    fn wythoff_array_column(column: &Self) -> WythoffArrayColumn<Self>;
+
+    /// Returns whether (x, y) is a cold position in Wythoff's game, that is,
+    /// a position from which the player to move loses with perfect play.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Wythoff;
+    ///
+    /// assert!(u32::is_cold_position(&0, &0));
+    /// assert!(u32::is_cold_position(&5, &3));
+    /// assert!(!u32::is_cold_position(&5, &4));
+    /// ```
+    fn is_cold_position(x: &Self, y: &Self) -> bool;
+
+    /// Returns an `Option` containing either a cold position that can be
+    /// reached in one move of Wythoff's game from the hot position (x, y) or
+    /// `None` if (x, y) is already cold.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Wythoff;
+    ///
+    /// assert_eq!(u32::winning_move(&5, &3), None);
+    /// let (x, y) = u32::winning_move(&5, &4).unwrap();
+    /// assert!(u32::is_cold_position(&x, &y));
+    /// ```
+    fn winning_move(x: &Self, y: &Self) -> Option<(Self, Self)>;
+}
+
+/// The iterator returned by `Wythoff::wythoff_array_row`.
+pub struct WythoffArrayRow<T> {
+    a: Option<T>,
+    b: Option<T>,
+}
+
+/// The iterator returned by `Wythoff::wythoff_array_column`.
+pub struct WythoffArrayColumn<T> {
+    row: Option<T>,
+    column: T,
+}
+
+macro_rules! wythoff_trait_for_unsigned {
+    ($type:ty) => {
+        impl $crate::sequences::wythoff::Wythoff for $type {
+            fn lower_wythoff(n: &Self) -> Option<Self> {
+                // ⌊nφ⌋ = n + ⌊n/φ⌋, and ⌊n/φ⌋ is found by shifting the
+                // Zeckendorf representation of n down by one index, less one
+                // when it ends in an even-indexed Fibonacci number. That
+                // representation is taken greedily from the pairs
+                // (F(k − 1), F(k)) walked down from the largest.
+                let pairs = <$type as $crate::sequences::Fibonacci>::fibonacci_pairs();
+                let mut remainder = *n;
+                let mut shifted: $type = 0;
+                let mut smallest_index = 1;
+                for (k, (previous, term)) in pairs.enumerate().skip(1).rev() {
+                    if term <= remainder {
+                        remainder -= term;
+                        shifted += previous;
+                        smallest_index = k + 1;
+                    }
+                }
+                if smallest_index % 2 == 0 {
+                    shifted -= 1;
+                }
+                n.checked_add(shifted)
+            }
+
+            fn upper_wythoff(n: &Self) -> Option<Self> {
+                Self::lower_wythoff(n)?.checked_add(*n)
+            }
+
+            fn wythoff_array(row: &Self, column: &Self) -> Option<Self> {
+                let mut row = Self::wythoff_array_row(row);
+                let column = <usize as ::core::convert::TryFrom<$type>>::try_from(*column).ok()?;
+                row.nth(column)
+            }
+
+            fn wythoff_array_row(row: &Self) -> $crate::sequences::wythoff::WythoffArrayRow<Self> {
+                let start = row.checked_add(1).and_then(|n| Self::lower_wythoff(&n));
+                $crate::sequences::wythoff::WythoffArrayRow {
+                    a: start.and_then(|n| Self::lower_wythoff(&n)),
+                    b: start.and_then(|n| Self::upper_wythoff(&n)),
+                }
+            }
+
+            fn wythoff_array_column(
+                column: &Self,
+            ) -> $crate::sequences::wythoff::WythoffArrayColumn<Self> {
+                $crate::sequences::wythoff::WythoffArrayColumn {
+                    row: ::core::option::Option::Some(0),
+                    column: *column,
+                }
+            }
+
+            fn is_cold_position(x: &Self, y: &Self) -> bool {
+                let (x, y) = if x <= y { (*x, *y) } else { (*y, *x) };
+                Self::lower_wythoff(&(y - x)) == ::core::option::Option::Some(x)
+            }
+
+            fn winning_move(x: &Self, y: &Self) -> Option<(Self, Self)> {
+                if Self::is_cold_position(x, y) {
+                    return None;
+                }
+
+                // The index n for which f(n) = target, where f is increasing.
+                let index_of = |target: $type, f: fn(&$type) -> Option<$type>| {
+                    let (mut low, mut high) = (0, target);
+                    while low < high {
+                        let middle = low + (high - low) / 2;
+                        match f(&middle) {
+                            ::core::option::Option::Some(value) if value < target => {
+                                low = middle + 1
+                            }
+                            _ => high = middle,
+                        }
+                    }
+                    ::core::option::Option::Some(low).filter(|n| f(n) == Some(target))
+                };
+                // The other pile of the cold position containing a pile of
+                // size n.
+                let partner = |n: $type| {
+                    if let ::core::option::Option::Some(k) = index_of(n, Self::lower_wythoff) {
+                        Self::upper_wythoff(&k)
+                    } else {
+                        index_of(n, Self::upper_wythoff).and_then(|k| Self::lower_wythoff(&k))
+                    }
+                };
+
+                if let ::core::option::Option::Some(p) = partner(*x).filter(|p| p < y) {
+                    return Some((*x, p));
+                }
+                if let ::core::option::Option::Some(p) = partner(*y).filter(|p| p < x) {
+                    return Some((p, *y));
+                }
+                // Otherwise, taking equally from both piles keeps their
+                // difference and reaches the cold position with that
+                // difference.
+                let (smaller, larger) = if x <= y { (*x, *y) } else { (*y, *x) };
+                let target = Self::lower_wythoff(&(larger - smaller))?;
+                let taken = smaller.checked_sub(target)?;
+                Some((*x - taken, *y - taken))
+            }
+        }
+
+        impl ::core::iter::Iterator for $crate::sequences::wythoff::WythoffArrayRow<$type> {
+            type Item = $type;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                let result = self.a?;
+                self.a = self.b;
+                self.b = self.a.and_then(|b| b.checked_add(result));
+                ::core::option::Option::Some(result)
+            }
+        }
+
+        impl ::core::iter::Iterator for $crate::sequences::wythoff::WythoffArrayColumn<$type> {
+            type Item = $type;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                let result = <$type as $crate::sequences::wythoff::Wythoff>::wythoff_array(
+                    &self.row?,
+                    &self.column,
+                );
+                self.row = match result {
+                    ::core::option::Option::Some(_) => self.row.and_then(|row| row.checked_add(1)),
+                    ::core::option::Option::None => ::core::option::Option::None,
+                };
+                result
+            }
+        }
+    };
+}
+
+wythoff_trait_for_unsigned!(::core::primitive::u8);
+wythoff_trait_for_unsigned!(::core::primitive::u16);
+wythoff_trait_for_unsigned!(::core::primitive::u32);
+wythoff_trait_for_unsigned!(::core::primitive::u64);
+wythoff_trait_for_unsigned!(::core::primitive::u128);
+wythoff_trait_for_unsigned!(::core::primitive::usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::number_theory::QuadraticIrrational;
+    use crate::sequences::beatty::{beatty_sequence, complementary_beatty_sequence};
+
+    macro_rules! test_wythoff_against_beatty {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let phi = QuadraticIrrational::golden_ratio();
+                let lower = beatty_sequence(phi);
+                let upper = complementary_beatty_sequence(phi).unwrap();
+                for (n, (a, b)) in (1..=20_000u64).zip(lower.zip(upper)) {
+                    if n as u128 > <$type>::MAX as u128 {
+                        break;
+                    }
+                    let fits = |value: i128| {
+                        ::core::option::Option::Some(value as $type)
+                            .filter(|_| value as u128 <= <$type>::MAX as u128)
+                    };
+                    ::core::assert_eq!(<$type>::lower_wythoff(&(n as $type)), fits(a));
+                    ::core::assert_eq!(<$type>::upper_wythoff(&(n as $type)), fits(b));
+                }
+            }
+        };
+    }
+
+    test_wythoff_against_beatty!(::core::primitive::u8, u8_wythoff);
+    test_wythoff_against_beatty!(::core::primitive::u16, u16_wythoff);
+    test_wythoff_against_beatty!(::core::primitive::u32, u32_wythoff);
+    test_wythoff_against_beatty!(::core::primitive::u64, u64_wythoff);
+    test_wythoff_against_beatty!(::core::primitive::u128, u128_wythoff);
+    test_wythoff_against_beatty!(::core::primitive::usize, usize_wythoff);
+
+    #[test]
+    fn large_lower_wythoff() {
+        // ⌊nφ⌋ = ⌊(n + √(5n²)) / 2⌋.
+        let exact = |n: u128| {
+            let n = rug::Integer::from(n);
+            ((n.clone().square() * 5u8).sqrt() + n) / 2u8
+        };
+        for &n in &[u64::MAX / 2, 1 << 62, 11400714819323198485, u64::MAX] {
+            assert_eq!(
+                u64::lower_wythoff(&n).map(rug::Integer::from),
+                Some(exact(n as u128)).filter(|a| *a <= u64::MAX)
+            );
+        }
+        for &n in &[1 << 100, u128::MAX / 2, u128::MAX / 3] {
+            assert_eq!(
+                u128::lower_wythoff(&n).map(rug::Integer::from),
+                Some(exact(n)).filter(|a| *a <= u128::MAX)
+            );
+        }
+    }
+
+    #[test]
+    fn wythoff_array() {
+        let expected = [
+            [1u32, 2, 3, 5, 8, 13],
+            [4, 7, 11, 18, 29, 47],
+            [6, 10, 16, 26, 42, 68],
+            [9, 15, 24, 39, 63, 102],
+            [12, 20, 32, 52, 84, 136],
+        ];
+        for (row, values) in expected.iter().enumerate() {
+            let row_values: Vec<_> = u32::wythoff_array_row(&(row as u32)).take(6).collect();
+            assert_eq!(row_values, values);
+            for (column, &value) in values.iter().enumerate() {
+                assert_eq!(
+                    u32::wythoff_array(&(row as u32), &(column as u32)),
+                    Some(value)
+                );
+                assert_eq!(
+                    u32::wythoff_array_column(&(column as u32)).nth(row),
+                    Some(value)
+                );
+            }
+        }
+
+        // Every positive integer appears exactly once in the array.
+        let mut seen = vec![false; 1000];
+        let mut row = 0;
+        while let Some(first) = u32::wythoff_array(&row, &0).filter(|&first| first < 1000) {
+            for value in u32::wythoff_array_row(&row).take_while(|&value| value < 1000) {
+                assert!(!seen[value as usize]);
+                seen[value as usize] = true;
+            }
+            assert!(first > 0);
+            row += 1;
+        }
+        assert!(seen[1..].iter().all(|&seen| seen));
+
+        assert_eq!(u8::wythoff_array_column(&0).last(), Some(255));
+    }
+
+    #[test]
+    fn wythoff_game() {
+        const SIZE: usize = 60;
+        // Brute-force the cold positions: a position is cold exactly when
+        // every move from it reaches a hot position.
+        let mut cold = [[false; SIZE]; SIZE];
+        for x in 0..SIZE {
+            for y in 0..SIZE {
+                let reaches_cold = (0..x).any(|i| cold[i][y])
+                    || (0..y).any(|j| cold[x][j])
+                    || (1..=x.min(y)).any(|k| cold[x - k][y - k]);
+                cold[x][y] = !reaches_cold;
+            }
+        }
+
+        for x in 0..SIZE {
+            for y in 0..SIZE {
+                let (a, b) = (x as u8, y as u8);
+                assert_eq!(u8::is_cold_position(&a, &b), cold[x][y]);
+                match u8::winning_move(&a, &b) {
+                    None => assert!(cold[x][y]),
+                    Some((p, q)) => {
+                        assert!(!cold[x][y]);
+                        assert!(cold[p as usize][q as usize]);
+                        let (p, q) = (p as usize, q as usize);
+                        let legal = (p == x && q < y)
+                            || (q == y && p < x)
+                            || (p < x && x - p == y.wrapping_sub(q) && q < y);
+                        assert!(legal);
+                    }
+                }
+            }
+        }
+    }
+}
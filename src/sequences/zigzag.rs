@@ -0,0 +1,245 @@
+pub trait Zigzag: Sized {
+    /// The exact type of the `Iterator<Item = Self>` returned by
+    /// `zigzag_iter`.
+    type Iter: Iterator<Item = Self>;
+
+    /// Returns an `Iterator<Item = Self>` implementation that goes through all
+    /// Euler zigzag numbers, the numbers of alternating permutations of n
+    /// elements, from zero until just before overflow. The even-indexed terms
+    /// are the secant numbers and the odd-indexed terms are the tangent
+    /// numbers.
+    ///
+    /// Each term is the end of a row of the Seidel–Entringer triangle, the
+    /// boustrophedon transform of 1, 0, 0, …, whose every entry is at most
+    /// the end of its row, so the iterator remembers only the last row it
+    /// made.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Zigzag;
+    ///
+    /// let terms: Vec<_> = u16::zigzag_iter().collect();
+    /// assert_eq!(terms, vec![1, 1, 1, 2, 5, 16, 61, 272, 1385, 7936, 50521]);
+    /// ```
+    fn zigzag_iter() -> Self::Iter;
+
+    /// Returns an `Option` containing either the n<sup>th</sup> Euler zigzag
+    /// number or `None` if that would cause overflow or n is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathematical::sequences::Zigzag;
+    ///
+    /// assert_eq!(u32::nth_zigzag(&12), Some(2_702_765));
+    /// assert_eq!(u128::nth_zigzag(&30), Some(441_543_893_249_023_104_553_682_821));
+    /// assert_eq!(i8::nth_zigzag(&6), Some(61));
+    /// assert_eq!(i8::nth_zigzag(&7), None);
+    /// assert_eq!(i8::nth_zigzag(&-1), None);
+    /// ```
+    fn nth_zigzag(n: &Self) -> Option<Self>;
+}
+
+/// The iterator returned by `Zigzag::zigzag_iter`.
+///
+/// For `rug::Integer`, it also implements `StreamingSequence`, whose
+/// `next_ref` method lends out each term instead of cloning it.
+pub struct ZigzagIter<T> {
+    row: Vec<T>,
+    overflowed: bool,
+}
+
+macro_rules! zigzag_trait_for_primitive {
+    ($type:ty) => {
+        impl $crate::sequences::zigzag::Zigzag for $type {
+            type Iter = $crate::sequences::zigzag::ZigzagIter<$type>;
+
+            fn zigzag_iter() -> Self::Iter {
+                $crate::sequences::zigzag::ZigzagIter {
+                    row: ::std::vec::Vec::new(),
+                    overflowed: false,
+                }
+            }
+
+            fn nth_zigzag(n: &Self) -> ::core::option::Option<Self> {
+                let n = <usize as ::core::convert::TryFrom<$type>>::try_from(*n).ok()?;
+                Self::zigzag_iter().nth(n)
+            }
+        }
+
+        impl ::core::iter::Iterator for $crate::sequences::zigzag::ZigzagIter<$type> {
+            type Item = $type;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                if self.overflowed {
+                    return ::core::option::Option::None;
+                }
+                if self.row.is_empty() {
+                    self.row.push(1);
+                    return ::core::option::Option::Some(1);
+                }
+
+                // Each row starts with zero and adds the previous row read
+                // backwards.
+                let mut row: ::std::vec::Vec<$type> =
+                    ::std::vec::Vec::with_capacity(self.row.len() + 1);
+                row.push(0);
+                for &entry in self.row.iter().rev() {
+                    match row[row.len() - 1].checked_add(entry) {
+                        ::core::option::Option::Some(next) => row.push(next),
+                        ::core::option::Option::None => {
+                            self.overflowed = true;
+                            return ::core::option::Option::None;
+                        }
+                    }
+                }
+                self.row = row;
+                self.row.last().copied()
+            }
+        }
+
+        impl ::core::iter::FusedIterator for $crate::sequences::zigzag::ZigzagIter<$type> {}
+    };
+}
+
+zigzag_trait_for_primitive!(::core::primitive::i8);
+zigzag_trait_for_primitive!(::core::primitive::u8);
+zigzag_trait_for_primitive!(::core::primitive::i16);
+zigzag_trait_for_primitive!(::core::primitive::u16);
+zigzag_trait_for_primitive!(::core::primitive::i32);
+zigzag_trait_for_primitive!(::core::primitive::u32);
+zigzag_trait_for_primitive!(::core::primitive::i64);
+zigzag_trait_for_primitive!(::core::primitive::u64);
+zigzag_trait_for_primitive!(::core::primitive::i128);
+zigzag_trait_for_primitive!(::core::primitive::u128);
+zigzag_trait_for_primitive!(::core::primitive::isize);
+zigzag_trait_for_primitive!(::core::primitive::usize);
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Zigzag for rug::Integer {
+    type Iter = ZigzagIter<rug::Integer>;
+
+    fn zigzag_iter() -> Self::Iter {
+        ZigzagIter {
+            row: Vec::new(),
+            overflowed: false,
+        }
+    }
+
+    fn nth_zigzag(n: &Self) -> Option<Self> {
+        n.to_usize().and_then(|n| Self::zigzag_iter().nth(n))
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl crate::sequences::StreamingSequence for ZigzagIter<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next_ref(&mut self) -> Option<&Self::Item> {
+        if self.row.is_empty() {
+            self.row.push(rug::Integer::from(1));
+            return self.row.last();
+        }
+
+        let mut row = Vec::with_capacity(self.row.len() + 1);
+        row.push(rug::Integer::new());
+        for entry in self.row.iter().rev() {
+            let next = rug::Integer::from(&row[row.len() - 1] + entry);
+            row.push(next);
+        }
+        self.row = row;
+        self.row.last()
+    }
+}
+
+#[cfg(any(feature = "rug", doc, test))]
+#[doc(cfg(feature = "rug"))]
+impl Iterator for ZigzagIter<rug::Integer> {
+    type Item = rug::Integer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        crate::sequences::StreamingSequence::next_ref(self).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the first n zigzag numbers from the recurrence
+    /// 2A(m + 1) = Σ C(m, i) A(i) A(m − i) for m at least one.
+    fn zigzag_numbers(n: usize) -> Vec<rug::Integer> {
+        let mut terms = vec![rug::Integer::from(1), rug::Integer::from(1)];
+        while terms.len() < n {
+            let m = terms.len() - 1;
+            let mut sum = rug::Integer::new();
+            for i in 0..=m {
+                let binomial = rug::Integer::from(rug::Integer::binomial_u(m as u32, i as u32));
+                sum += binomial * &terms[i] * &terms[m - i];
+            }
+            terms.push(sum / 2u8);
+        }
+        terms.truncate(n);
+        terms
+    }
+
+    macro_rules! test_bounded_against_rug {
+        ($type:ty, $test_name:ident) => {
+            #[test]
+            fn $test_name() {
+                let max = rug::Integer::from(<$type>::MAX);
+                let expected: ::std::vec::Vec<rug::Integer> = zigzag_numbers(100)
+                    .into_iter()
+                    .take_while(|term| *term <= max)
+                    .collect();
+                let terms: ::std::vec::Vec<rug::Integer> =
+                    <$type>::zigzag_iter().map(rug::Integer::from).collect();
+                ::core::assert_eq!(terms, expected);
+
+                let mut iter = <$type>::zigzag_iter();
+                iter.by_ref().for_each(drop);
+                ::core::assert_eq!(iter.next(), ::core::option::Option::None);
+
+                for n in 0..=expected.len() as u8 {
+                    ::core::assert_eq!(
+                        <$type>::nth_zigzag(&(n as $type)).map(rug::Integer::from),
+                        expected.get(n as usize).cloned()
+                    );
+                }
+            }
+        };
+    }
+
+    test_bounded_against_rug!(::core::primitive::i8, i8_zigzag);
+    test_bounded_against_rug!(::core::primitive::u8, u8_zigzag);
+    test_bounded_against_rug!(::core::primitive::i16, i16_zigzag);
+    test_bounded_against_rug!(::core::primitive::u16, u16_zigzag);
+    test_bounded_against_rug!(::core::primitive::i32, i32_zigzag);
+    test_bounded_against_rug!(::core::primitive::u32, u32_zigzag);
+    test_bounded_against_rug!(::core::primitive::i64, i64_zigzag);
+    test_bounded_against_rug!(::core::primitive::u64, u64_zigzag);
+    test_bounded_against_rug!(::core::primitive::i128, i128_zigzag);
+    test_bounded_against_rug!(::core::primitive::u128, u128_zigzag);
+    test_bounded_against_rug!(::core::primitive::isize, isize_zigzag);
+    test_bounded_against_rug!(::core::primitive::usize, usize_zigzag);
+
+    #[test]
+    fn rug_zigzag() {
+        let terms: Vec<_> = rug::Integer::zigzag_iter().take(300).collect();
+        assert_eq!(terms, zigzag_numbers(300));
+        assert_eq!(
+            rug::Integer::nth_zigzag(&rug::Integer::from(300)).as_ref(),
+            zigzag_numbers(301).last()
+        );
+        assert_eq!(rug::Integer::nth_zigzag(&rug::Integer::from(-1)), None);
+
+        use crate::sequences::StreamingSequence;
+        let mut streaming = rug::Integer::zigzag_iter();
+        for term in terms.iter() {
+            assert_eq!(streaming.next_ref(), Some(term));
+        }
+    }
+}